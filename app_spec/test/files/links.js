@@ -167,6 +167,31 @@ module.exports = scenario => {
 
       t.equal(3, bob_posts_time_2.Ok.links.length)
       t.equal(3, alice_posts_time_2.Ok.links.length)
+
+      const bob_posts_cursor = await bob.call('app', 'simple', 'get_my_links_with_cursor_pagination',
+      {
+        base: alice.info('app').agentAddress,
+        cursor: null,
+        limit: 3
+      })
+      t.equal(3, bob_posts_cursor.Ok.links.length)
+      t.ok(bob_posts_cursor.Ok.next_cursor)
+
+      // a link added between pages must not shift the next page
+      await alice.callSync('app', 'simple', 'create_link',
+        { base: alice.info('app').agentAddress, target: 'Holo world 5' }
+      )
+
+      const bob_posts_cursor_2 = await bob.call('app', 'simple', 'get_my_links_with_cursor_pagination',
+      {
+        base: alice.info('app').agentAddress,
+        cursor: bob_posts_cursor.Ok.next_cursor,
+        limit: 3
+      })
+      t.equal(1, bob_posts_cursor_2.Ok.links.length)
+      t.equal(undefined, bob_posts_cursor_2.Ok.next_cursor)
+      const first_page = bob_posts_cursor.Ok.links.map(link => link.address)
+      t.notOk(first_page.includes(bob_posts_cursor_2.Ok.links[0].address))
   })
 
   scenario('get_links_crud', async (s, t) => {
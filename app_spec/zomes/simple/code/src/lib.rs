@@ -151,6 +151,23 @@ pub fn handle_get_my_links_with_time_pagination(
     )
 }
 
+pub fn handle_get_my_links_with_cursor_pagination(
+    agent: Address,
+    cursor: Option<GetLinksCursor>,
+    limit: usize,
+) -> ZomeApiResult<GetLinksResult> {
+    let options = GetLinksOptions {
+        pagination: Some(Pagination::Cursor(CursorPagination { cursor, limit })),
+        ..GetLinksOptions::default()
+    };
+    hdk::get_links_with_options(
+        &agent,
+        LinkMatch::Exactly("authored_simple_posts"),
+        LinkMatch::Any,
+        options,
+    )
+}
+
 pub fn handle_get_my_links_count(
     agent: Address,
     status_request: LinksStatusRequestKind,
@@ -291,6 +308,11 @@ define_zome! {
             outputs: |result: ZomeApiResult<GetLinksResult>|,
             handler: handle_get_my_links_with_time_pagination
         }
+        get_my_links_with_cursor_pagination: {
+            inputs: |base: Address,cursor:Option<GetLinksCursor>,limit:usize|,
+            outputs: |result: ZomeApiResult<GetLinksResult>|,
+            handler: handle_get_my_links_with_cursor_pagination
+        }
         get_my_links_count: {
             inputs: |base: Address,status_request:LinksStatusRequestKind,tag:String|,
             outputs: |result: ZomeApiResult<GetLinksResultCount>|,
@@ -314,6 +336,6 @@ define_zome! {
     ]
 
     traits: {
-        hc_public [create_anchor, get_entry, create_link, delete_link, get_my_links, test_emit_signal,get_my_links_count,create_link_with_tag,get_my_links_count_by_tag,delete_link_with_tag,get_my_links_with_tag,encrypt,decrypt,get_my_links_with_pagination,get_my_links_with_time_pagination]
+        hc_public [create_anchor, get_entry, create_link, delete_link, get_my_links, test_emit_signal,get_my_links_count,create_link_with_tag,get_my_links_count_by_tag,delete_link_with_tag,get_my_links_with_tag,encrypt,decrypt,get_my_links_with_pagination,get_my_links_with_time_pagination,get_my_links_with_cursor_pagination]
     }
 }
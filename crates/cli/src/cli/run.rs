@@ -14,7 +14,7 @@ use holochain_conductor_lib::{
 use holochain_core_types::agent::AgentId;
 use holochain_net::sim2h_worker::Sim2hConfig;
 use holochain_persistence_api::cas::content::AddressableContent;
-use std::{fs, path::PathBuf};
+use std::{collections::BTreeMap, fs, path::PathBuf};
 
 pub enum Networking {
     Sim2h(String),
@@ -167,6 +167,7 @@ fn dna_configuration(dna_path: &PathBuf) -> DnaConfiguration {
             .to_string(),
         hash: dna.address().to_string(),
         uuid: None,
+        properties: None,
     }
 }
 
@@ -196,6 +197,14 @@ fn instance_configuration(storage: StorageConfiguration) -> InstanceConfiguratio
         dna: DNA_CONFIG_ID.into(),
         agent: AGENT_CONFIG_ID.into(),
         storage,
+        holding_workflow_pool_size: None,
+        auto_block_warranted_agents: false,
+        membrane_proof: None,
+        zome_config: BTreeMap::new(),
+        properties: None,
+        query_cache_ttl_ms: None,
+        query_cache_size: None,
+        dna_hash: None,
     }
 }
 
@@ -276,7 +285,10 @@ mod tests {
     use holochain_core_types::dna::Dna;
     use holochain_net::sim2h_worker::Sim2hConfig;
     use holochain_persistence_api::cas::content::AddressableContent;
-    use std::fs::{create_dir, File};
+    use std::{
+        collections::BTreeMap,
+        fs::{create_dir, File},
+    };
 
     #[test]
     // flagged as broken for:
@@ -345,6 +357,7 @@ mod tests {
                 file: temp_path.to_str().unwrap().to_string(),
                 hash: dna.address().to_string(),
                 uuid: Default::default(),
+                properties: Default::default(),
             }
         )
     }
@@ -374,6 +387,14 @@ mod tests {
                 dna: "hc-run-dna".to_string(),
                 agent: "hc-run-agent".to_string(),
                 storage: StorageConfiguration::Memory,
+                holding_workflow_pool_size: None,
+                auto_block_warranted_agents: false,
+                membrane_proof: None,
+                zome_config: BTreeMap::new(),
+                properties: None,
+                query_cache_ttl_ms: None,
+                query_cache_size: None,
+                dna_hash: None,
             }
         )
     }
@@ -8,12 +8,13 @@ use crate::{
     keystore::{Keystore, PRIMARY_KEYBUNDLE_ID},
     NEW_RELIC_LICENSE_KEY,
 };
-use holochain_core_types::error::HolochainError;
+use holochain_core_types::{error::HolochainError, membrane_proof::MembraneProof};
 use holochain_locksmith::RwLock;
 use holochain_persistence_api::{cas::content::AddressableContent, hash::HashString};
 
 use json_patch;
 use std::{
+    collections::BTreeMap,
     fs::{self, create_dir_all},
     path::PathBuf,
     sync::Arc,
@@ -41,6 +42,7 @@ pub trait ConductorAdmin {
         dna_id: &String,
         agent_id: &String,
         storage: Option<&str>,
+        membrane_proof: Option<MembraneProof>,
     ) -> Result<(), HolochainError>;
     fn remove_instance(&mut self, id: &String) -> Result<(), HolochainError>;
     fn add_interface(&mut self, new_instance: InterfaceConfiguration)
@@ -81,7 +83,8 @@ impl ConductorAdmin for Conductor {
     /// This function may also take an optional `properties` parameter. This can be any valid JSON
     /// and will be injected in the dna package prior to installation. Existing properties will also be kept and
     /// overriden by the passed properties in the case of collisions. This will change the dna hash!
-    /// (Note injecting properties requires that copy=true)
+    /// The properties are also stored in the DNA configuration so they get merged again
+    /// whenever the DNA is loaded from a file that was not copied.
     fn install_dna_from_file(
         &mut self,
         path: PathBuf,
@@ -111,11 +114,6 @@ impl ConductorAdmin for Conductor {
         }
 
         if let Some(props) = properties {
-            if !copy {
-                return Err(HolochainError::ConfigError(
-                    "Cannot install DNA with properties unless copy flag is true".into(),
-                ));
-            }
             json_patch::merge(&mut dna.properties, &props);
         }
 
@@ -136,6 +134,7 @@ impl ConductorAdmin for Conductor {
             file: config_path_str.into(),
             hash: dna.address().to_string(),
             uuid,
+            properties: properties.cloned(),
         };
 
         let mut new_config = self.config.clone();
@@ -197,6 +196,7 @@ impl ConductorAdmin for Conductor {
         dna_id: &String,
         agent_id: &String,
         storage: Option<&str>,
+        membrane_proof: Option<MembraneProof>,
     ) -> Result<(), HolochainError> {
         let mut new_config = self.config.clone();
         let storage_path = self
@@ -233,6 +233,14 @@ impl ConductorAdmin for Conductor {
             dna: dna_id.to_string(),
             agent: agent_id.to_string(),
             storage: storage_config,
+            holding_workflow_pool_size: None,
+            auto_block_warranted_agents: false,
+            membrane_proof,
+            zome_config: BTreeMap::new(),
+            properties: None,
+            query_cache_ttl_ms: None,
+            query_cache_size: None,
+            dna_hash: None,
         };
         new_config.instances.push(new_instance_config);
         new_config.check_consistency(&mut self.dna_loader)?;
@@ -848,12 +856,14 @@ type = 'cmd'"#
                     file: String::from("app_spec.dna.json"),
                     hash: String::from("QmaJiTs75zU7kMFYDkKgrCYaH8WtnYNkmYX3tPt7ycbtRq"),
                     uuid: Default::default(),
+                    properties: Default::default(),
                 },
                 DnaConfiguration {
                     id: String::from("new-dna"),
                     file: String::from("new-dna.dna.json"),
                     hash: String::from(new_dna.address()),
                     uuid: Default::default(),
+                    properties: Default::default(),
                 },
             ]
         );
@@ -930,12 +940,14 @@ id = 'new-dna'"#,
                     file: String::from("app_spec.dna.json"),
                     hash: String::from("QmaJiTs75zU7kMFYDkKgrCYaH8WtnYNkmYX3tPt7ycbtRq"),
                     uuid: Default::default(),
+                    properties: Default::default(),
                 },
                 DnaConfiguration {
                     id: String::from("new-dna"),
                     file: output_dna_file.to_str().unwrap().to_string(),
                     hash: String::from(new_dna.address()),
                     uuid: Default::default(),
+                    properties: Default::default(),
                 },
             ]
         );
@@ -986,19 +998,16 @@ id = 'new-dna'"#,
         new_dna_path.push("new-dna.dna.json");
         let new_props = json!({"propertyKey": "value"});
 
-        assert_eq!(
-            conductor.install_dna_from_file(
+        assert!(conductor
+            .install_dna_from_file(
                 new_dna_path.clone(),
-                String::from("new-dna-with-props"),
+                String::from("new-dna-with-props-not-copied"),
                 false,
                 None,
                 Some(&new_props),
                 None,
-            ),
-            Err(HolochainError::ConfigError(
-                "Cannot install DNA with properties unless copy flag is true".into()
-            )),
-        );
+            )
+            .is_ok());
 
         assert!(conductor
             .install_dna_from_file(
@@ -1015,10 +1024,10 @@ id = 'new-dna'"#,
             Arc::get_mut(&mut test_dna_loader()).unwrap()(&PathBuf::from("new-dna.dna.json"))
                 .unwrap();
         let original_hash = new_dna.address();
-        new_dna.properties = new_props;
+        new_dna.properties = new_props.clone();
         let new_hash = new_dna.address();
         assert_ne!(original_hash, new_hash);
-        assert_eq!(conductor.config().dnas.len(), 2,);
+        assert_eq!(conductor.config().dnas.len(), 3,);
 
         let mut output_dna_file = current_dir()
             .expect("Could not get current dir")
@@ -1037,12 +1046,21 @@ id = 'new-dna'"#,
                     file: String::from("app_spec.dna.json"),
                     hash: String::from("QmaJiTs75zU7kMFYDkKgrCYaH8WtnYNkmYX3tPt7ycbtRq"),
                     uuid: Default::default(),
+                    properties: Default::default(),
+                },
+                DnaConfiguration {
+                    id: String::from("new-dna-with-props-not-copied"),
+                    file: String::from("new-dna.dna.json"),
+                    hash: String::from(new_dna.address()),
+                    uuid: Default::default(),
+                    properties: Some(new_props.clone()),
                 },
                 DnaConfiguration {
                     id: String::from("new-dna-with-props"),
                     file: output_dna_file.to_str().unwrap().to_string(),
                     hash: String::from(new_dna.address()),
                     uuid: Default::default(),
+                    properties: Some(new_props.clone()),
                 },
             ]
         );
@@ -1105,18 +1123,21 @@ id = 'new-dna'"#,
                     file: String::from("app_spec.dna.json"),
                     hash: String::from("QmaJiTs75zU7kMFYDkKgrCYaH8WtnYNkmYX3tPt7ycbtRq"),
                     uuid: Default::default(),
+                    properties: Default::default(),
                 },
                 DnaConfiguration {
                     id: String::from("new-dna-with-uuid-1"),
                     file: new_dna_path.to_string_lossy().to_string(),
                     hash: String::from(new_dna.address()),
                     uuid: Some(uuid.clone()),
+                    properties: Default::default(),
                 },
                 DnaConfiguration {
                     id: String::from("new-dna-with-uuid-2"),
                     file: output_dna_file.to_str().unwrap().to_string(),
                     hash: String::from(new_dna.address()),
                     uuid: Some(uuid.clone()),
+                    properties: Default::default(),
                 },
             ]
         );
@@ -1156,6 +1177,7 @@ id = 'new-dna'"#,
             &String::from("new-dna"),
             &String::from("test-agent-1"),
             None,
+            None,
         );
 
         assert_eq!(add_result, Ok(()));
@@ -1441,6 +1463,7 @@ type = 'http'"#,
                 &String::from("new-dna"),
                 &String::from("test-agent-1"),
                 None,
+                None,
             ),
             Ok(())
         );
@@ -557,6 +557,26 @@ impl Conductor {
         instance.write().unwrap().stop()
     }
 
+    /// Lets the given instance author entries again after its source chain was seen forked
+    pub fn resolve_chain_forks(&self, id: &String) -> Result<(), HolochainInstanceError> {
+        let instance = self.instances.get(id)?;
+        notify(format!(
+            "Resolving source chain forks of instance \"{}\"...",
+            id
+        ));
+        instance.read().unwrap().resolve_chain_forks()
+    }
+
+    /// Lets the given instance try again to validate what it had given up on
+    pub fn retry_abandoned_validations(&self, id: &String) -> Result<(), HolochainInstanceError> {
+        let instance = self.instances.get(id)?;
+        notify(format!(
+            "Retrying abandoned validations of instance \"{}\"...",
+            id
+        ));
+        instance.read().unwrap().retry_abandoned_validations()
+    }
+
     /// Starts all instances
     pub fn start_all_instances(&mut self) -> Result<(), HolochainInstanceError> {
         notify("Start all instances".to_string());
@@ -886,6 +906,30 @@ impl Conductor {
 
                 context_builder = context_builder.with_instance_name(&instance_name);
 
+                if let Some(pool_size) = instance_config.holding_workflow_pool_size {
+                    context_builder = context_builder.with_holding_workflow_pool_size(pool_size);
+                }
+
+                if instance_config.auto_block_warranted_agents {
+                    context_builder = context_builder.with_warranted_agents_blocked();
+                }
+
+                if let Some(membrane_proof) = instance_config.membrane_proof {
+                    context_builder = context_builder.with_membrane_proof(membrane_proof);
+                }
+
+                context_builder =
+                    context_builder.with_zome_config_overrides(instance_config.zome_config);
+
+                if let Some(ttl_ms) = instance_config.query_cache_ttl_ms {
+                    context_builder =
+                        context_builder.with_query_cache_ttl(Duration::from_millis(ttl_ms));
+                }
+
+                if let Some(size) = instance_config.query_cache_size {
+                    context_builder = context_builder.with_query_cache_size(size);
+                }
+
                 if let Some(metric_publisher_config) = &self.config.metric_publisher {
                     debug!("Setting metric publisher in context_builder to: {:?}", metric_publisher_config);
                     context_builder = context_builder.with_metric_publisher(&metric_publisher_config);
@@ -909,6 +953,11 @@ impl Conductor {
                     ))
                 })?;
 
+                // Properties set in the DNA config change the DNA and thus its hash
+                // which we check below.
+                if let Some(properties) = &dna_config.properties {
+                    json_patch::merge(&mut dna.properties, properties);
+                }
 
                 match dna_config.uuid {
                     Some(uuid) => {
@@ -928,7 +977,10 @@ impl Conductor {
                             .expect("Fail to get a mutable reference to 'dna loader'.")(&dna_file) {
                             // If the file is correctly loaded, meaning it exists in the file system,
                             // we can operate on its computed DNA hash
-                            Ok(dna) => {
+                            Ok(mut dna) => {
+                                if let Some(properties) = &dna_config.properties {
+                                    json_patch::merge(&mut dna.properties, properties);
+                                }
                                 let dna_hash_computed_from_file = dna.address();
                                 Conductor::check_dna_consistency_from_all_sources(
                                     &context,
@@ -962,6 +1014,28 @@ impl Conductor {
                     }
                 };
 
+                // Properties set in the instance config give this instance its own DNA
+                // on top of the checked one, so we check its new hash if one is expected.
+                if let Some(properties) = &instance_config.properties {
+                    json_patch::merge(&mut dna.properties, properties);
+                    notify(format!(
+                        "Instance {} overrides DNA properties, its DNA hash is {}",
+                        id,
+                        dna.address()
+                    ));
+                }
+                if let Some(expected_hash) = &instance_config.dna_hash {
+                    let dna_hash_computed = dna.address();
+                    if HashString::from(expected_hash.clone()) != dna_hash_computed {
+                        let msg = format!(
+                            "Conductor: DNA hash of instance {} mismatch: 'Conductor config' != 'Conductor instance': '{}' != '{}'",
+                            id, expected_hash, dna_hash_computed
+                        );
+                        log_error!(context, "{}", msg);
+                        return Err(msg);
+                    }
+                }
+
                 let mut context_clone = context.clone();
                 let context = Arc::new(context);
                 Holochain::load(context)
@@ -2606,6 +2680,38 @@ pub mod tests {
         ));
     }
 
+    #[test]
+    fn test_instance_properties_get_checked_against_the_instance_dna_hash() {
+        let mut conductor = test_conductor(10071, 10072);
+        let id = String::from("test-instance-2");
+        let properties = json!({ "network_size": 20 });
+        let mut expected_dna = Dna::try_from(JsonString::from_json(&example_dna_string())).unwrap();
+        json_patch::merge(&mut expected_dna.properties, &properties);
+
+        let instance_config = conductor
+            .config
+            .instances
+            .iter_mut()
+            .find(|instance| instance.id == id)
+            .unwrap();
+        instance_config.properties = Some(properties);
+        instance_config.dna_hash = Some(String::from("QmWrongHash"));
+        assert!(conductor.instantiate_from_config(&id).is_err());
+
+        conductor
+            .config
+            .instances
+            .iter_mut()
+            .find(|instance| instance.id == id)
+            .unwrap()
+            .dna_hash = Some(expected_dna.address().to_string());
+        let instance = conductor.instantiate_from_config(&id).unwrap();
+        assert_eq!(
+            instance.context().unwrap().get_dna().unwrap().address(),
+            expected_dna.address()
+        );
+    }
+
     #[test]
     fn test_start_stop_instance() {
         let mut conductor = test_conductor(10051, 10052);
@@ -1,11 +1,22 @@
 use crate::{conductor::Conductor, NEW_RELIC_LICENSE_KEY};
-use holochain_core::state_dump::StateDump;
+use holochain_core::{
+    dht::{dht_store::ChainForkReport, pending_validations::PendingValidationReport},
+    state_dump::StateDump,
+};
 use holochain_core_types::error::HolochainError;
 use holochain_persistence_api::cas::content::Address;
 
 pub trait ConductorDebug {
     fn running_instances(&self) -> Result<Vec<String>, HolochainError>;
     fn state_dump_for_instance(&self, instance_id: &String) -> Result<StateDump, HolochainError>;
+    fn pending_validations_for_instance(
+        &self,
+        instance_id: &String,
+    ) -> Result<Vec<PendingValidationReport>, HolochainError>;
+    fn chain_forks_for_instance(
+        &self,
+        instance_id: &String,
+    ) -> Result<Vec<ChainForkReport>, HolochainError>;
     fn get_type_and_content_from_cas(
         &self,
         address: &Address,
@@ -24,6 +35,22 @@ impl ConductorDebug for Conductor {
         Ok(hc.read().unwrap().get_state_dump()?)
     }
 
+    fn pending_validations_for_instance(
+        &self,
+        instance_id: &String,
+    ) -> Result<Vec<PendingValidationReport>, HolochainError> {
+        let hc = self.instances.get(instance_id)?;
+        Ok(hc.read().unwrap().get_pending_validations()?)
+    }
+
+    fn chain_forks_for_instance(
+        &self,
+        instance_id: &String,
+    ) -> Result<Vec<ChainForkReport>, HolochainError> {
+        let hc = self.instances.get(instance_id)?;
+        Ok(hc.read().unwrap().get_chain_forks()?)
+    }
+
     fn get_type_and_content_from_cas(
         &self,
         address: &Address,
@@ -18,6 +18,7 @@ use holochain_core_types::{
         Dna,
     },
     error::{HcResult, HolochainError},
+    membrane_proof::MembraneProof,
 };
 use holochain_json_api::json::JsonString;
 use holochain_metrics::MetricPublisherConfig;
@@ -28,7 +29,7 @@ use lib3h::engine::EngineConfig;
 use petgraph::{algo::toposort, graph::DiGraph, prelude::NodeIndex};
 use serde::Deserialize;
 use std::{
-    collections::{HashMap, HashSet},
+    collections::{BTreeMap, HashMap, HashSet},
     convert::TryFrom,
     fs::File,
     io::prelude::*,
@@ -267,6 +268,15 @@ impl Configuration {
                 Arc::get_mut(&mut dna_loader).unwrap()(&PathBuf::from(dna_config.file.clone()))
                     .map_err(|_| format!("Could not load DNA file \"{}\"", dna_config.file))?;
 
+            for zome_name in instance.zome_config.keys() {
+                dna.zomes.contains_key(zome_name).ok_or_else(|| {
+                    format!(
+                        "Zome \"{}\" not found in DNA \"{}\", mentioned in zome_config of instance \"{}\"",
+                        zome_name, instance.dna, instance.id
+                    )
+                })?;
+            }
+
             for zome in dna.zomes.values() {
                 for bridge in zome.bridges.iter() {
                     if bridge.presence == BridgePresence::Required {
@@ -699,6 +709,10 @@ pub struct DnaConfiguration {
     pub hash: String,
     #[serde(default)]
     pub uuid: Option<String>,
+    /// Properties that get merged into the `properties` of the DNA when it is loaded.
+    /// This changes the DNA hash, so `hash` has to be the hash of the DNA with these applied.
+    #[serde(default)]
+    pub properties: Option<serde_json::Value>,
 }
 
 impl TryFrom<DnaConfiguration> for Dna {
@@ -707,7 +721,11 @@ impl TryFrom<DnaConfiguration> for Dna {
         let mut f = File::open(dna_config.file)?;
         let mut contents = String::new();
         f.read_to_string(&mut contents)?;
-        Dna::try_from(JsonString::from_json(&contents)).map_err(|err| err.into())
+        let mut dna = Dna::try_from(JsonString::from_json(&contents))?;
+        if let Some(properties) = &dna_config.properties {
+            json_patch::merge(&mut dna.properties, properties);
+        }
+        Ok(dna)
     }
 }
 
@@ -719,6 +737,43 @@ pub struct InstanceConfiguration {
     pub dna: String,
     pub agent: String,
     pub storage: StorageConfiguration,
+    /// Number of workers validating incoming DHT data concurrently.
+    /// Uses the default of holochain_core if not set.
+    #[serde(default)]
+    pub holding_workflow_pool_size: Option<usize>,
+    /// If set, the instance stops accepting direct messages and DHT data from agents
+    /// it holds a warrant against.
+    #[serde(default)]
+    pub auto_block_warranted_agents: bool,
+    /// Credential (like an invite code or a certificate) that gets committed with the
+    /// agent entry when the instance is initialized. The DNA's agent validation decides
+    /// if it lets the agent join.
+    #[serde(default)]
+    pub membrane_proof: Option<MembraneProof>,
+    /// Configs that replace the `config` of the named zomes for this instance only.
+    /// They are returned by `hdk::zome_config()` instead of the config given in the DNA.
+    /// The DNA and its hash stay the same, so instances with different zome configs
+    /// still join the same network.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub zome_config: BTreeMap<String, serde_json::Value>,
+    /// Properties that get merged into the `properties` of the DNA for this instance only,
+    /// on top of the ones of the DNA configuration.
+    /// This changes the DNA hash, so the instance joins its own network.
+    #[serde(default)]
+    pub properties: Option<serde_json::Value>,
+    /// Expected hash of the DNA of this instance with its `properties` applied.
+    /// Loading the instance fails if the hash differs.
+    #[serde(default)]
+    pub dna_hash: Option<String>,
+    /// Milliseconds for which results of get_entry and get_links network queries may be
+    /// used by reads with the `CacheOk` cache policy.
+    /// Uses the default of holochain_core if not set.
+    #[serde(default)]
+    pub query_cache_ttl_ms: Option<u64>,
+    /// Maximum number of network query results the instance caches.
+    /// Uses the default of holochain_core if not set.
+    #[serde(default)]
+    pub query_cache_size: Option<usize>,
 }
 
 /// This configures the Content Addressable Storage (CAS) that
@@ -1086,6 +1141,7 @@ pub mod tests {
     id = "app spec instance"
     dna = "app spec rust"
     agent = "test agent"
+    membrane_proof = "invite code 42"
         [instances.storage]
         type = "file"
         path = "app_spec_storage"
@@ -1146,6 +1202,10 @@ pub mod tests {
         assert_eq!(instance_config.id, "app spec instance");
         assert_eq!(instance_config.dna, "app spec rust");
         assert_eq!(instance_config.agent, "test agent");
+        assert_eq!(
+            instance_config.membrane_proof,
+            Some(MembraneProof::new("invite code 42"))
+        );
         assert_eq!(config.logger.logger_level, "debug");
         assert_eq!(config.logger.rules.rules.len(), 1);
 
@@ -1232,6 +1292,79 @@ pub mod tests {
         assert_eq!(config.check_consistency(&mut test_dna_loader()), Err("DNA configuration \"WRONG DNA ID\" not found, mentioned in instance \"app spec instance\"".to_string()));
     }
 
+    #[test]
+    fn test_zome_config_overrides() {
+        let toml = r#"
+    [[agents]]
+    id = "test agent"
+    name = "Holo Tester 1"
+    public_address = "HoloTester1-------------------------------------------------------------------------AHi1"
+    keystore_file = "holo_tester.key"
+
+    [[dnas]]
+    id = "app spec rust"
+    file = "app_spec.dna.json"
+    hash = "Qm328wyq38924y"
+
+    [[instances]]
+    id = "app spec instance"
+    dna = "app spec rust"
+    agent = "test agent"
+        [instances.storage]
+        type = "memory"
+        [instances.zome_config.blog]
+        max_length = 140
+    "#;
+
+        let config: Configuration =
+            load_configuration(toml).expect("Failed to load config from toml string");
+
+        assert_eq!(
+            config.instances[0].zome_config.get("blog"),
+            Some(&json!({ "max_length": 140 }))
+        );
+        assert_eq!(config.check_consistency(&mut test_dna_loader()), Err("Zome \"blog\" not found in DNA \"app spec rust\", mentioned in zome_config of instance \"app spec instance\"".to_string()));
+    }
+
+    #[test]
+    fn test_dna_properties_overrides() {
+        let toml = r#"
+    [[agents]]
+    id = "test agent"
+    name = "Holo Tester 1"
+    public_address = "HoloTester1-------------------------------------------------------------------------AHi1"
+    keystore_file = "holo_tester.key"
+
+    [[dnas]]
+    id = "app spec rust"
+    file = "app_spec.dna.json"
+    hash = "Qm328wyq38924y"
+        [dnas.properties]
+        network_size = 10
+
+    [[instances]]
+    id = "app spec instance"
+    dna = "app spec rust"
+    agent = "test agent"
+        [instances.storage]
+        type = "memory"
+        [instances.properties]
+        network_size = 20
+    "#;
+
+        let config: Configuration =
+            load_configuration(toml).expect("Failed to load config from toml string");
+
+        assert_eq!(
+            config.dnas[0].properties,
+            Some(json!({ "network_size": 10 }))
+        );
+        assert_eq!(
+            config.instances[0].properties,
+            Some(json!({ "network_size": 20 }))
+        );
+    }
+
     #[test]
     fn test_inconsistent_config_interface_1() {
         let toml = r#"
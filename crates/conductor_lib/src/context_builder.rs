@@ -1,5 +1,12 @@
-use holochain_core::{context::Context, persister::SimplePersister, signal::SignalSender};
-use holochain_core_types::{agent::AgentId, eav::Attribute, error::HolochainError};
+use holochain_core::{
+    context::Context,
+    network::query_cache::{QueryCache, DEFAULT_QUERY_CACHE_SIZE, DEFAULT_QUERY_CACHE_TTL},
+    persister::SimplePersister,
+    signal::SignalSender,
+};
+use holochain_core_types::{
+    agent::AgentId, eav::Attribute, error::HolochainError, membrane_proof::MembraneProof,
+};
 use holochain_locksmith::RwLock;
 use holochain_net::p2p_config::P2pConfig;
 use holochain_persistence_api::{
@@ -13,9 +20,11 @@ use holochain_tracing;
 
 use jsonrpc_core::IoHandler;
 use std::{
+    collections::BTreeMap,
     fs,
     path::{Path, PathBuf},
     sync::Arc,
+    time::Duration,
 };
 
 use holochain_metrics::{DefaultMetricPublisher, MetricPublisher, MetricPublisherConfig};
@@ -42,6 +51,12 @@ pub struct ContextBuilder {
     signal_tx: Option<SignalSender>,
     tracer: Option<holochain_tracing::Tracer>,
     state_dump_logging: bool,
+    holding_workflow_pool_size: Option<usize>,
+    auto_block_warranted_agents: bool,
+    membrane_proof: Option<MembraneProof>,
+    zome_config_overrides: BTreeMap<String, serde_json::Value>,
+    query_cache_ttl: Option<Duration>,
+    query_cache_size: Option<usize>,
     metric_publisher: Option<Arc<RwLock<dyn MetricPublisher>>>,
 }
 
@@ -58,6 +73,12 @@ impl ContextBuilder {
             signal_tx: None,
             tracer: None,
             state_dump_logging: false,
+            holding_workflow_pool_size: None,
+            auto_block_warranted_agents: false,
+            membrane_proof: None,
+            zome_config_overrides: BTreeMap::new(),
+            query_cache_ttl: None,
+            query_cache_size: None,
             metric_publisher: None,
         }
     }
@@ -173,6 +194,46 @@ impl ContextBuilder {
         self
     }
 
+    /// Sets the number of workers that validate incoming DHT data concurrently.
+    pub fn with_holding_workflow_pool_size(mut self, pool_size: usize) -> Self {
+        self.holding_workflow_pool_size = Some(pool_size);
+        self
+    }
+
+    /// Makes the instance ignore direct messages and DHT data from agents
+    /// that it holds a warrant against.
+    pub fn with_warranted_agents_blocked(mut self) -> Self {
+        self.auto_block_warranted_agents = true;
+        self
+    }
+
+    /// Sets the membrane proof that gets committed with the agent entry at genesis.
+    pub fn with_membrane_proof(mut self, membrane_proof: MembraneProof) -> Self {
+        self.membrane_proof = Some(membrane_proof);
+        self
+    }
+
+    /// Sets the configs that replace the ones given in the DNA, by zome name.
+    pub fn with_zome_config_overrides(
+        mut self,
+        zome_config_overrides: BTreeMap<String, serde_json::Value>,
+    ) -> Self {
+        self.zome_config_overrides = zome_config_overrides;
+        self
+    }
+
+    /// Sets how long results of network queries may be used by reads that accept cached data.
+    pub fn with_query_cache_ttl(mut self, ttl: Duration) -> Self {
+        self.query_cache_ttl = Some(ttl);
+        self
+    }
+
+    /// Sets how many results of network queries get cached at most.
+    pub fn with_query_cache_size(mut self, size: usize) -> Self {
+        self.query_cache_size = Some(size);
+        self
+    }
+
     pub fn with_metric_publisher(mut self, config: &MetricPublisherConfig) -> Self {
         let config = match &config {
             MetricPublisherConfig::CloudWatchLogs(config) => {
@@ -212,7 +273,7 @@ impl ContextBuilder {
             .metric_publisher
             .unwrap_or_else(|| Arc::new(RwLock::new(DefaultMetricPublisher::default())));
 
-        Context::new(
+        let mut context = Context::new(
             &self
                 .instance_name
                 .unwrap_or_else(|| "Anonymous-instance".to_string()),
@@ -233,7 +294,23 @@ impl ContextBuilder {
                 self.tracer
                     .unwrap_or_else(|| holochain_tracing::null_tracer()),
             ),
-        )
+        );
+        if let Some(pool_size) = self.holding_workflow_pool_size {
+            context.holding_workflow_pool_size = pool_size;
+        }
+        context.auto_block_warranted_agents = self.auto_block_warranted_agents;
+        context.membrane_proof = self.membrane_proof;
+        context.zome_config_overrides = self.zome_config_overrides;
+        if self.query_cache_ttl.is_some() || self.query_cache_size.is_some() {
+            *context
+                .query_cache
+                .lock()
+                .expect("Could not lock query cache") = QueryCache::new(
+                self.query_cache_size.unwrap_or(DEFAULT_QUERY_CACHE_SIZE),
+                self.query_cache_ttl.unwrap_or(DEFAULT_QUERY_CACHE_TTL),
+            );
+        }
+        context
     }
 }
 
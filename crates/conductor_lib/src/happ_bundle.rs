@@ -1,6 +1,6 @@
 use crate::{config::*, port_utils::get_free_port, NEW_RELIC_LICENSE_KEY};
 use boolinator::Boolinator;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Serialize, Deserialize)]
 pub struct HappBundle {
@@ -113,6 +113,7 @@ impl HappBundle {
                     file,
                     hash: happ_instance.dna_hash.clone(),
                     uuid: None,
+                    properties: None,
                 }
             })
             .collect::<Vec<_>>();
@@ -125,6 +126,14 @@ impl HappBundle {
                 dna: happ_instance.id.clone(),
                 agent: agent_config.id.clone(),
                 storage: storage.clone(),
+                holding_workflow_pool_size: None,
+                auto_block_warranted_agents: false,
+                membrane_proof: None,
+                zome_config: BTreeMap::new(),
+                properties: None,
+                query_cache_ttl_ms: None,
+                query_cache_size: None,
+                dna_hash: None,
             })
             .collect::<Vec<_>>();
 
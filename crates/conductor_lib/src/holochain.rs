@@ -101,6 +101,7 @@ use holochain_core::{
     nucleus::{call_zome_function, ZomeFnCall},
     persister::{Persister, SimplePersister},
     wasm_engine::{run_dna, WasmCallData},
+    workflows::chain_fork::resolve_own_chain_forks,
 };
 use holochain_core_types::{
     dna::{capabilities::CapabilityRequest, Dna},
@@ -110,6 +111,10 @@ use holochain_core_types::{
 use holochain_json_api::json::JsonString;
 
 use holochain_core::{
+    dht::{
+        actions::abandon_holding_workflow::retry_abandoned_holding_workflows,
+        dht_store::ChainForkReport, pending_validations::PendingValidationReport,
+    },
     state::StateWrapper,
     state_dump::{address_to_content_and_type, StateDump},
 };
@@ -299,6 +304,58 @@ impl Holochain {
         )))
     }
 
+    pub fn get_pending_validations(
+        &self,
+    ) -> Result<Vec<PendingValidationReport>, HolochainInstanceError> {
+        self.check_instance()?;
+        Ok(self
+            .context
+            .clone()
+            .expect("Context must be Some since we've checked it with check_instance()? above")
+            .state()
+            .ok_or(HolochainInstanceError::InstanceNotInitialized)?
+            .dht()
+            .pending_validation_reports())
+    }
+
+    pub fn get_chain_forks(&self) -> Result<Vec<ChainForkReport>, HolochainInstanceError> {
+        self.check_instance()?;
+        Ok(self
+            .context
+            .clone()
+            .expect("Context must be Some since we've checked it with check_instance()? above")
+            .state()
+            .ok_or(HolochainInstanceError::InstanceNotInitialized)?
+            .dht()
+            .chain_fork_reports()?)
+    }
+
+    /// Marks all recorded forks of this instance's source chain as resolved
+    /// so that it can author entries again.
+    pub fn resolve_chain_forks(&self) -> Result<(), HolochainInstanceError> {
+        self.check_instance()?;
+        resolve_own_chain_forks(
+            &self
+                .context
+                .clone()
+                .expect("Context must be Some since we've checked it with check_instance()? above"),
+        );
+        Ok(())
+    }
+
+    /// Puts all holding workflows of this instance that were abandoned after too many
+    /// failed attempts back into the validation queue.
+    pub fn retry_abandoned_validations(&self) -> Result<(), HolochainInstanceError> {
+        self.check_instance()?;
+        retry_abandoned_holding_workflows(
+            &self
+                .context
+                .clone()
+                .expect("Context must be Some since we've checked it with check_instance()? above"),
+        );
+        Ok(())
+    }
+
     pub fn get_type_and_content_from_cas(
         &self,
         address: &Address,
@@ -5,7 +5,8 @@ use holochain_core::nucleus::actions::call_zome_function::make_cap_request_for_c
 
 use crate::Holochain;
 use holochain_core_types::{
-    agent::AgentId, dna::capabilities::CapabilityRequest, signature::Provenance,
+    agent::AgentId, dna::capabilities::CapabilityRequest, membrane_proof::MembraneProof,
+    signature::Provenance,
 };
 use holochain_dpki::key_bundle::KeyBundle;
 use holochain_json_api::json::JsonString;
@@ -395,6 +396,9 @@ impl ConductorApiBuilder {
     ///     * `id`: [string] Name for the new instance
     ///     * `agent_id`: [string] Agent to run this instance with
     ///     * `dna_id`: [string] DNA to run in this instance
+    ///     * `storage`: [string] (optional) One of `memory`, `file`, `pickle` or `lmdb` (default)
+    ///     * `membrane_proof`: [string] (optional) Credential to join the DNA with, like an
+    ///       invite code. Gets committed with the agent entry and checked by the DNA's agent validation
     ///
     ///  * `admin/instance/remove`
     ///     Removes an instance. Also remove its any uses of it in interfaces.
@@ -411,6 +415,18 @@ impl ConductorApiBuilder {
     ///     Params:
     ///     * `id`: [string] Which instance to stop?
     ///
+    ///  * `admin/instance/resolve_forks`
+    ///     Marks all recorded forks of an instance's source chain as resolved so that it
+    ///     can author entries again (see `debug/forks`).
+    ///     Params:
+    ///     * `id`: [string] Which instance's forks to resolve?
+    ///
+    ///  * `admin/instance/retry_validations`
+    ///     Puts all validations of an instance that were abandoned after too many failed
+    ///     attempts back into its validation queue (see `debug/pending_validations`).
+    ///     Params:
+    ///     * `id`: [string] Which instance's validations to retry?
+    ///
     ///  * `admin/instance/list`
     ///     Returns an array of all instances that are configured.
     ///
@@ -547,11 +563,15 @@ impl ConductorApiBuilder {
             let dna_id = Self::get_as_string("dna_id", &params_map)?;
             let agent_id = Self::get_as_string("agent_id", &params_map)?;
             let storage = Self::get_as_string("storage", &params_map).ok();
+            let membrane_proof = Self::get_as_string("membrane_proof", &params_map)
+                .ok()
+                .map(MembraneProof::from);
             conductor_call!(|c| c.add_instance(
                 &id,
                 &dna_id,
                 &agent_id,
-                storage.as_ref().map(String::as_str)
+                storage.as_ref().map(String::as_str),
+                membrane_proof
             ))?;
             Ok(json!({"success": true}))
         });
@@ -577,6 +597,22 @@ impl ConductorApiBuilder {
             Ok(json!({"success": true}))
         });
 
+        self.io
+            .add_method("admin/instance/resolve_forks", move |params| {
+                let params_map = Self::unwrap_params_map(params)?;
+                let id = Self::get_as_string("id", &params_map)?;
+                conductor_call!(|c| c.resolve_chain_forks(&id))?;
+                Ok(json!({"success": true}))
+            });
+
+        self.io
+            .add_method("admin/instance/retry_validations", move |params| {
+                let params_map = Self::unwrap_params_map(params)?;
+                let id = Self::get_as_string("id", &params_map)?;
+                conductor_call!(|c| c.retry_abandoned_validations(&id))?;
+                Ok(json!({"success": true}))
+            });
+
         self.io.add_method("admin/instance/list", move |_params| {
             let instances = conductor_call!(
                 |c| Ok(c.config().instances) as Result<Vec<InstanceConfiguration>, String>
@@ -771,6 +807,24 @@ impl ConductorApiBuilder {
     ///   - `held_aspects` [bool] (optional) If set to false, will exclude the holding map entries
     ///   - `queued_holding_workflows` [bool] (optional If set to false, will exclude contents of the validation queue
     ///
+    /// - `debug/pending_validations`
+    ///   Returns an array with one object per entry aspect that is queued for validation,
+    ///   currently being validated or was abandoned after too many attempts.
+    ///   Each object lists the entry and header address, the holding workflow, its status,
+    ///   the number of failed attempts, the last failure reason, the dependencies that are
+    ///   not held yet and how long it will take until the next attempt.
+    ///   Params:
+    ///   - `instance_id` ID of the instance of which the pending validations are requested
+    ///
+    /// - `debug/forks`
+    ///   Returns an array with one object per source chain fork the instance has seen
+    ///   while holding headers. Each object lists the author, the header both forking
+    ///   headers link to, the forking headers and whether the fork was resolved.
+    ///   Unresolved forks of the instance's own chain stop it from authoring entries
+    ///   until they get resolved with `admin/instance/resolve_forks`.
+    ///   Params:
+    ///   - `instance_id` ID of the instance of which the forks are requested
+    ///
     /// - `debug/fetch_cas`
     ///   Returns content of a given instance's CAS.
     ///   Params:
@@ -808,6 +862,24 @@ impl ConductorApiBuilder {
                 .map_err(|e| jsonrpc_core::Error::invalid_params(e.to_string()))?)
         });
 
+        self.io
+            .add_method("debug/pending_validations", move |params| {
+                let params_map = Self::unwrap_params_map(params)?;
+                let instance_id = Self::get_as_string("instance_id", &params_map)?;
+                let pending =
+                    conductor_call!(|c| c.pending_validations_for_instance(&instance_id))?;
+                Ok(serde_json::to_value(pending)
+                    .map_err(|e| jsonrpc_core::Error::invalid_params(e.to_string()))?)
+            });
+
+        self.io.add_method("debug/forks", move |params| {
+            let params_map = Self::unwrap_params_map(params)?;
+            let instance_id = Self::get_as_string("instance_id", &params_map)?;
+            let forks = conductor_call!(|c| c.chain_forks_for_instance(&instance_id))?;
+            Ok(serde_json::to_value(forks)
+                .map_err(|e| jsonrpc_core::Error::invalid_params(e.to_string()))?)
+        });
+
         self.io.add_method("debug/fetch_cas", move |params| {
             let params_map = Self::unwrap_params_map(params)?;
             let instance_id = Self::get_as_string("instance_id", &params_map)?;
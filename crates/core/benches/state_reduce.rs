@@ -0,0 +1,128 @@
+//! Benchmarks for the per-action cost of reducing the DHT state-slice.
+//!
+//! Every reducer produces a new `DhtStore` from the previous one by cloning it.
+//! The queue of holding workflows used to be a `VecDeque`, so every clone copied
+//! all queued validations. It is a persistent `im::Vector` now, which shares all
+//! unchanged data with the previous version. The `*_copying_the_queue` benchmarks
+//! are the baseline from before: the same reduction plus a full copy of the queue.
+//!
+//! Run with `cargo bench -p holochain_core`.
+
+#![feature(test)]
+
+extern crate test;
+
+use holochain_core::{
+    action::{Action, ActionWrapper},
+    dht::{
+        dht_reducers::reduce,
+        dht_store::DhtStore,
+        pending_validations::{PendingValidationStruct, ValidatingWorkflow},
+    },
+    network::entry_with_header::EntryWithHeader,
+};
+use holochain_core_types::{
+    chain_header::{test_provenances, ChainHeader},
+    entry::{entry_type::AppEntryType, Entry},
+    network::entry_aspect::EntryAspect,
+    time::test_iso_8601,
+};
+use holochain_json_api::json::JsonString;
+use holochain_locksmith::RwLock;
+use holochain_persistence_api::cas::content::AddressableContent;
+use holochain_persistence_mem::{cas::memory::MemoryStorage, eav::memory::EavMemoryStorage};
+use std::{collections::VecDeque, sync::Arc};
+use test::Bencher;
+
+/// Number of entries held in the benchmarked DHT shard
+const SHARD_SIZE: usize = 10_000;
+
+/// Number of pending validations queued in the benchmarked DHT shard
+const QUEUE_SIZE: usize = 1_000;
+
+fn bench_entry(i: usize) -> Entry {
+    Entry::App(
+        AppEntryType::from("bench"),
+        JsonString::from_json(&format!("\"bench entry {}\"", i)),
+    )
+}
+
+fn bench_header(entry: &Entry) -> ChainHeader {
+    ChainHeader::new(
+        &entry.entry_type(),
+        &entry.address(),
+        &test_provenances("sig"),
+        &None,
+        &None,
+        &None,
+        &test_iso_8601(),
+    )
+}
+
+fn content_aspect(i: usize) -> EntryAspect {
+    let entry = bench_entry(i);
+    let header = bench_header(&entry);
+    EntryAspect::Content(entry, header)
+}
+
+fn queue_action(i: usize) -> ActionWrapper {
+    let entry = bench_entry(i);
+    let header = bench_header(&entry);
+    let pending = PendingValidationStruct::new(
+        EntryWithHeader { entry, header },
+        ValidatingWorkflow::HoldEntry,
+    );
+    ActionWrapper::new(Action::QueueHoldingWorkflow((Arc::new(pending), None)))
+}
+
+/// Builds a DHT store that holds `SHARD_SIZE` entries and has `QUEUE_SIZE`
+/// pending validations queued.
+fn large_shard() -> Arc<DhtStore> {
+    let store = DhtStore::new(
+        Arc::new(RwLock::new(MemoryStorage::new())),
+        Arc::new(RwLock::new(EavMemoryStorage::new())),
+    );
+    let store = (0..SHARD_SIZE).fold(Arc::new(store), |store, i| {
+        reduce(
+            store,
+            &ActionWrapper::new(Action::HoldAspect(content_aspect(i))),
+        )
+    });
+    (SHARD_SIZE..SHARD_SIZE + QUEUE_SIZE).fold(store, |store, i| reduce(store, &queue_action(i)))
+}
+
+#[bench]
+fn hold_aspect_on_large_shard(b: &mut Bencher) {
+    let store = large_shard();
+    let action = ActionWrapper::new(Action::HoldAspect(content_aspect(SHARD_SIZE * 2)));
+    b.iter(|| reduce(store.clone(), &action));
+}
+
+#[bench]
+fn queue_holding_workflow_on_large_shard(b: &mut Bencher) {
+    let store = large_shard();
+    let action = queue_action(SHARD_SIZE * 2);
+    b.iter(|| reduce(store.clone(), &action));
+}
+
+/// Reduces the given action like `reduce` did while the queue was a `VecDeque`,
+/// i.e. with a full copy of the queue when cloning the store for the new state.
+fn reduce_copying_the_queue(store: &Arc<DhtStore>, action: &ActionWrapper) -> Arc<DhtStore> {
+    let queue: VecDeque<_> = store.queued_holding_workflows().iter().cloned().collect();
+    test::black_box(queue);
+    reduce(store.clone(), action)
+}
+
+#[bench]
+fn hold_aspect_on_large_shard_copying_the_queue(b: &mut Bencher) {
+    let store = large_shard();
+    let action = ActionWrapper::new(Action::HoldAspect(content_aspect(SHARD_SIZE * 2)));
+    b.iter(|| reduce_copying_the_queue(&store, &action));
+}
+
+#[bench]
+fn queue_holding_workflow_on_large_shard_copying_the_queue(b: &mut Bencher) {
+    let store = large_shard();
+    let action = queue_action(SHARD_SIZE * 2);
+    b.iter(|| reduce_copying_the_queue(&store, &action));
+}
@@ -1,6 +1,6 @@
 use crate::{
     agent::state::AgentState,
-    dht::pending_validations::PendingValidation,
+    dht::pending_validations::{HoldingWorkflowFollowUp, PendingValidation},
     network::{
         direct_message::DirectMessage,
         entry_aspect::EntryAspect,
@@ -17,11 +17,21 @@ use crate::{
 };
 
 use holochain_core_types::{
-    chain_header::ChainHeader, crud_status::CrudStatus, dna::Dna, entry::Entry,
-    signature::Provenance, validation::ValidationPackage,
+    agent_activity::{AgentActivityFilter, ChainForkEvidence},
+    chain_header::ChainHeader,
+    countersigning::CountersigningOutcome,
+    crud_status::CrudStatus,
+    custom_validation_package::CustomValidationPackage,
+    dna::Dna,
+    entry::{purged_entry::PurgedEntry, Entry},
+    signature::Provenance,
+    validation::ValidationPackage,
+    validation_receipt::ValidationReceipt,
+    warrant::Warrant,
 };
 use holochain_net::{connection::net_connection::NetHandler, p2p_config::P2pConfig};
 use holochain_persistence_api::cas::content::Address;
+use holochain_wasm_utils::api_serialization::emit_signal::EmitSignalArgs;
 use lib3h_protocol::data_types::{EntryListData, FetchEntryData, QueryEntryData};
 use snowflake;
 use std::{
@@ -86,6 +96,8 @@ impl Hash for ActionWrapper {
 pub enum QueryKey {
     Entry(GetEntryKey),
     Links(GetLinksKey),
+    Entries(GetEntriesKey),
+    AgentActivity(GetAgentActivityKey),
 }
 
 ///This is a payload for the Get Method
@@ -93,6 +105,8 @@ pub enum QueryKey {
 pub enum QueryPayload {
     Entry,
     Links((Option<CrudStatus>, GetLinksNetworkQuery)),
+    Entries,
+    AgentActivity(AgentActivityFilter),
 }
 
 /// All Actions for the Holochain Instance Store, according to Redux pattern.
@@ -111,6 +125,14 @@ pub enum Action {
     /// Does not validate, assumes entry is valid.
     Commit((Entry, Option<Address>, Vec<Provenance>)),
 
+    /// Stores the signed package our custom validation package callback built for
+    /// one of our headers next to it, to be served to validators.
+    AddCustomValidationPackage(CustomValidationPackage),
+
+    /// Overwrites the content of one of our private entries with the given tombstone,
+    /// in the chain storage as well as in the local DHT storage.
+    PurgeEntry(PurgedEntry),
+
     // -------------
     // DHT actions:
     // -------------
@@ -122,10 +144,31 @@ pub enum Action {
     /// Removes the given item from the holding queue.
     RemoveQueuedHoldingWorkflow(PendingValidation),
 
+    /// Moves the given item from the holding queue to the list of holding workflows
+    /// that are currently being processed by the holding worker pool.
+    StartHoldingWorkflow(PendingValidation),
+
+    /// Removes the given item from the list of holding workflows in process
+    /// and queues or abandons its retry if the attempt has to be repeated.
+    FinishHoldingWorkflow((PendingValidation, HoldingWorkflowFollowUp)),
+
+    /// Puts all abandoned holding workflows back into the queue
+    /// so they get another full set of attempts.
+    RetryAbandonedHoldingWorkflows,
+
     /// Adds an entry aspect to the local DHT shard.
     /// Does not validate, assumes referenced entry is valid.
     HoldAspect(EntryAspect),
 
+    /// Stores a validation receipt that a validator sent us for one of our entries.
+    AddValidationReceipt(ValidationReceipt),
+
+    /// Stores evidence that an agent forked its source chain.
+    RecordChainFork(ChainForkEvidence),
+
+    /// Marks all recorded forks of the source chain of the given agent as resolved.
+    ResolveChainForks(Address),
+
     //action for updating crudstatus
     CrudStatus((EntryWithHeader, CrudStatus)),
 
@@ -148,6 +191,22 @@ pub enum Action {
     /// Note that the given address is that of the entry NOT the address of the header itself
     PublishHeaderEntry(Address),
 
+    /// Publish to the network a warrant about an entry that failed validation.
+    /// It gets published as aspect of the invalid entry.
+    PublishWarrant(Warrant),
+
+    /// Makes the network module send the given validation receipt to the author
+    /// with the given address. Receipts don't get a response.
+    SendValidationReceipt((Address, ValidationReceipt)),
+
+    /// Makes the network module send the outcome of a countersigning session
+    /// to the signer with the given address. Outcomes don't get a response.
+    SendCountersigningOutcome((Address, CountersigningOutcome)),
+
+    /// Makes the network module send the given signal to the agent with the given
+    /// address, whose conductor emits it. Signals don't get a response.
+    SendRemoteSignal((Address, EmitSignalArgs)),
+
     /// Performs a Network Query Action based on the key and payload, used for links and Entries.
     /// Includes the timeout information: system time of dispatch and duration until it timeouts.
     Query((QueryKey, QueryPayload, Option<(SystemTime, Duration)>)),
@@ -207,6 +266,11 @@ pub enum Action {
     /// Clean up the custom send response result so the state doesn't grow indefinitely.
     ClearCustomSendResponse(String),
 
+    /// The network stored our direct message with the given ID for its disconnected
+    /// recipient, so there won't be a response. Resolves the send as queued.
+    /// Triggered from the network handler.
+    HandleQueuedDirectMessage(String),
+
     /// Sends the given data as JsonProtocol::HandleGetAuthoringEntryListResult
     RespondAuthoringList(EntryListData),
 
@@ -279,6 +343,27 @@ pub struct GetEntryKey {
     pub id: String,
 }
 
+/// The unique key that represents a batched Get request for several entries
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize)]
+pub struct GetEntriesKey {
+    /// The addresses of the entries to get.
+    /// The query gets sent to the authority of the first one.
+    pub addresses: Vec<Address>,
+
+    /// A unique ID that is used to pair the eventual result to this request
+    pub id: String,
+}
+
+/// The unique key that represents a query for the published headers of an agent
+#[derive(Clone, PartialEq, Eq, Hash, Debug, Serialize)]
+pub struct GetAgentActivityKey {
+    /// The address of the agent whose activity we want
+    pub agent: Address,
+
+    /// A unique ID that is used to pair the eventual result to this request
+    pub id: String,
+}
+
 /// Everything the network module needs to know in order to send a
 /// direct message.
 #[derive(Clone, PartialEq, Debug, Serialize)]
@@ -296,6 +381,10 @@ pub struct DirectMessageData {
     /// Should be true if we are responding to a previous message with this message.
     /// msg_id should then be the same as the in the message that we received.
     pub is_response: bool,
+
+    /// If the recipient is not connected, have the network keep the message and deliver
+    /// it once the recipient reconnects, instead of failing.
+    pub deliver_later: bool,
 }
 
 /// Everything the network needs to initialize
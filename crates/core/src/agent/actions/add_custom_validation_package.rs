@@ -0,0 +1,72 @@
+use crate::{
+    action::{Action, ActionWrapper},
+    agent::state::AgentActionResponse,
+    context::Context,
+    instance::dispatch_action,
+    NEW_RELIC_LICENSE_KEY,
+};
+use futures::{future::Future, task::Poll};
+use holochain_core_types::{
+    custom_validation_package::CustomValidationPackage, error::HolochainError,
+};
+use holochain_persistence_api::cas::content::Address;
+use std::{pin::Pin, sync::Arc};
+
+/// Stores the given custom validation package next to its header in our chain store.
+/// Resolves to the address the package got stored under once it is stored.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub async fn add_custom_validation_package(
+    package: CustomValidationPackage,
+    context: &Arc<Context>,
+) -> Result<Address, HolochainError> {
+    let action_wrapper = ActionWrapper::new(Action::AddCustomValidationPackage(package));
+    dispatch_action(context.action_channel(), action_wrapper.clone());
+    AddCustomValidationPackageFuture {
+        context: context.clone(),
+        action: action_wrapper,
+    }
+    .await
+}
+
+/// AddCustomValidationPackageFuture resolves to the result of storing the package
+/// Tracks the state for a response to its ActionWrapper
+pub struct AddCustomValidationPackageFuture {
+    context: Arc<Context>,
+    action: ActionWrapper,
+}
+
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+impl Future for AddCustomValidationPackageFuture {
+    type Output = Result<Address, HolochainError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context) -> Poll<Self::Output> {
+        if let Some(err) = self
+            .context
+            .action_channel_error("AddCustomValidationPackageFuture")
+        {
+            return Poll::Ready(Err(err));
+        }
+        //
+        // TODO: connect the waker to state updates for performance reasons
+        // See: https://github.com/holochain/holochain-rust/issues/314
+        //
+        cx.waker().clone().wake();
+        if let Some(state) = self.context.try_state() {
+            match state.agent().actions().get(&self.action) {
+                Some(r) => match r.response() {
+                    AgentActionResponse::AddCustomValidationPackage(result) => {
+                        dispatch_action(
+                            self.context.action_channel(),
+                            ActionWrapper::new(Action::ClearActionResponse(*self.action.id())),
+                        );
+                        Poll::Ready(result.clone())
+                    }
+                    _ => unreachable!(),
+                },
+                None => Poll::Pending,
+            }
+        } else {
+            Poll::Pending
+        }
+    }
+}
@@ -6,7 +6,7 @@ use crate::{
     NEW_RELIC_LICENSE_KEY,
 };
 use futures::{future::Future, task::Poll};
-use holochain_core_types::{entry::Entry, error::HolochainError};
+use holochain_core_types::{entry::Entry, error::HolochainError, signature::Provenance};
 use holochain_persistence_api::cas::content::Address;
 use std::{pin::Pin, sync::Arc};
 
@@ -21,10 +21,27 @@ pub async fn commit_entry(
     maybe_link_update_delete: Option<Address>,
     context: &Arc<Context>,
 ) -> Result<Address, HolochainError> {
+    commit_entry_with_provenances(entry, maybe_link_update_delete, vec![], context).await
+}
+
+/// Commits the entry with a header that carries the given provenances of other agents
+/// in addition to our own.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub async fn commit_entry_with_provenances(
+    entry: Entry,
+    maybe_link_update_delete: Option<Address>,
+    provenances: Vec<Provenance>,
+    context: &Arc<Context>,
+) -> Result<Address, HolochainError> {
+    context
+        .query_cache
+        .lock()
+        .expect("Could not lock query cache")
+        .invalidate_commit(&entry, maybe_link_update_delete.as_ref());
     let action_wrapper = ActionWrapper::new(Action::Commit((
         entry.clone(),
         maybe_link_update_delete,
-        vec![],
+        provenances,
     )));
     dispatch_action(context.action_channel(), action_wrapper.clone());
     CommitFuture {
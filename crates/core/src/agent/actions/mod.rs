@@ -1 +1,2 @@
+pub mod add_custom_validation_package;
 pub mod commit;
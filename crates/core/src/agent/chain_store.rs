@@ -10,18 +10,21 @@ use holochain_core_types::{
         HcResult,
         RibosomeErrorCode::{self, *},
     },
+    time::Iso8601,
 };
 use holochain_locksmith::RwLock;
 use holochain_persistence_api::cas::{
     content::{Address, AddressableContent, Content},
     storage::ContentAddressableStorage,
 };
-use std::{str::FromStr, sync::Arc};
+use std::{collections::HashMap, str::FromStr, sync::Arc};
 
 #[derive(Debug, Clone)]
 pub struct ChainStore {
     // Storages holding local shard data
     content_storage: Arc<RwLock<dyn ContentAddressableStorage>>,
+    // Latest header of each EntryType, shared between all clones of this store
+    type_index: Arc<RwLock<ChainStoreTypeIndex>>,
 }
 
 impl PartialEq for ChainStore {
@@ -34,11 +37,28 @@ impl PartialEq for ChainStore {
     }
 }
 
+/// Index of the latest ChainHeader of every EntryType as seen from the chain header `top`.
+/// Type queries start from here and follow .link_same_type instead of walking the whole chain
+/// to find the first header of their EntryType.
+#[derive(Default, Debug)]
+struct ChainStoreTypeIndex {
+    top: Option<Address>,
+    latest_by_type: HashMap<EntryType, ChainHeader>,
+}
+
 #[derive(Default, Debug, Clone)]
 pub struct ChainStoreQueryOptions {
     pub start: usize,
     pub limit: usize,
     pub headers: bool,
+    /// Return the oldest headers first instead of the newest
+    pub reverse: bool,
+    /// Only include headers with a timestamp at or after this one
+    pub since: Option<Iso8601>,
+    /// Only include headers with a timestamp at or before this one
+    pub until: Option<Iso8601>,
+    /// Continue after the header with this address, as returned in ChainStoreQueryPage::next_cursor
+    pub cursor: Option<Address>,
 }
 
 #[derive(Debug)]
@@ -47,17 +67,27 @@ pub enum ChainStoreQueryResult {
     Headers(Vec<ChainHeader>),
 }
 
+#[derive(Debug)]
+pub struct ChainStoreQueryPage {
+    pub result: ChainStoreQueryResult,
+    /// Address of the last header returned if the page is full and there might be more
+    pub next_cursor: Option<Address>,
+}
+
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
 impl ChainStore {
     pub fn new(content_storage: Arc<RwLock<dyn ContentAddressableStorage>>) -> Self {
-        ChainStore { content_storage }
+        ChainStore {
+            content_storage,
+            type_index: Arc::new(RwLock::new(ChainStoreTypeIndex::default())),
+        }
     }
 
     pub fn iter(&self, start_chain_header: &Option<ChainHeader>) -> ChainStoreIterator {
         ChainStoreIterator::new(self.content_storage.clone(), start_chain_header.clone())
     }
 
-    /// Scans the local chain for the first Entry of EntryType, and then creates a
+    /// Looks up the latest Entry of EntryType in the type index, and then creates a
     /// ChainStoreTypeIter to return the sequence of all Entrys with the same EntryType. Requires a
     /// single EntryType.
     pub fn iter_type(
@@ -67,11 +97,74 @@ impl ChainStore {
     ) -> ChainStoreTypeIterator {
         ChainStoreTypeIterator::new(
             self.content_storage.clone(),
-            self.iter(start_chain_header)
-                .find(|chain_header| chain_header.entry_type() == entry_type),
+            self.latest_of_type(start_chain_header, entry_type),
         )
     }
 
+    /// Returns the header with the given address if it is part of the chain at or before
+    /// start_chain_header. Only the headers of its EntryType get walked, from the type index.
+    /// Headers of other chains that are in the same CAS, like the ones we hold for the DHT,
+    /// are not returned.
+    pub fn get_chain_header(
+        &self,
+        start_chain_header: &Option<ChainHeader>,
+        header_address: &Address,
+    ) -> HcResult<Option<ChainHeader>> {
+        let header = match self.get_raw(header_address)? {
+            Some(content) => match ChainHeader::try_from_content(&content) {
+                Ok(header) => header,
+                Err(_) => return Ok(None),
+            },
+            None => return Ok(None),
+        };
+        Ok(self
+            .iter_type(start_chain_header, header.entry_type())
+            .find(|chain_header| chain_header.address() == *header_address))
+    }
+
+    /// Adds a header that was just appended to the chain to the type index.
+    /// Headers that don't extend the indexed chain are ignored, the index then gets rebuilt
+    /// with the next type query.
+    pub fn index_header(&self, chain_header: &ChainHeader) {
+        let mut index = self.type_index.write().unwrap();
+        if index.top.is_some() && index.top == chain_header.link() {
+            index
+                .latest_by_type
+                .insert(chain_header.entry_type().clone(), chain_header.clone());
+            index.top = Some(chain_header.address());
+        }
+    }
+
+    /// Returns the latest header of the given EntryType at or before start_chain_header,
+    /// (re)building the type index by scanning the chain once if it is not at start_chain_header.
+    fn latest_of_type(
+        &self,
+        start_chain_header: &Option<ChainHeader>,
+        entry_type: &EntryType,
+    ) -> Option<ChainHeader> {
+        let start_chain_header = start_chain_header.as_ref()?;
+        let start_address = start_chain_header.address();
+        self.index_header(start_chain_header);
+        {
+            let index = self.type_index.read().unwrap();
+            if index.top.as_ref() == Some(&start_address) {
+                return index.latest_by_type.get(entry_type).cloned();
+            }
+        }
+
+        let mut latest_by_type = HashMap::new();
+        for chain_header in self.iter(&Some(start_chain_header.clone())) {
+            latest_by_type
+                .entry(chain_header.entry_type().clone())
+                .or_insert(chain_header);
+        }
+        let latest = latest_by_type.get(entry_type).cloned();
+        let mut index = self.type_index.write().unwrap();
+        index.top = Some(start_address);
+        index.latest_by_type = latest_by_type;
+        latest
+    }
+
     // Supply a None for options to get defaults (all elements, no ChainHeaders just Addresses)
     pub fn query(
         &self,
@@ -79,6 +172,20 @@ impl ChainStore {
         entry_type_names: &[&str],
         options: ChainStoreQueryOptions,
     ) -> Result<ChainStoreQueryResult, RibosomeErrorCode> {
+        self.query_page(start_chain_header, entry_type_names, options)
+            .map(|page| page.result)
+    }
+
+    /// Like query() but also returns a cursor to pass in ChainStoreQueryOptions::cursor to get
+    /// the next page.  Cursors are header addresses, so they stay valid while the chain grows.
+    /// Fails with UnknownQueryCursor if the cursor is not one of the headers selected by the
+    /// given entry type names and options.
+    pub fn query_page(
+        &self,
+        start_chain_header: &Option<ChainHeader>,
+        entry_type_names: &[&str],
+        options: ChainStoreQueryOptions,
+    ) -> Result<ChainStoreQueryPage, RibosomeErrorCode> {
         // Get entry_type name(s), if any.  If empty/blank, returns the complete source chain.  A
         // single matching entry type name with no glob pattern matching will use the single
         // entry_type optimization.  Otherwise, we'll construct a GlobSet match and scan the list to
@@ -97,28 +204,11 @@ impl ChainStore {
         } else {
             options.limit
         };
-        let headers = options.headers;
 
-        let vector = match entry_type_names {
-            // Vec<Address> or Vec<ChainHeader>
+        let matching: Box<dyn Iterator<Item = ChainHeader>> = match entry_type_names {
             [] | [""] | ["**"] => {
                 // No filtering desired; uses bare .iter()
-                if headers {
-                    ChainStoreQueryResult::Headers(
-                        self.iter(start_chain_header)
-                            .skip(start)
-                            .take(limit)
-                            .collect(),
-                    )
-                } else {
-                    ChainStoreQueryResult::Addresses(
-                        self.iter(start_chain_header)
-                            .skip(start)
-                            .take(limit)
-                            .map(|header| header.entry_address().to_owned())
-                            .collect(),
-                    )
-                }
+                Box::new(self.iter(start_chain_header))
             }
             [one] if !is_glob_str(one) => {
                 // Single EntryType without "glob" pattern; uses .iter_type()
@@ -126,22 +216,7 @@ impl ChainStore {
                     Ok(inner) => inner,
                     Err(..) => return Err(UnknownEntryType),
                 };
-                if headers {
-                    ChainStoreQueryResult::Headers(
-                        self.iter_type(start_chain_header, &entry_type)
-                            .skip(start)
-                            .take(limit)
-                            .collect(),
-                    )
-                } else {
-                    ChainStoreQueryResult::Addresses(
-                        self.iter_type(start_chain_header, &entry_type)
-                            .skip(start)
-                            .take(limit)
-                            .map(|header| header.entry_address().to_owned())
-                            .collect(),
-                    )
-                }
+                Box::new(self.iter_type(start_chain_header, &entry_type))
             }
             rest => {
                 // 1 or more EntryTypes, may or may not include glob wildcards.  Create a
@@ -159,32 +234,64 @@ impl ChainStore {
                     );
                 }
                 let globset = builder.build().map_err(|_| UnknownEntryType)?;
-                if headers {
-                    ChainStoreQueryResult::Headers(
-                        self.iter(start_chain_header)
-                            .filter(|header| {
-                                !globset.matches(header.entry_type().to_string()).is_empty()
-                            })
-                            .skip(start)
-                            .take(limit)
-                            .collect(),
-                    )
-                } else {
-                    ChainStoreQueryResult::Addresses(
-                        self.iter(start_chain_header)
-                            .filter(|header| {
-                                !globset.matches(header.entry_type().to_string()).is_empty()
-                            })
-                            .skip(start)
-                            .take(limit)
-                            .map(|header| header.entry_address().to_owned())
-                            .collect(),
-                    )
-                }
+                Box::new(self.iter(start_chain_header).filter(move |header| {
+                    !globset.matches(header.entry_type().to_string()).is_empty()
+                }))
             }
         };
 
-        Ok(vector)
+        // Timestamps of one chain are not guaranteed to be ordered, so we filter instead of
+        // stopping at the first header out of range.
+        let since = options.since;
+        let until = options.until;
+        let matching = matching.filter(move |header| {
+            since
+                .as_ref()
+                .map_or(true, |since| header.timestamp() >= since)
+                && until
+                    .as_ref()
+                    .map_or(true, |until| header.timestamp() <= until)
+        });
+
+        // Reversing needs the whole selection since headers only link to their predecessors
+        let mut ordered: Box<dyn Iterator<Item = ChainHeader>> = if options.reverse {
+            let mut all: Vec<ChainHeader> = matching.collect();
+            all.reverse();
+            Box::new(all.into_iter())
+        } else {
+            Box::new(matching)
+        };
+
+        if let Some(cursor) = options.cursor {
+            // Skips everything up to and including the cursor. A cursor that is not part of
+            // the selection (e.g. returned by a query with other names or options) can't be
+            // continued from, so we fail instead of returning an empty page.
+            if !ordered.any(|header| header.address() == cursor) {
+                return Err(UnknownQueryCursor);
+            }
+        }
+
+        let page: Vec<ChainHeader> = ordered.skip(start).take(limit).collect();
+        let next_cursor = if page.len() == limit {
+            page.last().map(|header| header.address())
+        } else {
+            None
+        };
+
+        let result = if options.headers {
+            ChainStoreQueryResult::Headers(page)
+        } else {
+            ChainStoreQueryResult::Addresses(
+                page.into_iter()
+                    .map(|header| header.entry_address().to_owned())
+                    .collect(),
+            )
+        };
+
+        Ok(ChainStoreQueryPage {
+            result,
+            next_cursor,
+        })
     }
 }
 
@@ -320,11 +427,12 @@ pub mod tests {
             entry_type::{test_entry_type_b, AppEntryType},
             test_entry, test_entry_b, test_entry_c, Entry,
         },
-        time::test_iso_8601,
+        error::RibosomeErrorCode::UnknownQueryCursor,
+        time::{test_iso_8601, Iso8601},
     };
     use holochain_json_api::json::{JsonString, RawString};
     use holochain_locksmith::RwLock;
-    use holochain_persistence_api::cas::content::AddressableContent;
+    use holochain_persistence_api::cas::content::{Address, AddressableContent};
     use holochain_persistence_file::cas::file::FilesystemStorage;
     use tempfile;
 
@@ -375,6 +483,57 @@ pub mod tests {
         assert_eq!(expected, found);
     }
 
+    #[test]
+    fn get_chain_header_only_finds_headers_of_the_chain() {
+        let chain_store = test_chain_store();
+
+        let chain_header_a = test_chain_header();
+        let entry_b = test_entry_b();
+        let chain_header_b = ChainHeader::new(
+            &entry_b.entry_type(),
+            &entry_b.address(),
+            &test_provenances("sig"),
+            &Some(chain_header_a.address()),
+            &None,
+            &None,
+            &test_iso_8601(),
+        );
+        // Held for somebody else, in the same CAS
+        let other_header = ChainHeader::new(
+            &entry_b.entry_type(),
+            &entry_b.address(),
+            &test_provenances("other sig"),
+            &None,
+            &None,
+            &None,
+            &test_iso_8601(),
+        );
+        for chain_header in vec![&chain_header_a, &chain_header_b, &other_header] {
+            let storage = chain_store.content_storage.clone();
+            (*storage.write().unwrap())
+                .add(chain_header)
+                .expect("could not add header to cas");
+        }
+
+        let top = Some(chain_header_b.clone());
+        assert_eq!(
+            chain_store.get_chain_header(&top, &chain_header_a.address()),
+            Ok(Some(chain_header_a.clone()))
+        );
+        assert_eq!(
+            chain_store.get_chain_header(&top, &chain_header_b.address()),
+            Ok(Some(chain_header_b))
+        );
+        assert_eq!(
+            chain_store.get_chain_header(&top, &other_header.address()),
+            Ok(None)
+        );
+        assert_eq!(
+            chain_store.get_chain_header(&top, &entry_b.address()),
+            Ok(None)
+        );
+    }
+
     #[test]
     /// show entry typed Iterator implementation for chain store
     fn type_iterator_test() {
@@ -805,6 +964,152 @@ pub mod tests {
         }
     }
 
+    #[test]
+    /// show query() ordering, timestamp filters and cursor pagination
+    fn query_page_test() {
+        let chain_store = test_chain_store();
+        let storage = chain_store.content_storage.clone();
+
+        // Five headers alternating between two entry types, one second apart
+        let mut headers: Vec<ChainHeader> = Vec::new();
+        for i in 0..5 {
+            let entry = if i % 2 == 0 {
+                test_entry()
+            } else {
+                test_entry_b()
+            };
+            let link_same_type = headers
+                .iter()
+                .rev()
+                .find(|header| *header.entry_type() == entry.entry_type())
+                .map(|header| header.address());
+            let header = ChainHeader::new(
+                &entry.entry_type(),
+                &entry.address(),
+                &test_provenances("sig"),
+                &headers.last().map(|header| header.address()),
+                &link_same_type,
+                &None,
+                &Iso8601::from(1_000 + i as i64),
+            );
+            (*storage.write().unwrap())
+                .add(&header)
+                .expect("could not add header to cas");
+            headers.push(header);
+        }
+        let top = Some(headers[4].clone());
+        let query_headers = |names: &[&str], options: ChainStoreQueryOptions| {
+            let page = chain_store
+                .query_page(
+                    &top,
+                    names,
+                    ChainStoreQueryOptions {
+                        headers: true,
+                        ..options
+                    },
+                )
+                .unwrap();
+            match page.result {
+                ChainStoreQueryResult::Headers(found) => (found, page.next_cursor),
+                other => panic!("Unexpected query value {:?}", other),
+            }
+        };
+
+        // Newest first by default, oldest first in reverse
+        let (found, _) = query_headers(&[], ChainStoreQueryOptions::default());
+        assert_eq!(found, headers.iter().rev().cloned().collect::<Vec<_>>());
+        let (found, _) = query_headers(
+            &[],
+            ChainStoreQueryOptions {
+                reverse: true,
+                ..Default::default()
+            },
+        );
+        assert_eq!(found, headers);
+
+        // Timestamp range is inclusive on both ends
+        let (found, _) = query_headers(
+            &[],
+            ChainStoreQueryOptions {
+                since: Some(Iso8601::from(1_001)),
+                until: Some(Iso8601::from(1_003)),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            found,
+            vec![headers[3].clone(), headers[2].clone(), headers[1].clone()]
+        );
+
+        // Pages of one entry type, continued with the returned cursor
+        let entry_type = test_entry().entry_type().to_string();
+        let (found, cursor) = query_headers(
+            &[&entry_type],
+            ChainStoreQueryOptions {
+                limit: 2,
+                ..Default::default()
+            },
+        );
+        assert_eq!(found, vec![headers[4].clone(), headers[2].clone()]);
+        assert_eq!(cursor, Some(headers[2].address()));
+        let (found, cursor) = query_headers(
+            &[&entry_type],
+            ChainStoreQueryOptions {
+                limit: 2,
+                cursor,
+                ..Default::default()
+            },
+        );
+        assert_eq!(found, vec![headers[0].clone()]);
+        assert_eq!(cursor, None);
+
+        // Cursors outside of the selection are rejected instead of giving an empty page
+        let other_type_cursor = Some(headers[3].address());
+        for cursor in vec![other_type_cursor, Some(Address::from("unknown"))] {
+            assert_eq!(
+                chain_store
+                    .query_page(
+                        &top,
+                        &[&entry_type],
+                        ChainStoreQueryOptions {
+                            cursor,
+                            ..Default::default()
+                        },
+                    )
+                    .err(),
+                Some(UnknownQueryCursor)
+            );
+        }
+
+        // The type index follows headers appended to the chain
+        let entry = test_entry_b();
+        let header = ChainHeader::new(
+            &entry.entry_type(),
+            &entry.address(),
+            &test_provenances("sig"),
+            &Some(headers[4].address()),
+            &Some(headers[3].address()),
+            &None,
+            &Iso8601::from(1_005),
+        );
+        (*storage.write().unwrap())
+            .add(&header)
+            .expect("could not add header to cas");
+        chain_store.index_header(&header);
+        assert_eq!(
+            chain_store
+                .iter_type(&Some(header.clone()), &entry.entry_type())
+                .collect::<Vec<_>>(),
+            vec![header.clone(), headers[3].clone(), headers[1].clone()]
+        );
+        assert_eq!(
+            chain_store
+                .iter_type(&Some(headers[2].clone()), &entry.entry_type())
+                .collect::<Vec<_>>(),
+            vec![headers[1].clone()]
+        );
+    }
+
     use globset::{Glob, GlobBuilder, GlobSetBuilder};
 
     #[test]
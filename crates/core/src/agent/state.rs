@@ -165,6 +165,7 @@ pub enum AgentActionResponse {
     FetchEntry(Option<Entry>),
     GetLinks(Result<Vec<Address>, HolochainError>),
     LinkEntries(Result<Entry, HolochainError>),
+    AddCustomValidationPackage(Result<Address, HolochainError>),
 }
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize, DefaultJson)]
@@ -207,7 +208,7 @@ pub fn create_new_chain_header(
     let mut provenances: Vec<Provenance> = provenances.to_vec();
     provenances.push(Provenance::new(agent_address, signature));
 
-    Ok(ChainHeader::new(
+    let header = ChainHeader::new(
         &entry.entry_type(),
         &entry.address(),
         &provenances,
@@ -225,7 +226,14 @@ pub fn create_new_chain_header(
             duration_since_epoch.as_secs() as i64,
             duration_since_epoch.subsec_nanos(),
         ),
-    ))
+    );
+    // Also sign the header itself, so others can trust its link and timestamp
+    let header_signature = Signature::from(
+        root_state
+            .conductor_api()
+            .execute(header.signing_payload(), CryptoMethod::Sign)?,
+    );
+    Ok(header.with_header_signature(header_signature))
 }
 
 /// Create an entry-with-header for a header.
@@ -285,6 +293,7 @@ fn reduce_commit_entry(
     .and_then(|chain_header| {
         agent_state.chain_store.add(entry)?;
         agent_state.chain_store.add(&chain_header)?;
+        agent_state.chain_store.index_header(&chain_header);
         Ok((chain_header, entry.address()))
     })
     .and_then(|(chain_header, address)| {
@@ -298,6 +307,49 @@ fn reduce_commit_entry(
     );
 }
 
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+fn reduce_add_custom_validation_package(
+    agent_state: &mut AgentState,
+    _root_state: &State,
+    action_wrapper: &ActionWrapper,
+) {
+    let action = action_wrapper.action();
+    let package = unwrap_to!(action => Action::AddCustomValidationPackage);
+    let result = agent_state
+        .chain_store
+        .add(package)
+        .map(|_| package.address())
+        .map_err(|error| {
+            error!(
+                "Could not store custom validation package for header {}: {:?}",
+                package.header_address(),
+                error
+            );
+            error
+        });
+    agent_state.actions.insert(
+        action_wrapper.clone(),
+        Response::from(AgentActionResponse::AddCustomValidationPackage(result)),
+    );
+}
+
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+fn reduce_purge_entry(
+    agent_state: &mut AgentState,
+    _root_state: &State,
+    action_wrapper: &ActionWrapper,
+) {
+    let action = action_wrapper.action();
+    let tombstone = unwrap_to!(action => Action::PurgeEntry);
+    if let Err(error) = agent_state.chain_store.add(tombstone) {
+        error!(
+            "Could not purge entry {} from chain storage: {:?}",
+            tombstone.purged_entry_address(),
+            error
+        );
+    }
+}
+
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
 fn reduce_prune(agent_state: &mut AgentState, _root_state: &State, action_wrapper: &ActionWrapper) {
     assert_eq!(action_wrapper.action(), &Action::Prune);
@@ -344,6 +396,8 @@ fn resolve_reducer(action_wrapper: &ActionWrapper) -> Option<AgentReduceFn> {
     match action_wrapper.action() {
         Action::ClearActionResponse(_) => Some(reduce_clear_action_response),
         Action::Commit(_) => Some(reduce_commit_entry),
+        Action::AddCustomValidationPackage(_) => Some(reduce_add_custom_validation_package),
+        Action::PurgeEntry(_) => Some(reduce_purge_entry),
         Action::Prune => Some(reduce_prune),
         _ => None,
     }
@@ -376,9 +430,10 @@ pub mod tests {
     };
     use holochain_core_types::{
         chain_header::{test_chain_header, ChainHeader},
+        custom_validation_package::CustomValidationPackage,
         entry::{expected_entry_address, test_entry, Entry},
         error::HolochainError,
-        signature::Signature,
+        signature::{Provenance, Signature},
     };
     use holochain_json_api::json::JsonString;
     use holochain_persistence_api::cas::content::AddressableContent;
@@ -428,6 +483,45 @@ pub mod tests {
         assert_eq!(response.response(), &test_action_response_commit(),);
     }
 
+    #[test]
+    /// test for storing custom validation packages next to their header
+    fn test_reduce_add_custom_validation_package() {
+        let context = test_context("bob", None);
+        let mut agent_state = test_agent_state(Some(context.agent_id.address()));
+        let state = State::new_with_agent(context, agent_state.clone());
+        let header_address = test_chain_header().address();
+        let package = CustomValidationPackage::new(
+            header_address.clone(),
+            String::from("[]"),
+            Provenance::new(Address::from("author"), Signature::fake()),
+        );
+
+        let action_wrapper =
+            ActionWrapper::new(Action::AddCustomValidationPackage(package.clone()));
+        reduce_add_custom_validation_package(&mut agent_state, &state, &action_wrapper);
+        assert_eq!(
+            agent_state
+                .actions()
+                .get(&action_wrapper)
+                .map(|r| r.response()),
+            Some(&AgentActionResponse::AddCustomValidationPackage(Ok(
+                package.address()
+            ))),
+        );
+
+        let content = agent_state
+            .chain_store()
+            .get_raw(&CustomValidationPackage::address_for_header(
+                &header_address,
+            ))
+            .unwrap()
+            .expect("custom validation package should be stored under its header");
+        assert_eq!(
+            CustomValidationPackage::try_from_content(&content),
+            Ok(package)
+        );
+    }
+
     #[test]
     /// test response to json
     fn test_commit_response_to_json() {
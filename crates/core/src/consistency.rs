@@ -192,10 +192,7 @@ impl ConsistencyModel {
                 EntryAspect::LinkRemove(_, header) => Some(ConsistencySignal::new_terminal(
                     ConsistencyEvent::RemoveLink(header.entry_address().clone()),
                 )),
-                EntryAspect::Header(_) => {
-                    error!("Got EntryAspect::Header type, unexpectedly");
-                    None
-                }
+                EntryAspect::Header(_) | EntryAspect::Warrant(_) => None,
             }
 
             Action::QueueZomeFunctionCall(call) => Some(ConsistencySignal::new_pending(
@@ -1,15 +1,22 @@
-use holochain_core_types::{entry::Entry, error::HcResult};
+use holochain_core_types::{
+    entry::{purged_entry::PurgedEntry, Entry},
+    error::HcResult,
+};
 use holochain_persistence_api::cas::content::{Address, AddressableContent, Content};
 
 pub trait GetContent {
     /// Return the content at this address, do not attempt to convert to an entry
     fn get_raw(&self, address: &Address) -> HcResult<Option<Content>>;
 
-    /// Get an entry from this storage
+    /// Get an entry from this storage.
+    /// Entries whose content got replaced by a PurgedEntry tombstone are not found.
     fn get(&self, address: &Address) -> HcResult<Option<Entry>> {
         if let Some(json) = self.get_raw(address)? {
-            let entry = Entry::try_from_content(&json)?;
-            Ok(Some(entry))
+            match Entry::try_from_content(&json) {
+                Ok(entry) => Ok(Some(entry)),
+                Err(_) if PurgedEntry::try_from_content(&json).is_ok() => Ok(None),
+                Err(err) => Err(err.into()),
+            }
         } else {
             Ok(None) // no errors but entry is not in chain CAS
         }
@@ -1,11 +1,13 @@
 use crate::{
     action::{Action, ActionWrapper},
     content_store::GetContent,
-    instance::Observer,
-    network::state::NetworkState,
+    instance::{Observer, DEFAULT_HOLDING_WORKFLOW_POOL_SIZE},
+    network::{query_cache::QueryCache, state::NetworkState},
+    nucleus::validation::cache::ValidationCache,
     persister::Persister,
     signal::{Signal, SignalSender},
     state::StateWrapper,
+    workflows::countersigning::CountersigningSession,
     NEW_RELIC_LICENSE_KEY,
 };
 use crossbeam_channel::{unbounded, Receiver, Sender};
@@ -25,6 +27,7 @@ use holochain_core_types::{
         Entry,
     },
     error::{HcResult, HolochainError},
+    membrane_proof::MembraneProof,
 };
 use holochain_json_api::{error::JsonError, json::JsonString};
 use holochain_locksmith::{Mutex, MutexGuard, RwLock, RwLockReadGuard};
@@ -40,6 +43,7 @@ use holochain_persistence_api::{
 use holochain_tracing as ht;
 use jsonrpc_core::{self, IoHandler};
 use std::{
+    collections::BTreeMap,
     sync::{
         atomic::{AtomicBool, Ordering::Relaxed},
         Arc,
@@ -79,7 +83,16 @@ pub struct InstanceStats {
     pub number_held_aspects: usize,
     pub number_pending_validations: usize,
     pub number_delayed_validations: usize,
+    pub number_running_validations: usize,
+    /// Number of queued validations that are ready to run and only wait for a free worker
+    pub validation_queue_depth: usize,
+    pub validation_pool_size: usize,
     pub number_running_zome_calls: usize,
+    pub validation_cache_hits: u64,
+    pub validation_cache_misses: u64,
+    /// Share of validation callback runs that were answered from the validation cache
+    pub validation_cache_hit_rate: f64,
+    pub validation_cache_size: usize,
     pub offline: bool,
 }
 
@@ -103,6 +116,21 @@ pub struct Context {
     pub(crate) signal_tx: Option<Sender<Signal>>,
     pub(crate) instance_is_alive: Arc<AtomicBool>,
     pub state_dump_logging: bool,
+    /// Number of worker threads that run holding workflows concurrently
+    pub holding_workflow_pool_size: usize,
+    /// Ignore direct messages and DHT data from agents we hold a warrant against
+    pub auto_block_warranted_agents: bool,
+    /// Credential that gets committed with the agent entry at genesis to join the DNA
+    pub membrane_proof: Option<MembraneProof>,
+    /// Configs that replace the ones given in the DNA for this instance, by zome name
+    pub zome_config_overrides: BTreeMap<String, serde_json::Value>,
+    /// Outcomes of validation callbacks we have already run
+    pub validation_cache: Arc<Mutex<ValidationCache>>,
+    /// Results of get_entry and get_links queries to the network
+    pub query_cache: Arc<Mutex<QueryCache>>,
+    /// The countersigning session we have signed an entry in and are waiting for
+    /// the outcome of. There can only be one at a time and it locks our source chain.
+    pub countersigning_session: Arc<Mutex<Option<CountersigningSession>>>,
     thread_pool: ThreadPool,
     pub redux_wants_write: Arc<AtomicBool>,
     pub metric_publisher: Arc<RwLock<dyn MetricPublisher>>,
@@ -170,6 +198,13 @@ impl Context {
             )),
             instance_is_alive: Arc::new(AtomicBool::new(true)),
             state_dump_logging,
+            holding_workflow_pool_size: DEFAULT_HOLDING_WORKFLOW_POOL_SIZE,
+            auto_block_warranted_agents: false,
+            membrane_proof: None,
+            zome_config_overrides: BTreeMap::new(),
+            validation_cache: Arc::new(Mutex::new(ValidationCache::default())),
+            query_cache: Arc::new(Mutex::new(QueryCache::default())),
+            countersigning_session: Arc::new(Mutex::new(None)),
             thread_pool: ThreadPool::new().expect("Could not create thread pool for futures"),
             redux_wants_write: Arc::new(AtomicBool::new(false)),
             metric_publisher,
@@ -207,6 +242,13 @@ impl Context {
             conductor_api: ConductorApi::new(Self::test_check_conductor_api(None, agent_id)),
             instance_is_alive: Arc::new(AtomicBool::new(true)),
             state_dump_logging,
+            holding_workflow_pool_size: DEFAULT_HOLDING_WORKFLOW_POOL_SIZE,
+            auto_block_warranted_agents: false,
+            membrane_proof: None,
+            zome_config_overrides: BTreeMap::new(),
+            validation_cache: Arc::new(Mutex::new(ValidationCache::default())),
+            query_cache: Arc::new(Mutex::new(QueryCache::default())),
+            countersigning_session: Arc::new(Mutex::new(None)),
             thread_pool: ThreadPool::new().expect("Could not create thread pool for futures"),
             redux_wants_write: Arc::new(AtomicBool::new(false)),
             metric_publisher,
@@ -285,6 +327,21 @@ impl Context {
         dna
     }
 
+    /// Returns the config of the given zome, which is the override set for this instance
+    /// or, if there is none, the config given in the DNA.
+    pub fn get_zome_config(&self, zome_name: &str) -> Result<serde_json::Value, HolochainError> {
+        if let Some(config) = self.zome_config_overrides.get(zome_name) {
+            return Ok(config.clone());
+        }
+        let dna = self
+            .get_dna()
+            .ok_or_else(|| HolochainError::ErrorGeneric("No DNA set".to_string()))?;
+        dna.zomes
+            .get(zome_name)
+            .map(|zome| zome.config.value().clone())
+            .ok_or_else(|| HolochainError::ErrorGeneric(format!("Unknown zome '{}'", zome_name)))
+    }
+
     #[autotrace]
     pub fn get_wasm(&self, zome: &str) -> Option<DnaWasm> {
         let dna = self.get_dna().expect("Callback called without DNA set!");
@@ -423,6 +480,10 @@ impl Context {
             .state()
             .ok_or_else(|| "Couldn't get instance state".to_string())?;
         let dht_store = state.dht();
+        let validation_cache = self
+            .validation_cache
+            .lock()
+            .expect("Could not lock validation cache");
         let holding_map = dht_store.get_holding_map().bare();
         Ok(InstanceStats {
             number_held_entries: holding_map.keys().count(),
@@ -435,7 +496,14 @@ impl Context {
                 .iter()
                 .filter(|p| p.timeout.is_some())
                 .count(),
+            number_running_validations: dht_store.in_process_holding_workflows().len(),
+            validation_queue_depth: dht_store.ready_queued_holding_workflows().count(),
+            validation_pool_size: self.holding_workflow_pool_size,
             number_running_zome_calls: state.nucleus().running_zome_calls.len(),
+            validation_cache_hits: validation_cache.hits(),
+            validation_cache_misses: validation_cache.misses(),
+            validation_cache_hit_rate: validation_cache.hit_rate(),
+            validation_cache_size: validation_cache.len(),
             offline: false,
         })
     }
@@ -0,0 +1,15 @@
+use crate::{
+    action::{Action, ActionWrapper},
+    context::Context,
+    instance::dispatch_action,
+};
+use std::sync::Arc;
+
+/// Puts all abandoned holding workflows back into the queue, e.g. after the missing
+/// dependencies became available through other means or the network came back.
+pub fn retry_abandoned_holding_workflows(context: &Arc<Context>) {
+    dispatch_action(
+        context.action_channel(),
+        ActionWrapper::new(Action::RetryAbandonedHoldingWorkflows),
+    );
+}
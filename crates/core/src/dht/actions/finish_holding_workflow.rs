@@ -0,0 +1,52 @@
+use crate::{
+    action::{Action, ActionWrapper},
+    context::Context,
+    dht::pending_validations::{HoldingWorkflowFollowUp, PendingValidation},
+    instance::dispatch_action,
+    NEW_RELIC_LICENSE_KEY,
+};
+use futures::{future::Future, task::Poll};
+use std::{pin::Pin, sync::Arc};
+
+/// Removes the given holding workflow from the list of workflows in process
+/// and applies the given follow-up, i.e. queues or abandons its retry.
+/// Resolves as soon as the state reflects that.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub async fn finish_holding_workflow(
+    pending: PendingValidation,
+    follow_up: HoldingWorkflowFollowUp,
+    context: Arc<Context>,
+) {
+    let action_wrapper =
+        ActionWrapper::new(Action::FinishHoldingWorkflow((pending.clone(), follow_up)));
+    dispatch_action(context.action_channel(), action_wrapper.clone());
+    FinishHoldingWorkflowFuture { context, pending }.await
+}
+
+pub struct FinishHoldingWorkflowFuture {
+    context: Arc<Context>,
+    pending: PendingValidation,
+}
+
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+impl Future for FinishHoldingWorkflowFuture {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context) -> Poll<Self::Output> {
+        //
+        // TODO: connect the waker to state updates for performance reasons
+        // See: https://github.com/holochain/holochain-rust/issues/314
+        //
+        cx.waker().clone().wake();
+
+        if let Some(state) = self.context.try_state() {
+            if state.dht().is_in_process_holding_workflow(&self.pending) {
+                Poll::Pending
+            } else {
+                Poll::Ready(())
+            }
+        } else {
+            Poll::Pending
+        }
+    }
+}
@@ -9,6 +9,11 @@ use holochain_core_types::{error::HolochainError, network::entry_aspect::EntryAs
 use std::{pin::Pin, sync::Arc};
 
 pub async fn hold_aspect(aspect: EntryAspect, context: Arc<Context>) -> Result<(), HolochainError> {
+    context
+        .query_cache
+        .lock()
+        .expect("Could not lock query cache")
+        .invalidate_aspect(&aspect);
     let action_wrapper = ActionWrapper::new(Action::HoldAspect(aspect.clone()));
     dispatch_action(context.action_channel(), action_wrapper.clone());
     HoldAspectFuture { context, aspect }.await
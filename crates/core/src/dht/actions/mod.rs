@@ -1,3 +1,6 @@
+pub mod abandon_holding_workflow;
+pub mod finish_holding_workflow;
 pub mod hold_aspect;
 pub mod queue_holding_workflow;
 pub mod remove_queued_holding_workflow;
+pub mod start_holding_workflow;
@@ -0,0 +1,46 @@
+use crate::{
+    action::{Action, ActionWrapper},
+    context::Context,
+    dht::pending_validations::PendingValidation,
+    instance::dispatch_action,
+    NEW_RELIC_LICENSE_KEY,
+};
+use futures::{future::Future, task::Poll};
+use std::{pin::Pin, sync::Arc};
+
+/// Takes the given holding workflow out of the queue and marks it as in process.
+/// Resolves as soon as the state reflects that.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub async fn start_holding_workflow(pending: PendingValidation, context: Arc<Context>) {
+    let action_wrapper = ActionWrapper::new(Action::StartHoldingWorkflow(pending.clone()));
+    dispatch_action(context.action_channel(), action_wrapper.clone());
+    StartHoldingWorkflowFuture { context, pending }.await
+}
+
+pub struct StartHoldingWorkflowFuture {
+    context: Arc<Context>,
+    pending: PendingValidation,
+}
+
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+impl Future for StartHoldingWorkflowFuture {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context) -> Poll<Self::Output> {
+        //
+        // TODO: connect the waker to state updates for performance reasons
+        // See: https://github.com/holochain/holochain-rust/issues/314
+        //
+        cx.waker().clone().wake();
+
+        if let Some(state) = self.context.try_state() {
+            if state.dht().has_exact_queued_holding_workflow(&self.pending) {
+                Poll::Pending
+            } else {
+                Poll::Ready(())
+            }
+        } else {
+            Poll::Pending
+        }
+    }
+}
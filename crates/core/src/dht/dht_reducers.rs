@@ -2,9 +2,12 @@
 
 use crate::{
     action::{Action, ActionWrapper},
+    content_store::AddContent,
     dht::{
         dht_store::DhtStore,
-        pending_validations::{PendingValidationWithTimeout, ValidationTimeout},
+        pending_validations::{
+            HoldingWorkflowFollowUp, PendingValidationWithTimeout, ValidationTimeout,
+        },
     },
     NEW_RELIC_LICENSE_KEY,
 };
@@ -15,10 +18,13 @@ use super::dht_inner_reducers::{
     reduce_update_entry_inner, LinkModification,
 };
 
-use holochain_core_types::{entry::Entry, network::entry_aspect::EntryAspect};
+use holochain_core_types::{
+    entry::{deletion_entry::DeletionEntry, Entry},
+    network::entry_aspect::EntryAspect,
+};
 use holochain_persistence_api::cas::content::AddressableContent;
+use im::Vector;
 use itertools::Itertools;
-use std::collections::VecDeque;
 // A function that might return a mutated DhtStore
 type DhtReducer = fn(&DhtStore, &ActionWrapper) -> Option<DhtStore>;
 
@@ -46,8 +52,15 @@ fn resolve_reducer(action_wrapper: &ActionWrapper) -> Option<DhtReducer> {
     match action_wrapper.action() {
         Action::Commit(_) => Some(reduce_commit_entry),
         Action::HoldAspect(_) => Some(reduce_hold_aspect),
+        Action::AddValidationReceipt(_) => Some(reduce_add_validation_receipt),
+        Action::PurgeEntry(_) => Some(reduce_purge_entry),
+        Action::RecordChainFork(_) => Some(reduce_record_chain_fork),
+        Action::ResolveChainForks(_) => Some(reduce_resolve_chain_forks),
         Action::QueueHoldingWorkflow(_) => Some(reduce_queue_holding_workflow),
         Action::RemoveQueuedHoldingWorkflow(_) => Some(reduce_remove_queued_holding_workflow),
+        Action::StartHoldingWorkflow(_) => Some(reduce_start_holding_workflow),
+        Action::FinishHoldingWorkflow(_) => Some(reduce_finish_holding_workflow),
+        Action::RetryAbandonedHoldingWorkflows => Some(reduce_retry_abandoned_holding_workflows),
         Action::Prune => Some(reduce_prune),
         _ => None,
     }
@@ -85,9 +98,21 @@ pub(crate) fn reduce_hold_aspect(
     // );
     match aspect {
         EntryAspect::Content(entry, header) => {
-            match reduce_store_entry_inner(&mut new_store, entry) {
+            // Content that got purged stays purged, even if other nodes still gossip it
+            let store_result = if new_store.is_purged(&entry.address()).unwrap_or(false) {
+                Ok(())
+            } else {
+                reduce_store_entry_inner(&mut new_store, entry)
+            };
+            match store_result {
                 Ok(()) => {
                     new_store.add_header_for_entry(&entry, &header).ok()?;
+                    // Headers signed as a whole also go into the per-author index
+                    // that agent activity and chain forks get read from.
+                    // hold_entry_workflow checked their signature.
+                    if header.header_signature().is_some() {
+                        new_store.add_agent_activity(&header).ok()?;
+                    }
                     Some(new_store)
                 }
                 Err(e) => {
@@ -137,16 +162,32 @@ pub(crate) fn reduce_hold_aspect(
             if let Some(crud_link) = header.link_update_delete() {
                 let _ =
                     reduce_remove_entry_inner(&mut new_store, &crud_link, &header.entry_address());
+                if DeletionEntry::from_header(&header).map_or(false, |deletion| deletion.is_purge())
+                {
+                    if let Err(e) = new_store.purge_entry(&crud_link, &header) {
+                        error!("{}", e);
+                    }
+                }
                 Some(new_store)
             } else {
                 error!("EntryAspect::Update without crud_link in header received!");
                 None
             }
         }
-        EntryAspect::Header(_) => {
-            error!("Got EntryAspect::Header which is not implemented.");
-            None
-        }
+        EntryAspect::Header(header) => match new_store.add_agent_activity(&header) {
+            Ok(()) => Some(new_store),
+            Err(e) => {
+                error!("{}", e);
+                None
+            }
+        },
+        EntryAspect::Warrant(warrant) => match new_store.add_warrant(&warrant) {
+            Ok(()) => Some(new_store),
+            Err(e) => {
+                error!("{}", e);
+                None
+            }
+        },
     }
 }
 
@@ -182,6 +223,12 @@ pub fn reduce_queue_holding_workflow(
         if old_store.has_same_queued_holding_worfkow(pending) {
             warn!("Tried to add pending validation to queue which is already queued!");
             None
+        } else if old_store.has_same_in_process_holding_workflow(pending) {
+            warn!("Tried to add pending validation to queue which is currently being processed!");
+            None
+        } else if old_store.has_same_abandoned_holding_workflow(pending) {
+            warn!("Tried to add pending validation to queue which was abandoned before!");
+            None
         } else {
             let mut new_store = (*old_store).clone();
             new_store
@@ -207,7 +254,7 @@ pub fn reduce_prune(old_store: &DhtStore, _action_wrapper: &ActionWrapper) -> Op
             )
         })
         .cloned()
-        .collect::<VecDeque<_>>();
+        .collect::<Vector<_>>();
 
     if pruned_queue.len() < old_store.queued_holding_workflows.len() {
         let mut new_store = (*old_store).clone();
@@ -235,6 +282,136 @@ pub fn reduce_remove_queued_holding_workflow(
     Some(new_store)
 }
 
+#[allow(unknown_lints)]
+#[allow(clippy::needless_pass_by_value)]
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn reduce_start_holding_workflow(
+    old_store: &DhtStore,
+    action_wrapper: &ActionWrapper,
+) -> Option<DhtStore> {
+    let action = action_wrapper.action();
+    let pending = unwrap_to!(action => Action::StartHoldingWorkflow);
+    let mut new_store = (*old_store).clone();
+    match new_store.start_holding_workflow(pending) {
+        Some(_) => Some(new_store),
+        None => {
+            error!("Got Action::StartHoldingWorkflow for an item that is not queued!");
+            None
+        }
+    }
+}
+
+#[allow(unknown_lints)]
+#[allow(clippy::needless_pass_by_value)]
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn reduce_finish_holding_workflow(
+    old_store: &DhtStore,
+    action_wrapper: &ActionWrapper,
+) -> Option<DhtStore> {
+    let action = action_wrapper.action();
+    let (pending, follow_up) = unwrap_to!(action => Action::FinishHoldingWorkflow);
+    let mut new_store = (*old_store).clone();
+    if new_store.finish_holding_workflow(pending).is_none() {
+        error!("Got Action::FinishHoldingWorkflow for an item that is not in process!");
+        return None;
+    }
+    match follow_up {
+        HoldingWorkflowFollowUp::Done => (),
+        HoldingWorkflowFollowUp::Retry(retry, delay) => new_store
+            .queued_holding_workflows
+            .push_back(PendingValidationWithTimeout::new(
+                retry.clone(),
+                Some(ValidationTimeout::from(*delay)),
+            )),
+        HoldingWorkflowFollowUp::Abandon(abandoned) => {
+            new_store.abandon_holding_workflow(abandoned)
+        }
+    }
+    Some(new_store)
+}
+
+#[allow(unknown_lints)]
+#[allow(clippy::needless_pass_by_value)]
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn reduce_retry_abandoned_holding_workflows(
+    old_store: &DhtStore,
+    _action_wrapper: &ActionWrapper,
+) -> Option<DhtStore> {
+    if old_store.abandoned_holding_workflows().is_empty() {
+        None
+    } else {
+        let mut new_store = (*old_store).clone();
+        new_store.retry_abandoned_holding_workflows();
+        Some(new_store)
+    }
+}
+
+#[allow(unknown_lints)]
+#[allow(clippy::needless_pass_by_value)]
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub(crate) fn reduce_add_validation_receipt(
+    old_store: &DhtStore,
+    action_wrapper: &ActionWrapper,
+) -> Option<DhtStore> {
+    let receipt = unwrap_to!(action_wrapper.action() => Action::AddValidationReceipt);
+    let mut new_store = (*old_store).clone();
+    match new_store.add_validation_receipt(receipt) {
+        Ok(()) => Some(new_store),
+        Err(e) => {
+            error!("{}", e);
+            None
+        }
+    }
+}
+
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub(crate) fn reduce_purge_entry(
+    old_store: &DhtStore,
+    action_wrapper: &ActionWrapper,
+) -> Option<DhtStore> {
+    let tombstone = unwrap_to!(action_wrapper.action() => Action::PurgeEntry);
+    let mut new_store = (*old_store).clone();
+    match new_store.add(tombstone) {
+        Ok(()) => Some(new_store),
+        Err(e) => {
+            error!("{}", e);
+            None
+        }
+    }
+}
+
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub(crate) fn reduce_record_chain_fork(
+    old_store: &DhtStore,
+    action_wrapper: &ActionWrapper,
+) -> Option<DhtStore> {
+    let evidence = unwrap_to!(action_wrapper.action() => Action::RecordChainFork);
+    let mut new_store = (*old_store).clone();
+    match new_store.add_chain_fork(evidence) {
+        Ok(()) => Some(new_store),
+        Err(e) => {
+            error!("{}", e);
+            None
+        }
+    }
+}
+
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub(crate) fn reduce_resolve_chain_forks(
+    old_store: &DhtStore,
+    action_wrapper: &ActionWrapper,
+) -> Option<DhtStore> {
+    let agent = unwrap_to!(action_wrapper.action() => Action::ResolveChainForks);
+    let mut new_store = (*old_store).clone();
+    match new_store.resolve_chain_forks(agent) {
+        Ok(()) => Some(new_store),
+        Err(e) => {
+            error!("{}", e);
+            None
+        }
+    }
+}
+
 #[cfg(test)]
 pub mod tests {
 
@@ -243,13 +420,19 @@ pub mod tests {
         content_store::{AddContent, GetContent},
         dht::{
             dht_reducers::{
-                reduce, reduce_hold_aspect, reduce_queue_holding_workflow,
-                reduce_remove_queued_holding_workflow,
+                reduce, reduce_finish_holding_workflow, reduce_hold_aspect,
+                reduce_queue_holding_workflow, reduce_remove_queued_holding_workflow,
+                reduce_retry_abandoned_holding_workflows, reduce_start_holding_workflow,
             },
             dht_store::{create_get_links_eavi_query, DhtStore},
-            pending_validations::{PendingValidation, PendingValidationStruct, ValidatingWorkflow},
+            pending_validations::{
+                is_retryable, HoldingWorkflowFollowUp, PendingValidation, PendingValidationStatus,
+                PendingValidationStruct, ValidatingWorkflow,
+            },
+        },
+        instance::{
+            tests::test_context, RETRY_VALIDATION_DURATION_MAX, RETRY_VALIDATION_DURATION_MIN,
         },
-        instance::tests::test_context,
         network::entry_with_header::EntryWithHeader,
         state::test_store,
     };
@@ -259,6 +442,7 @@ pub mod tests {
         chain_header::test_chain_header,
         eav::Attribute,
         entry::{test_entry, test_sys_entry, Entry},
+        error::HolochainError,
         link::{link_data::LinkData, Link, LinkActionKind},
         network::entry_aspect::EntryAspect,
     };
@@ -616,4 +800,181 @@ pub mod tests {
         let (next_pending, _) = store.next_queued_holding_workflow().unwrap();
         assert_eq!(update, next_pending);
     }
+
+    #[test]
+    pub fn test_holding_workflows_in_process() {
+        let context = test_context("test", None);
+        let store = DhtStore::new(context.dht_storage.clone(), context.eav_storage.clone());
+
+        let test_entry = test_entry();
+        let hold = create_pending_validation(test_entry.clone(), ValidatingWorkflow::HoldEntry);
+        let action = ActionWrapper::new(Action::QueueHoldingWorkflow((hold.clone(), None)));
+        let store = reduce_queue_holding_workflow(&store, &action).unwrap();
+
+        let link = Link::new(
+            &test_entry.address(),
+            &test_entry.address(),
+            "test_link",
+            "test-tag",
+        );
+        let link_data = LinkData::from_link(
+            &link,
+            LinkActionKind::ADD,
+            test_chain_header(),
+            test_agent_id(),
+        );
+        let hold_link =
+            create_pending_validation(Entry::LinkAdd(link_data), ValidatingWorkflow::HoldLink);
+        let action = ActionWrapper::new(Action::QueueHoldingWorkflow((hold_link.clone(), None)));
+        let store = reduce_queue_holding_workflow(&store, &action).unwrap();
+
+        let action = ActionWrapper::new(Action::StartHoldingWorkflow(hold.clone()));
+        let store = reduce_start_holding_workflow(&store, &action).unwrap();
+
+        assert_eq!(store.queued_holding_workflows().len(), 1);
+        assert_eq!(store.in_process_holding_workflows().len(), 1);
+        assert!(store.is_in_process_holding_workflow(&hold));
+
+        // the link depends on the entry that is still being validated
+        assert_eq!(store.next_queued_holding_workflow(), None);
+
+        // the same aspect can not be queued again while it is being validated
+        let action =
+            ActionWrapper::new(Action::QueueHoldingWorkflow((Arc::new(hold.same()), None)));
+        assert_eq!(reduce_queue_holding_workflow(&store, &action), None);
+
+        let action = ActionWrapper::new(Action::FinishHoldingWorkflow((
+            hold.clone(),
+            HoldingWorkflowFollowUp::Done,
+        )));
+        let store = reduce_finish_holding_workflow(&store, &action).unwrap();
+
+        assert_eq!(store.in_process_holding_workflows().len(), 0);
+        let (next_pending, _) = store.next_queued_holding_workflow().unwrap();
+        assert_eq!(hold_link, next_pending);
+
+        // starting something that is not queued does not change the store
+        let action = ActionWrapper::new(Action::StartHoldingWorkflow(hold.clone()));
+        assert_eq!(reduce_start_holding_workflow(&store, &action), None);
+    }
+
+    #[test]
+    pub fn test_retry_backoff() {
+        let hold = create_pending_validation(test_entry(), ValidatingWorkflow::HoldEntry);
+        assert_eq!(hold.attempts, 0);
+        assert_eq!(hold.last_error, None);
+
+        let retry = hold.retry(&HolochainError::ValidationPending);
+        assert_eq!(retry.attempts, 1);
+        assert_eq!(
+            retry.last_error,
+            Some(HolochainError::ValidationPending.to_string())
+        );
+        assert_eq!(retry.retry_delay(), RETRY_VALIDATION_DURATION_MIN);
+
+        let retry = retry.retry(&HolochainError::ValidationPending);
+        assert_eq!(retry.attempts, 2);
+        assert_eq!(retry.retry_delay(), RETRY_VALIDATION_DURATION_MIN * 2);
+
+        let retry = (0..40).fold(retry, |retry, _| {
+            retry.retry(&HolochainError::ValidationPending)
+        });
+        assert_eq!(retry.retry_delay(), RETRY_VALIDATION_DURATION_MAX);
+    }
+
+    #[test]
+    pub fn test_abandoned_holding_workflows() {
+        let context = test_context("test", None);
+        let store = DhtStore::new(context.dht_storage.clone(), context.eav_storage.clone());
+
+        let hold = create_pending_validation(test_entry(), ValidatingWorkflow::HoldEntry);
+        let action = ActionWrapper::new(Action::QueueHoldingWorkflow((hold.clone(), None)));
+        let store = reduce_queue_holding_workflow(&store, &action).unwrap();
+        let action = ActionWrapper::new(Action::StartHoldingWorkflow(hold.clone()));
+        let store = reduce_start_holding_workflow(&store, &action).unwrap();
+        let abandoned = Arc::new(hold.retry(&HolochainError::ValidationPending));
+        let action = ActionWrapper::new(Action::FinishHoldingWorkflow((
+            hold.clone(),
+            HoldingWorkflowFollowUp::Abandon(abandoned.clone()),
+        )));
+        let store = reduce_finish_holding_workflow(&store, &action).unwrap();
+        assert_eq!(store.in_process_holding_workflows().len(), 0);
+        assert_eq!(store.abandoned_holding_workflows().len(), 1);
+        assert_eq!(store.next_queued_holding_workflow(), None);
+
+        // abandoned aspects don't get queued again until they are retried explicitly
+        let action =
+            ActionWrapper::new(Action::QueueHoldingWorkflow((Arc::new(hold.same()), None)));
+        assert_eq!(reduce_queue_holding_workflow(&store, &action), None);
+
+        let reports = store.pending_validation_reports();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].status, PendingValidationStatus::Abandoned);
+        assert_eq!(reports[0].attempts, 1);
+        assert_eq!(reports[0].entry_address, test_entry().address());
+        assert_eq!(reports[0].workflow, ValidatingWorkflow::HoldEntry);
+
+        let action = ActionWrapper::new(Action::RetryAbandonedHoldingWorkflows);
+        let store = reduce_retry_abandoned_holding_workflows(&store, &action).unwrap();
+        assert!(store.abandoned_holding_workflows().is_empty());
+        let (queued, delay) = store.next_queued_holding_workflow().unwrap();
+        assert_eq!(queued.attempts, 0);
+        assert_eq!(queued.last_error, abandoned.last_error);
+        assert_eq!(delay, None);
+        assert_eq!(
+            reduce_retry_abandoned_holding_workflows(&store, &action),
+            None
+        );
+    }
+
+    #[test]
+    pub fn test_retry_gets_queued_when_finishing() {
+        let context = test_context("test", None);
+        let store = DhtStore::new(context.dht_storage.clone(), context.eav_storage.clone());
+
+        let hold = create_pending_validation(test_entry(), ValidatingWorkflow::HoldEntry);
+        let action = ActionWrapper::new(Action::QueueHoldingWorkflow((hold.clone(), None)));
+        let store = reduce_queue_holding_workflow(&store, &action).unwrap();
+        let action = ActionWrapper::new(Action::StartHoldingWorkflow(hold.clone()));
+        let store = reduce_start_holding_workflow(&store, &action).unwrap();
+
+        let retry = Arc::new(hold.retry(&HolochainError::ValidationPending));
+        let action = ActionWrapper::new(Action::FinishHoldingWorkflow((
+            hold.clone(),
+            HoldingWorkflowFollowUp::Retry(
+                retry.clone(),
+                (SystemTime::now(), Duration::from_secs(0)),
+            ),
+        )));
+        let store = reduce_finish_holding_workflow(&store, &action).unwrap();
+
+        // the retry is queued in the same step that takes the attempt out of process
+        assert_eq!(store.in_process_holding_workflows().len(), 0);
+        assert_eq!(store.queued_holding_workflows().len(), 1);
+        let (next_pending, delay) = store.next_queued_holding_workflow().unwrap();
+        assert_eq!(next_pending, retry);
+        assert_eq!(delay, Some(Duration::from_secs(0)));
+
+        // finishing the same attempt twice does not queue another retry
+        assert_eq!(reduce_finish_holding_workflow(&store, &action), None);
+    }
+
+    #[test]
+    pub fn test_retryable_errors() {
+        assert!(is_retryable(&HolochainError::ValidationPending));
+        assert!(is_retryable(&HolochainError::Timeout));
+        assert!(is_retryable(&HolochainError::EntryNotFoundLocally));
+        assert!(is_retryable(&HolochainError::List(vec![
+            HolochainError::Timeout,
+            HolochainError::ValidationPending,
+        ])));
+        assert!(!is_retryable(&HolochainError::ValidationFailed(
+            String::from("invalid")
+        )));
+        assert!(!is_retryable(&HolochainError::List(vec![
+            HolochainError::Timeout,
+            HolochainError::ValidationFailed(String::from("invalid")),
+        ])));
+        assert!(!is_retryable(&HolochainError::List(Vec::new())));
+    }
 }
@@ -2,20 +2,26 @@ use crate::{
     content_store::{AddContent, GetContent},
     dht::{
         aspect_map::{AspectMap, AspectMapBare},
-        pending_validations::{PendingValidationWithTimeout, ValidationTimeout},
+        pending_validations::{
+            PendingValidationReport, PendingValidationStatus, PendingValidationWithTimeout,
+            ValidationTimeout,
+        },
     },
     NEW_RELIC_LICENSE_KEY,
 };
 use holochain_core_types::{
+    agent_activity::ChainForkEvidence,
     chain_header::ChainHeader,
     crud_status::CrudStatus,
     eav::{Attribute, EaviQuery, EntityAttributeValueIndex},
-    entry::Entry,
+    entry::{purged_entry::PurgedEntry, Entry},
     error::{HcResult, HolochainError},
     network::{
         entry_aspect::EntryAspect,
         query::{GetLinksQueryConfiguration, Pagination, SortOrder},
     },
+    validation_receipt::ValidationReceipt,
+    warrant::Warrant,
 };
 use holochain_json_api::{error::JsonError, json::JsonString};
 use holochain_locksmith::RwLock;
@@ -26,18 +32,18 @@ use holochain_persistence_api::{
     },
     eav::{EavFilter, EntityAttributeValueStorage, IndexFilter},
 };
+use lib3h_protocol::types::EntryHash;
 use regex::Regex;
 
-use crate::{dht::pending_validations::PendingValidation, state::StateWrapper};
+use crate::{
+    dht::pending_validations::PendingValidation, instance::MAX_ABANDONED_HOLDING_WORKFLOWS,
+    state::StateWrapper,
+};
 use chrono::{offset::FixedOffset, DateTime};
 use holochain_json_api::error::JsonResult;
 use holochain_persistence_api::error::PersistenceResult;
-use std::{
-    collections::{BTreeSet, VecDeque},
-    convert::TryFrom,
-    sync::Arc,
-    time::Duration,
-};
+use im::{HashSet, Vector};
+use std::{collections::BTreeSet, convert::TryFrom, sync::Arc, time::Duration};
 
 /// The state-slice for the DHT.
 /// Holds the CAS and EAVi that's used for the agent's local shard
@@ -51,7 +57,16 @@ pub struct DhtStore {
     /// All the entry aspects that the network has told us to hold
     holding_map: AspectMap,
 
-    pub(crate) queued_holding_workflows: VecDeque<PendingValidationWithTimeout>,
+    pub(crate) queued_holding_workflows: Vector<PendingValidationWithTimeout>,
+
+    /// Holding workflows that got taken from the queue and are currently being run
+    /// by the holding worker pool
+    pub(crate) in_process_holding_workflows: Vector<PendingValidation>,
+
+    /// Holding workflows that exceeded the maximum number of attempts.
+    /// These don't get run again. Only the latest MAX_ABANDONED_HOLDING_WORKFLOWS are kept,
+    /// aspects that got evicted can be queued again when they get gossiped to us.
+    pub(crate) abandoned_holding_workflows: Vector<PendingValidation>,
 }
 
 impl PartialEq for DhtStore {
@@ -70,14 +85,27 @@ impl PartialEq for DhtStore {
 #[derive(Clone, Debug, Deserialize, Serialize, DefaultJson)]
 pub struct DhtStoreSnapshot {
     pub holding_map: AspectMapBare,
-    queued_holding_workflows: VecDeque<PendingValidationWithTimeout>,
+    queued_holding_workflows: Vector<PendingValidationWithTimeout>,
+    #[serde(default)]
+    abandoned_holding_workflows: Vector<PendingValidation>,
 }
 
 impl From<&StateWrapper> for DhtStoreSnapshot {
     fn from(state: &StateWrapper) -> Self {
+        let dht = state.dht();
+        // Workflows that are in process have not finished yet, so we persist them
+        // as queued again to have them run after a restart.
+        let mut queued_holding_workflows = dht.queued_holding_workflows.clone();
+        queued_holding_workflows.extend(
+            dht.in_process_holding_workflows
+                .iter()
+                .cloned()
+                .map(|pending| PendingValidationWithTimeout::new(pending, None)),
+        );
         DhtStoreSnapshot {
-            holding_map: state.dht().get_holding_map().bare().clone(),
-            queued_holding_workflows: state.dht().queued_holding_workflows.clone(),
+            holding_map: dht.get_holding_map().bare().clone(),
+            queued_holding_workflows,
+            abandoned_holding_workflows: dht.abandoned_holding_workflows.clone(),
         }
     }
 }
@@ -129,6 +157,16 @@ pub fn create_get_links_eavi_query<'a>(
     ))
 }
 
+/// A recorded source chain fork as returned by the `debug/forks` conductor API function.
+#[derive(Clone, Debug, Serialize)]
+pub struct ChainForkReport {
+    #[serde(flatten)]
+    pub evidence: ChainForkEvidence,
+    /// Set once the fork got marked as resolved, after which the forked agent
+    /// can write to its source chain again
+    pub resolved: bool,
+}
+
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
 impl DhtStore {
     // LifeCycle
@@ -141,7 +179,9 @@ impl DhtStore {
             content_storage,
             meta_storage,
             holding_map: AspectMap::new(),
-            queued_holding_workflows: VecDeque::new(),
+            queued_holding_workflows: Vector::new(),
+            in_process_holding_workflows: Vector::new(),
+            abandoned_holding_workflows: Vector::new(),
         }
     }
 
@@ -153,6 +193,7 @@ impl DhtStore {
         let mut new_dht_store = Self::new(content_storage, meta_storage);
         new_dht_store.holding_map = snapshot.holding_map.into();
         new_dht_store.queued_holding_workflows = snapshot.queued_holding_workflows;
+        new_dht_store.abandoned_holding_workflows = snapshot.abandoned_holding_workflows;
         new_dht_store
     }
 
@@ -172,11 +213,14 @@ impl DhtStore {
         let get_links_query = create_get_links_eavi_query(address, link_type, tag)?;
         let filtered = self.meta_storage.read()?.fetch_eavi(&get_links_query)?;
         let pagination = configuration.pagination;
+        let sort_order = configuration.sort_order.unwrap_or_default();
         let filter_with_sort_order: Box<dyn Iterator<Item = EntityAttributeValueIndex>> =
-            match configuration.sort_order.unwrap_or_default() {
+            match sort_order {
                 SortOrder::Ascending => Box::new(filtered.into_iter()),
                 SortOrder::Descending => Box::new(filtered.into_iter().rev()),
             };
+        // Cursor pages are cut after filtering by crud status so that they are always full
+        let mut cursor_limit = usize::max_value();
         let filter_with_pagination: Box<dyn Iterator<Item = EntityAttributeValueIndex>> =
             match pagination {
                 Some(paginate) => match paginate {
@@ -197,6 +241,23 @@ impl DhtStore {
                             .skip(size_pagination.page_size * size_pagination.page_number)
                             .take(size_pagination.page_size),
                     ),
+                    Pagination::Cursor(cursor_pagination) => {
+                        cursor_limit = cursor_pagination.limit;
+                        match cursor_pagination.cursor {
+                            Some(cursor) => {
+                                Box::new(filter_with_sort_order.skip_while(move |eavi| {
+                                    let position = (eavi.index(), eavi.value());
+                                    let cursor_position =
+                                        (cursor.timestamp(), cursor.link_add_address().clone());
+                                    match sort_order {
+                                        SortOrder::Ascending => position <= cursor_position,
+                                        SortOrder::Descending => position >= cursor_position,
+                                    }
+                                }))
+                            }
+                            None => filter_with_sort_order,
+                        }
+                    }
                 },
                 None => filter_with_sort_order,
             };
@@ -207,6 +268,7 @@ impl DhtStore {
                 _ => (s, CrudStatus::Deleted),
             })
             .filter(|link_crud| crud_filter.map(|crud| crud == link_crud.1).unwrap_or(true))
+            .take(cursor_limit)
             .collect())
     }
 
@@ -285,6 +347,249 @@ impl DhtStore {
         Ok(())
     }
 
+    /// Replaces the content of the given deleted entry with a tombstone if the purging
+    /// deletion was made by its original author, i.e. if all held headers of the entry
+    /// are signed by the author of the deletion. Otherwise other agents committed the same
+    /// content and it is kept for them. Headers and CRUD metadata of the entry are kept.
+    /// Returns whether the content got purged.
+    pub fn purge_entry(
+        &mut self,
+        entry_address: &Address,
+        deletion_header: &ChainHeader,
+    ) -> Result<bool, HolochainError> {
+        let purger = deletion_header.author();
+        let headers = self.get_headers(entry_address.clone())?;
+        if purger.is_none()
+            || headers.is_empty()
+            || headers.iter().any(|header| header.author() != purger)
+        {
+            return Ok(false);
+        }
+        self.add(&PurgedEntry::new(
+            entry_address.clone(),
+            deletion_header.entry_address().clone(),
+        ))?;
+        Ok(true)
+    }
+
+    /// Returns true if the content stored at the given address is a PurgedEntry tombstone.
+    pub fn is_purged(&self, entry_address: &Address) -> Result<bool, HolochainError> {
+        Ok(self.get_raw(entry_address)?.map_or(false, |content| {
+            PurgedEntry::try_from_content(&content).is_ok()
+        }))
+    }
+
+    /// Adds a warrant to the CAS and indexes it in the EAV by the address of the invalid
+    /// entry as well as by the addresses of all the agents it is against.
+    pub fn add_warrant(&mut self, warrant: &Warrant) -> Result<(), HolochainError> {
+        self.add(warrant)?;
+        let invalid_entry = warrant.invalid_header().entry_address().clone();
+        for entity in std::iter::once(invalid_entry).chain(warrant.authors()) {
+            let eavi =
+                EntityAttributeValueIndex::new(&entity, &Attribute::Warrant, &warrant.address())?;
+            self.meta_storage.write().unwrap().add_eavi(&eavi)?;
+        }
+        Ok(())
+    }
+
+    /// Get all warrants held in the local shard that are either about the entry
+    /// at the given address or against the agent with the given address.
+    pub fn get_warrants(&self, address: &Address) -> Result<Vec<Warrant>, HolochainError> {
+        self.meta_storage
+            .read()
+            .unwrap()
+            .fetch_eavi(&EaviQuery::new(
+                Some(address.clone()).into(),
+                Some(Attribute::Warrant).into(),
+                None.into(),
+                IndexFilter::LatestByAttribute,
+                None,
+            ))?
+            .into_iter()
+            .filter_map(|eavi| match self.get_raw(&eavi.value()) {
+                Ok(Some(content)) => {
+                    Some(Warrant::try_from_content(&content).map_err(|e| e.into()))
+                }
+                Ok(None) => None,
+                Err(e) => Some(Err(e)),
+            })
+            .collect()
+    }
+
+    /// Adds a published header to the CAS and indexes it in the EAV by the address
+    /// of its author, so we can answer agent activity queries as an authority of that agent.
+    pub fn add_agent_activity(&mut self, header: &ChainHeader) -> Result<(), HolochainError> {
+        let author = header.author().ok_or_else(|| {
+            HolochainError::ErrorGeneric(format!("Header {} has no author", header.address()))
+        })?;
+        self.add(header)?;
+        let eavi =
+            EntityAttributeValueIndex::new(&author, &Attribute::AgentActivity, &header.address())?;
+        self.meta_storage.write().unwrap().add_eavi(&eavi)?;
+        Ok(())
+    }
+
+    /// Get all headers of the agent with the given address held in the local shard.
+    pub fn get_agent_activity(&self, agent: &Address) -> Result<Vec<ChainHeader>, HolochainError> {
+        self.meta_storage
+            .read()
+            .unwrap()
+            .fetch_eavi(&EaviQuery::new(
+                Some(agent.clone()).into(),
+                Some(Attribute::AgentActivity).into(),
+                None.into(),
+                IndexFilter::LatestByAttribute,
+                None,
+            ))?
+            .into_iter()
+            .filter_map(|eavi| match self.get(&eavi.value()) {
+                Ok(Some(Entry::ChainHeader(header))) => Some(Ok(header)),
+                Ok(Some(_)) => Some(Err(HolochainError::ErrorGeneric(
+                    "Unexpected non-chain_header entry".to_string(),
+                ))),
+                Ok(None) => None,
+                Err(e) => Some(Err(e)),
+            })
+            .collect()
+    }
+
+    /// Adds evidence of a source chain fork to the CAS and indexes it in the EAV
+    /// by the address of the agent that forked its chain.
+    pub fn add_chain_fork(&mut self, evidence: &ChainForkEvidence) -> Result<(), HolochainError> {
+        self.add(evidence)?;
+        let eavi = EntityAttributeValueIndex::new(
+            &evidence.author,
+            &Attribute::ChainFork,
+            &evidence.address(),
+        )?;
+        self.meta_storage.write().unwrap().add_eavi(&eavi)?;
+        Ok(())
+    }
+
+    /// Marks all chain forks recorded against the given agent so far as resolved.
+    pub fn resolve_chain_forks(&mut self, agent: &Address) -> Result<(), HolochainError> {
+        for evidence in self.get_chain_forks(Some(agent.clone()))? {
+            let eavi = EntityAttributeValueIndex::new(
+                agent,
+                &Attribute::ResolvedChainFork,
+                &evidence.address(),
+            )?;
+            self.meta_storage.write().unwrap().add_eavi(&eavi)?;
+        }
+        Ok(())
+    }
+
+    fn fetch_chain_fork_addresses(
+        &self,
+        maybe_agent: Option<Address>,
+        attribute: Attribute,
+    ) -> Result<Vec<Address>, HolochainError> {
+        Ok(self
+            .meta_storage
+            .read()
+            .unwrap()
+            .fetch_eavi(&EaviQuery::new(
+                maybe_agent.into(),
+                Some(attribute).into(),
+                None.into(),
+                IndexFilter::LatestByAttribute,
+                None,
+            ))?
+            .into_iter()
+            .map(|eavi| eavi.value())
+            .collect())
+    }
+
+    /// Get the evidence of all chain forks of the given agent, or of all agents if None,
+    /// that got recorded in the local shard.
+    pub fn get_chain_forks(
+        &self,
+        maybe_agent: Option<Address>,
+    ) -> Result<Vec<ChainForkEvidence>, HolochainError> {
+        self.fetch_chain_fork_addresses(maybe_agent, Attribute::ChainFork)?
+            .into_iter()
+            .filter_map(|address| match self.get_raw(&address) {
+                Ok(Some(content)) => {
+                    Some(ChainForkEvidence::try_from_content(&content).map_err(|e| e.into()))
+                }
+                Ok(None) => None,
+                Err(e) => Some(Err(e)),
+            })
+            .collect()
+    }
+
+    /// Get the chain forks of the given agent that have not been marked as resolved.
+    pub fn get_unresolved_chain_forks(
+        &self,
+        agent: &Address,
+    ) -> Result<Vec<ChainForkEvidence>, HolochainError> {
+        let resolved =
+            self.fetch_chain_fork_addresses(Some(agent.clone()), Attribute::ResolvedChainFork)?;
+        Ok(self
+            .get_chain_forks(Some(agent.clone()))?
+            .into_iter()
+            .filter(|evidence| !resolved.contains(&evidence.address()))
+            .collect())
+    }
+
+    /// Lists all recorded chain forks for the `debug/forks` conductor API function.
+    pub fn chain_fork_reports(&self) -> Result<Vec<ChainForkReport>, HolochainError> {
+        let resolved = self.fetch_chain_fork_addresses(None, Attribute::ResolvedChainFork)?;
+        Ok(self
+            .get_chain_forks(None)?
+            .into_iter()
+            .map(|evidence| ChainForkReport {
+                resolved: resolved.contains(&evidence.address()),
+                evidence,
+            })
+            .collect())
+    }
+
+    /// Adds a validation receipt to the CAS and indexes it in the EAV by the address
+    /// of the header as well as the address of the entry it confirms.
+    pub fn add_validation_receipt(
+        &mut self,
+        receipt: &ValidationReceipt,
+    ) -> Result<(), HolochainError> {
+        self.add(receipt)?;
+        for entity in &[receipt.header_address(), receipt.entry_address()] {
+            let eavi = EntityAttributeValueIndex::new(
+                entity,
+                &Attribute::ValidationReceipt,
+                &receipt.address(),
+            )?;
+            self.meta_storage.write().unwrap().add_eavi(&eavi)?;
+        }
+        Ok(())
+    }
+
+    /// Get all validation receipts we have received for the header or entry
+    /// with the given address.
+    pub fn get_validation_receipts(
+        &self,
+        address: &Address,
+    ) -> Result<Vec<ValidationReceipt>, HolochainError> {
+        self.meta_storage
+            .read()
+            .unwrap()
+            .fetch_eavi(&EaviQuery::new(
+                Some(address.clone()).into(),
+                Some(Attribute::ValidationReceipt).into(),
+                None.into(),
+                IndexFilter::LatestByAttribute,
+                None,
+            ))?
+            .into_iter()
+            .filter_map(|eavi| match self.get_raw(&eavi.value()) {
+                Ok(Some(content)) => {
+                    Some(ValidationReceipt::try_from_content(&content).map_err(|e| e.into()))
+                }
+                Ok(None) => None,
+                Err(e) => Some(Err(e)),
+            })
+            .collect()
+    }
+
     pub fn mark_aspect_as_held(&mut self, aspect: &EntryAspect) {
         self.holding_map.add(aspect);
     }
@@ -310,11 +615,31 @@ impl DhtStore {
     pub(crate) fn next_queued_holding_workflow(
         &self,
     ) -> Option<(PendingValidation, Option<Duration>)> {
+        self.ready_queued_holding_workflows().next()
+    }
+
+    /// Returns all queued holding workflows that could be run right now, in queue order.
+    /// These are the ones that neither depend on another queued or in process workflow,
+    /// nor have a retry delay that has not elapsed yet.
+    pub(crate) fn ready_queued_holding_workflows(
+        &self,
+    ) -> impl Iterator<Item = (PendingValidation, Option<Duration>)> {
+        let in_process = self.in_process_holding_workflows.clone();
         self.queued_holding_workflows
             .clone()
             .into_iter()
             // filter so only free pending (those without dependencies also pending) are considered
             .filter(free_pending_filter(&self.queued_holding_workflows))
+            // and also not those waiting for a dependency that is being validated at the moment
+            .filter(move |PendingValidationWithTimeout { pending, .. }| {
+                !in_process.iter().any(|current| {
+                    let current_address = current.entry_with_header.entry.address();
+                    pending.dependencies.contains(&current_address)
+                        || (current.entry_with_header.header.entry_address()
+                            == pending.entry_with_header.header.entry_address()
+                            && current.workflow == pending.workflow)
+                })
+            })
             // skip those for which the sleep delay has not elapsed
             .skip_while(|PendingValidationWithTimeout { timeout, .. }| {
                 if let Some(ValidationTimeout {
@@ -334,7 +659,6 @@ impl DhtStore {
             .map(|PendingValidationWithTimeout { pending, timeout }| {
                 (pending, timeout.map(|t| Some(t.delay)).unwrap_or(None))
             })
-            .next()
     }
 
     pub(crate) fn has_exact_queued_holding_workflow(&self, pending: &PendingValidation) -> bool {
@@ -357,10 +681,26 @@ impl DhtStore {
         )
     }
 
-    pub(crate) fn queued_holding_workflows(&self) -> &VecDeque<PendingValidationWithTimeout> {
+    pub fn queued_holding_workflows(&self) -> &Vector<PendingValidationWithTimeout> {
         &self.queued_holding_workflows
     }
 
+    pub fn in_process_holding_workflows(&self) -> &Vector<PendingValidation> {
+        &self.in_process_holding_workflows
+    }
+
+    pub(crate) fn has_same_in_process_holding_workflow(&self, pending: &PendingValidation) -> bool {
+        self.in_process_holding_workflows.iter().any(|current| {
+            current.entry_with_header.header.entry_address()
+                == pending.entry_with_header.header.entry_address()
+                && current.workflow == pending.workflow
+        })
+    }
+
+    pub(crate) fn is_in_process_holding_workflow(&self, pending: &PendingValidation) -> bool {
+        self.in_process_holding_workflows.contains(pending)
+    }
+
     pub(crate) fn remove_holding_workflow(
         &mut self,
         item: &PendingValidation,
@@ -368,11 +708,115 @@ impl DhtStore {
         self.queued_holding_workflows()
             .iter()
             .position(|PendingValidationWithTimeout { pending, .. }| pending == item)
-            .and_then(|index| self.queued_holding_workflows.remove(index))
+            .map(|index| self.queued_holding_workflows.remove(index))
+    }
+
+    /// Takes the given item out of the queue and marks it as being in process.
+    /// Returns None if the item was not queued.
+    pub(crate) fn start_holding_workflow(
+        &mut self,
+        item: &PendingValidation,
+    ) -> Option<PendingValidationWithTimeout> {
+        let removed = self.remove_holding_workflow(item)?;
+        self.in_process_holding_workflows.push_back(item.clone());
+        Some(removed)
+    }
+
+    pub(crate) fn finish_holding_workflow(
+        &mut self,
+        item: &PendingValidation,
+    ) -> Option<PendingValidation> {
+        self.in_process_holding_workflows
+            .iter()
+            .position(|pending| pending == item)
+            .map(|index| self.in_process_holding_workflows.remove(index))
+    }
+
+    pub fn abandoned_holding_workflows(&self) -> &Vector<PendingValidation> {
+        &self.abandoned_holding_workflows
+    }
+
+    pub(crate) fn has_same_abandoned_holding_workflow(&self, pending: &PendingValidation) -> bool {
+        self.abandoned_holding_workflows.iter().any(|current| {
+            current.entry_with_header.header.entry_address()
+                == pending.entry_with_header.header.entry_address()
+                && current.workflow == pending.workflow
+        })
+    }
+
+    /// Evicts the oldest abandoned workflow if there are more than
+    /// MAX_ABANDONED_HOLDING_WORKFLOWS so the list does not grow forever.
+    pub(crate) fn abandon_holding_workflow(&mut self, item: &PendingValidation) {
+        self.abandoned_holding_workflows.push_back(item.clone());
+        while self.abandoned_holding_workflows.len() > MAX_ABANDONED_HOLDING_WORKFLOWS {
+            self.abandoned_holding_workflows.pop_front();
+        }
+    }
+
+    /// Moves all abandoned holding workflows back into the queue with their attempts reset.
+    pub(crate) fn retry_abandoned_holding_workflows(&mut self) {
+        let abandoned = std::mem::replace(&mut self.abandoned_holding_workflows, Vector::new());
+        for pending in abandoned {
+            self.queued_holding_workflows
+                .push_back(PendingValidationWithTimeout::new(
+                    Arc::new(pending.revive()),
+                    None,
+                ));
+        }
     }
-}
 
-use im::HashSet;
+    /// Lists every holding workflow that is queued, in process or abandoned
+    /// together with the information needed to tell why it has not been held yet.
+    pub fn pending_validation_reports(&self) -> Vec<PendingValidationReport> {
+        let report = |pending: &PendingValidation,
+                      status: PendingValidationStatus,
+                      retry_in: Option<Duration>| {
+            let missing_dependencies = pending
+                .dependencies
+                .iter()
+                .filter(|dependency| {
+                    self.holding_map
+                        .per_entry(&EntryHash::from((*dependency).clone()))
+                        .is_none()
+                })
+                .cloned()
+                .collect();
+            PendingValidationReport {
+                entry_address: pending.entry_with_header.entry.address(),
+                header_address: pending.entry_with_header.header.address(),
+                workflow: pending.workflow.clone(),
+                status,
+                attempts: pending.attempts,
+                last_error: pending.last_error.clone(),
+                missing_dependencies,
+                retry_in_ms: retry_in.map(|duration| duration.as_millis() as u64),
+            }
+        };
+
+        let queued = self.queued_holding_workflows.iter().map(
+            |PendingValidationWithTimeout { pending, timeout }| {
+                let retry_in = timeout.as_ref().and_then(|timeout| {
+                    timeout
+                        .time_of_dispatch
+                        .elapsed()
+                        .ok()
+                        .and_then(|elapsed| timeout.delay.checked_sub(elapsed))
+                });
+                report(pending, PendingValidationStatus::Queued, retry_in)
+            },
+        );
+        let in_process = self
+            .in_process_holding_workflows
+            .iter()
+            .map(|pending| report(pending, PendingValidationStatus::InProcess, None));
+        let abandoned = self
+            .abandoned_holding_workflows
+            .iter()
+            .map(|pending| report(pending, PendingValidationStatus::Abandoned, None));
+
+        queued.chain(in_process).chain(abandoned).collect()
+    }
+}
 
 fn free_pending_filter<I>(pending: &I) -> Box<dyn Fn(&PendingValidationWithTimeout) -> bool>
 where
@@ -416,7 +860,12 @@ pub mod tests {
     };
     use holochain_core_types::{
         chain_header::test_chain_header_with_sig,
-        entry::{test_entry, test_entry_a, test_entry_b, test_entry_c},
+        entry::{
+            deletion_entry::DeletionEntry, test_entry, test_entry_a, test_entry_b, test_entry_c,
+        },
+        network::query::{CursorPagination, GetLinksCursor},
+        signature::{Provenance, Signature},
+        time::test_iso_8601,
     };
 
     use holochain_persistence_api::{
@@ -440,6 +889,144 @@ pub mod tests {
         assert_eq!(headers, vec![header1, header2]);
     }
 
+    #[test]
+    fn purge_drops_content_of_entries_committed_by_the_purger_only() {
+        let mut store = DhtStore::new(
+            Arc::new(RwLock::new(
+                ExampleContentAddressableStorage::new().unwrap(),
+            )),
+            Arc::new(RwLock::new(ExampleEntityAttributeValueStorage::new())),
+        );
+        let entry = test_entry();
+        let header = test_chain_header_with_sig("sig1");
+        store.add(&entry).unwrap();
+        store.add_header_for_entry(&entry, &header).unwrap();
+
+        let deletion = Entry::Deletion(DeletionEntry::new_purge(entry.address()));
+        let deletion_header = ChainHeader::new(
+            &deletion.entry_type(),
+            &deletion.address(),
+            &vec![Provenance::new(
+                Address::from("someone else"),
+                Signature::from("sig"),
+            )],
+            &None,
+            &None,
+            &Some(entry.address()),
+            &test_iso_8601(),
+        );
+        assert_eq!(
+            store.purge_entry(&entry.address(), &deletion_header),
+            Ok(false)
+        );
+        assert_eq!(store.get(&entry.address()), Ok(Some(entry.clone())));
+
+        let deletion_header = ChainHeader::new(
+            &deletion.entry_type(),
+            &deletion.address(),
+            header.provenances(),
+            &None,
+            &None,
+            &Some(entry.address()),
+            &test_iso_8601(),
+        );
+        assert_eq!(
+            store.purge_entry(&entry.address(), &deletion_header),
+            Ok(true)
+        );
+        assert_eq!(store.is_purged(&entry.address()), Ok(true));
+        assert_eq!(store.get(&entry.address()), Ok(None));
+        assert_eq!(store.get_headers(entry.address()), Ok(vec![header]));
+    }
+
+    #[test]
+    fn chain_forks_stay_unresolved_until_resolved() {
+        let mut store = DhtStore::new(
+            Arc::new(RwLock::new(
+                ExampleContentAddressableStorage::new().unwrap(),
+            )),
+            Arc::new(RwLock::new(ExampleEntityAttributeValueStorage::new())),
+        );
+        let header1 = test_chain_header_with_sig("sig1");
+        let header2 = test_chain_header_with_sig("sig2");
+        let author = header1.author().unwrap();
+        store.add_agent_activity(&header1).unwrap();
+        store.add_agent_activity(&header2).unwrap();
+        assert_eq!(store.get_agent_activity(&author).unwrap().len(), 2);
+
+        let evidence = ChainForkEvidence::from_headers(&header1, &header2).unwrap();
+        store.add_chain_fork(&evidence).unwrap();
+        assert_eq!(
+            store.get_unresolved_chain_forks(&author),
+            Ok(vec![evidence.clone()])
+        );
+
+        store.resolve_chain_forks(&author).unwrap();
+        assert_eq!(store.get_unresolved_chain_forks(&author), Ok(vec![]));
+        let reports = store.chain_fork_reports().unwrap();
+        assert_eq!(reports.len(), 1);
+        assert_eq!(reports[0].evidence, evidence);
+        assert!(reports[0].resolved);
+    }
+
+    #[test]
+    fn get_links_with_cursor_pagination() {
+        let mut store = DhtStore::new(
+            Arc::new(RwLock::new(
+                ExampleContentAddressableStorage::new().unwrap(),
+            )),
+            Arc::new(RwLock::new(ExampleEntityAttributeValueStorage::new())),
+        );
+        let base = test_entry().address();
+        let link_attribute = Attribute::LinkTag("link-type".into(), "tag".into());
+        let add_link = |store: &mut DhtStore, index: i64| {
+            let link_add_address = Address::from(format!("link-add-{}", index));
+            store
+                .add_eavi(
+                    &EntityAttributeValueIndex::new_with_index(
+                        &base,
+                        &link_attribute,
+                        &link_add_address,
+                        index,
+                    )
+                    .unwrap(),
+                )
+                .unwrap();
+            link_add_address
+        };
+        let links: Vec<Address> = (1..=4).map(|index| add_link(&mut store, index)).collect();
+
+        let get_page = |store: &DhtStore, cursor: Option<GetLinksCursor>| {
+            store
+                .get_links(
+                    base.clone(),
+                    "link-type".into(),
+                    "tag".into(),
+                    None,
+                    GetLinksQueryConfiguration {
+                        pagination: Some(Pagination::Cursor(CursorPagination { cursor, limit: 3 })),
+                        ..Default::default()
+                    },
+                )
+                .unwrap()
+                .into_iter()
+                .map(|(eavi, _)| eavi.value())
+                .collect::<Vec<_>>()
+        };
+
+        // Newest first by default
+        let first_page = get_page(&store, None);
+        assert_eq!(
+            first_page,
+            vec![links[3].clone(), links[2].clone(), links[1].clone()]
+        );
+
+        // A link added after the first page does not shift the second one
+        add_link(&mut store, 5);
+        let cursor = GetLinksCursor::new(2, links[1].clone());
+        assert_eq!(get_page(&store, Some(cursor)), vec![links[0].clone()]);
+    }
+
     fn pending_validation_for_entry(
         entry: Entry,
         dependencies: Vec<Address>,
@@ -453,6 +1040,34 @@ pub mod tests {
         PendingValidationWithTimeout::new(Arc::new(pending_struct.clone()), None)
     }
 
+    #[test]
+    fn abandoned_holding_workflows_are_capped() {
+        let mut store = DhtStore::new(
+            Arc::new(RwLock::new(
+                ExampleContentAddressableStorage::new().unwrap(),
+            )),
+            Arc::new(RwLock::new(ExampleEntityAttributeValueStorage::new())),
+        );
+        let pending = pending_validation_for_entry(test_entry(), Vec::new()).pending;
+        let abandoned: Vec<PendingValidation> = (0..=MAX_ABANDONED_HOLDING_WORKFLOWS)
+            .map(|_| Arc::new(pending.same()))
+            .collect();
+        for item in &abandoned {
+            store.abandon_holding_workflow(item);
+        }
+
+        assert_eq!(
+            store.abandoned_holding_workflows().len(),
+            MAX_ABANDONED_HOLDING_WORKFLOWS
+        );
+        // the oldest one got evicted
+        assert_eq!(
+            store.abandoned_holding_workflows().front(),
+            Some(&abandoned[1])
+        );
+        assert_eq!(store.abandoned_holding_workflows().back(), abandoned.last());
+    }
+
     #[test]
     fn test_dependency_resolution_no_dependencies() {
         // A and B have no dependencies. Both should be free
@@ -1,5 +1,6 @@
 use crate::{
     entry::validation_dependencies::ValidationDependencies,
+    instance::{RETRY_VALIDATION_DURATION_MAX, RETRY_VALIDATION_DURATION_MIN},
     network::entry_with_header::EntryWithHeader,
 };
 use holochain_core_types::{
@@ -74,6 +75,12 @@ pub struct PendingValidationStruct {
     pub dependencies: Vec<Address>,
     pub workflow: ValidatingWorkflow,
     uuid: ProcessUniqueId,
+    /// Number of attempts to run this validation that had to be postponed
+    #[serde(default)]
+    pub attempts: u32,
+    /// Why the last attempt did not succeed
+    #[serde(default)]
+    pub last_error: Option<String>,
 }
 
 impl PendingValidationStruct {
@@ -84,6 +91,8 @@ impl PendingValidationStruct {
             dependencies,
             workflow,
             uuid: ProcessUniqueId::new(),
+            attempts: 0,
+            last_error: None,
         }
     }
 
@@ -92,6 +101,45 @@ impl PendingValidationStruct {
         clone.uuid = ProcessUniqueId::new();
         clone
     }
+
+    /// Creates the item to re-queue after an attempt failed with the given error.
+    pub fn retry(&self, error: &HolochainError) -> Self {
+        let mut retry = self.same();
+        retry.attempts += 1;
+        retry.last_error = Some(error.to_string());
+        retry
+    }
+
+    /// Creates the item to queue again after it was abandoned.
+    /// Starts over with the attempts but keeps the last error for diagnostics.
+    pub fn revive(&self) -> Self {
+        let mut revived = self.same();
+        revived.attempts = 0;
+        revived
+    }
+
+    /// Time to wait before the next attempt.
+    /// Exponential back-off: starts with RETRY_VALIDATION_DURATION_MIN, doubles with
+    /// every failed attempt and is capped by RETRY_VALIDATION_DURATION_MAX.
+    pub fn retry_delay(&self) -> Duration {
+        let exponent = self.attempts.saturating_sub(1).min(31);
+        RETRY_VALIDATION_DURATION_MIN
+            .checked_mul(1 << exponent)
+            .map(|delay| delay.min(RETRY_VALIDATION_DURATION_MAX))
+            .unwrap_or(RETRY_VALIDATION_DURATION_MAX)
+    }
+}
+
+/// Tells if a holding workflow that failed with the given error should be tried again.
+/// Missing dependencies and timeouts can resolve over time, invalid data can't.
+pub fn is_retryable(error: &HolochainError) -> bool {
+    match error {
+        HolochainError::ValidationPending
+        | HolochainError::Timeout
+        | HolochainError::EntryNotFoundLocally => true,
+        HolochainError::List(errors) => !errors.is_empty() && errors.iter().all(is_retryable),
+        _ => false,
+    }
 }
 
 impl TryFrom<EntryAspect> for PendingValidationStruct {
@@ -102,8 +150,11 @@ impl TryFrom<EntryAspect> for PendingValidationStruct {
                 EntryWithHeader::try_from_entry_and_header(entry, header)?,
                 ValidatingWorkflow::HoldEntry,
             )),
-            EntryAspect::Header(_header) => Err(HolochainError::NotImplemented(String::from(
-                "EntryAspect::Header",
+            EntryAspect::Header(_header) => Err(HolochainError::ErrorGeneric(String::from(
+                "EntryAspect::Header does not get validated by a holding workflow",
+            ))),
+            EntryAspect::Warrant(_warrant) => Err(HolochainError::ErrorGeneric(String::from(
+                "EntryAspect::Warrant does not get validated by a holding workflow",
             ))),
             EntryAspect::LinkAdd(link_data, header) => {
                 let entry = Entry::LinkAdd(link_data);
@@ -125,12 +176,12 @@ impl TryFrom<EntryAspect> for PendingValidationStruct {
             )),
             EntryAspect::Deletion(header) => {
                 // reconstruct the deletion entry from the header.
-                let deleted_entry_address = header.link_update_delete().ok_or_else(|| {
+                let deletion = DeletionEntry::from_header(&header).ok_or_else(|| {
                     HolochainError::ValidationFailed(String::from(
-                        "Deletion header is missing deletion link",
+                        "Deletion header is missing deletion link or does not match a deletion entry",
                     ))
                 })?;
-                let entry = Entry::Deletion(DeletionEntry::new(deleted_entry_address));
+                let entry = Entry::Deletion(deletion);
 
                 Ok(PendingValidationStruct::new(
                     EntryWithHeader::try_from_entry_and_header(entry, header)?,
@@ -199,3 +250,44 @@ impl PendingValidationWithTimeout {
         Self { pending, timeout }
     }
 }
+
+/// What happens to a holding workflow once a worker is done with it.
+/// It is applied in the same step that takes the workflow out of process,
+/// so no other worker can pick up the same aspect in between.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum HoldingWorkflowFollowUp {
+    /// Nothing, the aspect got held or can never be held
+    Done,
+    /// Queues the given retry with a delay, where the SystemTime is the time
+    /// the action got dispatched like with `Action::QueueHoldingWorkflow`
+    Retry(PendingValidation, (SystemTime, Duration)),
+    /// Abandons the given retry after it exceeded the maximum number of attempts
+    Abandon(PendingValidation),
+}
+
+/// Where a pending validation currently is in the holding pipeline.
+#[derive(Clone, Debug, PartialEq, Serialize)]
+pub enum PendingValidationStatus {
+    /// Waiting in the queue (possibly for its retry delay to elapse)
+    Queued,
+    /// Currently being run by a holding worker
+    InProcess,
+    /// Exceeded the maximum number of attempts and waits for `RetryAbandonedHoldingWorkflows`
+    Abandoned,
+}
+
+/// Diagnostic summary of one pending validation as returned by
+/// the `debug/pending_validations` conductor API function.
+#[derive(Clone, Debug, Serialize)]
+pub struct PendingValidationReport {
+    pub entry_address: Address,
+    pub header_address: Address,
+    pub workflow: ValidatingWorkflow,
+    pub status: PendingValidationStatus,
+    pub attempts: u32,
+    pub last_error: Option<String>,
+    /// Dependencies of this entry that are not held in the local shard yet
+    pub missing_dependencies: Vec<Address>,
+    /// Milliseconds until this item will be tried again, if it is waiting for its delay
+    pub retry_in_ms: Option<u64>,
+}
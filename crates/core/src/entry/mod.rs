@@ -13,7 +13,12 @@ pub trait CanPublish {
 impl CanPublish for EntryType {
     fn can_publish(&self, context: &Context) -> bool {
         match self {
-            EntryType::Dna | EntryType::CapTokenGrant | EntryType::CapTokenClaim => return false,
+            // membrane proofs can be secrets like invite codes, only validators
+            // of the AgentId entry get them from the author
+            EntryType::Dna
+            | EntryType::CapTokenGrant
+            | EntryType::CapTokenClaim
+            | EntryType::MembraneProof => return false,
             _ => {
                 if self.is_sys() {
                     return true;
@@ -67,6 +72,9 @@ pub mod tests {
             EntryType::ChainMigrate,
             EntryType::CapTokenClaim,
             EntryType::CapTokenGrant,
+            EntryType::MembraneProof,
+            EntryType::BlobChunk,
+            EntryType::BlobManifest,
         ]
     }
 
@@ -82,6 +90,7 @@ pub mod tests {
                 EntryType::Dna => assert!(!t.can_publish(&context)),
                 EntryType::CapTokenGrant => assert!(!t.can_publish(&context)),
                 EntryType::CapTokenClaim => assert!(!t.can_publish(&context)),
+                EntryType::MembraneProof => assert!(!t.can_publish(&context)),
                 EntryType::App(entry_type_name) => match entry_type_name.to_string().as_str() {
                     "testEntryType" => assert!(t.can_publish(&context)),
                     "testEntryTypeC" => {
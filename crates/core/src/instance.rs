@@ -2,9 +2,12 @@ use crate::{
     action::{Action, ActionWrapper},
     consistency::ConsistencyModel,
     context::{ActionReceiver, ActionSender, Context},
-    dht::actions::{
-        queue_holding_workflow::queue_holding_workflow,
-        remove_queued_holding_workflow::remove_queued_holding_workflow,
+    dht::{
+        actions::{
+            finish_holding_workflow::finish_holding_workflow,
+            start_holding_workflow::start_holding_workflow,
+        },
+        pending_validations::{is_retryable, HoldingWorkflowFollowUp},
     },
     network,
     persister::Persister,
@@ -36,12 +39,16 @@ use std::{
         Arc,
     },
     thread,
-    time::{Duration, Instant},
+    time::{Duration, Instant, SystemTime},
 };
+use threadpool::ThreadPool;
 
 pub const RECV_DEFAULT_TIMEOUT_MS: Duration = Duration::from_millis(10000);
 pub const RETRY_VALIDATION_DURATION_MIN: Duration = Duration::from_millis(500);
 pub const RETRY_VALIDATION_DURATION_MAX: Duration = Duration::from_secs(60 * 60);
+pub const MAX_VALIDATION_ATTEMPTS: u32 = 20;
+pub const MAX_ABANDONED_HOLDING_WORKFLOWS: usize = 1000;
+pub const DEFAULT_HOLDING_WORKFLOW_POOL_SIZE: usize = 4;
 
 /// Object representing a Holochain instance, i.e. a running holochain (DNA + DHT + source-chain)
 /// Holds the Event loop and processes it with the redux pattern.
@@ -80,6 +87,11 @@ impl Instance {
         scheduler
             .every(1.second())
             .run(scheduled_jobs::create_timeout_callback(context.clone()));
+        scheduler
+            .every(5.seconds())
+            .run(scheduled_jobs::create_countersigning_callback(
+                context.clone(),
+            ));
         scheduler
             .every(30.seconds())
             .run(scheduled_jobs::create_state_pruning_callback(
@@ -304,18 +316,29 @@ impl Instance {
         Ok(())
     }
 
+    /// Starts the thread that feeds queued holding workflows into a bounded pool of workers.
+    /// A workflow only gets handed to the pool if a worker is free and none of its
+    /// dependencies are still queued or in process, so dependencies get held first.
+    /// Taking a workflow from the queue marks it as in process in the state which makes
+    /// sure every aspect is only validated once at a time.
     fn start_holding_loop(&mut self, context: Arc<Context>) {
         let (kill_sender, kill_receiver) = crossbeam_channel::unbounded();
         self.kill_switch_holding = Some(kill_sender);
+        let pool_size = std::cmp::max(context.holding_workflow_pool_size, 1);
         thread::Builder::new()
             .name(format!(
                 "holding_loop/{}",
                 ProcessUniqueId::new().to_string()
             ))
             .spawn(move || {
+                let pool = ThreadPool::with_name(
+                    format!("holding_workflow/{}", ProcessUniqueId::new().to_string()),
+                    pool_size,
+                );
                 while kill_receiver.try_recv().is_err() {
                     log_trace!(context, "Checking holding queue...");
-                    loop {
+                    // Only take work from the queue if there is a free worker to run it
+                    while pool.active_count() + pool.queued_count() < pool_size {
                         // TODO: TRACING: it would be ideal to be able to associate a tracing Span with each queued holding workflow.
                         // To do this, we'd need to store a Span in each item of the DhtStore::queued_holding_workflows.
                         // However, Span is not Clone, and the entire DhtStore needs to be Cloned.
@@ -326,55 +349,57 @@ impl Instance {
                             .expect("Couldn't get state in run_pending_validations")
                             .dht();
                         let maybe_holding_workflow = dht_store.next_queued_holding_workflow();
-                        if let Some((pending, maybe_delay)) = maybe_holding_workflow {
+                        if let Some((pending, _)) = maybe_holding_workflow {
                             log_debug!(context, "Found queued validation: {:?}", pending);
-                            // NB: If for whatever reason we pop_next_holding_workflow anywhere else other than here,
-                            // we can run into a race condition.
-                            context.block_on(remove_queued_holding_workflow(
-                                pending.clone(),
-                                context.clone(),
-                            ));
+                            // NB: If for whatever reason we start a holding workflow anywhere else
+                            // other than here, we can run into a race condition.
+                            context
+                                .block_on(start_holding_workflow(pending.clone(), context.clone()));
 
                             let c = context.clone();
-                            let pending = pending.clone();
-
-                            let closure = async move || {
-                                match run_holding_workflow(pending.clone(), c.clone()).await {
-                                    // If we couldn't run the validation due to unresolved dependencies,
-                                    // we have to re-add this entry at the end of the queue:
-                                    Err(HolochainError::ValidationPending) => {
-                                        // And with a delay so we are not trying to re-validate many times per second.
-                                        let mut delay = maybe_delay
-                                            .map(|old_delay| {
-                                                // Exponential back-off:
-                                                // If this was delayed before we double the delay.
-                                                old_delay * 2
-                                            })
-                                            .unwrap_or(RETRY_VALIDATION_DURATION_MIN);
-
-                                        // Cap delay with max duration
-                                        if delay > RETRY_VALIDATION_DURATION_MAX {
-                                            delay = RETRY_VALIDATION_DURATION_MAX
+                            pool.execute(move || {
+                                let result =
+                                    c.block_on(run_holding_workflow(pending.clone(), c.clone()));
+                                let follow_up = match result {
+                                    // If we couldn't run the validation due to unresolved dependencies
+                                    // or a timeout, we have to re-add this entry at the end of the queue:
+                                    Err(ref error) if is_retryable(error) => {
+                                        let retry = Arc::new(pending.retry(error));
+                                        if retry.attempts >= MAX_VALIDATION_ATTEMPTS {
+                                            log_warn!(
+                                                c,
+                                                "Abandoning holding workflow after {} attempts: {:?}",
+                                                retry.attempts,
+                                                retry,
+                                            );
+                                            HoldingWorkflowFollowUp::Abandon(retry)
+                                        } else {
+                                            // And with a delay so we are not trying to re-validate many times per second.
+                                            let delay = retry.retry_delay();
+                                            HoldingWorkflowFollowUp::Retry(
+                                                retry,
+                                                (SystemTime::now(), delay),
+                                            )
                                         }
-
-                                        queue_holding_workflow(
-                                            Arc::new(pending.same()),
-                                            Some(delay),
-                                            c.clone(),
-                                        )
-                                        .await
                                     }
-                                    Err(e) => log_error!(
-                                        c,
-                                        "Error running holding workflow for {:?}: {:?}",
-                                        pending,
-                                        e,
-                                    ),
-                                    Ok(()) => log_info!(c, "Successfully processed: {:?}", pending),
-                                }
-                            };
-                            let future = closure();
-                            context.spawn_task(future);
+                                    Err(e) => {
+                                        log_error!(
+                                            c,
+                                            "Error running holding workflow for {:?}: {:?}",
+                                            pending,
+                                            e,
+                                        );
+                                        HoldingWorkflowFollowUp::Done
+                                    }
+                                    Ok(()) => {
+                                        log_info!(c, "Successfully processed: {:?}", pending);
+                                        HoldingWorkflowFollowUp::Done
+                                    }
+                                };
+                                // The retry gets queued in the same step that takes this attempt
+                                // out of process so no other worker can pick up the aspect in between
+                                c.block_on(finish_holding_workflow(pending, follow_up, c.clone()));
+                            });
                         } else {
                             break;
                         }
@@ -505,13 +530,22 @@ pub mod tests {
             state::{AgentActionResponse, AgentState},
         },
         context::{test_memory_network_config, Context},
+        dht::{
+            actions::queue_holding_workflow::dispatch_queue_holding_workflow,
+            pending_validations::{PendingValidationStruct, ValidatingWorkflow},
+        },
         logger::{test_logger, TestLogger},
+        network::entry_with_header::EntryWithHeader,
     };
     use holochain_core_types::{
         agent::AgentId,
-        chain_header::test_chain_header,
+        blob::BlobChunk,
+        chain_header::{test_chain_header, ChainHeader},
         dna::{zome::Zome, Dna},
         entry::{entry_type::EntryType, test_entry},
+        membrane_proof::MembraneProof,
+        network::entry_aspect::EntryAspect,
+        time::test_iso_8601,
     };
     use holochain_locksmith::{Mutex, RwLock};
     use holochain_persistence_api::cas::content::AddressableContent;
@@ -521,7 +555,7 @@ pub mod tests {
 
     use crate::persister::SimplePersister;
 
-    use std::{sync::Arc, thread::sleep, time::Duration};
+    use std::{collections::HashMap, sync::Arc, thread::sleep, time::Duration};
 
     use test_utils::mock_signing::registered_test_agent;
 
@@ -720,9 +754,28 @@ pub mod tests {
         dna: Dna,
         name: &str,
         network_name: Option<&str>,
+    ) -> Result<(Instance, Arc<Context>), String> {
+        test_instance_and_context_with_membrane_proof(dna, name, network_name, None)
+    }
+
+    /// create a test instance whose agent joins the DNA with the given membrane proof
+    #[cfg_attr(tarpaulin, skip)]
+    pub fn test_instance_and_context_with_membrane_proof(
+        dna: Dna,
+        name: &str,
+        network_name: Option<&str>,
+        membrane_proof: Option<MembraneProof>,
     ) -> Result<(Instance, Arc<Context>), String> {
         // Create instance and plug in our DNA
         let context = test_context_with_memory_network(name, network_name);
+        let context = match membrane_proof {
+            Some(membrane_proof) => {
+                let mut context = (*context).clone();
+                context.membrane_proof = Some(membrane_proof);
+                Arc::new(context)
+            }
+            None => context,
+        };
         let mut instance = Instance::new(context.clone());
         let context = instance.initialize(Some(dna.clone()), context.clone())?;
 
@@ -1010,4 +1063,83 @@ pub mod tests {
         let dht = context.dht_storage.read().unwrap();
         assert!(dht.contains(&entry.address()).unwrap());
     }
+
+    #[test]
+    /// Runs the holding worker pool with several workers and checks that every queued
+    /// aspect gets processed by exactly one of them
+    fn holding_workflows_get_processed_once() {
+        let (signal_tx, signal_rx) = unbounded();
+        let mut context = (*test_context_with_memory_network("jane", None)).clone();
+        context.signal_tx = Some(signal_tx);
+        context.holding_workflow_pool_size = 4;
+        let context = Arc::new(context);
+        let mut instance = Instance::new(context.clone());
+        let dna = test_utils::create_test_dna_with_wat("test_zome", None);
+        let context = instance.initialize(Some(dna), context).unwrap();
+
+        // blob chunks only need to be well-formed so their aspects get held right away
+        let aspects: Vec<EntryAspect> = (0..16)
+            .map(|i| {
+                let chunk = Entry::BlobChunk(BlobChunk::new(vec![i]));
+                let header = ChainHeader::new(
+                    &chunk.entry_type(),
+                    &chunk.address(),
+                    &[],
+                    &None,
+                    &None,
+                    &None,
+                    &test_iso_8601(),
+                );
+                EntryAspect::Content(chunk, header)
+            })
+            .collect();
+        for aspect in &aspects {
+            if let EntryAspect::Content(entry, header) = aspect {
+                let entry_with_header = EntryWithHeader {
+                    entry: entry.clone(),
+                    header: header.clone(),
+                };
+                dispatch_queue_holding_workflow(
+                    Arc::new(PendingValidationStruct::new(
+                        entry_with_header,
+                        ValidatingWorkflow::HoldEntry,
+                    )),
+                    None,
+                    context.clone(),
+                );
+            }
+        }
+
+        let start = Instant::now();
+        loop {
+            let dht = context.state().unwrap().dht();
+            if aspects
+                .iter()
+                .all(|aspect| dht.get_holding_map().contains(aspect))
+                && dht.queued_holding_workflows().is_empty()
+                && dht.in_process_holding_workflows().is_empty()
+            {
+                break;
+            }
+            assert!(
+                start.elapsed() < Duration::from_secs(30),
+                "Holding workflows did not finish in time"
+            );
+            sleep(Duration::from_millis(10));
+        }
+
+        let mut starts: HashMap<Address, usize> = HashMap::new();
+        for signal in signal_rx.try_iter() {
+            if let Signal::Trace(action_wrapper) = signal {
+                if let Action::StartHoldingWorkflow(pending) = action_wrapper.action() {
+                    *starts
+                        .entry(pending.entry_with_header.entry.address())
+                        .or_insert(0) += 1;
+                }
+            }
+        }
+        for aspect in &aspects {
+            assert_eq!(starts.get(aspect.entry_address()), Some(&1));
+        }
+    }
 }
@@ -8,6 +8,7 @@ use crate::{
 use futures::{future::Future, task::Poll};
 use holochain_core_types::{error::HolochainError, time::Timeout};
 use holochain_persistence_api::cas::content::Address;
+use holochain_wasm_utils::api_serialization::send::{SendOptions, SendResult};
 use rand::{distributions::Alphanumeric, thread_rng, Rng};
 use snowflake::ProcessUniqueId;
 use std::{pin::Pin, sync::Arc, time::SystemTime};
@@ -15,26 +16,65 @@ use std::{pin::Pin, sync::Arc, time::SystemTime};
 /// SendDirectMessage Action Creator for custom (=app) messages
 /// This triggers the network module to open a synchronous node-to-node connection
 /// by sending the given CustomDirectMessage and preparing to receive a response.
+/// With `deliver_later` set in the options, the send resolves as queued instead
+/// if the network stored the message for the disconnected recipient.
 #[autotrace]
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
 pub async fn custom_send(
     to_agent: Address,
     custom_direct_message: CustomDirectMessage,
+    options: SendOptions,
+    context: Arc<Context>,
+) -> Result<SendResult, HolochainError> {
+    send_and_wait(
+        to_agent,
+        DirectMessage::Custom(custom_direct_message),
+        options,
+        context,
+    )
+    .await
+}
+
+/// Sends the given DirectMessage to the agent and waits for the response
+/// to show up as custom direct message reply or for the timeout.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub(crate) async fn send_and_wait_for_response(
+    to_agent: Address,
+    direct_message: DirectMessage,
     timeout: Timeout,
     context: Arc<Context>,
 ) -> Result<String, HolochainError> {
+    let options = SendOptions {
+        timeout,
+        deliver_later: false,
+    };
+    match send_and_wait(to_agent, direct_message, options, context).await? {
+        SendResult::Response(response) => Ok(response),
+        SendResult::Queued => Err(HolochainError::ErrorGeneric(String::from(
+            "Direct message got queued although it was not sent to be delivered later",
+        ))),
+    }
+}
+
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+async fn send_and_wait(
+    to_agent: Address,
+    direct_message: DirectMessage,
+    options: SendOptions,
+    context: Arc<Context>,
+) -> Result<SendResult, HolochainError> {
     let rand_string: String = thread_rng().sample_iter(&Alphanumeric).take(10).collect();
     let id = format!("{}-{}", ProcessUniqueId::new().to_string(), rand_string);
-    let direct_message = DirectMessage::Custom(custom_direct_message);
     let direct_message_data = DirectMessageData {
         address: to_agent,
         message: direct_message,
         msg_id: id.clone(),
         is_response: false,
+        deliver_later: options.deliver_later,
     };
     let action_wrapper = ActionWrapper::new(Action::SendDirectMessage((
         direct_message_data,
-        Some((SystemTime::now(), timeout.into())),
+        Some((SystemTime::now(), options.timeout.into())),
     )));
     dispatch_action(context.action_channel(), action_wrapper);
 
@@ -46,6 +86,7 @@ pub async fn custom_send(
 }
 
 /// SendResponseFuture waits for a result to show up in NetworkState::custom_direct_message_replys
+/// or for the message to show up in NetworkState::queued_direct_messages
 pub struct SendResponseFuture {
     context: Arc<Context>,
     id: String,
@@ -53,7 +94,7 @@ pub struct SendResponseFuture {
 
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
 impl Future for SendResponseFuture {
-    type Output = Result<String, HolochainError>;
+    type Output = Result<SendResult, HolochainError>;
 
     fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context) -> Poll<Self::Output> {
         if let Some(err) = self.context.action_channel_error("SendResponseFuture") {
@@ -77,7 +118,14 @@ impl Future for SendResponseFuture {
                         self.context.action_channel(),
                         ActionWrapper::new(Action::ClearCustomSendResponse(self.id.clone())),
                     );
-                    Poll::Ready(result.clone())
+                    Poll::Ready(result.clone().map(SendResult::Response))
+                }
+                None if state.queued_direct_messages.contains(&self.id) => {
+                    dispatch_action(
+                        self.context.action_channel(),
+                        ActionWrapper::new(Action::ClearCustomSendResponse(self.id.clone())),
+                    );
+                    Poll::Ready(Ok(SendResult::Queued))
                 }
                 _ => Poll::Pending,
             }
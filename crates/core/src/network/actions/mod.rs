@@ -9,8 +9,12 @@ pub mod publish;
 #[autotrace]
 pub mod publish_header_entry;
 #[autotrace]
+pub mod publish_warrant;
+#[autotrace]
 pub mod query;
 #[autotrace]
+pub mod request_countersignature;
+#[autotrace]
 pub mod shutdown;
 
 use crate::state::ActionResponse;
@@ -23,6 +27,7 @@ use std::ops::Deref;
 pub enum NetworkActionResponse {
     Publish(HcResult<Address>),
     PublishHeaderEntry(HcResult<Address>),
+    PublishWarrant(HcResult<Address>),
     Respond(HcResult<()>),
 }
 
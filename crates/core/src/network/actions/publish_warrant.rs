@@ -0,0 +1,70 @@
+use crate::{
+    action::{Action, ActionWrapper},
+    context::Context,
+    instance::dispatch_action,
+    network::actions::NetworkActionResponse,
+    NEW_RELIC_LICENSE_KEY,
+};
+use futures::{future::Future, task::Poll};
+use holochain_core_types::{error::HcResult, warrant::Warrant};
+use holochain_persistence_api::cas::content::Address;
+use std::{pin::Pin, sync::Arc};
+
+/// Publish Warrant Action Creator
+/// Returns a future that resolves to the address of the published warrant.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub async fn publish_warrant(warrant: Warrant, context: &Arc<Context>) -> HcResult<Address> {
+    let action_wrapper = ActionWrapper::new(Action::PublishWarrant(warrant));
+    dispatch_action(context.action_channel(), action_wrapper.clone());
+    PublishWarrantFuture {
+        context: context.clone(),
+        action: action_wrapper,
+    }
+    .await
+}
+
+/// PublishWarrantFuture resolves to ActionResponse
+/// Tracks the state for a response to its ActionWrapper
+pub struct PublishWarrantFuture {
+    context: Arc<Context>,
+    action: ActionWrapper,
+}
+
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+impl Future for PublishWarrantFuture {
+    type Output = HcResult<Address>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context) -> Poll<Self::Output> {
+        if let Some(err) = self.context.action_channel_error("PublishWarrantFuture") {
+            return Poll::Ready(Err(err));
+        }
+
+        //
+        // TODO: connect the waker to state updates for performance reasons
+        // See: https://github.com/holochain/holochain-rust/issues/314
+        //
+        cx.waker().clone().wake();
+
+        if let Some(state) = self.context.try_state() {
+            let state = state.network();
+            if let Err(error) = state.initialized() {
+                return Poll::Ready(Err(error));
+            }
+            match state.actions().get(&self.action) {
+                Some(r) => match r.response() {
+                    NetworkActionResponse::PublishWarrant(result) => {
+                        dispatch_action(
+                            self.context.action_channel(),
+                            ActionWrapper::new(Action::ClearActionResponse(*self.action.id())),
+                        );
+                        Poll::Ready(result.clone())
+                    }
+                    _ => unreachable!(),
+                },
+                _ => Poll::Pending,
+            }
+        } else {
+            Poll::Pending
+        }
+    }
+}
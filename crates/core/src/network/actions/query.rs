@@ -1,5 +1,8 @@
 use crate::{
-    action::{Action, ActionWrapper, GetEntryKey, GetLinksKey, QueryKey, QueryPayload},
+    action::{
+        Action, ActionWrapper, GetAgentActivityKey, GetEntriesKey, GetEntryKey, GetLinksKey,
+        QueryKey, QueryPayload,
+    },
     context::Context,
     instance::dispatch_action,
     network::query::{GetLinksNetworkQuery, NetworkQueryResult},
@@ -9,7 +12,9 @@ use futures::{future::Future, task::Poll};
 
 use holochain_persistence_api::cas::content::Address;
 
-use holochain_core_types::{crud_status::CrudStatus, error::HcResult, time::Timeout};
+use holochain_core_types::{
+    agent_activity::AgentActivityFilter, crud_status::CrudStatus, error::HcResult, time::Timeout,
+};
 
 use std::{pin::Pin, sync::Arc};
 
@@ -28,6 +33,17 @@ use std::time::SystemTime;
 pub enum QueryMethod {
     Entry(Address),
     Link(GetLinksArgs, GetLinksNetworkQuery),
+    Entries(Vec<Address>),
+    AgentActivity(Address, AgentActivityFilter),
+}
+
+/// The CRUD status of the links a get_links request asks for, None for all of them
+pub fn links_crud_status(status_request: &LinksStatusRequestKind) -> Option<CrudStatus> {
+    match status_request {
+        LinksStatusRequestKind::All => None,
+        LinksStatusRequestKind::Deleted => Some(CrudStatus::Deleted),
+        LinksStatusRequestKind::Live => Some(CrudStatus::Live),
+    }
 }
 
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
@@ -51,16 +67,29 @@ pub async fn query(
                 tag: link_args.tag.clone(),
                 id: ProcessUniqueId::new().to_string(),
             };
-            let crud_status = match link_args.options.status_request {
-                LinksStatusRequestKind::All => None,
-                LinksStatusRequestKind::Deleted => Some(CrudStatus::Deleted),
-                LinksStatusRequestKind::Live => Some(CrudStatus::Live),
-            };
+            let crud_status = links_crud_status(&link_args.options.status_request);
             (
                 QueryKey::Links(key),
                 QueryPayload::Links((crud_status, query)),
             )
         }
+        QueryMethod::Entries(addresses) => {
+            let key = GetEntriesKey {
+                addresses,
+                id: ProcessUniqueId::new().to_string(),
+            };
+            (QueryKey::Entries(key), QueryPayload::Entries)
+        }
+        QueryMethod::AgentActivity(agent, filter) => {
+            let key = GetAgentActivityKey {
+                agent,
+                id: ProcessUniqueId::new().to_string(),
+            };
+            (
+                QueryKey::AgentActivity(key),
+                QueryPayload::AgentActivity(filter),
+            )
+        }
     };
 
     let entry = Action::Query((
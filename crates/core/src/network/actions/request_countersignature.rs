@@ -0,0 +1,31 @@
+use crate::{
+    context::Context,
+    network::{actions::custom_send::send_and_wait_for_response, direct_message::DirectMessage},
+    NEW_RELIC_LICENSE_KEY,
+};
+use holochain_core_types::{
+    countersigning::CountersigningRequest, error::HolochainError, signature::Provenance,
+    time::Timeout,
+};
+use holochain_json_api::json::JsonString;
+use holochain_persistence_api::cas::content::Address;
+use std::{convert::TryFrom, sync::Arc};
+
+/// Sends the countersigning request to the given signer and waits for its signature.
+/// Fails if the signer refuses to sign or doesn't respond in time.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub async fn request_countersignature(
+    signer: Address,
+    request: CountersigningRequest,
+    timeout: Timeout,
+    context: Arc<Context>,
+) -> Result<Provenance, HolochainError> {
+    let response = send_and_wait_for_response(
+        signer,
+        DirectMessage::CountersigningRequest(request),
+        timeout,
+        context,
+    )
+    .await?;
+    Provenance::try_from(JsonString::from_json(&response)).map_err(HolochainError::from)
+}
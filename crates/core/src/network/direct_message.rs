@@ -2,7 +2,14 @@ use holochain_persistence_api::cas::content::Address;
 
 use holochain_json_api::{error::JsonError, json::JsonString};
 
-use holochain_core_types::validation::ValidationPackage;
+use holochain_wasm_utils::api_serialization::emit_signal::EmitSignalArgs;
+
+use holochain_core_types::{
+    countersigning::{CountersigningOutcome, CountersigningRequest},
+    signature::Provenance,
+    validation::ValidationPackage,
+    validation_receipt::ValidationReceipt,
+};
 
 /// This is direct message that got created by the zome code through hdk::send().
 #[derive(Serialize, Deserialize, Clone, PartialEq, Debug, DefaultJson)]
@@ -35,4 +42,25 @@ pub enum DirectMessage {
     /// Option<> since there has to be a way to respond saying
     /// "I can't"
     ValidationPackage(Option<ValidationPackage>),
+
+    /// With this message a validator lets the author of an entry know
+    /// that it has validated and is holding the entry.
+    /// This message does not get a response.
+    ValidationReceipt(ValidationReceipt),
+
+    /// With this message the proposer of a countersigned entry asks
+    /// another signer to sign it.
+    CountersigningRequest(CountersigningRequest),
+
+    /// With this message a signer is responding to a CountersigningRequest
+    /// with its signature of the entry address, or the reason it refuses to sign.
+    CountersigningSignature(Result<Provenance, String>),
+
+    /// With this message the proposer tells all other signers whether to commit
+    /// the countersigned entry or to drop it. This message does not get a response.
+    CountersigningOutcome(CountersigningOutcome),
+
+    /// A signal that zome code of the sender wants our conductor to emit
+    /// to our UIs. This message does not get a response.
+    RemoteSignal(EmitSignalArgs),
 }
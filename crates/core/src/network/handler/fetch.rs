@@ -1,6 +1,7 @@
 use crate::{
     action::{Action, ActionWrapper},
     context::Context,
+    entry::CanPublish,
     instance::dispatch_action,
     network::handler::{
         get_content_aspect, get_meta_aspects_from_chain, get_meta_aspects_from_dht_eav,
@@ -8,6 +9,7 @@ use crate::{
     NEW_RELIC_LICENSE_KEY,
 };
 use holochain_core_types::network::entry_aspect::EntryAspect;
+use holochain_persistence_api::cas::content::AddressableContent;
 use lib3h_protocol::data_types::FetchEntryData;
 use std::{collections::HashSet, sync::Arc};
 
@@ -50,6 +52,50 @@ pub fn handle_fetch_entry(get_dht_data: FetchEntryData, context: Arc<Context>) {
         }
     }
 
+    // Warrants about this entry get gossiped even if we don't hold the entry itself
+    match context.state().unwrap().dht().get_warrants(&address) {
+        Ok(warrants) => warrants
+            .into_iter()
+            .filter(|warrant| warrant.invalid_header().entry_address() == &*address)
+            .for_each(|warrant| {
+                aspects.insert(EntryAspect::Warrant(warrant));
+            }),
+        Err(get_warrants_error) => {
+            log_error!(
+                context,
+                "net/handle_fetch_entry: Error getting warrants for entry ({:?}), error: {:?}",
+                address,
+                get_warrants_error,
+            );
+        }
+    }
+
+    // Agent activity is held under the agent's address, and we author our own
+    match context.state().unwrap().dht().get_agent_activity(&address) {
+        Ok(headers) => headers.into_iter().for_each(|header| {
+            aspects.insert(EntryAspect::Header(header));
+        }),
+        Err(get_activity_error) => {
+            log_error!(
+                context,
+                "net/handle_fetch_entry: Error getting agent activity of ({:?}), error: {:?}",
+                address,
+                get_activity_error,
+            );
+        }
+    }
+    if *address == context.agent_id.address() {
+        context
+            .state()
+            .unwrap()
+            .agent()
+            .iter_chain()
+            .filter(|chain_header| chain_header.entry_type().can_publish(&context))
+            .for_each(|chain_header| {
+                aspects.insert(EntryAspect::Header(chain_header));
+            });
+    }
+
     let aspects = aspects.into_iter().collect::<Vec<_>>();
 
     let action_wrapper = ActionWrapper::new(Action::RespondFetch((get_dht_data, aspects)));
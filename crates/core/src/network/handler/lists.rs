@@ -61,6 +61,14 @@ fn create_authoring_map(context: Arc<Context>) -> AspectMap {
             _ => panic!("get_content_aspect must return only EntryAspect::Content"),
         };
 
+        // Its header also gets published alone as agent activity to our own address:
+        address_map
+            .entry(EntryHash::from(context.agent_id.address()))
+            .or_insert_with(|| HashSet::new())
+            .insert(AspectHash::from(
+                EntryAspect::Header(header.clone()).address(),
+            ));
+
         // And then we deduce the according base entry and meta aspect from that entry
         // and its header:
         let maybe_meta_aspect = entry_to_meta_aspect(entry, header);
@@ -15,8 +15,10 @@ pub mod send;
 pub mod store;
 
 use crate::{
+    action::{Action, ActionWrapper},
     context::Context,
     entry::CanPublish,
+    instance::dispatch_action,
     network::{
         direct_message::DirectMessage,
         entry_aspect::EntryAspect,
@@ -36,7 +38,7 @@ use holochain_core_types::{
     chain_header::ChainHeader, eav::Attribute, entry::Entry, error::HolochainError, time::Timeout,
 };
 use holochain_json_api::json::JsonString;
-use holochain_net::connection::net_connection::NetHandler;
+use holochain_net::{connection::net_connection::NetHandler, sim2h_worker::MAILBOX_STORED};
 use holochain_persistence_api::cas::content::{Address, AddressableContent};
 use lib3h_protocol::{
     data_types::{DirectMessageData, GenericResultData, StoreEntryAspectData},
@@ -114,12 +116,47 @@ MessageData {{
     )
 }
 
+/// Returns true if we sent the direct message with the given ID
+/// for the network to deliver later.
+pub(crate) fn is_delivered_later(context: &Arc<Context>, request_id: &str) -> bool {
+    context
+        .network_state()
+        .map(|state| state.deliver_later_direct_messages.contains_key(request_id))
+        .unwrap_or(false)
+}
+
 // TODO Implement a failure workflow?
 #[autotrace]
 fn handle_failure_result(
     context: &Arc<Context>,
     failure_data: GenericResultData,
 ) -> Result<(), HolochainError> {
+    if is_delivered_later(context, &failure_data.request_id) {
+        // The network could neither deliver nor store our direct message
+        let waiting = context
+            .network_state()
+            .map(|state| {
+                state
+                    .direct_message_connections
+                    .contains_key(&failure_data.request_id)
+            })
+            .unwrap_or(false);
+        if waiting {
+            let reason = String::from_utf8_lossy(&failure_data.result_info).to_string();
+            dispatch_action(
+                context.action_channel(),
+                ActionWrapper::new(Action::HandleCustomSendResponse((
+                    failure_data.request_id.clone(),
+                    Err(reason),
+                ))),
+            );
+            dispatch_action(
+                context.action_channel(),
+                ActionWrapper::new(Action::ResolveDirectConnection(failure_data.request_id)),
+            );
+        }
+        return Ok(());
+    }
     log_warn!(
         context,
         "handle_failure_result: unhandle failure={:?}",
@@ -128,6 +165,31 @@ fn handle_failure_result(
     Ok(())
 }
 
+/// The only success results we care about tell us what happened to direct messages
+/// we wanted delivered later.
+fn handle_success_result(context: &Arc<Context>, success_data: GenericResultData) {
+    if !is_delivered_later(context, &success_data.request_id) {
+        return;
+    }
+    if success_data.result_info.as_slice() == MAILBOX_STORED.as_bytes() {
+        log_debug!(
+            context,
+            "net/handle: Direct message {} stored for disconnected recipient",
+            success_data.request_id
+        );
+        dispatch_action(
+            context.action_channel(),
+            ActionWrapper::new(Action::HandleQueuedDirectMessage(success_data.request_id)),
+        );
+    } else {
+        log_debug!(
+            context,
+            "net/handle: Stored direct message {} delivered",
+            success_data.request_id
+        );
+    }
+}
+
 /// Creates the network handler.
 /// The returned closure is called by the network thread for every network event that core
 /// has to handle.
@@ -165,6 +227,12 @@ pub fn create_handler(c: &Arc<Context>, my_dna_address: String) -> NetHandler {
                 log_warn!(context, "net/handle: FailureResult: {:?}", failure_data);
                 handle_failure_result(&context, failure_data).expect("handle_failure_result")
             }
+            Lib3hServerProtocol::SuccessResult(success_data) => {
+                if !is_my_dna(&my_dna_address, &success_data.space_address.to_string()) {
+                    return Ok(());
+                }
+                handle_success_result(&context, success_data)
+            }
             Lib3hServerProtocol::HandleStoreEntryAspect(dht_entry_data) => {
                 if !is_my_dna(&my_dna_address, &dht_entry_data.space_address.to_string()) {
                     return Ok(());
@@ -1,5 +1,8 @@
 use crate::{
-    action::{Action, ActionWrapper, GetEntryKey, GetLinksKey, QueryKey},
+    action::{
+        Action, ActionWrapper, GetAgentActivityKey, GetEntriesKey, GetEntryKey, GetLinksKey,
+        QueryKey,
+    },
     context::Context,
     entry::CanPublish,
     instance::dispatch_action,
@@ -11,14 +14,15 @@ use crate::{
     NEW_RELIC_LICENSE_KEY,
 };
 use holochain_core_types::{
+    agent_activity::{AgentActivity, AgentActivityFilter},
     crud_status::CrudStatus,
     eav::Attribute,
     entry::{Entry, EntryWithMetaAndHeader},
     error::HolochainError,
-    network::query::{GetLinkData, GetLinksQueryConfiguration},
+    network::query::{GetLinkData, GetLinksCursor, GetLinksQueryConfiguration, Pagination},
 };
 use holochain_json_api::json::JsonString;
-use holochain_persistence_api::cas::content::Address;
+use holochain_persistence_api::cas::content::{Address, AddressableContent};
 use holochain_wasm_utils::api_serialization::get_entry::{
     GetEntryArgs, GetEntryOptions, GetEntryResultType,
 };
@@ -27,19 +31,20 @@ use std::{convert::TryInto, sync::Arc};
 
 pub type LinkTag = String;
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+/// Gets the links on the given base from our local DHT shard
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
-fn get_links(
+pub(crate) fn get_links(
     context: &Arc<Context>,
     base: Address,
     link_type: String,
     tag: String,
     crud_status: Option<CrudStatus>,
     query_configuration: GetLinksQueryConfiguration,
-) -> Result<Vec<GetLinkData>, HolochainError> {
+) -> Result<(Vec<GetLinkData>, Option<GetLinksCursor>), HolochainError> {
     //get links
     let dht_store = context.state().unwrap().dht();
 
-    let (get_link, error): (Vec<_>, Vec<_>) = dht_store
+    let links = dht_store
         .get_links(
             base,
             link_type,
@@ -47,7 +52,19 @@ fn get_links(
             crud_status,
             query_configuration.clone(),
         )
-        .unwrap_or_default()
+        .unwrap_or_default();
+
+    // A full page of cursor pagination continues after its last link
+    let next_cursor = match &query_configuration.pagination {
+        Some(Pagination::Cursor(cursor_pagination)) if links.len() == cursor_pagination.limit => {
+            links
+                .last()
+                .map(|(eavi, _)| GetLinksCursor::new(eavi.index(), eavi.value()))
+        }
+        _ => None,
+    };
+
+    let (get_link, error): (Vec<_>, Vec<_>) = links
         .into_iter()
         //get tag
         .map(|(eavi, crud)| {
@@ -126,10 +143,13 @@ fn get_links(
 
     //if can't find target throw error
     if error.is_empty() {
-        Ok(get_link
-            .iter()
-            .map(|s| s.clone().unwrap())
-            .collect::<Vec<_>>())
+        Ok((
+            get_link
+                .iter()
+                .map(|s| s.clone().unwrap())
+                .collect::<Vec<_>>(),
+            next_cursor,
+        ))
     } else {
         Err(HolochainError::List(
             error
@@ -176,6 +196,38 @@ fn get_entry(context: &Arc<Context>, address: Address) -> Option<EntryWithMetaAn
         .unwrap_or(None)
 }
 
+/// Builds the activity of the given agent from the headers we hold for it,
+/// including our own public chain if we are asked about ourselves.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+fn get_agent_activity(
+    context: &Arc<Context>,
+    agent: Address,
+    filter: &AgentActivityFilter,
+) -> AgentActivity {
+    let state = context
+        .state()
+        .expect("Could not get state for handle_query_entry_data");
+    let mut headers = state
+        .dht()
+        .get_agent_activity(&agent)
+        .unwrap_or_else(|error| {
+            log_error!(
+                context,
+                "net: Error trying to get agent activity {:?}",
+                error
+            );
+            Vec::new()
+        });
+    if agent == context.agent_id.address() {
+        for chain_header in state.agent().iter_chain() {
+            if chain_header.entry_type().can_publish(&context) && !headers.contains(&chain_header) {
+                headers.push(chain_header);
+            }
+        }
+    }
+    AgentActivity::new(agent, headers, filter)
+}
+
 /// The network has sent us a query for entry data, so we need to examine
 /// the query and create appropriate actions for the different variants
 #[autotrace]
@@ -196,9 +248,11 @@ pub fn handle_query_entry_data(query_data: QueryEntryData, context: Arc<Context>
                     _ => GetLinksQueryConfiguration::default(),
                 },
             ) {
-                Ok(links) => {
+                Ok((links, next_cursor)) => {
                     let links_result = match query {
-                        GetLinksNetworkQuery::Links(_) => GetLinksNetworkResult::Links(links),
+                        GetLinksNetworkQuery::Links(_) => {
+                            GetLinksNetworkResult::Links(links, next_cursor)
+                        }
                         GetLinksNetworkQuery::Count => GetLinksNetworkResult::Count(links.len()),
                     };
                     let respond_links = NetworkQueryResult::Links(links_result, link_type, tag);
@@ -219,6 +273,25 @@ pub fn handle_query_entry_data(query_data: QueryEntryData, context: Arc<Context>
             let respond_get = NetworkQueryResult::Entry(maybe_entry);
             ActionWrapper::new(Action::RespondQuery((query_data, respond_get)))
         }
+        Ok(NetworkQuery::GetEntries(addresses)) => {
+            // We answer for every address, also the ones we don't hold so that
+            // the requester knows which ones to ask other authorities about.
+            let entries = addresses
+                .into_iter()
+                .map(|address| {
+                    let maybe_entry = get_entry(&context, address.clone());
+                    (address, maybe_entry)
+                })
+                .collect();
+            let respond_get = NetworkQueryResult::Entries(entries);
+            ActionWrapper::new(Action::RespondQuery((query_data, respond_get)))
+        }
+        Ok(NetworkQuery::GetAgentActivity(filter)) => {
+            let activity =
+                get_agent_activity(&context, query_data.entry_address.clone().into(), &filter);
+            let respond_activity = NetworkQueryResult::AgentActivity(activity);
+            ActionWrapper::new(Action::RespondQuery((query_data, respond_activity)))
+        }
         err => {
             log_error!(
                 context,
@@ -268,6 +341,26 @@ pub fn handle_query_entry_result(query_result_data: QueryEntryResultData, contex
                 }),
             )))
         }
+        Ok(NetworkQueryResult::Entries(entries)) => {
+            let addresses = entries.iter().map(|(address, _)| address.clone()).collect();
+            ActionWrapper::new(Action::HandleQuery((
+                NetworkQueryResult::Entries(entries),
+                QueryKey::Entries(GetEntriesKey {
+                    addresses,
+                    id: query_result_data.request_id,
+                }),
+            )))
+        }
+        Ok(NetworkQueryResult::AgentActivity(activity)) => {
+            let agent = activity.agent.clone();
+            ActionWrapper::new(Action::HandleQuery((
+                NetworkQueryResult::AgentActivity(activity),
+                QueryKey::AgentActivity(GetAgentActivityKey {
+                    agent,
+                    id: query_result_data.request_id,
+                }),
+            )))
+        }
         err => {
             log_error!(
                 context,
@@ -2,16 +2,21 @@ use crate::{
     action::{Action, ActionWrapper},
     context::Context,
     instance::dispatch_action,
-    network::direct_message::DirectMessage,
+    network::{direct_message::DirectMessage, handler::is_delivered_later},
+    signal::{Signal, UserSignal},
     workflows::{
+        countersigning::{handle_countersigning_outcome, respond_countersigning_request},
         handle_custom_direct_message::handle_custom_direct_message,
         respond_validation_package_request::respond_validation_package_request,
+        validation_receipt::hold_validation_receipt_workflow,
+        warrant::is_blocked_agent,
     },
     NEW_RELIC_LICENSE_KEY,
 };
 use std::sync::Arc;
 
 use holochain_json_api::{error::JsonError, json::JsonString};
+use holochain_persistence_api::cas::content::Address;
 use lib3h_protocol::data_types::DirectMessageData;
 use std::convert::TryFrom;
 
@@ -29,6 +34,16 @@ fn parse_direct_message(content: &[u8]) -> Result<DirectMessage, JsonError> {
 #[autotrace]
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
 pub fn handle_send_message(message_data: DirectMessageData, context: Arc<Context>) {
+    let sender: Address = message_data.from_agent_id.clone().into();
+    if is_blocked_agent(&sender, &context) {
+        log_warn!(
+            context,
+            "net/handle_send_message: Ignoring message from warranted agent {}",
+            sender,
+        );
+        return;
+    }
+
     let message = match parse_direct_message(&*message_data.content.clone()) {
         Ok(message) => message,
         Err(error) => {
@@ -81,6 +96,57 @@ pub fn handle_send_message(message_data: DirectMessageData, context: Arc<Context
             "net: Got DirectMessage::ValidationPackage as initial message. This should not happen.",
         )
         }
+        DirectMessage::ValidationReceipt(receipt) => {
+            if let Err(error) = hold_validation_receipt_workflow(receipt, context.clone()) {
+                log_warn!(
+                    context,
+                    "net/handle_send_message: Dropping validation receipt: {:?}",
+                    error
+                );
+            }
+        }
+        DirectMessage::CountersigningRequest(request) => {
+            context.spawn_task(respond_countersigning_request(
+                sender,
+                message_data.request_id,
+                request,
+                context.clone(),
+            ));
+        }
+        DirectMessage::CountersigningSignature(_) => {
+            log_error!(context,
+            "net: Got DirectMessage::CountersigningSignature as initial message. This should not happen.",
+        )
+        }
+        DirectMessage::RemoteSignal(signal) => {
+            // Like with emit_signal, nobody listening is not an error of the sender
+            if let Some(signal_tx) = context.signal_tx() {
+                let signal = Signal::User(UserSignal::remote(signal, sender));
+                if let Err(error) = signal_tx.send(signal) {
+                    log_error!(
+                        context,
+                        "net/handle_send_message: Could not emit remote signal: {:?}",
+                        error
+                    );
+                }
+            }
+        }
+        DirectMessage::CountersigningOutcome(outcome) => {
+            context.spawn_task({
+                let context = context.clone();
+                async move {
+                    if let Err(error) =
+                        handle_countersigning_outcome(sender, outcome, context.clone()).await
+                    {
+                        log_warn!(
+                            context,
+                            "net/handle_send_message: Dropping countersigning outcome: {:?}",
+                            error
+                        );
+                    }
+                }
+            });
+        }
     };
 }
 
@@ -111,7 +177,12 @@ pub fn handle_send_message_result(message_data: DirectMessageData, context: Arc<
     match response {
         DirectMessage::Custom(custom_direct_message) => {
             if initial_message.is_none() {
-                log_error!(context, "net: Received a custom direct message response but could not find message ID in history. Not able to process.");
+                if is_delivered_later(&context, &message_data.request_id) {
+                    // The send already resolved as queued when the network stored the message
+                    log_debug!(context, "net: Ignoring response to direct message {} delivered later.", message_data.request_id);
+                } else {
+                    log_error!(context, "net: Received a custom direct message response but could not find message ID in history. Not able to process.");
+                }
                 return;
             }
 
@@ -128,6 +199,34 @@ pub fn handle_send_message_result(message_data: DirectMessageData, context: Arc<
         DirectMessage::RequestValidationPackage(_) => log_error!(context,
             "net: Got DirectMessage::RequestValidationPackage as a response. This should not happen.",
         ),
+        DirectMessage::ValidationReceipt(_) => log_error!(context,
+            "net: Got DirectMessage::ValidationReceipt as a response. This should not happen.",
+        ),
+        DirectMessage::CountersigningRequest(_) => log_error!(context,
+            "net: Got DirectMessage::CountersigningRequest as a response. This should not happen.",
+        ),
+        DirectMessage::CountersigningOutcome(_) => log_error!(context,
+            "net: Got DirectMessage::CountersigningOutcome as a response. This should not happen.",
+        ),
+        DirectMessage::RemoteSignal(_) => log_error!(context,
+            "net: Got DirectMessage::RemoteSignal as a response. This should not happen.",
+        ),
+        DirectMessage::CountersigningSignature(response) => {
+            if initial_message.is_none() {
+                log_error!(context, "net: Received a countersignature but could not find message ID in history. Not able to process.");
+                return;
+            }
+
+            let action_wrapper = ActionWrapper::new(Action::HandleCustomSendResponse((
+                message_data.request_id.clone(),
+                response.map(|provenance| JsonString::from(provenance).to_string()),
+            )));
+            dispatch_action(context.action_channel(), action_wrapper);
+
+            let action_wrapper =
+                ActionWrapper::new(Action::ResolveDirectConnection(message_data.request_id));
+            dispatch_action(context.action_channel(), action_wrapper);
+        }
         DirectMessage::ValidationPackage(maybe_validation_package) => {
             if initial_message.is_none() {
                 log_error!(context, "net: Received a validation package but could not find message ID in history. Not able to process.");
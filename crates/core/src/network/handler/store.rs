@@ -4,6 +4,10 @@ use crate::{
         actions::queue_holding_workflow::dispatch_queue_holding_workflow,
         pending_validations::PendingValidationStruct,
     },
+    workflows::{
+        agent_activity::hold_agent_activity_workflow,
+        warrant::{hold_warrant_workflow, is_blocked_agent},
+    },
     NEW_RELIC_LICENSE_KEY,
 };
 use holochain_core_types::network::entry_aspect::EntryAspect;
@@ -36,6 +40,49 @@ pub fn handle_store(dht_data: StoreEntryAspectData, context: Arc<Context>) {
             );
             return;
         }
+        if let EntryAspect::Warrant(warrant) = aspect {
+            let c = context.clone();
+            let closure = async move || {
+                if let Err(error) = hold_warrant_workflow(warrant, c.clone()).await {
+                    log_error!(c, "net/handle_store: Error holding warrant: {:?}", error);
+                }
+            };
+            let future = closure();
+            context.spawn_task(future);
+            return;
+        }
+        // Published headers are held as agent activity of their author even if the
+        // author is warranted, since that is exactly what audits need to see.
+        if let EntryAspect::Header(header) = aspect {
+            let c = context.clone();
+            let closure = async move || {
+                if let Err(error) = hold_agent_activity_workflow(header, c.clone()).await {
+                    log_error!(
+                        c,
+                        "net/handle_store: Error holding agent activity: {:?}",
+                        error
+                    );
+                }
+            };
+            let future = closure();
+            context.spawn_task(future);
+            return;
+        }
+        if let Some(blocked_author) = aspect
+            .header()
+            .provenances()
+            .iter()
+            .map(|provenance| provenance.source())
+            .find(|author| is_blocked_agent(author, &context))
+        {
+            log_warn!(
+                context,
+                "net/handle_store: Dropping aspect authored by warranted agent {}: {:?}",
+                blocked_author,
+                aspect
+            );
+            return;
+        }
         match PendingValidationStruct::try_from(aspect) {
             Err(e) => log_error!(
                 context,
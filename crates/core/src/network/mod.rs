@@ -3,6 +3,7 @@ pub mod direct_message;
 pub mod entry_with_header;
 #[autotrace]
 pub mod handler;
+pub mod query_cache;
 #[autotrace]
 pub mod reducers;
 pub mod state;
@@ -340,7 +341,10 @@ pub mod tests {
             NetworkQueryResult::Links(query, _, _) => query,
             _ => panic!("Could not get query"),
         };
-        let links = unwrap_to!(links=>GetLinksNetworkResult::Links);
+        let links = match links {
+            GetLinksNetworkResult::Links(links, _) => links,
+            _ => panic!("Could not get links"),
+        };
         assert_eq!(links.len(), 2, "links = {:?}", links);
         // can be in any order
         assert!(
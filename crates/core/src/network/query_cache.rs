@@ -0,0 +1,392 @@
+//! Cache of get_entry and get_links results received from the network.
+//!
+//! Apps tend to read the same entries and links again and again. Unless we are an authority
+//! for them, every read is a network round trip. Results get cached per instance for a
+//! limited time and are dropped as soon as we see newer CRUD or link aspects for them,
+//! either because we authored or because we hold them.
+//! Every read chooses with a `CachePolicy` if a cached result is good enough.
+
+use holochain_core_types::{
+    entry::{Entry, EntryWithMetaAndHeader},
+    network::entry_aspect::EntryAspect,
+};
+use holochain_persistence_api::cas::content::{Address, AddressableContent};
+use holochain_wasm_utils::api_serialization::{
+    get_entry::CachePolicy,
+    get_links::{GetLinksArgs, GetLinksOptions, GetLinksResult},
+};
+use std::{
+    collections::{BTreeMap, HashMap},
+    time::{Duration, Instant},
+};
+
+/// Maximum number of query results kept per instance if not configured otherwise
+pub const DEFAULT_QUERY_CACHE_SIZE: usize = 1000;
+
+/// How long cached query results are good enough for reads with `CachePolicy::CacheOk`
+pub const DEFAULT_QUERY_CACHE_TTL: Duration = Duration::from_secs(30);
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+enum QueryCacheKey {
+    Entry(Address),
+    /// Timeout and cache policy are reset since they don't change the result
+    Links(GetLinksArgs),
+}
+
+impl QueryCacheKey {
+    fn links(args: &GetLinksArgs) -> Self {
+        QueryCacheKey::Links(GetLinksArgs {
+            options: GetLinksOptions {
+                timeout: Default::default(),
+                cache_policy: Default::default(),
+                ..args.options.clone()
+            },
+            ..args.clone()
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+enum QueryCacheValue {
+    Entry(EntryWithMetaAndHeader),
+    Links(GetLinksResult),
+}
+
+struct CachedResult {
+    value: QueryCacheValue,
+    fetched_at: Instant,
+    last_used: u64,
+}
+
+/// Bounded map of query results.
+/// The least recently used result gets evicted when the cache is full.
+pub struct QueryCache {
+    capacity: usize,
+    ttl: Duration,
+    results: HashMap<QueryCacheKey, CachedResult>,
+    /// Keys of all results by when they were last used, least recently used first
+    usage: BTreeMap<u64, QueryCacheKey>,
+    uses: u64,
+    hits: u64,
+    misses: u64,
+}
+
+impl QueryCache {
+    pub fn new(capacity: usize, ttl: Duration) -> Self {
+        QueryCache {
+            capacity,
+            ttl,
+            results: HashMap::new(),
+            usage: BTreeMap::new(),
+            uses: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    /// Marks the result with the given key as the most recently used one
+    fn touch(&mut self, key: &QueryCacheKey) {
+        if let Some(cached) = self.results.get_mut(key) {
+            self.usage.remove(&cached.last_used);
+            self.uses += 1;
+            cached.last_used = self.uses;
+            self.usage.insert(self.uses, key.clone());
+        }
+    }
+
+    fn remove(&mut self, key: &QueryCacheKey) {
+        if let Some(cached) = self.results.remove(key) {
+            self.usage.remove(&cached.last_used);
+        }
+    }
+
+    fn get(&mut self, key: &QueryCacheKey, cache_policy: &CachePolicy) -> Option<QueryCacheValue> {
+        let ttl = self.ttl;
+        let value = match cache_policy {
+            CachePolicy::NetworkOnly => return None,
+            CachePolicy::CacheOk => self
+                .results
+                .get(key)
+                .filter(|cached| cached.fetched_at.elapsed() < ttl),
+            CachePolicy::LocalOnly => self.results.get(key),
+        }
+        .map(|cached| cached.value.clone());
+        if value.is_some() {
+            self.touch(key);
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+        value
+    }
+
+    fn insert(&mut self, key: QueryCacheKey, value: QueryCacheValue) {
+        if self.capacity == 0 {
+            return;
+        }
+        self.remove(&key);
+        if self.results.len() >= self.capacity {
+            let least_recently_used = self.usage.values().next().cloned();
+            if let Some(least_recently_used) = least_recently_used {
+                self.remove(&least_recently_used);
+            }
+        }
+        self.uses += 1;
+        self.usage.insert(self.uses, key.clone());
+        self.results.insert(
+            key,
+            CachedResult {
+                value,
+                fetched_at: Instant::now(),
+                last_used: self.uses,
+            },
+        );
+    }
+
+    /// Returns the cached entry at the given address if the cache policy accepts it.
+    pub fn get_entry(
+        &mut self,
+        address: &Address,
+        cache_policy: &CachePolicy,
+    ) -> Option<EntryWithMetaAndHeader> {
+        match self.get(&QueryCacheKey::Entry(address.clone()), cache_policy) {
+            Some(QueryCacheValue::Entry(entry_with_meta_and_headers)) => {
+                Some(entry_with_meta_and_headers)
+            }
+            _ => None,
+        }
+    }
+
+    pub fn insert_entry(&mut self, address: Address, entry: EntryWithMetaAndHeader) {
+        self.insert(QueryCacheKey::Entry(address), QueryCacheValue::Entry(entry));
+    }
+
+    /// Returns the cached result of a get_links call with the same arguments
+    /// if the cache policy accepts it.
+    pub fn get_links(
+        &mut self,
+        args: &GetLinksArgs,
+        cache_policy: &CachePolicy,
+    ) -> Option<GetLinksResult> {
+        match self.get(&QueryCacheKey::links(args), cache_policy) {
+            Some(QueryCacheValue::Links(links)) => Some(links),
+            _ => None,
+        }
+    }
+
+    pub fn insert_links(&mut self, args: &GetLinksArgs, links: GetLinksResult) {
+        self.insert(QueryCacheKey::links(args), QueryCacheValue::Links(links));
+    }
+
+    pub fn invalidate_entry(&mut self, address: &Address) {
+        self.remove(&QueryCacheKey::Entry(address.clone()));
+    }
+
+    /// Drops the results of all get_links calls on the given base
+    pub fn invalidate_links(&mut self, base: &Address) {
+        let outdated: Vec<QueryCacheKey> = self
+            .results
+            .keys()
+            .filter(|key| match key {
+                QueryCacheKey::Links(args) => args.entry_address == *base,
+                QueryCacheKey::Entry(_) => false,
+            })
+            .cloned()
+            .collect();
+        for key in outdated {
+            self.remove(&key);
+        }
+    }
+
+    /// Drops all results that the given aspect we are about to hold makes outdated.
+    pub fn invalidate_aspect(&mut self, aspect: &EntryAspect) {
+        match aspect {
+            EntryAspect::Content(entry, _) => self.invalidate_entry(&entry.address()),
+            EntryAspect::Update(_, header) | EntryAspect::Deletion(header) => {
+                if let Some(crud_link) = header.link_update_delete() {
+                    self.invalidate_entry(&crud_link);
+                }
+            }
+            EntryAspect::LinkAdd(link_data, _) | EntryAspect::LinkRemove((link_data, _), _) => {
+                self.invalidate_links(link_data.link().base())
+            }
+            EntryAspect::Header(_) | EntryAspect::Warrant(_) => (),
+        }
+    }
+
+    /// Drops all results that the given entry we are committing makes outdated.
+    pub fn invalidate_commit(&mut self, entry: &Entry, maybe_link_update_delete: Option<&Address>) {
+        if let Some(crud_link) = maybe_link_update_delete {
+            self.invalidate_entry(crud_link);
+        }
+        match entry {
+            Entry::Deletion(deletion_entry) => {
+                self.invalidate_entry(deletion_entry.deleted_entry_address())
+            }
+            Entry::LinkAdd(link_data) | Entry::LinkRemove((link_data, _)) => {
+                self.invalidate_links(link_data.link().base())
+            }
+            _ => (),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.results.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.results.is_empty()
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+}
+
+impl Default for QueryCache {
+    fn default() -> Self {
+        QueryCache::new(DEFAULT_QUERY_CACHE_SIZE, DEFAULT_QUERY_CACHE_TTL)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use holochain_core_types::{
+        agent::test_agent_id,
+        chain_header::test_chain_header,
+        crud_status::CrudStatus,
+        entry::{test_entry, test_entry_b, EntryWithMeta},
+        link::link_data::LinkData,
+    };
+
+    fn entry_with_meta(entry: Entry) -> EntryWithMetaAndHeader {
+        EntryWithMetaAndHeader {
+            entry_with_meta: EntryWithMeta {
+                entry,
+                crud_status: CrudStatus::Live,
+                maybe_link_update_delete: None,
+                links_update_delete: Vec::new(),
+            },
+            headers: vec![test_chain_header()],
+        }
+    }
+
+    fn links_args(base: Address, tag: &str) -> GetLinksArgs {
+        GetLinksArgs {
+            entry_address: base,
+            link_type: "test-link".into(),
+            tag: tag.into(),
+            options: Default::default(),
+        }
+    }
+
+    #[test]
+    fn cache_policy_decides_about_cached_results() {
+        let mut cache = QueryCache::new(10, Duration::from_secs(60));
+        let entry = entry_with_meta(test_entry());
+        let address = test_entry().address();
+        assert_eq!(cache.get_entry(&address, &CachePolicy::CacheOk), None);
+
+        cache.insert_entry(address.clone(), entry.clone());
+        assert_eq!(cache.get_entry(&address, &CachePolicy::NetworkOnly), None);
+        assert_eq!(
+            cache.get_entry(&address, &CachePolicy::CacheOk),
+            Some(entry.clone())
+        );
+        assert_eq!(
+            cache.get_entry(&address, &CachePolicy::LocalOnly),
+            Some(entry)
+        );
+        assert_eq!(cache.hits(), 2);
+        assert_eq!(cache.misses(), 1);
+    }
+
+    #[test]
+    fn expired_results_are_only_used_local_only() {
+        let mut cache = QueryCache::new(10, Duration::from_millis(0));
+        let entry = entry_with_meta(test_entry());
+        let address = test_entry().address();
+        cache.insert_entry(address.clone(), entry.clone());
+        assert_eq!(cache.get_entry(&address, &CachePolicy::CacheOk), None);
+        assert_eq!(
+            cache.get_entry(&address, &CachePolicy::LocalOnly),
+            Some(entry)
+        );
+    }
+
+    #[test]
+    fn link_results_ignore_timeout_and_policy_and_get_invalidated_by_base() {
+        let mut cache = QueryCache::default();
+        let base = test_entry().address();
+        let args = links_args(base.clone(), "a");
+        cache.insert_links(&args, GetLinksResult::new(Vec::new()));
+        cache.insert_links(
+            &links_args(test_entry_b().address(), "a"),
+            GetLinksResult::new(Vec::new()),
+        );
+
+        let mut other_options = args.clone();
+        other_options.options.timeout = 1000.into();
+        other_options.options.cache_policy = CachePolicy::CacheOk;
+        assert!(cache
+            .get_links(&other_options, &CachePolicy::CacheOk)
+            .is_some());
+        assert!(cache
+            .get_links(&links_args(base.clone(), "b"), &CachePolicy::CacheOk)
+            .is_none());
+
+        let link_data = LinkData::new_add(
+            &base,
+            &test_entry_b().address(),
+            "a",
+            "test-link",
+            test_chain_header(),
+            test_agent_id(),
+        );
+        cache.invalidate_aspect(&EntryAspect::LinkAdd(link_data, test_chain_header()));
+        assert!(cache.get_links(&args, &CachePolicy::CacheOk).is_none());
+        assert_eq!(cache.len(), 1);
+    }
+
+    #[test]
+    fn commits_invalidate_updated_entries() {
+        let mut cache = QueryCache::default();
+        let address = test_entry().address();
+        cache.insert_entry(address.clone(), entry_with_meta(test_entry()));
+        cache.invalidate_commit(&test_entry_b(), Some(&address));
+        assert!(cache.is_empty());
+    }
+
+    #[test]
+    fn evicts_least_recently_used_result_when_full() {
+        let mut cache = QueryCache::new(2, Duration::from_secs(60));
+        cache.insert_entry(test_entry().address(), entry_with_meta(test_entry()));
+        cache.insert_entry(test_entry_b().address(), entry_with_meta(test_entry_b()));
+        assert!(cache
+            .get_entry(&test_entry().address(), &CachePolicy::CacheOk)
+            .is_some());
+
+        let base = test_entry().address();
+        cache.insert_links(
+            &links_args(base.clone(), "a"),
+            GetLinksResult::new(Vec::new()),
+        );
+        assert_eq!(cache.len(), 2);
+        assert!(cache
+            .get_entry(&test_entry_b().address(), &CachePolicy::CacheOk)
+            .is_none());
+        assert!(cache
+            .get_entry(&test_entry().address(), &CachePolicy::CacheOk)
+            .is_some());
+
+        cache.invalidate_links(&base);
+        cache.invalidate_entry(&test_entry().address());
+        assert!(cache.is_empty());
+        assert!(cache.usage.is_empty());
+    }
+}
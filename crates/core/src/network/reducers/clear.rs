@@ -37,4 +37,5 @@ pub fn reduce_clear_custom_send_response(
     let id = unwrap_to!(action => Action::ClearCustomSendResponse);
 
     network_state.custom_direct_message_replys.remove(id);
+    network_state.queued_direct_messages.remove(id);
 }
@@ -0,0 +1,23 @@
+use crate::{
+    action::ActionWrapper, network::state::NetworkState, state::State, NEW_RELIC_LICENSE_KEY,
+};
+
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn reduce_handle_queued_direct_message(
+    network_state: &mut NetworkState,
+    _root_state: &State,
+    action_wrapper: &ActionWrapper,
+) {
+    let action = action_wrapper.action();
+    let id = unwrap_to!(action => crate::action::Action::HandleQueuedDirectMessage);
+
+    // Only resolve sends that are still waiting, not the ones that timed out already
+    if network_state
+        .direct_message_connections
+        .remove(id)
+        .is_some()
+    {
+        network_state.direct_message_timeouts.remove(id);
+        network_state.queued_direct_messages.insert(id.clone());
+    }
+}
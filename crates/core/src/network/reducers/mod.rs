@@ -4,17 +4,22 @@ pub mod get_validation_package;
 pub mod handle_custom_send_response;
 pub mod handle_get_result;
 pub mod handle_get_validation_package;
+pub mod handle_queued_direct_message;
 pub mod init;
 pub mod prune;
 pub mod publish;
 pub mod publish_header_entry;
+pub mod publish_warrant;
 pub mod query;
 pub mod resolve_direct_connection;
 pub mod respond_authoring_list;
 pub mod respond_fetch;
 pub mod respond_gossip_list;
 pub mod respond_query;
+pub mod send_countersigning_outcome;
 pub mod send_direct_message;
+pub mod send_remote_signal;
+pub mod send_validation_receipt;
 pub mod shutdown;
 
 use crate::{
@@ -29,17 +34,22 @@ use crate::{
             handle_custom_send_response::reduce_handle_custom_send_response,
             handle_get_result::reduce_handle_get_result,
             handle_get_validation_package::reduce_handle_get_validation_package,
+            handle_queued_direct_message::reduce_handle_queued_direct_message,
             init::reduce_init,
             prune::reduce_prune,
             publish::reduce_publish,
             publish_header_entry::reduce_publish_header_entry,
+            publish_warrant::reduce_publish_warrant,
             query::{reduce_query, reduce_query_timeout},
             resolve_direct_connection::reduce_resolve_direct_connection,
             respond_authoring_list::reduce_respond_authoring_list,
             respond_fetch::reduce_respond_fetch_data,
             respond_gossip_list::reduce_respond_gossip_list,
             respond_query::reduce_respond_query,
+            send_countersigning_outcome::reduce_send_countersigning_outcome,
             send_direct_message::{reduce_send_direct_message, reduce_send_direct_message_timeout},
+            send_remote_signal::reduce_send_remote_signal,
+            send_validation_receipt::reduce_send_validation_receipt,
             shutdown::reduce_shutdown,
         },
         state::NetworkState,
@@ -78,17 +88,22 @@ fn resolve_reducer(action_wrapper: &ActionWrapper) -> Option<NetworkReduceFn> {
         Action::HandleCustomSendResponse(_) => Some(reduce_handle_custom_send_response),
         Action::HandleQuery(_) => Some(reduce_handle_get_result),
         Action::HandleGetValidationPackage(_) => Some(reduce_handle_get_validation_package),
+        Action::HandleQueuedDirectMessage(_) => Some(reduce_handle_queued_direct_message),
         Action::InitNetwork(_) => Some(reduce_init),
         Action::Prune => Some(reduce_prune),
         Action::Publish(_) => Some(reduce_publish),
         Action::PublishHeaderEntry(_) => Some(reduce_publish_header_entry),
+        Action::PublishWarrant(_) => Some(reduce_publish_warrant),
         Action::ResolveDirectConnection(_) => Some(reduce_resolve_direct_connection),
         Action::RespondAuthoringList(_) => Some(reduce_respond_authoring_list),
         Action::RespondGossipList(_) => Some(reduce_respond_gossip_list),
         Action::RespondFetch(_) => Some(reduce_respond_fetch_data),
         Action::RespondQuery(_) => Some(reduce_respond_query),
+        Action::SendCountersigningOutcome(_) => Some(reduce_send_countersigning_outcome),
         Action::SendDirectMessage(_) => Some(reduce_send_direct_message),
         Action::SendDirectMessageTimeout(_) => Some(reduce_send_direct_message_timeout),
+        Action::SendRemoteSignal(_) => Some(reduce_send_remote_signal),
+        Action::SendValidationReceipt(_) => Some(reduce_send_validation_receipt),
         Action::ShutdownNetwork => Some(reduce_shutdown),
         _ => None,
     }
@@ -137,6 +152,31 @@ pub fn send(
         .ok_or_else(|| HolochainError::ErrorGeneric("Network not initialized".to_string()))?
 }
 
+/// Sends the given direct message over the network and asks the network to keep it
+/// for the recipient if they are not connected, instead of failing.
+#[autotrace]
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn send_later(
+    network_state: &mut NetworkState,
+    data: DirectMessageData,
+) -> Result<(), HolochainError> {
+    network_state
+        .network
+        .as_mut()
+        .map(|network| {
+            let span: ht::Span = ht::with_top_or_null(|top| {
+                top.follower_("send-later-inner", |s| {
+                    s.tag(ht::Tag::new("msg", format!("{:?}", data))).start()
+                })
+                .into()
+            });
+            network
+                .send_direct_message_later(span.wrap(data).into())
+                .map_err(|error| HolochainError::IoError(error.to_string()))
+        })
+        .ok_or_else(|| HolochainError::ErrorGeneric("Network not initialized".to_string()))?
+}
+
 /// Sends the given DirectMessage to the node given by to_agent_id.
 /// This creates a transient connection as every node-to-node communication follows a
 /// request-response pattern. This function therefore logs the open connection
@@ -148,6 +188,23 @@ pub fn send_message(
     to_agent_id: &Address,
     message: DirectMessage,
 ) -> Result<(), HolochainError> {
+    let id = send_message_without_response(network_state, to_agent_id, message.clone())?;
+
+    network_state.direct_message_connections.insert(id, message);
+
+    Ok(())
+}
+
+/// Sends the given DirectMessage to the node given by to_agent_id without logging
+/// an open connection. Only to be used for messages that don't get a response.
+/// Returns the ID of the sent message.
+#[autotrace]
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn send_message_without_response(
+    network_state: &mut NetworkState,
+    to_agent_id: &Address,
+    message: DirectMessage,
+) -> Result<String, HolochainError> {
     let rand_string: String = thread_rng().sample_iter(&Alphanumeric).take(10).collect();
     let id = format!("{}-{}", ProcessUniqueId::new().to_string(), rand_string);
 
@@ -165,7 +222,5 @@ pub fn send_message(
     let msg = Lib3hClientProtocol::SendDirectMessage(data);
     let _ = send(network_state, msg)?;
 
-    network_state.direct_message_connections.insert(id, message);
-
-    Ok(())
+    Ok(id)
 }
@@ -1,7 +1,7 @@
 use crate::{
     action::{Action, ActionWrapper},
     network::state::NetworkState,
-    state::{State, ACTION_PRUNE_MS},
+    state::{State, ACTION_PRUNE_MS, DELIVER_LATER_PRUNE_MS},
     NEW_RELIC_LICENSE_KEY,
 };
 use std::time::Duration;
@@ -27,4 +27,11 @@ pub fn reduce_prune(
         })
         .cloned()
         .collect();
+
+    network_state
+        .deliver_later_direct_messages
+        .retain(|_, sent_at| match sent_at.elapsed() {
+            Ok(elapsed) => elapsed <= Duration::from_millis(DELIVER_LATER_PRUNE_MS),
+            Err(_) => true,
+        });
 }
@@ -139,6 +139,29 @@ fn publish_link_meta(
     )
 }
 
+/// Send to network a publish request of the header alone to the authorities of
+/// our agent address, so they can answer agent activity queries about us
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+fn publish_agent_activity(
+    network_state: &mut NetworkState,
+    entry_with_header: &EntryWithHeader,
+) -> Result<(), HolochainError> {
+    let agent_id = network_state.agent_id.clone().unwrap();
+    send(
+        network_state,
+        Lib3hClientProtocol::PublishEntry(ProvidedEntryData {
+            space_address: network_state.dna_address.clone().unwrap().into(),
+            provider_agent_id: agent_id.clone().into(),
+            entry: EntryData {
+                entry_address: Address::from(agent_id).into(),
+                aspect_list: vec![entry_data_to_entry_aspect_data(&EntryAspect::Header(
+                    entry_with_header.header.clone(),
+                ))],
+            },
+        }),
+    )
+}
+
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
 fn reduce_publish_inner(
     network_state: &mut NetworkState,
@@ -182,6 +205,7 @@ fn reduce_publish_inner(
             entry_with_header.entry.entry_type()
         ))),
     }
+    .and_then(|_| publish_agent_activity(network_state, &entry_with_header))
 }
 
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
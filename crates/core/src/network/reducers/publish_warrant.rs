@@ -0,0 +1,59 @@
+use crate::{
+    action::ActionWrapper,
+    network::{
+        actions::{NetworkActionResponse, Response},
+        entry_aspect::EntryAspect,
+        reducers::{publish::entry_data_to_entry_aspect_data, send},
+        state::NetworkState,
+    },
+    state::State,
+    NEW_RELIC_LICENSE_KEY,
+};
+use holochain_core_types::{error::HolochainError, warrant::Warrant};
+use holochain_persistence_api::cas::content::AddressableContent;
+use lib3h_protocol::{
+    data_types::{EntryData, ProvidedEntryData},
+    protocol_client::Lib3hClientProtocol,
+};
+
+/// Send to network a request to publish a warrant as aspect of the invalid entry it is about
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+fn reduce_publish_warrant_inner(
+    network_state: &mut NetworkState,
+    warrant: &Warrant,
+) -> Result<(), HolochainError> {
+    network_state.initialized()?;
+    let invalid_entry = warrant.invalid_header().entry_address().clone();
+    send(
+        network_state,
+        Lib3hClientProtocol::PublishEntry(ProvidedEntryData {
+            space_address: network_state.dna_address.clone().unwrap().into(),
+            provider_agent_id: network_state.agent_id.clone().unwrap().into(),
+            entry: EntryData {
+                entry_address: invalid_entry.into(),
+                aspect_list: vec![entry_data_to_entry_aspect_data(&EntryAspect::Warrant(
+                    warrant.clone(),
+                ))],
+            },
+        }),
+    )
+}
+
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn reduce_publish_warrant(
+    network_state: &mut NetworkState,
+    _root_state: &State,
+    action_wrapper: &ActionWrapper,
+) {
+    let action = action_wrapper.action();
+    let warrant = unwrap_to!(action => crate::action::Action::PublishWarrant);
+
+    let result = reduce_publish_warrant_inner(network_state, &warrant);
+    network_state.actions.insert(
+        action_wrapper.clone(),
+        Response::from(NetworkActionResponse::PublishWarrant(match result {
+            Ok(_) => Ok(warrant.address()),
+            Err(e) => Err(HolochainError::ErrorGeneric(e.to_string())),
+        })),
+    );
+}
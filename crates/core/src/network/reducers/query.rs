@@ -19,6 +19,15 @@ fn reduce_query_inner(
     let key_address = match key {
         QueryKey::Entry(key) => (key.id.clone(), key.address),
         QueryKey::Links(key) => (key.id.clone(), key.base_address),
+        // Batches start with the address of the lowest DHT location, the authorities
+        // of which are responsible for the other addresses as well
+        QueryKey::Entries(key) => (
+            key.id.clone(),
+            key.addresses.first().cloned().ok_or_else(|| {
+                HolochainError::ErrorGeneric("Can not query an empty list of entries".into())
+            })?,
+        ),
+        QueryKey::AgentActivity(key) => (key.id.clone(), key.agent),
     };
     send(
         network_state,
@@ -46,6 +55,11 @@ pub fn reduce_query(
             let (crud_status, query) = unwrap_to!(payload => crate::action::QueryPayload::Links);
             NetworkQuery::GetLinks(key.link_type.clone(), key.tag, *crud_status, query.clone())
         }
+        QueryKey::Entries(key) => NetworkQuery::GetEntries(key.addresses),
+        QueryKey::AgentActivity(_) => {
+            let filter = unwrap_to!(payload => crate::action::QueryPayload::AgentActivity);
+            NetworkQuery::GetAgentActivity(filter.clone())
+        }
     };
 
     let result = reduce_query_inner(network_state, key_type.clone(), network_query)
@@ -88,14 +102,20 @@ pub fn reduce_query_timeout(
 mod tests {
 
     use crate::{
-        action::{Action, ActionWrapper, GetEntryKey, GetLinksKey, QueryKey, QueryPayload},
+        action::{
+            Action, ActionWrapper, GetAgentActivityKey, GetEntriesKey, GetEntryKey, GetLinksKey,
+            QueryKey, QueryPayload,
+        },
         instance::tests::test_context,
         network::query::{GetLinksNetworkQuery, GetLinksQueryConfiguration},
         state::test_store,
     };
     use holochain_persistence_api::cas::content::AddressableContent;
 
-    use holochain_core_types::{entry::test_entry, error::HolochainError};
+    use holochain_core_types::{
+        entry::{test_entry, test_entry_b},
+        error::HolochainError,
+    };
 
     #[test]
     pub fn reduce_get_entry_without_network_initialized() {
@@ -125,6 +145,64 @@ mod tests {
         );
     }
 
+    #[test]
+    pub fn reduce_get_entries_without_network_initialized() {
+        let netname = Some("reduce_get_entries_without_network_initialized");
+        let context = test_context("alice", netname);
+        let store = test_store(context.clone());
+
+        let key = GetEntriesKey {
+            addresses: vec![test_entry().address(), test_entry_b().address()],
+            id: snowflake::ProcessUniqueId::new().to_string(),
+        };
+        let action = Action::Query((QueryKey::Entries(key.clone()), QueryPayload::Entries, None));
+        let action_wrapper = ActionWrapper::new(action);
+
+        let store = store.reduce(action_wrapper);
+        let maybe_get_entries_result = store
+            .network()
+            .get_query_results
+            .get(&QueryKey::Entries(key.clone()))
+            .map(|result| result.clone());
+        assert_eq!(
+            maybe_get_entries_result,
+            Some(Some(Err(HolochainError::ErrorGeneric(
+                "Network not initialized".to_string()
+            ))))
+        );
+    }
+
+    #[test]
+    pub fn reduce_get_agent_activity_without_network_initialized() {
+        let netname = Some("reduce_get_agent_activity_without_network_initialized");
+        let context = test_context("alice", netname);
+        let store = test_store(context.clone());
+
+        let key = GetAgentActivityKey {
+            agent: context.agent_id.address(),
+            id: snowflake::ProcessUniqueId::new().to_string(),
+        };
+        let action = Action::Query((
+            QueryKey::AgentActivity(key.clone()),
+            QueryPayload::AgentActivity(Default::default()),
+            None,
+        ));
+        let action_wrapper = ActionWrapper::new(action);
+
+        let store = store.reduce(action_wrapper);
+        let maybe_get_agent_activity_result = store
+            .network()
+            .get_query_results
+            .get(&QueryKey::AgentActivity(key.clone()))
+            .map(|result| result.clone());
+        assert_eq!(
+            maybe_get_agent_activity_result,
+            Some(Some(Err(HolochainError::ErrorGeneric(
+                "Network not initialized".to_string()
+            ))))
+        );
+    }
+
     #[test]
     // This test needs to be refactored.
     // It is non-deterministically failing with "sending on a closed channel" originating form
@@ -229,6 +307,7 @@ mod tests {
             entry: entry.clone(),
             crud_status: CrudStatus::Live,
             maybe_link_update_delete: None,
+            links_update_delete: Vec::new(),
         };
         let new_key = GetEntryKey {
             address: entry.address(),
@@ -0,0 +1,38 @@
+use crate::{
+    action::ActionWrapper,
+    network::{
+        direct_message::DirectMessage, reducers::send_message_without_response, state::NetworkState,
+    },
+    state::State,
+    NEW_RELIC_LICENSE_KEY,
+};
+use holochain_core_types::{countersigning::CountersigningOutcome, error::HolochainError};
+use holochain_persistence_api::cas::content::Address;
+
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+fn inner(
+    network_state: &mut NetworkState,
+    signer: &Address,
+    outcome: &CountersigningOutcome,
+) -> Result<(), HolochainError> {
+    network_state.initialized()?;
+    send_message_without_response(
+        network_state,
+        signer,
+        DirectMessage::CountersigningOutcome(outcome.clone()),
+    )
+    .map(|_| ())
+}
+
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn reduce_send_countersigning_outcome(
+    network_state: &mut NetworkState,
+    _root_state: &State,
+    action_wrapper: &ActionWrapper,
+) {
+    let action = action_wrapper.action();
+    let (signer, outcome) = unwrap_to!(action => crate::action::Action::SendCountersigningOutcome);
+    if let Err(error) = inner(network_state, signer, outcome) {
+        println!("err/net: Error sending countersigning outcome: {:?}", error);
+    }
+}
@@ -1,6 +1,9 @@
 use crate::{
     action::{ActionWrapper, DirectMessageData},
-    network::{reducers::send, state::NetworkState},
+    network::{
+        reducers::{send, send_later},
+        state::NetworkState,
+    },
     state::State,
     NEW_RELIC_LICENSE_KEY,
 };
@@ -9,6 +12,7 @@ use holochain_json_api::json::JsonString;
 use lib3h_protocol::{
     data_types::DirectMessageData as Lib3hDirectMessageData, protocol_client::Lib3hClientProtocol,
 };
+use std::time::SystemTime;
 
 #[autotrace]
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
@@ -28,16 +32,25 @@ fn inner(
         content: content.into(),
     };
 
-    let protocol_object = if direct_message_data.is_response {
-        Lib3hClientProtocol::HandleSendDirectMessageResult(data)
-    } else {
-        network_state
-            .direct_message_connections
-            .insert(data.request_id.clone(), direct_message_data.message.clone());
-        Lib3hClientProtocol::SendDirectMessage(data)
-    };
+    if direct_message_data.is_response {
+        return send(
+            network_state,
+            Lib3hClientProtocol::HandleSendDirectMessageResult(data),
+        );
+    }
 
-    send(network_state, protocol_object)
+    network_state
+        .direct_message_connections
+        .insert(data.request_id.clone(), direct_message_data.message.clone());
+
+    if direct_message_data.deliver_later {
+        network_state
+            .deliver_later_direct_messages
+            .insert(data.request_id.clone(), SystemTime::now());
+        send_later(network_state, data)
+    } else {
+        send(network_state, Lib3hClientProtocol::SendDirectMessage(data))
+    }
 }
 
 #[autotrace]
@@ -94,6 +107,7 @@ mod tests {
     };
     use holochain_core_types::{dna::Dna, error::HolochainError};
     use holochain_persistence_api::cas::content::Address;
+    use std::time::{Duration, SystemTime};
 
     #[test]
     pub fn reduce_send_direct_message_timeout_test() {
@@ -124,6 +138,7 @@ mod tests {
             message: custom_direct_message,
             msg_id: msg_id.clone(),
             is_response: false,
+            deliver_later: false,
         };
         let action_wrapper =
             ActionWrapper::new(Action::SendDirectMessage((direct_message_data, None)));
@@ -148,4 +163,59 @@ mod tests {
 
         assert_eq!(maybe_reply, Some(Err(HolochainError::Timeout)));
     }
+
+    #[test]
+    pub fn reduce_handle_queued_direct_message_test() {
+        let netname = Some("reduce_handle_queued_direct_message_test");
+        let context = test_context("alice", netname);
+        let mut store = test_store(context.clone());
+        store = store.reduce(ActionWrapper::new(Action::InitializeChain(Dna::new())));
+
+        let handler = create_handler(&context, String::from("queued_direct_message_test"));
+        store = store.reduce(ActionWrapper::new(Action::InitNetwork(NetworkSettings {
+            p2p_config: test_memory_network_config(netname),
+            dna_address: "queued_direct_message_test".into(),
+            agent_id: String::from("alice"),
+            handler,
+        })));
+
+        let msg_id = String::from("queued");
+        let direct_message_data = DirectMessageData {
+            address: Address::from("bogus"),
+            message: DirectMessage::Custom(CustomDirectMessage {
+                zome: String::from("test"),
+                payload: Ok(String::from("test")),
+            }),
+            msg_id: msg_id.clone(),
+            is_response: false,
+            deliver_later: true,
+        };
+        store = store.reduce(ActionWrapper::new(Action::SendDirectMessage((
+            direct_message_data,
+            Some((SystemTime::now(), Duration::from_secs(60))),
+        ))));
+        assert!(store
+            .network()
+            .deliver_later_direct_messages
+            .contains_key(&msg_id));
+
+        store = store.reduce(ActionWrapper::new(Action::HandleQueuedDirectMessage(
+            msg_id.clone(),
+        )));
+        assert!(store.network().queued_direct_messages.contains(&msg_id));
+        assert!(store
+            .network()
+            .direct_message_timeouts
+            .get(&msg_id)
+            .is_none());
+
+        // a second receipt, e.g. after a reconnect, doesn't queue it again
+        store = store.reduce(ActionWrapper::new(Action::ClearCustomSendResponse(
+            msg_id.clone(),
+        )));
+        store = store.reduce(ActionWrapper::new(Action::HandleQueuedDirectMessage(
+            msg_id.clone(),
+        )));
+        assert!(!store.network().queued_direct_messages.contains(&msg_id));
+    }
 }
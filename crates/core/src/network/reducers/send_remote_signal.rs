@@ -0,0 +1,39 @@
+use crate::{
+    action::ActionWrapper,
+    network::{
+        direct_message::DirectMessage, reducers::send_message_without_response, state::NetworkState,
+    },
+    state::State,
+    NEW_RELIC_LICENSE_KEY,
+};
+use holochain_core_types::error::HolochainError;
+use holochain_persistence_api::cas::content::Address;
+use holochain_wasm_utils::api_serialization::emit_signal::EmitSignalArgs;
+
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+fn inner(
+    network_state: &mut NetworkState,
+    agent: &Address,
+    signal: &EmitSignalArgs,
+) -> Result<(), HolochainError> {
+    network_state.initialized()?;
+    send_message_without_response(
+        network_state,
+        agent,
+        DirectMessage::RemoteSignal(signal.clone()),
+    )
+    .map(|_| ())
+}
+
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn reduce_send_remote_signal(
+    network_state: &mut NetworkState,
+    _root_state: &State,
+    action_wrapper: &ActionWrapper,
+) {
+    let action = action_wrapper.action();
+    let (agent, signal) = unwrap_to!(action => crate::action::Action::SendRemoteSignal);
+    if let Err(error) = inner(network_state, agent, signal) {
+        println!("err/net: Error sending remote signal: {:?}", error);
+    }
+}
@@ -0,0 +1,38 @@
+use crate::{
+    action::ActionWrapper,
+    network::{
+        direct_message::DirectMessage, reducers::send_message_without_response, state::NetworkState,
+    },
+    state::State,
+    NEW_RELIC_LICENSE_KEY,
+};
+use holochain_core_types::{error::HolochainError, validation_receipt::ValidationReceipt};
+use holochain_persistence_api::cas::content::Address;
+
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+fn inner(
+    network_state: &mut NetworkState,
+    author: &Address,
+    receipt: &ValidationReceipt,
+) -> Result<(), HolochainError> {
+    network_state.initialized()?;
+    send_message_without_response(
+        network_state,
+        author,
+        DirectMessage::ValidationReceipt(receipt.clone()),
+    )
+    .map(|_| ())
+}
+
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn reduce_send_validation_receipt(
+    network_state: &mut NetworkState,
+    _root_state: &State,
+    action_wrapper: &ActionWrapper,
+) {
+    let action = action_wrapper.action();
+    let (author, receipt) = unwrap_to!(action => crate::action::Action::SendValidationReceipt);
+    if let Err(error) = inner(network_state, author, receipt) {
+        println!("err/net: Error sending validation receipt: {:?}", error);
+    }
+}
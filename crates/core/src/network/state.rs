@@ -7,7 +7,7 @@ use boolinator::*;
 use holochain_core_types::{error::HolochainError, validation::ValidationPackage};
 use holochain_net::p2p_network::P2pNetwork;
 use holochain_persistence_api::cas::content::Address;
-use im::HashMap;
+use im::{HashMap, HashSet};
 use snowflake;
 use std::time::{Duration, SystemTime};
 
@@ -49,6 +49,15 @@ pub struct NetworkState {
 
     pub custom_direct_message_replys: HashMap<String, Result<String, HolochainError>>,
 
+    /// IDs of direct messages that the network stored for their disconnected recipient.
+    /// Entries get removed through Action::ClearCustomSendResponse.
+    pub queued_direct_messages: HashSet<String>,
+
+    /// IDs of direct messages we asked the network to deliver later, and when we sent them,
+    /// so we can tell what results and late responses from the network are about.
+    /// Entries get pruned after DELIVER_LATER_PRUNE_MS.
+    pub deliver_later_direct_messages: HashMap<String, SystemTime>,
+
     id: snowflake::ProcessUniqueId,
 }
 
@@ -73,6 +82,8 @@ impl NetworkState {
             direct_message_connections: HashMap::new(),
             direct_message_timeouts: HashMap::new(),
             custom_direct_message_replys: HashMap::new(),
+            queued_direct_messages: HashSet::new(),
+            deliver_later_direct_messages: HashMap::new(),
 
             id: snowflake::ProcessUniqueId::new(),
         }
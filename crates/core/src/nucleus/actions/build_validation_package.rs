@@ -7,12 +7,14 @@ use crate::{
     wasm_engine::callback::{
         validation_package::get_validation_package_definition, CallbackResult,
     },
+    workflows::custom_validation_package::custom_validation_package,
     NEW_RELIC_LICENSE_KEY,
 };
 use holochain_core_types::{
     chain_header::ChainHeader,
     entry::{entry_type::EntryType, Entry},
     error::HolochainError,
+    membrane_proof::MembraneProof,
     signature::Provenance,
     validation::{ValidationPackage, ValidationPackageDefinition::*},
 };
@@ -61,6 +63,14 @@ pub fn build_validation_package<'a>(
         EntryType::AgentId => {
             // FIXME
         }
+
+        EntryType::MembraneProof => {
+            // validated with the AgentId entry
+        }
+
+        EntryType::BlobChunk | EntryType::BlobManifest => {
+            // blob entries are checked on their own
+        }
         _ => {
             return Err(HolochainError::ValidationFailed(format!(
                 "Attempted to validate system entry type {:?}",
@@ -116,6 +126,14 @@ pub fn build_validation_package<'a>(
         })
         .and_then(|package_definition| {
             Ok(match package_definition {
+                CustomCallback => {
+                    let custom_package =
+                        custom_validation_package(&entry, &entry_header, &context)?;
+                    let mut package = ValidationPackage::only_header(entry_header);
+                    package.custom = Some(custom_package.data().clone());
+                    package.custom_signature = Some(custom_package.author().clone());
+                    package
+                }
                 Entry => ValidationPackage::only_header(entry_header),
                 ChainEntries => {
                     let mut package = ValidationPackage::only_header(entry_header);
@@ -148,6 +166,40 @@ pub fn build_validation_package<'a>(
                 }
             })
         })
+        .map(|mut package| {
+            if let Entry::AgentId(_) = entry {
+                package.previous_header =
+                    all_chain_headers_before_header(&context, &package.chain_header)
+                        .into_iter()
+                        .next();
+                package.membrane_proof =
+                    package
+                        .previous_header
+                        .as_ref()
+                        .and_then(|previous_header| {
+                            membrane_proof_of_header(&context, previous_header)
+                        });
+            }
+            package
+        })
+}
+
+/// Returns the membrane proof committed with the given header, if it is one.
+/// Used to hand the membrane proof to the validation of the AgentId entry that follows it.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+fn membrane_proof_of_header(context: &Arc<Context>, header: &ChainHeader) -> Option<MembraneProof> {
+    if *header.entry_type() != EntryType::MembraneProof {
+        return None;
+    }
+    match context
+        .state()?
+        .agent()
+        .chain_store()
+        .get(header.entry_address())
+    {
+        Ok(Some(Entry::MembraneProof(membrane_proof))) => Some(membrane_proof),
+        _ => None,
+    }
 }
 
 // given a slice of headers return the entries for those marked public
@@ -214,6 +266,9 @@ mod tests {
             source_chain_entries: None,
             source_chain_headers: None,
             custom: None,
+            custom_signature: None,
+            membrane_proof: None,
+            previous_header: None,
         };
 
         assert_eq!(maybe_validation_package.unwrap(), expected);
@@ -246,6 +301,9 @@ mod tests {
             )),
             source_chain_headers: None,
             custom: None,
+            custom_signature: None,
+            membrane_proof: None,
+            previous_header: None,
         };
 
         assert_eq!(maybe_validation_package.unwrap(), expected);
@@ -274,6 +332,9 @@ mod tests {
             source_chain_entries: None,
             source_chain_headers: Some(all_chain_headers_before_header(&context, &chain_header)),
             custom: None,
+            custom_signature: None,
+            membrane_proof: None,
+            previous_header: None,
         };
 
         assert_eq!(maybe_validation_package.unwrap(), expected);
@@ -301,6 +362,9 @@ mod tests {
             source_chain_entries: Some(public_chain_entries_from_headers(&context, &headers)),
             source_chain_headers: Some(headers),
             custom: None,
+            custom_signature: None,
+            membrane_proof: None,
+            previous_header: None,
         };
 
         assert_eq!(maybe_validation_package.unwrap(), expected);
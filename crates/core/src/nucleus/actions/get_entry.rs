@@ -34,11 +34,13 @@ pub(crate) fn get_entry_from_dht(
     context.state().unwrap().dht().get(address)
 }
 
+/// Returns the CRUD status of the entry and the addresses of all entries that updated
+/// or deleted it, sorted so every node follows concurrent updates in the same order.
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
 pub(crate) fn get_entry_crud_meta_from_dht(
     context: &Arc<Context>,
     address: &Address,
-) -> Result<Option<(CrudStatus, Option<Address>)>, HolochainError> {
+) -> Result<Option<(CrudStatus, Vec<Address>)>, HolochainError> {
     // Get crud-status
     let status_eavs = context.state().unwrap().dht().fetch_eavi(&EaviQuery::new(
         Some(address.clone()).into(),
@@ -77,25 +79,24 @@ pub(crate) fn get_entry_crud_meta_from_dht(
             crud_status = CrudStatus::Modified;
         }
     }
-    // Get crud-link
-    let mut maybe_link_update_delete = None;
+    // Get crud-links
+    // Concurrent updates leave several crud-links, so we need all of them and not only
+    // the one that happened to arrive last at this node.
     let link_eavs = context.state().unwrap().dht().fetch_eavi(&EaviQuery::new(
         Some(address.clone()).into(),
         Some(Attribute::CrudLink).into(),
         None.into(),
-        IndexFilter::LatestByAttribute,
+        IndexFilter::Range(None, None),
         None,
     ))?;
-    assert!(
-        link_eavs.len() <= 1,
-        "link_eavs.len() = {}",
-        link_eavs.len()
-    );
-    if link_eavs.len() == 1 {
-        maybe_link_update_delete = Some(link_eavs.iter().next().unwrap().value());
-    }
+    let links_update_delete = link_eavs
+        .into_iter()
+        .map(|eav| eav.value())
+        .collect::<BTreeSet<Address>>()
+        .into_iter()
+        .collect();
     // Done
-    Ok(Some((crud_status, maybe_link_update_delete)))
+    Ok(Some((crud_status, links_update_delete)))
 }
 
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
@@ -111,26 +112,27 @@ pub fn get_entry_with_meta(
     };
 
     // 2. try to get the entry's metadata
-    let (crud_status, maybe_link_update_delete) =
-        match get_entry_crud_meta_from_dht(context, &address)? {
-            Some(crud_info) => crud_info,
-            None => {
-                log_debug!(
-                    context,
-                    "Entry with address: {} exists in CAS but has no CRUD status! Returning None",
-                    address
-                );
-                return Ok(None); //If we cannot get the CRUD status for above entry it is not an
-                                 //entry that is held by this DHT. It might be in the DHT CAS
-                                 //because DHT and chain share the same CAS or it maybe just got
-                                 //added by a concurrent process but the CRUD status is still about
-                                 //to get set. Either way, we should treat it as not existent (yet).
-            }
-        };
+    let (crud_status, links_update_delete) = match get_entry_crud_meta_from_dht(context, &address)?
+    {
+        Some(crud_info) => crud_info,
+        None => {
+            log_debug!(
+                context,
+                "Entry with address: {} exists in CAS but has no CRUD status! Returning None",
+                address
+            );
+            return Ok(None); //If we cannot get the CRUD status for above entry it is not an
+                             //entry that is held by this DHT. It might be in the DHT CAS
+                             //because DHT and chain share the same CAS or it maybe just got
+                             //added by a concurrent process but the CRUD status is still about
+                             //to get set. Either way, we should treat it as not existent (yet).
+        }
+    };
     let item = EntryWithMeta {
         entry,
         crud_status,
-        maybe_link_update_delete,
+        maybe_link_update_delete: links_update_delete.first().cloned(),
+        links_update_delete,
     };
     Ok(Some(item))
 }
@@ -138,7 +140,10 @@ pub fn get_entry_with_meta(
 #[cfg(test)]
 pub mod tests {
     use crate::{content_store::AddContent, instance::tests::test_context_with_state};
-    use holochain_core_types::entry::test_entry;
+    use holochain_core_types::{
+        crud_status::{create_crud_link_eav, create_crud_status_eav, CrudStatus},
+        entry::{test_entry, test_entry_a, test_entry_b},
+    };
     use holochain_persistence_api::cas::content::AddressableContent;
 
     #[test]
@@ -151,6 +156,32 @@ pub mod tests {
         let result = super::get_entry_from_dht(&context, &entry.address());
         assert_eq!(Ok(Some(entry.clone())), result);
     }
+
+    #[test]
+    fn test_get_entry_with_meta_lists_concurrent_updates() {
+        let entry = test_entry();
+        let context = test_context_with_state(None);
+        let mut dht = (*context.state().unwrap().dht()).clone();
+        dht.add(&entry).unwrap();
+        dht.add_eavi(&create_crud_status_eav(&entry.address(), CrudStatus::Modified).unwrap())
+            .unwrap();
+        for update in &[test_entry_b(), test_entry_a()] {
+            dht.add_eavi(&create_crud_link_eav(&entry.address(), &update.address()).unwrap())
+                .unwrap();
+        }
+
+        let mut updates = vec![test_entry_a().address(), test_entry_b().address()];
+        updates.sort();
+        let entry_with_meta = super::get_entry_with_meta(&context, entry.address())
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry_with_meta.crud_status, CrudStatus::Modified);
+        assert_eq!(entry_with_meta.links_update_delete, updates);
+        assert_eq!(
+            entry_with_meta.maybe_link_update_delete,
+            Some(updates[0].clone())
+        );
+    }
     /*
         #[test]
         fn test_get_entry_from_agent_chain() {
@@ -94,6 +94,21 @@ pub async fn initialize_chain(
         )));
     }
 
+    // Commit the membrane proof, if we were given one, right before the AgentId
+    // so it ends up in the validation package of the AgentId entry
+    if let Some(membrane_proof) = context_clone.membrane_proof.clone() {
+        let membrane_proof_commit =
+            commit_entry(Entry::MembraneProof(membrane_proof), None, &context_clone).await;
+        if membrane_proof_commit.is_err() {
+            let error = membrane_proof_commit.err().unwrap();
+            dispatch_error_result(&context_clone, error.clone());
+            return Err(HolochainError::InitializationFailed(format!(
+                "Error committing membrane proof: {:?}",
+                error
+            )));
+        }
+    }
+
     // Commit AgentId to chain
     let agent_id_entry = Entry::AgentId(context_clone.agent_id.clone());
     let agent_id_commit = commit_entry(agent_id_entry, None, &context_clone).await;
@@ -1,13 +1,13 @@
 use crate::{
     context::Context,
     nucleus::{
-        validation::{ValidationError, ValidationResult},
+        validation::{cache::ValidationCacheKey, ValidationError, ValidationResult},
         CallbackFnCall,
     },
     wasm_engine::{self, runtime::WasmCallData},
     NEW_RELIC_LICENSE_KEY,
 };
-use holochain_core_types::error::HolochainError;
+use holochain_core_types::{error::HolochainError, validation::ValidationData};
 use holochain_persistence_api::cas::content::Address;
 use std::sync::Arc;
 
@@ -18,19 +18,49 @@ use holochain_metrics::with_latency_publishing;
 /// `zome_call`.
 /// Dispatches an `Action::ReturnValidationResult` after completion of the WASM call.
 /// Returns a future that waits for the result to appear in the nucleus state.
+/// Definite outcomes get remembered in the instance's validation cache so validating
+/// the same entry with the same header and validation package again skips the WASM call.
 #[no_autotrace] // TODO: get autotrace working for this future
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
 pub async fn run_validation_callback(
-    _address: Address,
+    address: Address,
+    validation_data: &ValidationData,
     call: CallbackFnCall,
     context: &Arc<Context>,
 ) -> ValidationResult {
+    let cache_key = context
+        .state()
+        .and_then(|state| state.network().dna_address.clone())
+        .map(|dna_address| {
+            ValidationCacheKey::new(
+                dna_address,
+                address,
+                validation_data,
+                format!("{}/{}", call.zome_name, call.fn_name),
+            )
+        });
+    if let Some(ref key) = cache_key {
+        if let Some(outcome) = context
+            .validation_cache
+            .lock()
+            .expect("Could not lock validation cache")
+            .get(key)
+        {
+            log_debug!(
+                context,
+                "validation/cache: using cached outcome for {}",
+                key.entry_address
+            );
+            return outcome;
+        }
+    }
+
     let metric_name_prefix = format!(
         "run_validation_callback.{}.{}",
         call.zome_name, call.fn_name
     );
 
-    with_latency_publishing!(
+    let outcome = with_latency_publishing!(
         metric_name_prefix,
         context.metric_publisher,
         |()| {
@@ -59,9 +89,22 @@ pub async fn run_validation_callback(
                         panic!(error_string)
                     }
                 }
+                // the callback called a host function that is not permitted in validation
+                Err(error @ HolochainError::ValidationFailed(_)) => {
+                    Err(ValidationError::Error(error))
+                }
                 Err(error) => panic!(error.to_string()), // same here
             }
         },
         ()
-    )
+    );
+
+    if let Some(key) = cache_key {
+        context
+            .validation_cache
+            .lock()
+            .expect("Could not lock validation cache")
+            .insert(key, outcome.clone());
+    }
+    outcome
 }
@@ -11,12 +11,12 @@ use holochain_json_api::{
     json::JsonString,
 };
 use holochain_persistence_api::cas::content::{Address, AddressableContent, Content};
-use im::{HashMap, HashSet};
+use im::{HashMap, HashSet, Vector};
 use serde::{
     de::{Error, Visitor},
     Deserialize, Deserializer, Serialize, Serializer,
 };
-use std::{collections::VecDeque, convert::TryFrom, fmt};
+use std::{convert::TryFrom, fmt};
 
 #[autotrace]
 #[derive(Clone, Debug, PartialEq, Deserialize, Serialize, DefaultJson)]
@@ -102,7 +102,7 @@ pub struct NucleusState {
     // Transient fields:
     pub dna: Option<Dna>, //DNA is transient here because it is stored in the chain and gets
     //read from there when loading an instance/chain.
-    pub queued_zome_calls: VecDeque<ZomeFnCall>,
+    pub queued_zome_calls: Vector<ZomeFnCall>,
     pub running_zome_calls: HashSet<ZomeFnCall>,
     pub hdk_function_calls: HashMap<ZomeFnCall, ZomeFnCallState>,
     pub zome_call_results: HashMap<ZomeFnCall, Result<JsonString, HolochainError>>,
@@ -114,7 +114,7 @@ impl NucleusState {
         NucleusState {
             dna: None,
             status: NucleusStatus::New,
-            queued_zome_calls: VecDeque::new(),
+            queued_zome_calls: Vector::new(),
             running_zome_calls: HashSet::new(),
             zome_call_results: HashMap::new(),
             hdk_function_calls: HashMap::new(),
@@ -176,7 +176,7 @@ impl From<NucleusStateSnapshot> for NucleusState {
         NucleusState {
             dna: None,
             status: snapshot.status,
-            queued_zome_calls: VecDeque::new(),
+            queued_zome_calls: Vector::new(),
             running_zome_calls: HashSet::new(),
             zome_call_results: HashMap::new(),
             hdk_function_calls: HashMap::new(),
@@ -201,12 +201,12 @@ impl AddressableContent for NucleusStateSnapshot {
 
 #[derive(Clone, Default, Debug, PartialEq, Serialize)]
 pub struct ZomeFnCallState {
-    hdk_fn_invocations: Vec<(HdkFnCall, Option<HdkFnCallResult>)>,
+    hdk_fn_invocations: Vector<(HdkFnCall, Option<HdkFnCallResult>)>,
 }
 
 impl ZomeFnCallState {
     pub fn begin_hdk_call(&mut self, call: HdkFnCall) {
-        self.hdk_fn_invocations.push((call, None))
+        self.hdk_fn_invocations.push_back((call, None))
     }
 
     pub fn end_hdk_call(
@@ -214,7 +214,7 @@ impl ZomeFnCallState {
         call: HdkFnCall,
         result: HdkFnCallResult,
     ) -> Result<(), HolochainError> {
-        if let Some((current_call, current_result)) = self.hdk_fn_invocations.pop() {
+        if let Some((current_call, current_result)) = self.hdk_fn_invocations.pop_back() {
             if call != current_call {
                 Err(HolochainError::new(
                     "HDK call other than the current call was ended.",
@@ -224,7 +224,7 @@ impl ZomeFnCallState {
                     "Ending and HDK which was already ended.",
                 ))
             } else {
-                self.hdk_fn_invocations.push((call, Some(result)));
+                self.hdk_fn_invocations.push_back((call, Some(result)));
                 Ok(())
             }
         } else {
@@ -9,8 +9,8 @@ use crate::{
 };
 use holochain_core_types::{
     agent::AgentId,
-    entry::Entry,
-    validation::{EntryValidationData, ValidationData},
+    entry::{entry_type::EntryType, Entry},
+    validation::{EntryValidationData, ValidationData, ValidationPackage},
 };
 use holochain_persistence_api::cas::content::AddressableContent;
 use holochain_wasm_utils::api_serialization::validation::AgentIdValidationArgs;
@@ -28,6 +28,9 @@ pub async fn validate_agent_entry(
 
     let agent_id = unwrap_to!(entry => Entry::AgentId);
 
+    validate_membrane_proof(&validation_data.package)?;
+
+    let cache_validation_data = validation_data.clone();
     let params = AgentIdValidationArgs {
         validation_data: EntryValidationData::<AgentId>::Create {
             entry: agent_id.to_owned(),
@@ -41,7 +44,7 @@ pub async fn validate_agent_entry(
         let call = CallbackFnCall::new(&zome_name, "__hdk_validate_agent_entry", params.clone());
         // Need to return a boxed future for it to work with join_all
         // https://users.rust-lang.org/t/the-trait-unpin-is-not-implemented-for-genfuture-error-when-using-join-all/23612/2
-        run_validation_callback(entry.address(), call, &context).boxed()
+        run_validation_callback(entry.address(), &cache_validation_data, call, &context).boxed()
     }))
     .await;
 
@@ -62,3 +65,107 @@ pub async fn validate_agent_entry(
         ))
     }
 }
+
+/// Checks that the membrane proof in the validation package of an AgentId entry is the
+/// one the author committed right before it, so it can't be forged, swapped or dropped.
+/// The previous header is tied to the chain by the signed link of the agent entry's header.
+fn validate_membrane_proof(package: &ValidationPackage) -> ValidationResult {
+    let previous_header = match (package.chain_header.link(), &package.previous_header) {
+        (Some(link), Some(previous_header)) if previous_header.address() == link => previous_header,
+        (None, None) if package.membrane_proof.is_none() => return Ok(()),
+        _ => {
+            return Err(ValidationError::Fail(String::from(
+                "Validation package does not hold the header before the agent entry",
+            )))
+        }
+    };
+    let committed_proof = *previous_header.entry_type() == EntryType::MembraneProof;
+    match &package.membrane_proof {
+        Some(membrane_proof)
+            if committed_proof
+                && Entry::MembraneProof(membrane_proof.clone()).address()
+                    == *previous_header.entry_address() =>
+        {
+            Ok(())
+        }
+        None if !committed_proof => Ok(()),
+        _ => Err(ValidationError::Fail(String::from(
+            "Membrane proof is not the one committed before the agent entry",
+        ))),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::validate_membrane_proof;
+    use holochain_core_types::{
+        agent::test_agent_id,
+        chain_header::{test_provenances, ChainHeader},
+        entry::{test_unpublishable_entry, Entry},
+        membrane_proof::MembraneProof,
+        time::test_iso_8601,
+        validation::ValidationPackage,
+    };
+    use holochain_persistence_api::cas::content::{Address, AddressableContent};
+
+    fn header_after(entry: &Entry, link: Option<Address>) -> ChainHeader {
+        ChainHeader::new(
+            &entry.entry_type(),
+            &entry.address(),
+            &test_provenances("sig"),
+            &link,
+            &None,
+            &None,
+            &test_iso_8601(),
+        )
+    }
+
+    /// Package of an agent entry committed after the given entry
+    fn agent_package(previous_entry: &Entry) -> ValidationPackage {
+        let previous_header = header_after(previous_entry, Some(Address::from("dna header")));
+        let agent_header = header_after(
+            &Entry::AgentId(test_agent_id()),
+            Some(previous_header.address()),
+        );
+        let mut package = ValidationPackage::only_header(agent_header);
+        package.previous_header = Some(previous_header);
+        package
+    }
+
+    #[test]
+    fn membrane_proof_has_to_be_the_committed_one() {
+        let membrane_proof = MembraneProof::new("invite code 42");
+        let proof_entry = Entry::MembraneProof(membrane_proof.clone());
+
+        let mut package = agent_package(&proof_entry);
+        package.membrane_proof = Some(membrane_proof);
+        assert_eq!(validate_membrane_proof(&package), Ok(()));
+
+        // a forged or swapped proof
+        let mut forged = package.clone();
+        forged.membrane_proof = Some(MembraneProof::new("forged invite"));
+        assert!(validate_membrane_proof(&forged).is_err());
+
+        // dropping the committed proof
+        let mut dropped = package.clone();
+        dropped.membrane_proof = None;
+        assert!(validate_membrane_proof(&dropped).is_err());
+
+        // a previous header that the agent entry does not link to
+        let mut unlinked = package.clone();
+        unlinked.previous_header = Some(header_after(&proof_entry, None));
+        assert!(validate_membrane_proof(&unlinked).is_err());
+        unlinked.previous_header = None;
+        assert!(validate_membrane_proof(&unlinked).is_err());
+    }
+
+    #[test]
+    fn membrane_proof_needs_a_committed_proof_entry() {
+        let mut package = agent_package(&test_unpublishable_entry());
+        assert_eq!(validate_membrane_proof(&package), Ok(()));
+
+        // attaching a proof that was never committed
+        package.membrane_proof = Some(MembraneProof::new("invite code 42"));
+        assert!(validate_membrane_proof(&package).is_err());
+    }
+}
@@ -4,7 +4,10 @@ use crate::{
         actions::{
             get_entry::get_entry_from_dht, run_validation_callback::run_validation_callback,
         },
-        validation::{entry_to_validation_data, ValidationError, ValidationResult},
+        validation::{
+            countersigning::validate_countersignatures, entry_schema::validate_entry_schema,
+            entry_to_validation_data, ValidationError, ValidationResult,
+        },
         CallbackFnCall,
     },
     NEW_RELIC_LICENSE_KEY,
@@ -25,18 +28,37 @@ pub async fn validate_app_entry(
     context: &Arc<Context>,
     link: Option<Address>,
     validation_data: ValidationData,
+    require_countersignatures: bool,
 ) -> ValidationResult {
     let dna = context.get_dna().expect("Callback called without DNA set!");
 
     let zome_name = dna
         .get_zome_name_for_app_entry_type(&app_entry_type)
         .ok_or(ValidationError::NotImplemented)?;
+
+    // Cheap native checks before we call into the zome
+    if let (Some(entry_type_def), Entry::App(_, entry_value)) = (
+        dna.get_entry_type_def(&String::from(app_entry_type.clone())),
+        &entry,
+    ) {
+        validate_entry_schema(&app_entry_type, entry_value, entry_type_def)?;
+        if require_countersignatures {
+            validate_countersignatures(
+                &app_entry_type,
+                entry_value,
+                entry_type_def,
+                &validation_data,
+            )?;
+        }
+    }
+
     if let Some(expected_link_update) = link.clone() {
         get_entry_from_dht(&context.clone(), &expected_link_update).map_err(|_| {
             ValidationError::UnresolvedDependencies(vec![expected_link_update.clone()])
         })?;
     };
 
+    let cache_validation_data = validation_data.clone();
     let params = EntryValidationArgs {
         validation_data: entry_to_validation_data(context.clone(), &entry, link, validation_data)
             .map_err(|_| {
@@ -45,5 +67,5 @@ pub async fn validate_app_entry(
     };
     let call = CallbackFnCall::new(&zome_name, "__hdk_validate_app_entry", params);
 
-    run_validation_callback(entry.address(), call, &context).await
+    run_validation_callback(entry.address(), &cache_validation_data, call, &context).await
 }
@@ -0,0 +1,145 @@
+use crate::{
+    context::Context,
+    nucleus::{
+        actions::run_validation_callback::run_validation_callback,
+        validation::{ValidationError, ValidationResult},
+        CallbackFnCall,
+    },
+    NEW_RELIC_LICENSE_KEY,
+};
+use holochain_core_types::{
+    entry::{entry_type::EntryType, Entry},
+    validation::{EntryValidationData, ValidationData},
+};
+use holochain_persistence_api::cas::content::AddressableContent;
+use holochain_wasm_utils::api_serialization::validation::EntryValidationArgs;
+
+use std::sync::Arc;
+
+/// Blob chunks are opaque, so they only need to be well-formed.
+/// Manifests additionally go through app validation if a zome opts in by defining
+/// a validating entry type for them, so apps can restrict the size or authors of blobs.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub async fn validate_blob_entry(
+    entry: Entry,
+    validation_data: ValidationData,
+    context: &Arc<Context>,
+) -> ValidationResult {
+    let manifest = match &entry {
+        Entry::BlobChunk(chunk) => {
+            return chunk
+                .check()
+                .map_err(|err| ValidationError::Fail(err.to_string()))
+        }
+        Entry::BlobManifest(manifest) => manifest,
+        _ => {
+            return Err(ValidationError::Fail(String::from(
+                "Entry is not part of a blob",
+            )))
+        }
+    };
+    manifest
+        .check()
+        .map_err(|err| ValidationError::Fail(err.to_string()))?;
+
+    let dna = context.get_dna().expect("Callback called without DNA set!");
+    let zome_name = match dna.get_zome_name_for_sys_entry_type(&EntryType::BlobManifest) {
+        Some(zome_name) => zome_name,
+        None => return Ok(()),
+    };
+
+    let cache_validation_data = validation_data.clone();
+    let params = EntryValidationArgs {
+        validation_data: EntryValidationData::Create {
+            entry: entry.clone(),
+            validation_data,
+        },
+    };
+    let call = CallbackFnCall::new(&zome_name, "__hdk_validate_app_entry", params);
+
+    run_validation_callback(entry.address(), &cache_validation_data, call, &context).await
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::validate_blob_entry;
+    use crate::{
+        instance::tests::test_instance_and_context,
+        nucleus::validation::ValidationError,
+        wasm_engine::api::{tests::test_zome_api_function_wasm, ZomeApiFunction},
+    };
+    use holochain_core_types::{
+        blob::{BlobChunk, BlobManifest},
+        chain_header::test_chain_header,
+        dna::entry_types::EntryTypeDef,
+        entry::{test_entry, Entry},
+        validation::{ValidationData, ValidationPackage},
+    };
+    use holochain_persistence_api::cas::content::AddressableContent;
+
+    fn test_validation_data() -> ValidationData {
+        ValidationData {
+            package: ValidationPackage::only_header(test_chain_header()),
+            ..Default::default()
+        }
+    }
+
+    fn test_manifest() -> Entry {
+        let chunk = Entry::BlobChunk(BlobChunk::new(vec![1, 2, 3]));
+        Entry::BlobManifest(BlobManifest::new(3, vec![chunk.address()]))
+    }
+
+    #[test]
+    fn blob_entries_only_need_to_be_well_formed_by_default() {
+        let dna = test_utils::create_test_dna_with_wat("test_zome", None);
+        let (_instance, context) = test_instance_and_context(dna, None).unwrap();
+
+        let validate =
+            |entry| context.block_on(validate_blob_entry(entry, test_validation_data(), &context));
+        assert_eq!(validate(Entry::BlobChunk(BlobChunk::new(vec![1]))), Ok(()));
+        assert!(validate(Entry::BlobChunk(BlobChunk::new(vec![]))).is_err());
+        // genesis already ran validation callbacks for the agent entry
+        let callbacks_run = context.validation_cache.lock().unwrap().len();
+        assert_eq!(validate(test_manifest()), Ok(()));
+        assert_eq!(
+            context.validation_cache.lock().unwrap().len(),
+            callbacks_run
+        );
+        assert!(validate(Entry::BlobManifest(BlobManifest::new(3, vec![]))).is_err());
+        assert_eq!(
+            validate(test_entry()),
+            Err(ValidationError::Fail(String::from(
+                "Entry is not part of a blob"
+            )))
+        );
+    }
+
+    #[test]
+    fn blob_manifests_get_app_validation_if_a_zome_opts_in() {
+        // the callback of this test zome accepts everything
+        let mut dna = test_utils::create_test_dna_with_wasm(
+            "test_zome",
+            test_zome_api_function_wasm(ZomeApiFunction::Debug.as_str()),
+        );
+        dna.zomes
+            .get_mut("test_zome")
+            .unwrap()
+            .entry_types
+            .insert("%blob_manifest".into(), EntryTypeDef::new());
+        let (_instance, context) = test_instance_and_context(dna, None).unwrap();
+        let validate =
+            |entry| context.block_on(validate_blob_entry(entry, test_validation_data(), &context));
+        // genesis already ran validation callbacks for the agent entry
+        let genesis_callbacks = context.validation_cache.lock().unwrap().len();
+        let callbacks_run = || context.validation_cache.lock().unwrap().len() - genesis_callbacks;
+
+        assert_eq!(validate(test_manifest()), Ok(()));
+        assert_eq!(callbacks_run(), 1);
+        // chunks don't get app validation
+        assert_eq!(validate(Entry::BlobChunk(BlobChunk::new(vec![1]))), Ok(()));
+        assert_eq!(callbacks_run(), 1);
+        // malformed manifests get rejected before calling into the zome
+        assert!(validate(Entry::BlobManifest(BlobManifest::new(3, vec![]))).is_err());
+        assert_eq!(callbacks_run(), 1);
+    }
+}
@@ -4,7 +4,7 @@ use crate::{
 };
 use holochain_core_types::{
     chain_header::ChainHeader,
-    entry::{Entry, EntryWithMeta, EntryWithMetaAndHeader},
+    entry::{entry_type::EntryType, Entry, EntryWithMeta, EntryWithMetaAndHeader},
     error::HolochainError,
     time::Timeout,
     validation::{ValidationPackage, ValidationPackageDefinition},
@@ -97,6 +97,13 @@ pub(crate) async fn try_make_validation_package_dht(
     );
     let entry_header = entry_with_header.header.clone();
 
+    if let ValidationPackageDefinition::CustomCallback = validation_package_definition {
+        // only the author can sign the package built by the callback
+        return Err(HolochainError::ErrorGeneric(
+            "Custom validation packages can only be retrieved from the author".to_string(),
+        ));
+    }
+
     log_debug!(context, "Retrieving chain headers...");
 
     let chain_headers = all_chain_headers_before_header_dht(context.clone(), &entry_header).await?;
@@ -105,9 +112,23 @@ pub(crate) async fn try_make_validation_package_dht(
 
     let mut package = ValidationPackage::only_header(entry_header.clone());
 
+    // Membrane proofs don't get published, an AgentId that follows one
+    // can only be validated with the package from its author
+    if let Entry::AgentId(_) = entry_with_header.entry {
+        if chain_headers
+            .first()
+            .map(|header| *header.entry_type() == EntryType::MembraneProof)
+            .unwrap_or(false)
+        {
+            return Err(HolochainError::ErrorGeneric(
+                "Membrane proofs can only be retrieved from the author".to_string(),
+            ));
+        }
+    }
+
     match validation_package_definition {
         ValidationPackageDefinition::Entry => {
-            // this should never happen but it will produce the correct package anyway
+            // only AgentId entries get here, they need the chain headers to look for a membrane proof
         }
         ValidationPackageDefinition::ChainEntries => {
             package.source_chain_entries =
@@ -122,6 +143,7 @@ pub(crate) async fn try_make_validation_package_dht(
                 Some(public_chain_entries_from_headers_dht(context.clone(), &chain_headers).await?);
         }
         ValidationPackageDefinition::Custom(string) => package.custom = Some(string.to_string()),
+        ValidationPackageDefinition::CustomCallback => unreachable!(),
     };
     Ok(package)
 }
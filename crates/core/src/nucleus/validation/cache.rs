@@ -0,0 +1,215 @@
+//! Cache of validation callback outcomes.
+//!
+//! The same entry often gets validated several times: on commit, on hold, as dependency
+//! of updates and removes and on retries. Since validation callbacks are deterministic
+//! for a given DNA, entry, header and validation package we can remember their outcome
+//! and skip running the WASM again.
+
+use crate::nucleus::validation::{ValidationError, ValidationResult};
+use holochain_core_types::validation::ValidationData;
+use holochain_json_api::json::JsonString;
+use holochain_persistence_api::cas::content::{Address, AddressableContent};
+use std::collections::{HashMap, VecDeque};
+
+/// Maximum number of validation outcomes kept per instance
+pub const DEFAULT_VALIDATION_CACHE_SIZE: usize = 1000;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ValidationCacheKey {
+    pub dna_address: Address,
+    pub entry_address: Address,
+    pub header_address: Address,
+    /// Hash of the validation package together with the lifecycle it gets validated in,
+    /// since validation callbacks may treat lifecycles differently
+    pub package_hash: Address,
+    /// Zome and name of the validation callback
+    pub callback: String,
+}
+
+impl ValidationCacheKey {
+    pub fn new(
+        dna_address: Address,
+        entry_address: Address,
+        validation_data: &ValidationData,
+        callback: String,
+    ) -> Self {
+        let serialized_validation_data =
+            serde_json::to_string(validation_data).expect("ValidationData is always serializable");
+        ValidationCacheKey {
+            dna_address,
+            entry_address,
+            header_address: validation_data.package.chain_header.address(),
+            package_hash: JsonString::from_json(&serialized_validation_data).address(),
+            callback,
+        }
+    }
+}
+
+/// Bounded map of validation outcomes.
+/// Only definite outcomes (valid or invalid) get cached. Missing dependencies and errors
+/// could resolve with the next try.
+/// All outcomes get dropped as soon as a key of a different DNA shows up.
+pub struct ValidationCache {
+    capacity: usize,
+    dna_address: Option<Address>,
+    outcomes: HashMap<ValidationCacheKey, ValidationResult>,
+    insertion_order: VecDeque<ValidationCacheKey>,
+    hits: u64,
+    misses: u64,
+}
+
+impl ValidationCache {
+    pub fn new(capacity: usize) -> Self {
+        ValidationCache {
+            capacity,
+            dna_address: None,
+            outcomes: HashMap::new(),
+            insertion_order: VecDeque::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    fn invalidate_on_dna_change(&mut self, key: &ValidationCacheKey) {
+        if self.dna_address.as_ref() != Some(&key.dna_address) {
+            self.outcomes.clear();
+            self.insertion_order.clear();
+            self.dna_address = Some(key.dna_address.clone());
+        }
+    }
+
+    /// Returns the cached outcome for the given key and counts the hit or miss.
+    pub fn get(&mut self, key: &ValidationCacheKey) -> Option<ValidationResult> {
+        self.invalidate_on_dna_change(key);
+        let outcome = self.outcomes.get(key).cloned();
+        if outcome.is_some() {
+            self.hits += 1;
+        } else {
+            self.misses += 1;
+        }
+        outcome
+    }
+
+    /// Remembers the given outcome if it is definite.
+    /// Evicts the oldest outcome if the cache is full.
+    pub fn insert(&mut self, key: ValidationCacheKey, outcome: ValidationResult) {
+        match outcome {
+            Ok(()) | Err(ValidationError::Fail(_)) => (),
+            _ => return,
+        }
+        self.invalidate_on_dna_change(&key);
+        if self.capacity == 0 || self.outcomes.contains_key(&key) {
+            return;
+        }
+        if self.outcomes.len() >= self.capacity {
+            if let Some(oldest) = self.insertion_order.pop_front() {
+                self.outcomes.remove(&oldest);
+            }
+        }
+        self.insertion_order.push_back(key.clone());
+        self.outcomes.insert(key, outcome);
+    }
+
+    pub fn len(&self) -> usize {
+        self.outcomes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.outcomes.is_empty()
+    }
+
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Share of lookups that were answered from the cache
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+impl Default for ValidationCache {
+    fn default() -> Self {
+        ValidationCache::new(DEFAULT_VALIDATION_CACHE_SIZE)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use holochain_core_types::validation::EntryLifecycle;
+
+    fn key(dna: &str, entry: &str) -> ValidationCacheKey {
+        ValidationCacheKey::new(
+            Address::from(dna),
+            Address::from(entry),
+            &ValidationData::default(),
+            String::from("test_zome/__hdk_validate_app_entry"),
+        )
+    }
+
+    #[test]
+    fn caches_definite_outcomes_and_counts_hits() {
+        let mut cache = ValidationCache::new(10);
+        assert_eq!(cache.get(&key("dna", "a")), None);
+
+        cache.insert(key("dna", "a"), Ok(()));
+        cache.insert(key("dna", "b"), Err(ValidationError::Fail("no".into())));
+        cache.insert(
+            key("dna", "c"),
+            Err(ValidationError::UnresolvedDependencies(vec![])),
+        );
+        assert_eq!(cache.len(), 2);
+
+        assert_eq!(cache.get(&key("dna", "a")), Some(Ok(())));
+        assert_eq!(
+            cache.get(&key("dna", "b")),
+            Some(Err(ValidationError::Fail("no".into())))
+        );
+        assert_eq!(cache.get(&key("dna", "c")), None);
+        assert_eq!(cache.hits(), 2);
+        assert_eq!(cache.misses(), 2);
+        assert!((cache.hit_rate() - 0.5).abs() < std::f64::EPSILON);
+    }
+
+    #[test]
+    fn lifecycle_is_part_of_the_key() {
+        let mut validation_data = ValidationData::default();
+        validation_data.lifecycle = EntryLifecycle::Dht;
+        let dht_key = ValidationCacheKey::new(
+            Address::from("dna"),
+            Address::from("a"),
+            &validation_data,
+            String::from("test_zome/__hdk_validate_app_entry"),
+        );
+        assert_ne!(dht_key, key("dna", "a"));
+    }
+
+    #[test]
+    fn evicts_oldest_outcome_when_full() {
+        let mut cache = ValidationCache::new(2);
+        cache.insert(key("dna", "a"), Ok(()));
+        cache.insert(key("dna", "b"), Ok(()));
+        cache.insert(key("dna", "c"), Ok(()));
+        assert_eq!(cache.len(), 2);
+        assert_eq!(cache.get(&key("dna", "a")), None);
+        assert_eq!(cache.get(&key("dna", "c")), Some(Ok(())));
+    }
+
+    #[test]
+    fn dna_change_invalidates_cache() {
+        let mut cache = ValidationCache::new(10);
+        cache.insert(key("dna", "a"), Ok(()));
+        assert_eq!(cache.get(&key("other_dna", "a")), None);
+        assert!(cache.is_empty());
+    }
+}
@@ -0,0 +1,176 @@
+//! Native check of countersigned app entries.
+//! Entry types can name fields of their content that hold the agents who all have to sign
+//! entries of this type. Every one of them needs a provenance in the header. That the
+//! signatures are valid is checked for all provenances already.
+
+use crate::nucleus::validation::{ValidationError, ValidationResult};
+use holochain_core_types::{
+    dna::entry_types::EntryTypeDef,
+    entry::{entry_type::AppEntryType, AppEntryValue},
+    validation::ValidationData,
+};
+use holochain_persistence_api::cas::content::Address;
+use serde_json::Value;
+
+/// Returns the addresses of the agents that have to sign the given app entry,
+/// read from the fields named in `countersigners` of its entry type definition.
+/// Empty if the entry type doesn't require countersigning.
+pub fn required_countersigners(
+    app_entry_type: &AppEntryType,
+    entry_value: &AppEntryValue,
+    entry_type_def: &EntryTypeDef,
+) -> Result<Vec<Address>, ValidationError> {
+    if entry_type_def.countersigners.is_empty() {
+        return Ok(Vec::new());
+    }
+    let entry_type_name = String::from(app_entry_type.clone());
+    let fail = |reason: String| {
+        ValidationError::Fail(format!(
+            "Countersigned entry of type \"{}\" {}",
+            entry_type_name, reason
+        ))
+    };
+    let content: Value = serde_json::from_str(&entry_value.to_string())
+        .map_err(|e| fail(format!("is not valid JSON: {}", e)))?;
+
+    let mut signers = Vec::new();
+    for field in entry_type_def.countersigners.iter() {
+        match content.get(field) {
+            Some(Value::String(address)) => signers.push(Address::from(address.as_str())),
+            Some(Value::Array(addresses)) => {
+                for address in addresses {
+                    match address {
+                        Value::String(address) => signers.push(Address::from(address.as_str())),
+                        _ => return Err(fail(format!("has a non-address in field \"{}\"", field))),
+                    }
+                }
+            }
+            _ => {
+                return Err(fail(format!(
+                    "has no signer address in field \"{}\"",
+                    field
+                )))
+            }
+        }
+    }
+    signers.sort();
+    signers.dedup();
+    Ok(signers)
+}
+
+/// Checks that every agent named in the countersigner fields of the app entry
+/// has signed the header.
+pub fn validate_countersignatures(
+    app_entry_type: &AppEntryType,
+    entry_value: &AppEntryValue,
+    entry_type_def: &EntryTypeDef,
+    validation_data: &ValidationData,
+) -> ValidationResult {
+    let sources = validation_data.sources();
+    let missing: Vec<String> =
+        required_countersigners(app_entry_type, entry_value, entry_type_def)?
+            .into_iter()
+            .filter(|signer| !sources.contains(signer))
+            .map(String::from)
+            .collect();
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(ValidationError::Fail(format!(
+            "Countersigned entry of type \"{}\" is missing the signatures of {}",
+            String::from(app_entry_type.clone()),
+            missing.join(", ")
+        )))
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use holochain_core_types::{
+        chain_header::ChainHeader,
+        entry::test_entry,
+        signature::{Provenance, Signature},
+        time::test_iso_8601,
+        validation::ValidationPackage,
+    };
+    use holochain_json_api::json::JsonString;
+    use holochain_persistence_api::cas::content::AddressableContent;
+
+    fn countersigned_def() -> EntryTypeDef {
+        let mut def = EntryTypeDef::new();
+        def.countersigners = vec![String::from("spender"), String::from("receivers")];
+        def
+    }
+
+    fn validation_data_signed_by(signers: &[&str]) -> ValidationData {
+        let entry = test_entry();
+        let provenances: Vec<Provenance> = signers
+            .iter()
+            .map(|signer| Provenance::new(Address::from(*signer), Signature::fake()))
+            .collect();
+        let header = ChainHeader::new(
+            &entry.entry_type(),
+            &entry.address(),
+            &provenances,
+            &None,
+            &None,
+            &None,
+            &test_iso_8601(),
+        );
+        ValidationData {
+            package: ValidationPackage::only_header(header),
+            ..Default::default()
+        }
+    }
+
+    fn transaction() -> AppEntryValue {
+        JsonString::from_json(r#"{"spender":"alice","receivers":["bob","carol"],"amount":5}"#)
+    }
+
+    #[test]
+    fn required_countersigners_are_read_from_the_named_fields() {
+        assert_eq!(
+            required_countersigners(&"transaction".into(), &transaction(), &countersigned_def()),
+            Ok(vec![
+                Address::from("alice"),
+                Address::from("bob"),
+                Address::from("carol")
+            ])
+        );
+        assert_eq!(
+            required_countersigners(&"transaction".into(), &transaction(), &EntryTypeDef::new()),
+            Ok(vec![])
+        );
+        assert!(required_countersigners(
+            &"transaction".into(),
+            &JsonString::from_json(r#"{"spender":"alice","amount":5}"#),
+            &countersigned_def()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn entries_need_signatures_of_all_countersigners() {
+        assert_eq!(
+            validate_countersignatures(
+                &"transaction".into(),
+                &transaction(),
+                &countersigned_def(),
+                &validation_data_signed_by(&["bob", "carol", "alice"]),
+            ),
+            Ok(())
+        );
+        assert_eq!(
+            validate_countersignatures(
+                &"transaction".into(),
+                &transaction(),
+                &countersigned_def(),
+                &validation_data_signed_by(&["alice", "bob"]),
+            ),
+            Err(ValidationError::Fail(String::from(
+                "Countersigned entry of type \"transaction\" is missing the signatures of carol"
+            )))
+        );
+    }
+}
@@ -0,0 +1,390 @@
+//! Native checks of app entries against the JSON Schema and maximum size that can
+//! optionally be given for their entry type in the DNA.
+//! These run before the zome's validation callback so malformed entries get
+//! rejected cheaply and with the same error messages across all zomes.
+//!
+//! Only a subset of JSON Schema is supported: `type`, `enum`, `const`,
+//! `properties`, `required`, `additionalProperties`, `items`, `minItems`,
+//! `maxItems`, `minLength`, `maxLength`, `pattern`, `minimum` and `maximum`.
+//! Schemas with other keywords get rejected when the DNA is loaded,
+//! see `EntryTypeDef::check_schema`.
+
+use crate::nucleus::validation::{ValidationError, ValidationResult};
+use holochain_core_types::{
+    dna::entry_types::EntryTypeDef,
+    entry::{entry_type::AppEntryType, AppEntryValue},
+};
+use regex::Regex;
+use serde_json::{Map, Value};
+
+/// Checks the given app entry value against the schema and maximum size
+/// of its entry type definition.
+pub fn validate_entry_schema(
+    app_entry_type: &AppEntryType,
+    entry_value: &AppEntryValue,
+    entry_type_def: &EntryTypeDef,
+) -> ValidationResult {
+    let entry_type_name = String::from(app_entry_type.clone());
+    let content = entry_value.to_string();
+
+    if let Some(max_size) = entry_type_def.max_size {
+        if content.len() > max_size {
+            return Err(ValidationError::Fail(format!(
+                "Entry of type \"{}\" is {} bytes which exceeds the maximum size of {} bytes",
+                entry_type_name,
+                content.len(),
+                max_size
+            )));
+        }
+    }
+
+    if let Some(schema) = &entry_type_def.schema {
+        let schema: Value = serde_json::from_str(&schema.to_string()).map_err(|e| {
+            ValidationError::Fail(format!(
+                "Schema of entry type \"{}\" is not valid JSON: {}",
+                entry_type_name, e
+            ))
+        })?;
+        let value: Value = serde_json::from_str(&content).map_err(|e| {
+            ValidationError::Fail(format!(
+                "Entry of type \"{}\" is not valid JSON: {}",
+                entry_type_name, e
+            ))
+        })?;
+        check(&schema, &value, "").map_err(|reason| {
+            ValidationError::Fail(format!(
+                "Entry of type \"{}\" does not match its schema: {}",
+                entry_type_name, reason
+            ))
+        })?;
+    }
+
+    Ok(())
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+fn has_type(value: &Value, expected: &str) -> bool {
+    match expected {
+        "integer" => value.as_i64().is_some() || value.as_u64().is_some(),
+        "number" => value.is_number(),
+        other => type_name(value) == other,
+    }
+}
+
+fn location(path: &str) -> &str {
+    if path.is_empty() {
+        "/"
+    } else {
+        path
+    }
+}
+
+fn check(schema: &Value, value: &Value, path: &str) -> Result<(), String> {
+    let schema = match schema {
+        // `true` and `{}` accept everything, `false` nothing
+        Value::Bool(true) => return Ok(()),
+        Value::Bool(false) => return Err(format!("{}: no value allowed", location(path))),
+        Value::Object(schema) => schema,
+        _ => return Err(format!("{}: schema has to be an object", location(path))),
+    };
+
+    check_type(schema, value, path)?;
+
+    if let Some(Value::Array(allowed)) = schema.get("enum") {
+        if !allowed.contains(value) {
+            return Err(format!(
+                "{}: {} is not one of the allowed values",
+                location(path),
+                value
+            ));
+        }
+    }
+    if let Some(constant) = schema.get("const") {
+        if constant != value {
+            return Err(format!("{}: expected {}", location(path), constant));
+        }
+    }
+
+    match value {
+        Value::Object(object) => check_object(schema, object, path),
+        Value::Array(items) => check_array(schema, items, path),
+        Value::String(string) => check_string(schema, string, path),
+        Value::Number(_) => check_number(schema, value, path),
+        _ => Ok(()),
+    }
+}
+
+fn check_type(schema: &Map<String, Value>, value: &Value, path: &str) -> Result<(), String> {
+    let expected: Vec<&str> = match schema.get("type") {
+        None => return Ok(()),
+        Some(Value::String(t)) => vec![t.as_str()],
+        Some(Value::Array(types)) => types.iter().filter_map(|t| t.as_str()).collect(),
+        Some(_) => return Err(format!("{}: invalid \"type\" in schema", location(path))),
+    };
+    if expected.iter().any(|t| has_type(value, t)) {
+        Ok(())
+    } else {
+        Err(format!(
+            "{}: expected {} but got {}",
+            location(path),
+            expected.join(" or "),
+            type_name(value)
+        ))
+    }
+}
+
+fn check_object(
+    schema: &Map<String, Value>,
+    object: &Map<String, Value>,
+    path: &str,
+) -> Result<(), String> {
+    if let Some(Value::Array(required)) = schema.get("required") {
+        for key in required.iter().filter_map(|key| key.as_str()) {
+            if !object.contains_key(key) {
+                return Err(format!(
+                    "{}: missing required property \"{}\"",
+                    location(path),
+                    key
+                ));
+            }
+        }
+    }
+
+    let properties = schema.get("properties").and_then(|p| p.as_object());
+    for (key, property_value) in object {
+        let property_path = format!("{}/{}", path, key);
+        match properties.and_then(|p| p.get(key)) {
+            Some(property_schema) => check(property_schema, property_value, &property_path)?,
+            None => match schema.get("additionalProperties") {
+                Some(Value::Bool(false)) => {
+                    return Err(format!(
+                        "{}: property \"{}\" is not allowed",
+                        location(path),
+                        key
+                    ))
+                }
+                Some(additional_schema) => {
+                    check(additional_schema, property_value, &property_path)?
+                }
+                None => (),
+            },
+        }
+    }
+    Ok(())
+}
+
+fn check_array(schema: &Map<String, Value>, items: &[Value], path: &str) -> Result<(), String> {
+    if let Some(min) = schema.get("minItems").and_then(|m| m.as_u64()) {
+        if (items.len() as u64) < min {
+            return Err(format!(
+                "{}: expected at least {} items but got {}",
+                location(path),
+                min,
+                items.len()
+            ));
+        }
+    }
+    if let Some(max) = schema.get("maxItems").and_then(|m| m.as_u64()) {
+        if (items.len() as u64) > max {
+            return Err(format!(
+                "{}: expected at most {} items but got {}",
+                location(path),
+                max,
+                items.len()
+            ));
+        }
+    }
+    if let Some(item_schema) = schema.get("items") {
+        for (index, item) in items.iter().enumerate() {
+            check(item_schema, item, &format!("{}/{}", path, index))?;
+        }
+    }
+    Ok(())
+}
+
+fn check_string(schema: &Map<String, Value>, string: &str, path: &str) -> Result<(), String> {
+    let length = string.chars().count() as u64;
+    if let Some(min) = schema.get("minLength").and_then(|m| m.as_u64()) {
+        if length < min {
+            return Err(format!(
+                "{}: expected at least {} characters but got {}",
+                location(path),
+                min,
+                length
+            ));
+        }
+    }
+    if let Some(max) = schema.get("maxLength").and_then(|m| m.as_u64()) {
+        if length > max {
+            return Err(format!(
+                "{}: expected at most {} characters but got {}",
+                location(path),
+                max,
+                length
+            ));
+        }
+    }
+    if let Some(pattern) = schema.get("pattern").and_then(|p| p.as_str()) {
+        let regex = Regex::new(pattern).map_err(|_| {
+            format!(
+                "{}: invalid \"pattern\" in schema: {}",
+                location(path),
+                pattern
+            )
+        })?;
+        if !regex.is_match(string) {
+            return Err(format!(
+                "{}: \"{}\" does not match pattern {}",
+                location(path),
+                string,
+                pattern
+            ));
+        }
+    }
+    Ok(())
+}
+
+fn check_number(schema: &Map<String, Value>, number: &Value, path: &str) -> Result<(), String> {
+    let number = number.as_f64().unwrap_or_default();
+    if let Some(min) = schema.get("minimum").and_then(|m| m.as_f64()) {
+        if number < min {
+            return Err(format!(
+                "{}: {} is less than the minimum of {}",
+                location(path),
+                number,
+                min
+            ));
+        }
+    }
+    if let Some(max) = schema.get("maximum").and_then(|m| m.as_f64()) {
+        if number > max {
+            return Err(format!(
+                "{}: {} is greater than the maximum of {}",
+                location(path),
+                number,
+                max
+            ));
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use holochain_json_api::json::JsonString;
+
+    fn post_type_def() -> EntryTypeDef {
+        let mut entry_type_def = EntryTypeDef::new();
+        entry_type_def.schema = Some(JsonString::from_json(
+            r#"{
+                "type": "object",
+                "required": ["content", "tags"],
+                "additionalProperties": false,
+                "properties": {
+                    "content": { "type": "string", "minLength": 1, "maxLength": 10 },
+                    "tags": { "type": "array", "items": { "type": "string", "pattern": "^[a-z]+$" } },
+                    "stars": { "type": "integer", "minimum": 0, "maximum": 5 },
+                    "status": { "enum": ["draft", "published"] }
+                }
+            }"#,
+        ));
+        entry_type_def
+    }
+
+    fn validate(value: &str, entry_type_def: &EntryTypeDef) -> ValidationResult {
+        validate_entry_schema(
+            &AppEntryType::from("post"),
+            &JsonString::from_json(value),
+            entry_type_def,
+        )
+    }
+
+    fn fail(reason: &str) -> ValidationResult {
+        Err(ValidationError::Fail(format!(
+            "Entry of type \"post\" does not match its schema: {}",
+            reason
+        )))
+    }
+
+    #[test]
+    fn entries_without_schema_are_valid() {
+        assert_eq!(validate("\"anything\"", &EntryTypeDef::new()), Ok(()));
+    }
+
+    #[test]
+    fn entries_matching_the_schema_are_valid() {
+        assert_eq!(
+            validate(
+                r#"{"content":"hi","tags":["a","b"],"stars":5,"status":"draft"}"#,
+                &post_type_def()
+            ),
+            Ok(())
+        );
+    }
+
+    #[test]
+    fn schema_violations_are_reported_with_their_path() {
+        let def = post_type_def();
+        assert_eq!(
+            validate("[]", &def),
+            fail("/: expected object but got array")
+        );
+        assert_eq!(
+            validate(r#"{"content":"hi"}"#, &def),
+            fail("/: missing required property \"tags\"")
+        );
+        assert_eq!(
+            validate(r#"{"content":"hi","tags":[],"foo":1}"#, &def),
+            fail("/: property \"foo\" is not allowed")
+        );
+        assert_eq!(
+            validate(r#"{"content":"","tags":[]}"#, &def),
+            fail("/content: expected at least 1 characters but got 0")
+        );
+        assert_eq!(
+            validate(r#"{"content":"hi","tags":["a",1]}"#, &def),
+            fail("/tags/1: expected string but got number")
+        );
+        assert_eq!(
+            validate(r#"{"content":"hi","tags":["A"]}"#, &def),
+            fail("/tags/0: \"A\" does not match pattern ^[a-z]+$")
+        );
+        assert_eq!(
+            validate(r#"{"content":"hi","tags":[],"stars":1.5}"#, &def),
+            fail("/stars: expected integer but got number")
+        );
+        assert_eq!(
+            validate(r#"{"content":"hi","tags":[],"stars":6}"#, &def),
+            fail("/stars: 6 is greater than the maximum of 5")
+        );
+        assert_eq!(
+            validate(r#"{"content":"hi","tags":[],"status":"gone"}"#, &def),
+            fail("/status: \"gone\" is not one of the allowed values")
+        );
+    }
+
+    #[test]
+    fn entries_exceeding_max_size_are_invalid() {
+        let mut def = EntryTypeDef::new();
+        def.max_size = Some(5);
+        assert_eq!(validate("\"abc\"", &def), Ok(()));
+        assert_eq!(
+            validate("\"abcd\"", &def),
+            Err(ValidationError::Fail(
+                "Entry of type \"post\" is 6 bytes which exceeds the maximum size of 5 bytes"
+                    .to_string()
+            ))
+        );
+    }
+}
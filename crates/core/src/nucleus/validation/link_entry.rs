@@ -94,6 +94,7 @@ pub async fn validate_link_entry(
             target.entry_type().to_string(),
         )))?;
 
+    let cache_validation_data = validation_data.clone();
     let validation_data = match entry.clone() {
         Entry::LinkAdd(link) => Ok(LinkValidationData::LinkAdd {
             link,
@@ -118,5 +119,5 @@ pub async fn validate_link_entry(
         params,
     );
 
-    run_validation_callback(address, call, context).await
+    run_validation_callback(address, &cache_validation_data, call, context).await
 }
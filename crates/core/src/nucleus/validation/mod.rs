@@ -15,7 +15,11 @@ use std::sync::Arc;
 
 mod agent_entry;
 mod app_entry;
+mod blob_entry;
 pub mod build_from_dht;
+pub mod cache;
+pub mod countersigning;
+mod entry_schema;
 mod header_address;
 mod link_entry;
 mod provenances;
@@ -71,7 +75,12 @@ impl From<ValidationError> for HolochainError {
 ///    the validation package.
 /// 2. Validates provenances given in the header by verifying the cryptographic signatures
 ///    against the source agent addresses.
-/// 3. Finally spawns a thread to run the type specific validation callback in a Ribosome.
+/// 3. If the validation package contains data built by the author's custom validation
+///    package callback, verifies the author's signature of it.
+/// 4. For app entries, checks the entry against the JSON schema and maximum size given for
+///    its entry type in the DNA, and that all agents it names as countersigners signed it.
+/// 5. Finally spawns a thread to run the type specific validation callback in a Ribosome.
+///    Blob manifests only get one if a zome defines a validating entry type for them.
 ///
 /// All of this actually happens in the functions of the sub modules. This function is the
 /// main validation entry point and, like a workflow, stays high-level.
@@ -86,6 +95,7 @@ pub async fn validate_entry(
     //check_entry_type(entry.entry_type(), context)?;
     header_address::validate_header_address(&entry, &validation_data.package.chain_header)?;
     provenances::validate_provenances(&validation_data)?;
+    provenances::validate_custom_package_signature(&validation_data)?;
 
     match entry.entry_type() {
         // DNA entries are not validated currently and always valid
@@ -99,6 +109,7 @@ pub async fn validate_entry(
                 context,
                 link,
                 validation_data,
+                true,
             )
             .await
         }
@@ -127,10 +138,48 @@ pub async fn validate_entry(
         // chain headers always pass for now. In future this should check that the entry is valid
         EntryType::ChainHeader => Ok(()),
 
+        // membrane proofs are checked by the agent validation callbacks as part of the
+        // validation package of the AgentId entry that follows them, together with the
+        // header they got committed with
+        EntryType::MembraneProof => Ok(()),
+
+        EntryType::BlobChunk | EntryType::BlobManifest => {
+            blob_entry::validate_blob_entry(entry.clone(), validation_data, context).await
+        }
+
         _ => Err(ValidationError::NotImplemented),
     }
 }
 
+/// Validates an entry that we are asked to countersign, before we sign it.
+/// The same as validate_entry for app entries, except that the other countersigners'
+/// signatures are not there yet.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub async fn validate_countersigning_proposal(
+    entry: Entry,
+    validation_data: ValidationData,
+    context: &Arc<Context>,
+) -> ValidationResult {
+    header_address::validate_header_address(&entry, &validation_data.package.chain_header)?;
+    provenances::validate_provenances(&validation_data)?;
+    match entry.entry_type() {
+        EntryType::App(app_entry_type) => {
+            app_entry::validate_app_entry(
+                entry.clone(),
+                app_entry_type,
+                context,
+                None,
+                validation_data,
+                false,
+            )
+            .await
+        }
+        _ => Err(ValidationError::Fail(String::from(
+            "Only app entries can be countersigned",
+        ))),
+    }
+}
+
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
 pub fn entry_to_validation_data(
     context: Arc<Context>,
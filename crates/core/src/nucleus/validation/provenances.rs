@@ -3,8 +3,11 @@ use crate::{
     NEW_RELIC_LICENSE_KEY,
 };
 use boolinator::Boolinator;
-use holochain_core_types::validation::ValidationData;
+use holochain_core_types::{
+    custom_validation_package::CustomValidationPackage, validation::ValidationData,
+};
 use holochain_dpki::utils::Verify;
+use holochain_persistence_api::cas::content::AddressableContent;
 
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
 pub fn validate_provenances(validation_data: &ValidationData) -> ValidationResult {
@@ -34,3 +37,110 @@ pub fn validate_provenances(validation_data: &ValidationData) -> ValidationResul
         .collect::<Result<Vec<()>, ValidationError>>()?;
     Ok(())
 }
+
+/// If the validation package carries custom data built by the author's custom validation
+/// package callback, checks that one of the authors of the header signed it.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn validate_custom_package_signature(validation_data: &ValidationData) -> ValidationResult {
+    let package = &validation_data.package;
+    let signature = match &package.custom_signature {
+        Some(signature) => signature,
+        None => return Ok(()),
+    };
+    let header = &package.chain_header;
+    let fail = || {
+        ValidationError::Fail(format!(
+            "Custom validation package of entry {} not signed by its author",
+            header.entry_address(),
+        ))
+    };
+    if !validation_data.sources().contains(&signature.source()) {
+        return Err(fail());
+    }
+    let payload = CustomValidationPackage::signing_payload(
+        &header.address(),
+        package
+            .custom
+            .as_ref()
+            .map(String::as_str)
+            .unwrap_or_default(),
+    );
+    match signature.verify(payload) {
+        Ok(true) => Ok(()),
+        _ => Err(fail()),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::instance::tests::test_context;
+    use holochain_core_types::{
+        chain_header::ChainHeader,
+        entry::test_entry,
+        signature::{Provenance, Signature},
+        time::test_iso_8601,
+        validation::{ValidationData, ValidationPackage},
+    };
+    use holochain_persistence_api::cas::content::Address;
+    use test_utils::mock_signing::mock_signer;
+
+    fn validation_data_with_custom_package(
+        author: &Address,
+        signer: Provenance,
+        data: &str,
+    ) -> ValidationData {
+        let entry = test_entry();
+        let header = ChainHeader::new(
+            &entry.entry_type(),
+            &entry.address(),
+            &[Provenance::new(author.clone(), Signature::fake())],
+            &None,
+            &None,
+            &None,
+            &test_iso_8601(),
+        );
+        let mut package = ValidationPackage::only_header(header);
+        package.custom = Some(String::from(data));
+        package.custom_signature = Some(signer);
+        ValidationData {
+            package,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn custom_packages_signed_by_the_author_are_valid() {
+        let context = test_context("alice", None);
+        let author = context.agent_id.address();
+        let mut validation_data = validation_data_with_custom_package(
+            &author,
+            Provenance::new(author.clone(), Signature::fake()),
+            "[]",
+        );
+        let payload = CustomValidationPackage::signing_payload(
+            &validation_data.package.chain_header.address(),
+            "[]",
+        );
+        validation_data.package.custom_signature = Some(Provenance::new(
+            author,
+            Signature::from(mock_signer(payload, &context.agent_id)),
+        ));
+        assert_eq!(validate_custom_package_signature(&validation_data), Ok(()));
+
+        // tampering with the data breaks the signature
+        validation_data.package.custom = Some(String::from("[\"forged\"]"));
+        assert!(validate_custom_package_signature(&validation_data).is_err());
+    }
+
+    #[test]
+    fn custom_packages_not_signed_by_the_author_are_invalid() {
+        let context = test_context("alice", None);
+        let validation_data = validation_data_with_custom_package(
+            &Address::from("somebody else"),
+            Provenance::new(context.agent_id.address(), Signature::fake()),
+            "[]",
+        );
+        assert!(validate_custom_package_signature(&validation_data).is_err());
+    }
+}
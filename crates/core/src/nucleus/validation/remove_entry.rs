@@ -39,6 +39,7 @@ pub async fn validate_remove_entry(
         .get_zome_name_for_app_entry_type(&app_entry_type)
         .ok_or(ValidationError::NotImplemented)?;
 
+    let cache_validation_data = validation_data.clone();
     let params = EntryValidationArgs {
         validation_data: entry_to_validation_data(context.clone(), &entry, None, validation_data)
             .map_err(|_| {
@@ -47,5 +48,5 @@ pub async fn validate_remove_entry(
     };
 
     let call = CallbackFnCall::new(&zome_name, "__hdk_validate_app_entry", params);
-    run_validation_callback(entry.address(), call, context).await
+    run_validation_callback(entry.address(), &cache_validation_data, call, context).await
 }
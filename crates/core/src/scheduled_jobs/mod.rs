@@ -5,6 +5,7 @@ use crate::{
     action::{Action, ActionWrapper},
     context::Context,
     instance::dispatch_action,
+    workflows::countersigning::{claim_expired_session, resolve_countersigning_session_workflow},
 };
 use std::sync::Arc;
 
@@ -23,6 +24,19 @@ pub fn create_timeout_callback(context: Arc<Context>) -> impl 'static + FnMut()
     }
 }
 
+pub fn create_countersigning_callback(
+    context: Arc<Context>,
+) -> impl 'static + FnMut() + Sync + Send {
+    move || {
+        if let Some(request) = claim_expired_session(&context) {
+            context.spawn_task(resolve_countersigning_session_workflow(
+                request,
+                context.clone(),
+            ));
+        }
+    }
+}
+
 pub fn create_state_pruning_callback(
     context: Arc<Context>,
 ) -> impl 'static + FnMut() + Sync + Send {
@@ -75,6 +75,7 @@ mod tests {
             message: custom_direct_message,
             msg_id: msg_id.clone(),
             is_response: false,
+            deliver_later: false,
         };
         let action_wrapper = ActionWrapper::new(Action::SendDirectMessage((
             direct_message_data,
@@ -1,6 +1,7 @@
 use crate::{action::ActionWrapper, consistency::ConsistencySignal};
 use crossbeam_channel::{unbounded, Receiver, Sender};
 use holochain_json_api::{error::JsonError, json::JsonString};
+use holochain_persistence_api::cas::content::Address;
 use holochain_wasm_utils::api_serialization::emit_signal::EmitSignalArgs;
 use serde::{Deserialize, Deserializer};
 use snowflake::ProcessUniqueId;
@@ -19,6 +20,10 @@ pub enum Signal {
 pub struct UserSignal {
     pub name: String,
     pub arguments: JsonString,
+    /// The agent that sent this signal with remote_signal(),
+    /// None for signals emitted by our own zomes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sender: Option<Address>,
 }
 
 impl From<EmitSignalArgs> for UserSignal {
@@ -26,6 +31,17 @@ impl From<EmitSignalArgs> for UserSignal {
         UserSignal {
             name: args.name,
             arguments: args.arguments,
+            sender: None,
+        }
+    }
+}
+
+impl UserSignal {
+    /// The signal another agent sent us with remote_signal()
+    pub fn remote(args: EmitSignalArgs, sender: Address) -> UserSignal {
+        UserSignal {
+            sender: Some(sender),
+            ..UserSignal::from(args)
         }
     }
 }
@@ -33,6 +33,10 @@ use std::{convert::TryInto, sync::Arc, time::SystemTime};
 
 pub const ACTION_PRUNE_MS: u64 = 60000;
 
+/// How long we remember direct messages we sent to be delivered later.
+/// Matches the default time sim2h keeps undelivered messages for.
+pub const DELIVER_LATER_PRUNE_MS: u64 = 24 * 60 * 60 * 1000;
+
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ActionResponse<T> {
     pub created_at: SystemTime,
@@ -9,7 +9,8 @@ use crate::{
 use holochain_core_types::{chain_header::ChainHeader, entry::Entry, error::HolochainError};
 use holochain_json_api::json::JsonString;
 use holochain_persistence_api::cas::content::{Address, AddressableContent};
-use std::{collections::VecDeque, convert::TryInto, sync::Arc};
+use im::Vector;
+use std::{convert::TryInto, sync::Arc};
 
 #[derive(Serialize)]
 pub struct StateDump {
@@ -19,7 +20,7 @@ pub struct StateDump {
     pub query_flows: Vec<QueryKey>,
     pub validation_package_flows: Vec<Address>,
     pub direct_message_flows: Vec<(String, DirectMessage)>,
-    pub queued_holding_workflows: VecDeque<PendingValidationWithTimeout>,
+    pub queued_holding_workflows: Vector<PendingValidationWithTimeout>,
     pub held_aspects: AspectMapBare,
     pub source_chain: Vec<ChainHeader>,
 }
@@ -0,0 +1,452 @@
+use crate::{
+    content_store::GetContent,
+    context::Context,
+    wasm_engine::{api::ZomeApiResult, Runtime},
+    workflows::{
+        author_entry::author_entry, get_entries_result::get_entries_result_workflow,
+        get_entry_result::get_entry_result_workflow,
+    },
+    NEW_RELIC_LICENSE_KEY,
+};
+use holochain_core_types::{
+    blob::{BlobChunk, BlobManifest},
+    entry::Entry,
+    error::HolochainError,
+};
+use holochain_persistence_api::cas::content::{Address, AddressableContent};
+use holochain_wasm_utils::api_serialization::{
+    blob::{GetBlobArgs, GetBlobResult, StoreBlobArgs},
+    get_entry::{GetEntriesArgs, GetEntryArgs, GetEntryOptions},
+};
+use std::{convert::TryFrom, sync::Arc};
+use wasmi::{RuntimeArgs, RuntimeValue};
+
+/// ZomeApiFunction::StoreBlob function code
+/// args: [0] encoded MemoryAllocation as u64
+/// Expected complex argument: StoreBlobArgs
+/// Returns an HcApiReturnCode as I64
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn invoke_store_blob(runtime: &mut Runtime, args: &RuntimeArgs) -> ZomeApiResult {
+    let context = runtime.context()?;
+    // deserialize args
+    let args_str = runtime.load_json_string_from_args(&args);
+    let input = match StoreBlobArgs::try_from(args_str.clone()) {
+        Ok(input) => input,
+        // Exit on error
+        Err(_) => {
+            log_error!(
+                context,
+                "zome: invoke_store_blob() failed to deserialize: {:?}",
+                args_str
+            );
+            return ribosome_error_code!(ArgumentDeserializationFailed);
+        }
+    };
+    let result = store_blob(&context, input);
+    runtime.store_result(result)
+}
+
+/// Commits a chunk, or a manifest once all of its chunks are on our chain
+fn store_blob(context: &Arc<Context>, input: StoreBlobArgs) -> Result<Address, HolochainError> {
+    let entry = match input {
+        StoreBlobArgs::Chunk(chunk) => {
+            chunk.check()?;
+            Entry::BlobChunk(chunk)
+        }
+        StoreBlobArgs::Manifest(manifest) => {
+            manifest.check()?;
+            let chain_store = context.state().unwrap().agent().chain_store();
+            for (index, address) in manifest.chunks.iter().enumerate() {
+                match chain_store.get(address)? {
+                    Some(Entry::BlobChunk(ref chunk))
+                        if chunk.len() as u64 == manifest.chunk_size(index) => {}
+                    _ => {
+                        return Err(HolochainError::ErrorGeneric(format!(
+                            "Chunk {} of the blob ({}) was not committed before its manifest",
+                            index, address
+                        )))
+                    }
+                }
+            }
+            Entry::BlobManifest(manifest)
+        }
+    };
+    context.block_on(author_entry(&entry, None, context, &vec![]))?;
+    Ok(entry.address())
+}
+
+/// ZomeApiFunction::GetBlob function code
+/// args: [0] encoded MemoryAllocation as u64
+/// Expected complex argument: GetBlobArgs
+/// Returns an HcApiReturnCode as I64
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn invoke_get_blob(runtime: &mut Runtime, args: &RuntimeArgs) -> ZomeApiResult {
+    let context = runtime.context()?;
+    // deserialize args
+    let args_str = runtime.load_json_string_from_args(&args);
+    let input = match GetBlobArgs::try_from(args_str.clone()) {
+        Ok(input) => input,
+        // Exit on error
+        Err(_) => {
+            log_error!(
+                context,
+                "zome: invoke_get_blob() failed to deserialize: {:?}",
+                args_str
+            );
+            return ribosome_error_code!(ArgumentDeserializationFailed);
+        }
+    };
+    let result = get_blob(&context, &input);
+    runtime.store_result(result)
+}
+
+/// Gets the manifest of a blob, unless the caller already has it, and the requested window
+/// of its chunks, which get fetched concurrently. Every chunk is checked against the manifest.
+fn get_blob(context: &Arc<Context>, input: &GetBlobArgs) -> Result<GetBlobResult, HolochainError> {
+    let options = GetEntryOptions {
+        timeout: input.timeout.clone(),
+        ..Default::default()
+    };
+    let manifest = match input.manifest {
+        Some(ref manifest) => {
+            if Entry::BlobManifest(manifest.clone()).address() != input.address {
+                return Err(HolochainError::ErrorGeneric(format!(
+                    "Given manifest is not the one of blob {}",
+                    input.address
+                )));
+            }
+            manifest.clone()
+        }
+        None => get_manifest(context, &input.address, &options)?,
+    };
+
+    let first_chunk = input.first_chunk.min(manifest.chunks.len());
+    let last_chunk = first_chunk
+        .saturating_add(input.chunk_count)
+        .min(manifest.chunks.len());
+    let addresses = manifest.chunks[first_chunk..last_chunk].to_vec();
+    let entries_result = context.block_on(get_entries_result_workflow(
+        context,
+        &GetEntriesArgs {
+            addresses: addresses.clone(),
+            options,
+        },
+    ))?;
+
+    let chunks = entries_result
+        .results
+        .into_iter()
+        .zip(addresses)
+        .enumerate()
+        .map(|(offset, (result, address))| {
+            check_chunk(&manifest, first_chunk + offset, &address, result?.latest())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(GetBlobResult { manifest, chunks })
+}
+
+fn get_manifest(
+    context: &Arc<Context>,
+    address: &Address,
+    options: &GetEntryOptions,
+) -> Result<BlobManifest, HolochainError> {
+    let manifest_result = context.block_on(get_entry_result_workflow(
+        context,
+        &GetEntryArgs {
+            address: address.clone(),
+            options: options.clone(),
+        },
+    ))?;
+    match manifest_result.latest() {
+        Some(Entry::BlobManifest(manifest)) => Ok(manifest),
+        Some(_) => Err(HolochainError::ErrorGeneric(format!(
+            "{} is not a blob manifest",
+            address
+        ))),
+        None => Err(HolochainError::ErrorGeneric(format!(
+            "Blob {} not found",
+            address
+        ))),
+    }
+}
+
+fn check_chunk(
+    manifest: &BlobManifest,
+    index: usize,
+    address: &Address,
+    maybe_entry: Option<Entry>,
+) -> Result<BlobChunk, HolochainError> {
+    match maybe_entry {
+        Some(entry) => {
+            if entry.address() != *address {
+                return Err(HolochainError::ErrorGeneric(format!(
+                    "Got {} for chunk {} of the blob, expected {}",
+                    entry.address(),
+                    index,
+                    address
+                )));
+            }
+            match entry {
+                Entry::BlobChunk(chunk) => {
+                    if chunk.len() as u64 != manifest.chunk_size(index) {
+                        return Err(HolochainError::ErrorGeneric(format!(
+                            "Chunk {} of the blob holds {} bytes, expected {}",
+                            index,
+                            chunk.len(),
+                            manifest.chunk_size(index)
+                        )));
+                    }
+                    Ok(chunk)
+                }
+                _ => Err(HolochainError::ErrorGeneric(format!(
+                    "{} is not a blob chunk",
+                    address
+                ))),
+            }
+        }
+        None => Err(HolochainError::ErrorGeneric(format!(
+            "Chunk {} of the blob ({}) not found",
+            index, address
+        ))),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::{check_chunk, get_blob, store_blob};
+    use crate::{
+        context::Context,
+        instance::{tests::test_instance_and_context, Instance},
+        wasm_engine::api::{
+            tests::{test_zome_api_function_call, test_zome_api_function_wasm, test_zome_name},
+            ZomeApiFunction,
+        },
+    };
+    use holochain_core_types::{
+        blob::{BlobChunk, BlobManifest, BLOB_CHUNK_SIZE},
+        entry::Entry,
+        error::{CoreError, ZomeApiInternalResult},
+    };
+    use holochain_json_api::json::JsonString;
+    use holochain_persistence_api::cas::content::{Address, AddressableContent};
+    use holochain_wasm_utils::api_serialization::blob::{
+        GetBlobArgs, GetBlobResult, StoreBlobArgs,
+    };
+    use std::{convert::TryFrom, sync::Arc};
+
+    fn test_instance(function: ZomeApiFunction, netname: &str) -> (Instance, Arc<Context>) {
+        let wasm = test_zome_api_function_wasm(function.as_str());
+        let dna = test_utils::create_test_dna_with_wasm(&test_zome_name(), wasm);
+        test_instance_and_context(dna, Some(netname)).expect("Could not create test instance")
+    }
+
+    /// Stores a blob of two full chunks and a short one, returns its manifest
+    fn store_test_blob(context: &Arc<Context>) -> BlobManifest {
+        let chunks: Vec<Address> = vec![
+            vec![1; BLOB_CHUNK_SIZE],
+            vec![2; BLOB_CHUNK_SIZE],
+            vec![3; 10],
+        ]
+        .into_iter()
+        .map(|data| {
+            store_blob(context, StoreBlobArgs::Chunk(BlobChunk::new(data)))
+                .expect("Could not store chunk")
+        })
+        .collect();
+        let manifest = BlobManifest::new(2 * BLOB_CHUNK_SIZE as u64 + 10, chunks);
+        store_blob(context, StoreBlobArgs::Manifest(manifest.clone()))
+            .expect("Could not store manifest");
+        manifest
+    }
+
+    fn failure_message(call_result: JsonString) -> String {
+        let result = ZomeApiInternalResult::try_from(call_result)
+            .expect("valid ZomeApiInternalResult JsonString");
+        assert!(!result.ok);
+        let core_err = CoreError::try_from(result).expect("valid CoreError JsonString");
+        core_err.kind.to_string()
+    }
+
+    #[test]
+    fn test_invoke_store_blob() {
+        let (_instance, context) =
+            test_instance(ZomeApiFunction::StoreBlob, "test_invoke_store_blob");
+        let chunk = BlobChunk::new(vec![1, 2, 3]);
+        let chunk_address = Entry::BlobChunk(chunk.clone()).address();
+        let manifest = BlobManifest::new(3, vec![chunk_address.clone()]);
+
+        // the manifest can't be stored before its chunk
+        let call_result = test_zome_api_function_call(
+            context.clone(),
+            JsonString::from(StoreBlobArgs::Manifest(manifest.clone())).to_bytes(),
+        );
+        assert_eq!(
+            failure_message(call_result),
+            format!(
+                "Chunk 0 of the blob ({}) was not committed before its manifest",
+                chunk_address
+            ),
+        );
+
+        let call_result = test_zome_api_function_call(
+            context.clone(),
+            JsonString::from(StoreBlobArgs::Chunk(chunk)).to_bytes(),
+        );
+        assert_eq!(
+            call_result,
+            JsonString::from_json(
+                &(String::from(JsonString::from(ZomeApiInternalResult::success(
+                    chunk_address
+                ))) + "\u{0}")
+            ),
+        );
+
+        let call_result = test_zome_api_function_call(
+            context.clone(),
+            JsonString::from(StoreBlobArgs::Manifest(manifest.clone())).to_bytes(),
+        );
+        assert_eq!(
+            call_result,
+            JsonString::from_json(
+                &(String::from(JsonString::from(ZomeApiInternalResult::success(
+                    Entry::BlobManifest(manifest).address()
+                ))) + "\u{0}")
+            ),
+        );
+    }
+
+    #[test]
+    fn test_store_blob_checks_chunk_sizes_of_manifest() {
+        let (_instance, context) = test_instance(
+            ZomeApiFunction::StoreBlob,
+            "test_store_blob_checks_chunk_sizes_of_manifest",
+        );
+        let chunk_address = store_blob(
+            &context,
+            StoreBlobArgs::Chunk(BlobChunk::new(vec![1, 2, 3])),
+        )
+        .expect("Could not store chunk");
+        assert!(store_blob(
+            &context,
+            StoreBlobArgs::Manifest(BlobManifest::new(4, vec![chunk_address])),
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_invoke_get_blob() {
+        let (_instance, context) = test_instance(ZomeApiFunction::GetBlob, "test_invoke_get_blob");
+        let chunk = BlobChunk::new(vec![1, 2, 3]);
+        let chunk_address = store_blob(&context, StoreBlobArgs::Chunk(chunk.clone()))
+            .expect("Could not store chunk");
+        let manifest = BlobManifest::new(3, vec![chunk_address]);
+        let address = store_blob(&context, StoreBlobArgs::Manifest(manifest.clone()))
+            .expect("Could not store manifest");
+
+        let call_result = test_zome_api_function_call(
+            context.clone(),
+            JsonString::from(GetBlobArgs {
+                address,
+                chunk_count: 4,
+                ..Default::default()
+            })
+            .to_bytes(),
+        );
+        assert_eq!(
+            call_result,
+            JsonString::from_json(
+                &(String::from(JsonString::from(ZomeApiInternalResult::success(
+                    GetBlobResult {
+                        manifest,
+                        chunks: vec![chunk],
+                    }
+                ))) + "\u{0}")
+            ),
+        );
+    }
+
+    #[test]
+    fn test_get_blob_windows() {
+        let (_instance, context) = test_instance(ZomeApiFunction::GetBlob, "test_get_blob_windows");
+        let manifest = store_test_blob(&context);
+        let address = Entry::BlobManifest(manifest.clone()).address();
+
+        let result = get_blob(
+            &context,
+            &GetBlobArgs {
+                address: address.clone(),
+                first_chunk: 1,
+                chunk_count: 4,
+                ..Default::default()
+            },
+        )
+        .expect("Could not get blob");
+        assert_eq!(result.manifest, manifest);
+        assert_eq!(
+            result.chunks,
+            vec![
+                BlobChunk::new(vec![2; BLOB_CHUNK_SIZE]),
+                BlobChunk::new(vec![3; 10])
+            ]
+        );
+
+        // a known manifest gets used instead of fetching it again
+        let result = get_blob(
+            &context,
+            &GetBlobArgs {
+                address: address.clone(),
+                first_chunk: 2,
+                chunk_count: 1,
+                manifest: Some(manifest.clone()),
+                ..Default::default()
+            },
+        )
+        .expect("Could not get blob with known manifest");
+        assert_eq!(result.chunks, vec![BlobChunk::new(vec![3; 10])]);
+
+        // but it has to be the manifest of the blob
+        let other_manifest = BlobManifest::new(10, vec![manifest.chunks[2].clone()]);
+        assert!(get_blob(
+            &context,
+            &GetBlobArgs {
+                address,
+                chunk_count: 1,
+                manifest: Some(other_manifest),
+                ..Default::default()
+            },
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_check_chunk() {
+        let chunk = BlobChunk::new(vec![3; 10]);
+        let address = Entry::BlobChunk(chunk.clone()).address();
+        let manifest = BlobManifest::new(
+            BLOB_CHUNK_SIZE as u64 + 10,
+            vec![Address::from("first"), address.clone()],
+        );
+
+        assert_eq!(
+            check_chunk(
+                &manifest,
+                1,
+                &address,
+                Some(Entry::BlobChunk(chunk.clone()))
+            ),
+            Ok(chunk.clone())
+        );
+        // a chunk with the wrong size for its position
+        assert!(check_chunk(&manifest, 0, &address, Some(Entry::BlobChunk(chunk))).is_err());
+        // an entry that is not the requested chunk
+        assert!(check_chunk(
+            &manifest,
+            1,
+            &address,
+            Some(Entry::BlobChunk(BlobChunk::new(vec![4; 10])))
+        )
+        .is_err());
+        assert!(check_chunk(&manifest, 1, &address, None).is_err());
+    }
+}
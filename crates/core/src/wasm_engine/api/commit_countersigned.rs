@@ -0,0 +1,46 @@
+use crate::{
+    wasm_engine::{api::ZomeApiResult, Runtime},
+    workflows::countersigning::commit_countersigned_entry_workflow,
+    NEW_RELIC_LICENSE_KEY,
+};
+use holochain_core_types::error::HolochainError;
+use holochain_wasm_utils::api_serialization::commit_entry::{
+    CommitCountersignedEntryArgs, CommitEntryResult,
+};
+use std::convert::TryFrom;
+use wasmi::{RuntimeArgs, RuntimeValue};
+
+/// ZomeApiFunction::CommitCountersignedEntry function code
+/// args: [0] encoded MemoryAllocation as u64
+/// Expected complex argument: CommitCountersignedEntryArgs
+/// Returns an HcApiReturnCode as I64
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn invoke_commit_countersigned_entry(
+    runtime: &mut Runtime,
+    args: &RuntimeArgs,
+) -> ZomeApiResult {
+    let context = runtime.context()?;
+    // deserialize args
+    let args_str = runtime.load_json_string_from_args(&args);
+    let input = match CommitCountersignedEntryArgs::try_from(args_str.clone()) {
+        Ok(input) => input,
+        // Exit on error
+        Err(_) => {
+            log_error!(
+                context,
+                "zome: invoke_commit_countersigned_entry() failed to deserialize: {:?}",
+                args_str
+            );
+            return ribosome_error_code!(ArgumentDeserializationFailed);
+        }
+    };
+    // Wait for all signatures and the commit
+    let result: Result<CommitEntryResult, HolochainError> = context
+        .block_on(commit_countersigned_entry_workflow(
+            &input.entry,
+            input.timeout,
+            &context,
+        ))
+        .map(CommitEntryResult::new);
+    runtime.store_result(result)
+}
@@ -1,9 +1,12 @@
 use crate::{
+    action::{Action, ActionWrapper},
+    instance::dispatch_action,
     signal::{Signal, UserSignal},
     wasm_engine::{api::ZomeApiResult, Runtime},
     NEW_RELIC_LICENSE_KEY,
 };
-use holochain_wasm_utils::api_serialization::emit_signal::EmitSignalArgs;
+use holochain_persistence_api::cas::content::AddressableContent;
+use holochain_wasm_utils::api_serialization::emit_signal::{EmitSignalArgs, RemoteSignalArgs};
 use std::convert::TryFrom;
 use wasmi::{RuntimeArgs, RuntimeValue};
 
@@ -50,6 +53,61 @@ pub fn invoke_emit_signal(runtime: &mut Runtime, args: &RuntimeArgs) -> ZomeApiR
     ribosome_success!()
 }
 
+/// ZomeApiFunction::RemoteSignal function code
+/// args: [0] encoded MemoryAllocation as u64
+/// Expected complex argument: RemoteSignalArgs
+/// Returns an HcApiReturnCode as I64
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn invoke_remote_signal(runtime: &mut Runtime, args: &RuntimeArgs) -> ZomeApiResult {
+    let context = runtime.context()?;
+
+    let args_str = runtime.load_json_string_from_args(&args);
+    let remote_signal_args = match RemoteSignalArgs::try_from(args_str.clone()) {
+        Ok(args) => args,
+        // Exit on error
+        Err(error) => {
+            log_error!(
+                context,
+                "zome: invoke_remote_signal failed to \
+                 deserialize arguments: {:?} with error {:?}",
+                args_str,
+                error
+            );
+            return ribosome_error_code!(ArgumentDeserializationFailed);
+        }
+    };
+
+    let agent_address = context.agent_id.address();
+    for agent in remote_signal_args.agents {
+        if agent == agent_address {
+            // No need for a network round trip to reach our own UIs
+            if let Some(sender) = context.signal_tx() {
+                let signal = Signal::User(UserSignal::remote(
+                    remote_signal_args.signal.clone(),
+                    agent_address.clone(),
+                ));
+                let _ = sender.send(signal).map_err(|err| {
+                    log_error!(
+                        context,
+                        "zome: invoke_remote_signal() could not send signal: {:?}",
+                        err,
+                    );
+                });
+            }
+        } else {
+            let action_wrapper = ActionWrapper::new(Action::SendRemoteSignal((
+                agent,
+                remote_signal_args.signal.clone(),
+            )));
+            dispatch_action(context.action_channel(), action_wrapper);
+        }
+    }
+
+    // Like emit_signal, this is fire-and-forget: whether the agents are online
+    // and listening does not concern the sender.
+    ribosome_success!()
+}
+
 #[cfg(test)]
 pub mod tests {
     use crate::{
@@ -65,7 +123,8 @@ pub mod tests {
     };
     use crossbeam_channel::unbounded;
     use holochain_json_api::json::JsonString;
-    use holochain_wasm_utils::api_serialization::emit_signal::EmitSignalArgs;
+    use holochain_persistence_api::cas::content::AddressableContent;
+    use holochain_wasm_utils::api_serialization::emit_signal::{EmitSignalArgs, RemoteSignalArgs};
     use std::sync::Arc;
 
     pub fn test_signal() -> UserSignal {
@@ -112,4 +171,34 @@ pub mod tests {
             assert!(false, "Expected a Signal::User");
         }
     }
+
+    /// test that signals we send to ourselves arrive without the network and name us as sender
+    #[test]
+    fn test_zome_api_function_remote_signal_to_self() {
+        let wasm = test_zome_api_function_wasm(ZomeApiFunction::RemoteSignal.as_str());
+        let dna = test_utils::create_test_dna_with_wasm(&test_zome_name(), wasm.clone());
+
+        let (_instance, context) =
+            test_instance_and_context(dna, None).expect("Could not create test instance");
+
+        let (tx, rx) = unbounded::<Signal>();
+        let mut context = (*context).clone();
+        context.signal_tx = Some(tx);
+        let context = Arc::new(context);
+
+        let args_string: JsonString = RemoteSignalArgs {
+            agents: vec![context.agent_id.address()],
+            signal: test_args(),
+        }
+        .into();
+        let _ = test_zome_api_function_call(context.clone(), args_string.to_bytes());
+
+        match rx.try_recv() {
+            Ok(Signal::User(user_signal)) => assert_eq!(
+                user_signal,
+                UserSignal::remote(test_args(), context.agent_id.address())
+            ),
+            other => panic!("Expected a Signal::User, got {:?}", other),
+        }
+    }
 }
@@ -0,0 +1,35 @@
+use crate::{
+    wasm_engine::{api::ZomeApiResult, Runtime},
+    workflows::get_entries_result::get_entries_result_workflow,
+    NEW_RELIC_LICENSE_KEY,
+};
+use holochain_wasm_utils::api_serialization::get_entry::GetEntriesArgs;
+use std::convert::TryFrom;
+use wasmi::{RuntimeArgs, RuntimeValue};
+
+/// ZomeApiFunction::GetEntries function code
+/// args: [0] encoded MemoryAllocation as u64
+/// Expected complex argument: GetEntriesArgs
+/// Returns an HcApiReturnCode as I64
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn invoke_get_entries(runtime: &mut Runtime, args: &RuntimeArgs) -> ZomeApiResult {
+    let context = runtime.context()?;
+    // deserialize args
+    let args_str = runtime.load_json_string_from_args(&args);
+    let input = match GetEntriesArgs::try_from(args_str.clone()) {
+        Ok(input) => input,
+        // Exit on error
+        Err(_) => {
+            log_error!(
+                context,
+                "zome: invoke_get_entries() failed to deserialize: {:?}",
+                args_str
+            );
+            return ribosome_error_code!(ArgumentDeserializationFailed);
+        }
+    };
+    // Create workflow future and block on it
+    let result = context.block_on(get_entries_result_workflow(&context, &input));
+    // Store result in wasm memory
+    runtime.store_result(result)
+}
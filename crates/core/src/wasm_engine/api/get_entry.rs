@@ -253,6 +253,7 @@ pub mod tests {
             entry: entry.clone(),
             crud_status: CrudStatus::Live,
             maybe_link_update_delete: None,
+            links_update_delete: Vec::new(),
         };
         // let header = create_new_chain_header(&entry, context.clone(), &None);
         let entry_result =
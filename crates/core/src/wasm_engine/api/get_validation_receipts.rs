@@ -0,0 +1,66 @@
+use crate::{
+    wasm_engine::{api::ZomeApiResult, Runtime},
+    workflows::validation_receipt::get_validation_receipt_signers,
+    NEW_RELIC_LICENSE_KEY,
+};
+use holochain_wasm_utils::api_serialization::get_validation_receipts::{
+    GetValidationReceiptsArgs, ValidationReceiptsResult,
+};
+use std::convert::TryFrom;
+use wasmi::{RuntimeArgs, RuntimeValue};
+
+/// ZomeApiFunction::GetValidationReceipts function code
+/// args: [0] encoded MemoryAllocation as u64
+/// Expected complex argument: GetValidationReceiptsArgs
+/// Returns an HcApiReturnCode as I64
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn invoke_get_validation_receipts(runtime: &mut Runtime, args: &RuntimeArgs) -> ZomeApiResult {
+    let context = runtime.context()?;
+
+    // deserialize args
+    let args_str = runtime.load_json_string_from_args(&args);
+    let input = match GetValidationReceiptsArgs::try_from(args_str.clone()) {
+        Ok(input) => input,
+        // Exit on error
+        Err(_) => {
+            log_error!(
+                context,
+                "zome: invoke_get_validation_receipts failed to deserialize GetValidationReceiptsArgs: {:?}",
+                args_str
+            );
+            return ribosome_error_code!(ArgumentDeserializationFailed);
+        }
+    };
+
+    let result = get_validation_receipt_signers(&input.address, &context).map(|signers| {
+        ValidationReceiptsResult {
+            count: signers.len(),
+            signers,
+        }
+    });
+
+    runtime.store_result(result)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::wasm_engine::{
+        api::{tests::test_zome_api_function, ZomeApiFunction},
+        Defn,
+    };
+    use holochain_json_api::json::JsonString;
+
+    #[test]
+    fn test_get_validation_receipts_of_unconfirmed_address_is_empty() {
+        let (call_result, _) = test_zome_api_function(
+            ZomeApiFunction::GetValidationReceipts.as_str(),
+            r#"{ "address": "QmUnconfirmed" }"#.as_bytes().to_vec(),
+        );
+        assert_eq!(
+            JsonString::from_json(
+                r#"{"ok":true,"value":"{\"count\":0,\"signers\":[]}","error":"null"}"#
+            ),
+            call_result,
+        );
+    }
+}
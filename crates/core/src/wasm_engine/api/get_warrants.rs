@@ -0,0 +1,61 @@
+use crate::{
+    wasm_engine::{api::ZomeApiResult, Runtime},
+    NEW_RELIC_LICENSE_KEY,
+};
+use holochain_core_types::error::HolochainError;
+use holochain_wasm_utils::api_serialization::get_warrants::{GetWarrantsArgs, GetWarrantsResult};
+use std::convert::TryFrom;
+use wasmi::{RuntimeArgs, RuntimeValue};
+
+/// ZomeApiFunction::GetWarrants function code
+/// args: [0] encoded MemoryAllocation as u64
+/// Expected complex argument: GetWarrantsArgs
+/// Returns an HcApiReturnCode as I64
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn invoke_get_warrants(runtime: &mut Runtime, args: &RuntimeArgs) -> ZomeApiResult {
+    let context = runtime.context()?;
+
+    // deserialize args
+    let args_str = runtime.load_json_string_from_args(&args);
+    let input = match GetWarrantsArgs::try_from(args_str.clone()) {
+        Ok(input) => input,
+        // Exit on error
+        Err(_) => {
+            log_error!(
+                context,
+                "zome: invoke_get_warrants failed to deserialize GetWarrantsArgs: {:?}",
+                args_str
+            );
+            return ribosome_error_code!(ArgumentDeserializationFailed);
+        }
+    };
+
+    let result = context
+        .state()
+        .ok_or_else(|| HolochainError::ErrorGeneric("State not initialized".to_string()))
+        .and_then(|state| state.dht().get_warrants(&input.address))
+        .map(|warrants| GetWarrantsResult { warrants });
+
+    runtime.store_result(result)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::wasm_engine::{
+        api::{tests::test_zome_api_function, ZomeApiFunction},
+        Defn,
+    };
+    use holochain_json_api::json::JsonString;
+
+    #[test]
+    fn test_get_warrants_of_unwarranted_address_is_empty() {
+        let (call_result, _) = test_zome_api_function(
+            ZomeApiFunction::GetWarrants.as_str(),
+            r#"{ "address": "QmUnwarranted" }"#.as_bytes().to_vec(),
+        );
+        assert_eq!(
+            JsonString::from_json(r#"{"ok":true,"value":"{\"warrants\":[]}","error":"null"}"#),
+            call_result,
+        );
+    }
+}
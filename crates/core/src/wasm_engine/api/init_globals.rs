@@ -1,5 +1,5 @@
 use crate::{
-    wasm_engine::{api::ZomeApiResult, Runtime},
+    wasm_engine::{api::ZomeApiResult, host_profile::HostFunctionProfile, Runtime},
     NEW_RELIC_LICENSE_KEY,
 };
 use holochain_core_types::entry::entry_type::EntryType;
@@ -73,24 +73,51 @@ pub fn invoke_init_globals(runtime: &mut Runtime, _args: &RuntimeArgs) -> ZomeAp
         globals.public_token = token;
     }
 
+    // Validation callbacks have to come to the same result on every node,
+    // so they only get to see the globals derived from the DNA
+    if HostFunctionProfile::for_call(&runtime.data) == HostFunctionProfile::Validation {
+        globals = ZomeApiGlobals {
+            dna_name: globals.dna_name,
+            dna_address: globals.dna_address,
+            agent_id_str: String::new(),
+            agent_address: Address::from(""),
+            agent_initial_hash: HashString::from(""),
+            agent_latest_hash: HashString::from(""),
+            public_token: Address::from(""),
+            cap_request: None,
+            properties: globals.properties,
+        };
+    }
+
     // Store it in wasm memory
     runtime.store_result(Ok(globals))
 }
 
 #[cfg(test)]
 pub mod tests {
-    use crate::wasm_engine::{
-        api::{tests::test_zome_api_function, ZomeApiFunction},
-        Defn,
+    use self::wabt::Wat2Wasm;
+    use crate::{
+        instance::tests::test_instance_and_context,
+        nucleus::CallbackFnCall,
+        wasm_engine::{
+            self,
+            api::{
+                tests::{test_zome_api_function, test_zome_name},
+                ZomeApiFunction,
+            },
+            runtime::WasmCallData,
+            Defn,
+        },
     };
     use holochain_core_types::{
         dna::capabilities::CapabilityRequest, error::ZomeApiInternalResult, signature::Signature,
     };
     use holochain_json_api::json::JsonString;
-    use holochain_persistence_api::cas::content::Address;
+    use holochain_persistence_api::cas::content::{Address, AddressableContent};
     use holochain_wasm_utils::api_serialization::ZomeApiGlobals;
     use std::convert::TryFrom;
     use test_utils::mock_signing::registered_test_agent;
+    use wabt;
 
     #[test]
     /// test that the correct globals values are created for zome calls
@@ -132,4 +159,64 @@ pub mod tests {
                                     )),
         );
     }
+
+    /// WASM with a validation callback that returns the globals it gets
+    fn test_validation_globals_wasm() -> Vec<u8> {
+        Wat2Wasm::new()
+            .canonicalize_lebs(false)
+            .write_debug_names(true)
+            .convert(
+                r#"
+(module
+    (import "env" "hc_init_globals"
+        (func $hc_init_globals (param i64) (result i64)))
+    (memory 1)
+    (export "memory" (memory 0))
+    (func (export "__hdk_validate_app_entry") (param $allocation i64) (result i64)
+        (call $hc_init_globals (get_local $allocation))
+    )
+    (func (export "__hdk_validate_agent_entry") (param $allocation i64) (result i64)
+        (i64.const 0)
+    )
+)
+"#,
+            )
+            .unwrap()
+            .as_ref()
+            .to_vec()
+    }
+
+    #[test]
+    /// test that validation callbacks only get the globals derived from the DNA
+    fn test_init_globals_in_validation() {
+        let dna = test_utils::create_test_dna_with_wasm(
+            &test_zome_name(),
+            test_validation_globals_wasm(),
+        );
+        let (_instance, context) = test_instance_and_context(dna, None).unwrap();
+        let call = CallbackFnCall::new(
+            &test_zome_name(),
+            "__hdk_validate_app_entry",
+            JsonString::empty_object(),
+        );
+        let call_result = wasm_engine::run_dna(
+            Some(vec![]),
+            WasmCallData::new_callback_call(context.clone(), call),
+        )
+        .expect("validation callback should be callable");
+
+        let zome_api_internal_result = ZomeApiInternalResult::try_from(call_result).unwrap();
+        let globals =
+            ZomeApiGlobals::try_from(JsonString::from_json(&zome_api_internal_result.value))
+                .unwrap();
+
+        assert_eq!(globals.dna_name, "TestApp");
+        assert_eq!(globals.dna_address, context.get_dna().unwrap().address());
+        assert_eq!(globals.agent_id_str, "");
+        assert_eq!(globals.agent_address, Address::from(""));
+        assert_eq!(globals.agent_initial_hash, Address::from(""));
+        assert_eq!(globals.agent_latest_hash, Address::from(""));
+        assert_eq!(globals.public_token, Address::from(""));
+        assert_eq!(globals.cap_request, None);
+    }
 }
@@ -1,14 +1,20 @@
 //! Module for ZomeApiFunctions
 //! ZomeApiFunctions are the functions provided by the ribosome that are callable by Zomes.
 
+pub mod blob;
 pub mod call;
 pub mod commit;
+pub mod commit_countersigned;
 pub mod debug;
 pub mod emit_signal;
 pub mod entry_address;
+pub mod get_agent_activity;
+pub mod get_entries;
 pub mod get_entry;
 pub mod get_links;
 pub mod get_links_count;
+pub mod get_validation_receipts;
+pub mod get_warrants;
 pub mod init_globals;
 pub mod link_entries;
 #[macro_use]
@@ -25,19 +31,26 @@ pub mod sign;
 pub mod sleep;
 pub mod update_entry;
 pub mod verify_signature;
+pub mod zome_config;
 
 use crate::wasm_engine::{
     api::{
+        blob::{invoke_get_blob, invoke_store_blob},
         call::invoke_call,
         capabilities::{invoke_commit_capability_claim, invoke_commit_capability_grant},
         commit::invoke_commit_app_entry,
+        commit_countersigned::invoke_commit_countersigned_entry,
         crypto::invoke_crypto,
         debug::invoke_debug,
-        emit_signal::invoke_emit_signal,
+        emit_signal::{invoke_emit_signal, invoke_remote_signal},
         entry_address::invoke_entry_address,
+        get_agent_activity::invoke_get_agent_activity,
+        get_entries::invoke_get_entries,
         get_entry::invoke_get_entry,
         get_links::invoke_get_links,
         get_links_count::invoke_get_links_count,
+        get_validation_receipts::invoke_get_validation_receipts,
+        get_warrants::invoke_get_warrants,
         init_globals::invoke_init_globals,
         keystore::{
             invoke_keystore_derive_key, invoke_keystore_derive_seed,
@@ -47,13 +60,14 @@ use crate::wasm_engine::{
         link_entries::invoke_link_entries,
         meta::invoke_meta,
         query::invoke_query,
-        remove_entry::invoke_remove_entry,
+        remove_entry::{invoke_purge_entry, invoke_remove_entry},
         remove_link::invoke_remove_link,
         send::invoke_send,
         sign::invoke_sign_one_time,
         sleep::invoke_sleep,
         update_entry::invoke_update_entry,
         verify_signature::invoke_verify_signature,
+        zome_config::invoke_zome_config,
     },
     runtime::Runtime,
 };
@@ -78,12 +92,25 @@ link_zome_api! {
     /// commit_entry(entry_type: String, entry_value: String) -> Address
     "hc_commit_entry", CommitAppEntry, invoke_commit_app_entry;
 
+    /// Commit an app entry together with all countersigners it names
+    /// commit_countersigned_entry(entry: Entry, timeout: Timeout) -> Address
+    "hc_commit_countersigned_entry", CommitCountersignedEntry, invoke_commit_countersigned_entry;
+
     /// Get an app entry from source chain by key (header hash)
     /// get_entry(address: Address) -> Entry
     "hc_get_entry", GetAppEntry, invoke_get_entry;
+
+    /// Get several entries in as few network round trips as possible
+    /// get_entries(addresses: Vec<Address>) -> Vec<Result<GetEntryResult>>
+    "hc_get_entries", GetEntries, invoke_get_entries;
+
     "hc_update_entry", UpdateEntry, invoke_update_entry;
     "hc_remove_entry", RemoveEntry, invoke_remove_entry;
 
+    /// Remove an entry and ask its holders to drop its content
+    /// purge_entry(address: Address) -> Address
+    "hc_purge_entry", PurgeEntry, invoke_purge_entry;
+
     /// Init Zome API Globals
     /// hc_init_globals() -> InitGlobalsOutput
     "hc_init_globals", InitGlobals, invoke_init_globals;
@@ -101,6 +128,24 @@ link_zome_api! {
     //Retrieve link count from DHT
     "hc_get_links_count", GetLinksCount, invoke_get_links_count;
 
+    /// Retrieve the warrants we hold about an entry or agent
+    "hc_get_warrants", GetWarrants, invoke_get_warrants;
+
+    /// Retrieve the validators that confirmed holding one of our entries
+    "hc_get_validation_receipts", GetValidationReceipts, invoke_get_validation_receipts;
+
+    /// Retrieve the published headers of an agent from its authorities
+    /// get_agent_activity(agent: Address, filter: AgentActivityFilter) -> AgentActivity
+    "hc_get_agent_activity", GetAgentActivity, invoke_get_agent_activity;
+
+    /// Commit a chunk or the manifest of a blob
+    /// store_blob(args: StoreBlobArgs) -> Address
+    "hc_store_blob", StoreBlob, invoke_store_blob;
+
+    /// Retrieve the manifest and a window of chunks of a blob
+    /// get_blob(args: GetBlobArgs) -> GetBlobResult
+    "hc_get_blob", GetBlob, invoke_get_blob;
+
     /// Query the local chain for entries
     "hc_query", Query, invoke_query;
 
@@ -152,8 +197,14 @@ link_zome_api! {
     /// Send a DNA defined signal to UIs and other listeners
     "hc_emit_signal", EmitSignal, invoke_emit_signal;
 
+    /// Send a DNA defined signal to the UIs of other agents
+    "hc_remote_signal", RemoteSignal, invoke_remote_signal;
+
     ///send a meta
     "hc_meta",Meta,invoke_meta;
+
+    /// Get the config of the calling zome, possibly overridden by the instance
+    "hc_zome_config", ZomeConfig, invoke_zome_config;
 }
 
 #[cfg(test)]
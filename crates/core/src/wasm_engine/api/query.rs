@@ -10,9 +10,11 @@ use holochain_persistence_api::cas::content::Address;
 use holochain_core_types::{
     chain_header::ChainHeader,
     entry::{entry_type::EntryType, Entry},
-    error::HolochainError,
+    error::{HolochainError, RibosomeErrorCode},
+};
+use holochain_wasm_utils::api_serialization::{
+    QueryArgs, QueryArgsNames, QueryCursor, QueryResult, QueryResultPage,
 };
-use holochain_wasm_utils::api_serialization::{QueryArgs, QueryArgsNames, QueryResult};
 use std::{convert::TryFrom, sync::Arc};
 use wasmi::{RuntimeArgs, RuntimeValue};
 
@@ -74,33 +76,38 @@ pub fn invoke_query(runtime: &mut Runtime, args: &RuntimeArgs) -> ZomeApiResult
         .expect("Couldn't get state in invoke_query")
         .agent();
     let top = agent.top_chain_header().expect("Should have init entries.");
-    let maybe_result = match query.entry_type_names {
-        // Result<ChainStoreQueryResult,...>
+    let options = ChainStoreQueryOptions {
+        start: query.options.start,
+        limit: query.options.limit,
+        headers: query.options.headers,
+        reverse: query.options.reverse,
+        since: query.options.since,
+        until: query.options.until,
+        cursor: query.options.cursor.map(Address::from),
+    };
+    let maybe_page = match query.entry_type_names {
+        // Result<ChainStoreQueryPage,...>
         QueryArgsNames::QueryList(pats) => {
             let refs: Vec<&str> = pats.iter().map(AsRef::as_ref).collect(); // Vec<String> -> Vec<&str>
-            agent.chain_store().query(
+            agent.chain_store().query_page(
                 &Some(top),
                 refs.as_slice(), // Vec<&str> -> Vec[&str]
-                ChainStoreQueryOptions {
-                    start: query.options.start,
-                    limit: query.options.limit,
-                    headers: query.options.headers,
-                },
+                options,
             )
         }
         QueryArgsNames::QueryName(name) => {
             let refs: Vec<&str> = vec![&name]; // String -> Vec<&str>
-            agent.chain_store().query(
+            agent.chain_store().query_page(
                 &Some(top),
                 refs.as_slice(), // Vec<&str> -> &[&str]
-                ChainStoreQueryOptions {
-                    start: query.options.start,
-                    limit: query.options.limit,
-                    headers: query.options.headers,
-                },
+                options,
             )
         }
     };
+    let (maybe_result, next_cursor) = match maybe_page {
+        Ok(page) => (Ok(page.result), page.next_cursor.map(QueryCursor::from)),
+        Err(code) => (Err(code), None),
+    };
     let result = match maybe_result {
         // TODO #793: the Err(_code) is the RibosomeErrorCode, but we can't import that type here.
         // Perhaps return chain_store().query should return Some(result)/None instead, and the fixed
@@ -145,10 +152,16 @@ pub fn invoke_query(runtime: &mut Runtime, args: &RuntimeArgs) -> ZomeApiResult
                 }
             }
         }),
+        Err(RibosomeErrorCode::UnknownQueryCursor) => {
+            return ribosome_error_code!(UnknownQueryCursor)
+        }
         Err(_code) => return ribosome_error_code!(UnknownEntryType),
     };
 
-    runtime.store_result(result)
+    runtime.store_result(result.map(|result| QueryResultPage {
+        result,
+        next_cursor,
+    }))
 }
 
 /// Get an local-chain Entry via the provided context, returning Entry or HolochainError on failure
@@ -1,10 +1,13 @@
 use crate::{
+    action::{Action, ActionWrapper},
+    entry::CanPublish,
+    instance::dispatch_action,
     wasm_engine::{api::ZomeApiResult, Runtime},
     workflows::{author_entry::author_entry, get_entry_result::get_entry_result_workflow},
     NEW_RELIC_LICENSE_KEY,
 };
 use holochain_core_types::{
-    entry::{deletion_entry::DeletionEntry, Entry},
+    entry::{deletion_entry::DeletionEntry, purged_entry::PurgedEntry, Entry},
     error::HolochainError,
 };
 
@@ -20,6 +23,22 @@ use wasmi::{RuntimeArgs, RuntimeValue};
 /// Stores/returns a RibosomeEncodedValue
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
 pub fn invoke_remove_entry(runtime: &mut Runtime, args: &RuntimeArgs) -> ZomeApiResult {
+    remove_entry(runtime, args, false)
+}
+
+/// ZomeApiFunction::PurgeEntry function code
+/// args: [0] encoded MemoryAllocation
+/// Expected Address argument
+/// Stores/returns a RibosomeEncodedValue
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn invoke_purge_entry(runtime: &mut Runtime, args: &RuntimeArgs) -> ZomeApiResult {
+    remove_entry(runtime, args, true)
+}
+
+/// Commits a deletion of the latest version of the entry at the given address.
+/// A purging deletion makes the holders of a public entry drop its content. Content of
+/// private entries only lives in our own storages, so we replace it with a tombstone here.
+fn remove_entry(runtime: &mut Runtime, args: &RuntimeArgs, purge: bool) -> ZomeApiResult {
     let context = runtime.context()?;
 
     // deserialize args
@@ -55,19 +74,34 @@ pub fn invoke_remove_entry(runtime: &mut Runtime, args: &RuntimeArgs) -> ZomeApi
     if !entry_result.found() {
         return ribosome_error_code!(EntryNotFound);
     }
-    let deleted_entry_address = entry_result.latest().unwrap().address();
+    let deleted_entry = entry_result.latest().unwrap();
+    let deleted_entry_address = deleted_entry.address();
 
     // Create deletion entry
-    let deletion_entry = Entry::Deletion(DeletionEntry::new(deleted_entry_address.clone()));
+    let deletion_entry = Entry::Deletion(if purge {
+        DeletionEntry::new_purge(deleted_entry_address.clone())
+    } else {
+        DeletionEntry::new(deleted_entry_address.clone())
+    });
 
     let res: Result<Address, HolochainError> = context
         .block_on(author_entry(
             &deletion_entry.clone(),
-            Some(deleted_entry_address),
+            Some(deleted_entry_address.clone()),
             &context.clone(),
             &vec![],
         ))
         .map(|_| deletion_entry.address());
 
+    if res.is_ok() && purge && !deleted_entry.entry_type().can_publish(&context) {
+        dispatch_action(
+            context.action_channel(),
+            ActionWrapper::new(Action::PurgeEntry(PurgedEntry::new(
+                deleted_entry_address,
+                deletion_entry.address(),
+            ))),
+        );
+    }
+
     runtime.store_result(res)
 }
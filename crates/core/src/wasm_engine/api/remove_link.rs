@@ -83,7 +83,7 @@ pub fn invoke_remove_link(runtime: &mut Runtime, args: &RuntimeArgs) -> ZomeApiR
         let response = response_result.expect("Could not get response");
         let links_result = match response {
             NetworkQueryResult::Links(query, _, _) => Ok(query),
-            NetworkQueryResult::Entry(_) => Err(HolochainError::ErrorGeneric(
+            _ => Err(HolochainError::ErrorGeneric(
                 "Could not get links for type".to_string(),
             )),
         };
@@ -93,7 +93,7 @@ pub fn invoke_remove_link(runtime: &mut Runtime, args: &RuntimeArgs) -> ZomeApiR
         } else {
             let links = links_result.expect("This is supposed to not fail");
             let links = match links {
-                GetLinksNetworkResult::Links(links) => links,
+                GetLinksNetworkResult::Links(links, _) => links,
                 _ => return ribosome_error_code!(WorkflowFailed),
             };
             let filtered_links = links
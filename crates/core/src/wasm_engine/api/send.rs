@@ -4,7 +4,7 @@ use crate::{
     NEW_RELIC_LICENSE_KEY,
 };
 use holochain_json_api::json::JsonString;
-use holochain_wasm_utils::api_serialization::send::SendArgs;
+use holochain_wasm_utils::api_serialization::send::{SendArgs, SendResult};
 use std::convert::TryFrom;
 use wasmi::{RuntimeArgs, RuntimeValue};
 
@@ -33,15 +33,20 @@ pub fn invoke_send(runtime: &mut Runtime, args: &RuntimeArgs) -> ZomeApiResult {
         zome: call_data.zome_name.clone(),
     };
 
+    // plain sends keep returning just the response
+    let deliver_later = args.options.deliver_later;
     let result = call_data
         .context
         .block_on(custom_send(
             args.to_agent,
             message,
-            args.options.0,
+            args.options,
             call_data.context.clone(),
         ))
-        .map(|s| JsonString::from_json(&s));
+        .map(|send_result| match send_result {
+            SendResult::Response(s) if !deliver_later => JsonString::from_json(&s),
+            send_result => JsonString::from(send_result),
+        });
 
     runtime.store_result(result)
 }
@@ -0,0 +1,55 @@
+use crate::{
+    wasm_engine::{api::ZomeApiResult, Runtime},
+    NEW_RELIC_LICENSE_KEY,
+};
+use holochain_json_api::json::JsonString;
+use wasmi::RuntimeArgs;
+
+/// ZomeApiFunction::ZomeConfig function code
+/// args: [0] encoded MemoryAllocation as u64
+/// Not expecting any complex input
+/// Returns an HcApiReturnCode as I64
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn invoke_zome_config(runtime: &mut Runtime, _args: &RuntimeArgs) -> ZomeApiResult {
+    let context = runtime.context()?;
+    let zome_name = runtime.call_data()?.zome_name;
+    let result = context.get_zome_config(&zome_name).map(JsonString::from);
+    runtime.store_result(result)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::{
+        instance::tests::test_context,
+        wasm_engine::{
+            api::{tests::test_zome_api_function, ZomeApiFunction},
+            Defn,
+        },
+    };
+    use holochain_json_api::json::JsonString;
+    use serde_json::json;
+
+    #[test]
+    fn test_zome_config_defaults_to_empty_object() {
+        let (call_result, _) = test_zome_api_function(
+            ZomeApiFunction::ZomeConfig.as_str(),
+            r#"{}"#.as_bytes().to_vec(),
+        );
+        assert_eq!(
+            JsonString::from_json(r#"{"ok":true,"value":"{}","error":"null"}"#),
+            call_result,
+        );
+    }
+
+    #[test]
+    fn test_zome_config_overrides_replace_dna_config() {
+        let mut context = (*test_context("alice", None)).clone();
+        context
+            .zome_config_overrides
+            .insert("test_zome".to_string(), json!({ "max_length": 140 }));
+        assert_eq!(
+            context.get_zome_config("test_zome"),
+            Ok(json!({ "max_length": 140 }))
+        );
+    }
+}
@@ -0,0 +1,188 @@
+use crate::{
+    context::Context,
+    nucleus::CallbackFnCall,
+    wasm_engine::{self, runtime::WasmCallData},
+    NEW_RELIC_LICENSE_KEY,
+};
+use holochain_core_types::{
+    entry::{entry_type::AppEntryType, Entry},
+    error::HolochainError,
+};
+use holochain_persistence_api::cas::content::{Address, AddressableContent};
+use holochain_wasm_utils::api_serialization::get_entry::ConflictResolutionArgs;
+use std::sync::Arc;
+
+/// Calls the conflict resolution callback of the zome that defines the given entry type
+/// with the entries at the heads of a forked update graph and returns the address of
+/// the head it picked as the latest entry.
+/// The heads have to be sorted by address so every node passes in the same input.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn resolve_conflict(
+    app_entry_type: &AppEntryType,
+    heads: Vec<Entry>,
+    context: Arc<Context>,
+) -> Result<Address, HolochainError> {
+    let dna = context.get_dna().expect("Callback called without DNA set!");
+    let zome_name = dna
+        .get_zome_name_for_app_entry_type(app_entry_type)
+        .ok_or_else(|| {
+            HolochainError::ErrorGeneric(format!(
+                "Unknown app entry type '{}'",
+                String::from(app_entry_type.clone()),
+            ))
+        })?;
+
+    let head_addresses: Vec<Address> = heads.iter().map(|entry| entry.address()).collect();
+    let params = ConflictResolutionArgs {
+        entry_type: String::from(app_entry_type.clone()),
+        heads,
+    };
+    let call = CallbackFnCall::new(&zome_name, "__hdk_resolve_conflict", params);
+    let result = wasm_engine::run_dna(
+        Some(call.parameters.to_bytes()),
+        WasmCallData::new_callback_call(context, call),
+    )?;
+
+    // The HDK returns the callback's Result serialized as JSON
+    match serde_json::from_str::<Result<Address, String>>(&result.to_string()) {
+        Ok(Ok(address)) if head_addresses.contains(&address) => Ok(address),
+        Ok(Ok(address)) => Err(HolochainError::ErrorGeneric(format!(
+            "Conflict resolution callback picked {} which is not one of the heads {:?}",
+            address, head_addresses
+        ))),
+        Ok(Err(error)) => Err(HolochainError::ErrorGeneric(format!(
+            "Conflict resolution callback failed: {}",
+            error
+        ))),
+        Err(_) => Err(HolochainError::SerializationError(String::from(
+            "__hdk_resolve_conflict returned an invalid result",
+        ))),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use self::wabt::Wat2Wasm;
+    use super::resolve_conflict;
+    use crate::{
+        context::Context,
+        instance::{tests::test_instance_and_context, Instance},
+    };
+    use holochain_core_types::{
+        entry::{
+            entry_type::{test_app_entry_type, AppEntryType},
+            test_entry, test_entry_with_value, Entry,
+        },
+        error::HolochainError,
+    };
+    use holochain_persistence_api::cas::content::AddressableContent;
+    use std::sync::Arc;
+    use wabt;
+
+    /// Offset of the callback result in the memory of the test WASM,
+    /// far enough from the input the ribosome writes at the start of the memory.
+    const RESULT_OFFSET: u64 = 32768;
+
+    /// WASM with a conflict resolution callback that returns the given JSON
+    pub fn test_conflict_resolution_wasm(result: &str) -> Vec<u8> {
+        Wat2Wasm::new()
+            .canonicalize_lebs(false)
+            .write_debug_names(true)
+            .convert(format!(
+                r#"
+(module
+    (memory 1)
+    (export "memory" (memory 0))
+    (data (i32.const {}) "{}")
+    (func (export "__hdk_validate_agent_entry") (param $allocation i64) (result i64)
+        (i64.const 0)
+    )
+    (func (export "__hdk_resolve_conflict") (param $allocation i64) (result i64)
+        (i64.const {})
+    )
+)
+"#,
+                RESULT_OFFSET,
+                result.replace('"', "\\\""),
+                (RESULT_OFFSET << 32) | result.len() as u64,
+            ))
+            .unwrap()
+            .as_ref()
+            .to_vec()
+    }
+
+    /// Instance with a DNA whose zome resolves conflicts of the entry type of `test_entry`
+    /// by returning the given JSON
+    pub fn test_conflict_resolution_instance(result: &str) -> (Instance, Arc<Context>) {
+        let mut dna = test_utils::create_test_dna_with_wasm(
+            "test_zome",
+            test_conflict_resolution_wasm(result),
+        );
+        dna.zomes
+            .get_mut("test_zome")
+            .unwrap()
+            .entry_types
+            .get_mut(&test_entry().entry_type())
+            .unwrap()
+            .resolves_conflicts = true;
+        test_instance_and_context(dna, None).unwrap()
+    }
+
+    /// Heads of a forked update graph, sorted by address
+    pub fn test_heads() -> Vec<Entry> {
+        let mut heads = vec![
+            test_entry_with_value("\"head a\""),
+            test_entry_with_value("\"head b\""),
+        ];
+        heads.sort_by_key(|entry| entry.address());
+        heads
+    }
+
+    #[test]
+    fn callback_picks_one_of_the_heads() {
+        let heads = test_heads();
+        let picked = heads[1].address();
+        let (_instance, context) =
+            test_conflict_resolution_instance(&format!("{{\"Ok\":\"{}\"}}", picked));
+
+        assert_eq!(
+            resolve_conflict(&test_app_entry_type(), heads, context),
+            Ok(picked)
+        );
+    }
+
+    #[test]
+    fn callback_has_to_pick_one_of_the_heads() {
+        let (_instance, context) = test_conflict_resolution_instance(&format!(
+            "{{\"Ok\":\"{}\"}}",
+            test_entry().address()
+        ));
+
+        match resolve_conflict(&test_app_entry_type(), test_heads(), context) {
+            Err(HolochainError::ErrorGeneric(message)) => assert!(message.starts_with(&format!(
+                "Conflict resolution callback picked {} which is not one of the heads",
+                test_entry().address()
+            ))),
+            other => panic!("unexpected result: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn callback_errors_get_passed_on() {
+        let (_instance, context) =
+            test_conflict_resolution_instance("{\"Err\":\"can not decide\"}");
+
+        assert_eq!(
+            resolve_conflict(&test_app_entry_type(), test_heads(), context.clone()),
+            Err(HolochainError::ErrorGeneric(String::from(
+                "Conflict resolution callback failed: can not decide"
+            )))
+        );
+        assert_eq!(
+            resolve_conflict(&AppEntryType::from("unknownType"), test_heads(), context),
+            Err(HolochainError::ErrorGeneric(String::from(
+                "Unknown app entry type 'unknownType'"
+            )))
+        );
+    }
+}
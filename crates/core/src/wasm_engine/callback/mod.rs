@@ -1,6 +1,7 @@
 //! Module for ZomeCallbacks
 //! ZomeCallbacks are functions in a Zome that are callable by the ribosome.
 
+pub mod conflict_resolution;
 pub mod init;
 pub mod links_utils;
 pub mod receive;
@@ -9,6 +9,7 @@ use crate::{
     NEW_RELIC_LICENSE_KEY,
 };
 use holochain_core_types::{
+    chain_header::ChainHeader,
     entry::{entry_type::EntryType, Entry},
     error::HolochainError,
     validation::ValidationPackageDefinition,
@@ -16,7 +17,9 @@ use holochain_core_types::{
 
 use holochain_json_api::json::JsonString;
 
-use holochain_wasm_utils::api_serialization::validation::LinkValidationPackageArgs;
+use holochain_wasm_utils::api_serialization::validation::{
+    CustomValidationPackageArgs, LinkValidationPackageArgs,
+};
 use std::{convert::TryFrom, sync::Arc};
 
 #[autotrace]
@@ -113,6 +116,9 @@ pub fn get_validation_package_definition(
         EntryType::CapTokenGrant => JsonString::from(ValidationPackageDefinition::Entry),
         EntryType::AgentId => JsonString::from(ValidationPackageDefinition::Entry),
         EntryType::ChainHeader => JsonString::from(ValidationPackageDefinition::Entry),
+        EntryType::MembraneProof => JsonString::from(ValidationPackageDefinition::Entry),
+        EntryType::BlobChunk => JsonString::from(ValidationPackageDefinition::Entry),
+        EntryType::BlobManifest => JsonString::from(ValidationPackageDefinition::Entry),
         _ => Err(HolochainError::NotImplemented(
             "get_validation_package_definition/3".into(),
         ))?,
@@ -131,3 +137,54 @@ pub fn get_validation_package_definition(
         }
     }
 }
+
+/// Calls the custom validation package callback of the zome that defines the type of the
+/// given entry and returns the package data it built for the given header.
+/// Only app entries can have custom validation package callbacks.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn build_custom_validation_package_data(
+    entry: &Entry,
+    chain_header: &ChainHeader,
+    context: Arc<Context>,
+) -> Result<String, HolochainError> {
+    let app_entry_type = match entry.entry_type() {
+        EntryType::App(app_entry_type) => app_entry_type,
+        other => {
+            return Err(HolochainError::NotImplemented(format!(
+                "Custom validation packages are only supported for app entries, not {:?}",
+                other
+            )))
+        }
+    };
+    let dna = context.get_dna().expect("Callback called without DNA set!");
+    let zome_name = dna
+        .get_zome_name_for_app_entry_type(&app_entry_type)
+        .ok_or_else(|| {
+            HolochainError::ValidationFailed(format!(
+                "Unknown app entry type '{}'",
+                String::from(app_entry_type.clone()),
+            ))
+        })?;
+
+    let params = CustomValidationPackageArgs {
+        entry: entry.clone(),
+        chain_header: chain_header.clone(),
+    };
+    let call = CallbackFnCall::new(&zome_name, "__hdk_build_custom_validation_package", params);
+    let result = wasm_engine::run_dna(
+        Some(call.parameters.to_bytes()),
+        WasmCallData::new_callback_call(context, call),
+    )?;
+
+    // The HDK returns the callback's Result serialized as JSON
+    match serde_json::from_str::<Result<serde_json::Value, String>>(&result.to_string()) {
+        Ok(Ok(data)) => Ok(data.to_string()),
+        Ok(Err(error)) => Err(HolochainError::ErrorGeneric(format!(
+            "Custom validation package callback failed: {}",
+            error
+        ))),
+        Err(_) => Err(HolochainError::SerializationError(String::from(
+            "__hdk_build_custom_validation_package returned an invalid result",
+        ))),
+    }
+}
@@ -1,4 +1,10 @@
-use crate::{wasm_engine::api::ZomeApiFunction, NEW_RELIC_LICENSE_KEY};
+use crate::{
+    wasm_engine::{
+        api::ZomeApiFunction,
+        host_profile::{HostFunctionProfile, FORBIDDEN_HOST_FUNCTION_INDEX_OFFSET},
+    },
+    NEW_RELIC_LICENSE_KEY,
+};
 use holochain_core_types::error::HolochainError;
 use std::{str::FromStr, sync::Arc};
 use wasmi::{
@@ -14,15 +20,22 @@ pub fn wasm_module_factory(wasm: Arc<Vec<u8>>) -> Result<Module, HolochainError>
 
 /// Creates a runnable WASM module instance from a module reference.
 /// Adds the Holochain specific API functions as imports.
+/// Functions the given profile does not permit still get imported (every zome imports all
+/// functions the HDK uses) but trap when they get called.
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
-pub fn wasm_instance_factory(module: &Module) -> Result<ModuleRef, HolochainError> {
+pub fn wasm_instance_factory(
+    module: &Module,
+    profile: HostFunctionProfile,
+) -> Result<ModuleRef, HolochainError> {
     // invoke_index and resolve_func work together to enable callable host functions
     // within WASM modules, which is how the core API functions
     // read about the Externals trait for more detail
 
     // Correlate the names of the core ZomeApiFunction's with their indexes
     // and declare its function signature (which is always the same)
-    struct RuntimeModuleImportResolver;
+    struct RuntimeModuleImportResolver {
+        profile: HostFunctionProfile,
+    }
     impl ModuleImportResolver for RuntimeModuleImportResolver {
         fn resolve_func(
             &self,
@@ -39,6 +52,12 @@ pub fn wasm_instance_factory(module: &Module) -> Result<ModuleRef, HolochainErro
                 }
             };
 
+            let index = if self.profile.permits(&api_fn) {
+                api_fn.clone() as usize
+            } else {
+                FORBIDDEN_HOST_FUNCTION_INDEX_OFFSET + api_fn.clone() as usize
+            };
+
             match api_fn {
                 // Abort is a way to receive useful debug info from
                 // assemblyscript memory allocators, see enum definition for function signature
@@ -52,20 +71,21 @@ pub fn wasm_instance_factory(module: &Module) -> Result<ModuleRef, HolochainErro
                         ][..],
                         None,
                     ),
-                    api_fn as usize,
+                    index,
                 )),
                 // All of our Zome API Functions have the same signature
                 _ => Ok(FuncInstance::alloc_host(
                     Signature::new(&[ValueType::I64][..], Some(ValueType::I64)),
-                    api_fn as usize,
+                    index,
                 )),
             }
         }
     }
 
     // Create Imports with previously described Resolver
+    let resolver = RuntimeModuleImportResolver { profile };
     let mut imports = ImportsBuilder::new();
-    imports.push_resolver("env", &RuntimeModuleImportResolver);
+    imports.push_resolver("env", &resolver);
 
     // Create module instance from wasm module, and start it if start is defined
     ModuleInstance::new(&module, &imports)
@@ -0,0 +1,240 @@
+//! Host function profiles restrict which Zome API functions a WASM instance gets to call.
+//!
+//! Validation callbacks have to come to the same result on every node that runs them.
+//! If they could read the network, the local source chain, keys or the clock, validators
+//! would disagree about the validity of the same data. The same holds for conflict resolution
+//! callbacks, which pick the latest of concurrent updates of an entry. So instances created for
+//! these callbacks get the restricted `Validation` profile that only permits these functions:
+//!
+//! * `hc_debug` - logging does not influence the result
+//! * `hc_init_globals` - needed by the HDK to set up `DNA_ADDRESS`, `PROPERTIES` etc.
+//!   Validation callbacks only get the globals derived from the DNA, the agent globals
+//!   and the public token are left empty
+//! * `hc_entry_address` - hashing is deterministic
+//! * `hc_verify_signature` - checking signatures given in the validation package
+//! * `hc_meta` - HDK version and hash
+//! * `abort` - reporting of allocator errors
+//!
+//! All other functions (like `hc_get_entry`, `hc_send`, `hc_sleep`, `hc_emit_signal`,
+//! `hc_commit_entry`, the keystore functions or `hc_zome_config`, whose result can differ
+//! between instances) trap with a `ForbiddenHostCallError`
+//! as soon as they get called, which makes the validation return an error.
+
+use crate::wasm_engine::{api::ZomeApiFunction, runtime::WasmCallData, Defn};
+use std::fmt;
+use wasmi::HostError;
+
+/// Host functions that get forbidden by the profile of an instance are resolved to their
+/// index plus this offset so the runtime can tell them apart and trap.
+pub const FORBIDDEN_HOST_FUNCTION_INDEX_OFFSET: usize = 1 << 16;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HostFunctionProfile {
+    /// All Zome API functions can be called. Used for zome function calls and for
+    /// callbacks other than validation.
+    Unrestricted,
+    /// Only deterministic functions can be called, see module documentation.
+    Validation,
+}
+
+impl HostFunctionProfile {
+    /// Returns the profile an instance for the given call has to be created with.
+    pub fn for_call(data: &WasmCallData) -> Self {
+        match data {
+            WasmCallData::CallbackCall(callback_data)
+                if is_validation_callback(&callback_data.call.fn_name) =>
+            {
+                HostFunctionProfile::Validation
+            }
+            _ => HostFunctionProfile::Unrestricted,
+        }
+    }
+
+    /// Returns true if instances with this profile may call the given function.
+    pub fn permits(self, api_fn: &ZomeApiFunction) -> bool {
+        match self {
+            HostFunctionProfile::Unrestricted => true,
+            HostFunctionProfile::Validation => match api_fn {
+                ZomeApiFunction::Abort
+                | ZomeApiFunction::Debug
+                | ZomeApiFunction::InitGlobals
+                | ZomeApiFunction::EntryAddress
+                | ZomeApiFunction::VerifySignature
+                | ZomeApiFunction::Meta => true,
+                _ => false,
+            },
+        }
+    }
+}
+
+/// Validation callbacks, the callbacks that define validation packages and the
+/// conflict resolution callback as exported by the HDK.
+fn is_validation_callback(fn_name: &str) -> bool {
+    fn_name.starts_with("__hdk_validate_")
+        || fn_name.starts_with("__hdk_get_validation_package")
+        || fn_name == "__hdk_resolve_conflict"
+}
+
+/// Trap raised when an instance calls a host function its profile does not permit.
+#[derive(Debug)]
+pub struct ForbiddenHostCallError {
+    pub function: ZomeApiFunction,
+    pub profile: HostFunctionProfile,
+}
+
+impl fmt::Display for ForbiddenHostCallError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.profile {
+            HostFunctionProfile::Validation => write!(
+                f,
+                "{} can not be called from validation callbacks since its result is not deterministic",
+                self.function.as_str()
+            ),
+            HostFunctionProfile::Unrestricted => {
+                write!(f, "{} can not be called", self.function.as_str())
+            }
+        }
+    }
+}
+
+impl HostError for ForbiddenHostCallError {}
+
+#[cfg(test)]
+pub mod tests {
+    use self::wabt::Wat2Wasm;
+    use super::*;
+    use crate::{
+        instance::tests::test_context,
+        nucleus::{tests::test_capability_request, CallbackFnCall, ZomeFnCall},
+        wasm_engine::{
+            factories::{wasm_instance_factory, wasm_module_factory},
+            memory::WasmPageManager,
+            runtime::Runtime,
+        },
+    };
+    use holochain_json_api::json::JsonString;
+    use std::sync::Arc;
+    use wabt;
+    use wasmi::RuntimeValue;
+
+    #[test]
+    fn validation_callbacks_get_the_validation_profile() {
+        let context = test_context("alice", None);
+        for fn_name in &[
+            "__hdk_validate_app_entry",
+            "__hdk_validate_agent_entry",
+            "__hdk_validate_link",
+            "__hdk_get_validation_package_for_entry_type",
+            "__hdk_get_validation_package_for_link",
+            "__hdk_resolve_conflict",
+        ] {
+            let call = CallbackFnCall::new("zome", fn_name, JsonString::empty_object());
+            assert_eq!(
+                HostFunctionProfile::for_call(&WasmCallData::new_callback_call(
+                    context.clone(),
+                    call
+                )),
+                HostFunctionProfile::Validation
+            );
+        }
+
+        let init = CallbackFnCall::new("zome", "init", JsonString::empty_object());
+        assert_eq!(
+            HostFunctionProfile::for_call(&WasmCallData::new_callback_call(context.clone(), init)),
+            HostFunctionProfile::Unrestricted
+        );
+        let zome_call = ZomeFnCall::new(
+            "zome",
+            test_capability_request(context.clone(), "__hdk_validate_app_entry", "{}"),
+            "__hdk_validate_app_entry",
+            "{}",
+        );
+        assert_eq!(
+            HostFunctionProfile::for_call(&WasmCallData::new_zome_call(context, zome_call)),
+            HostFunctionProfile::Unrestricted
+        );
+    }
+
+    #[test]
+    fn validation_profile_only_permits_deterministic_functions() {
+        let profile = HostFunctionProfile::Validation;
+        assert!(profile.permits(&ZomeApiFunction::Debug));
+        assert!(profile.permits(&ZomeApiFunction::EntryAddress));
+        assert!(profile.permits(&ZomeApiFunction::VerifySignature));
+        assert!(!profile.permits(&ZomeApiFunction::GetAppEntry));
+        assert!(!profile.permits(&ZomeApiFunction::Send));
+        assert!(!profile.permits(&ZomeApiFunction::Sleep));
+        assert!(!profile.permits(&ZomeApiFunction::EmitSignal));
+        assert!(!profile.permits(&ZomeApiFunction::KeystoreSign));
+        assert!(!profile.permits(&ZomeApiFunction::CommitAppEntry));
+        assert!(!profile.permits(&ZomeApiFunction::ZomeConfig));
+        assert!(HostFunctionProfile::Unrestricted.permits(&ZomeApiFunction::Send));
+    }
+
+    #[test]
+    fn forbidden_calls_explain_themselves() {
+        let error = ForbiddenHostCallError {
+            function: ZomeApiFunction::Send,
+            profile: HostFunctionProfile::Validation,
+        };
+        assert_eq!(
+            error.to_string(),
+            "hc_send can not be called from validation callbacks since its result is not deterministic"
+        );
+    }
+
+    /// WASM with a validation callback that calls hc_send
+    fn test_sending_validation_wasm() -> Vec<u8> {
+        Wat2Wasm::new()
+            .canonicalize_lebs(false)
+            .write_debug_names(true)
+            .convert(
+                r#"
+(module
+    (import "env" "hc_send"
+        (func $hc_send (param i64) (result i64)))
+    (memory 1)
+    (export "memory" (memory 0))
+    (func (export "__hdk_validate_app_entry") (param $allocation i64) (result i64)
+        (call $hc_send (get_local $allocation))
+    )
+)
+"#,
+            )
+            .unwrap()
+            .as_ref()
+            .to_vec()
+    }
+
+    #[test]
+    fn validation_callbacks_trap_on_forbidden_host_calls() {
+        let context = test_context("alice", None);
+        let module = wasm_module_factory(Arc::new(test_sending_validation_wasm())).unwrap();
+        let call = CallbackFnCall::new(
+            "zome",
+            "__hdk_validate_app_entry",
+            JsonString::empty_object(),
+        );
+        let data = WasmCallData::new_callback_call(context, call);
+        let instance =
+            wasm_instance_factory(&module, HostFunctionProfile::for_call(&data)).unwrap();
+        let mut runtime = Runtime {
+            memory_manager: WasmPageManager::new(&instance),
+            data,
+        };
+
+        let error = instance
+            .invoke_export(
+                "__hdk_validate_app_entry",
+                &[RuntimeValue::I64(0)],
+                &mut runtime,
+            )
+            .expect_err("calling hc_send from a validation callback should trap");
+        let forbidden = error
+            .as_host_error()
+            .and_then(|e| e.downcast_ref::<ForbiddenHostCallError>())
+            .expect("trap should be a ForbiddenHostCallError");
+        assert_eq!(forbidden.function, ZomeApiFunction::Send);
+        assert_eq!(forbidden.profile, HostFunctionProfile::Validation);
+    }
+}
@@ -3,6 +3,7 @@
 pub mod api;
 pub mod callback;
 pub mod factories;
+pub mod host_profile;
 pub mod memory;
 #[autotrace]
 mod run_dna;
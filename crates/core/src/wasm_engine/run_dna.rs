@@ -2,6 +2,7 @@ use crate::{
     nucleus::ZomeFnResult,
     wasm_engine::{
         factories::{wasm_instance_factory, wasm_module_factory},
+        host_profile::{ForbiddenHostCallError, HostFunctionProfile},
         memory::WasmPageManager,
         runtime::{Runtime, WasmCallData},
     },
@@ -61,7 +62,7 @@ fn get_module(data: WasmCallData) -> Result<ModuleArc, HolochainError> {
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
 pub fn run_dna(parameters: Option<Vec<u8>>, data: WasmCallData) -> ZomeFnResult {
     let wasm_module = get_module(data.clone())?;
-    let wasm_instance = wasm_instance_factory(&wasm_module)?;
+    let wasm_instance = wasm_instance_factory(&wasm_module, HostFunctionProfile::for_call(&data))?;
     // write input arguments for module call in memory Buffer
     let input_parameters: Vec<_> = parameters.unwrap_or_default();
 
@@ -114,6 +115,14 @@ pub fn run_dna(parameters: Option<Vec<u8>>, data: WasmCallData) -> ZomeFnResult
                 mut_runtime,
             )
             .map_err(|err| {
+                // Calls to forbidden host functions are a bug in the zome that validation
+                // should report instead of failing the whole Ribosome
+                if let Some(forbidden) = err
+                    .as_host_error()
+                    .and_then(|e| e.downcast_ref::<ForbiddenHostCallError>())
+                {
+                    return HolochainError::ValidationFailed(forbidden.to_string());
+                }
                 HolochainError::RibosomeFailed(format!(
                     "WASM invocation failed: {}. data = {:?}",
                     err, runtime.data
@@ -3,6 +3,9 @@ use crate::{
     nucleus::{CallbackFnCall, ZomeFnCall},
     wasm_engine::{
         api::{ZomeApiFunction, ZomeApiResult},
+        host_profile::{
+            ForbiddenHostCallError, HostFunctionProfile, FORBIDDEN_HOST_FUNCTION_INDEX_OFFSET,
+        },
         memory::WasmPageManager,
         Defn,
     },
@@ -227,6 +230,16 @@ impl Runtime {
 // by implementing the Externals trait from Wasmi.
 impl Externals for Runtime {
     fn invoke_index(&mut self, index: usize, args: RuntimeArgs) -> ZomeApiResult {
+        if index >= FORBIDDEN_HOST_FUNCTION_INDEX_OFFSET {
+            return Err(Trap::new(TrapKind::Host(Box::new(
+                ForbiddenHostCallError {
+                    function: ZomeApiFunction::from_index(
+                        index - FORBIDDEN_HOST_FUNCTION_INDEX_OFFSET,
+                    ),
+                    profile: HostFunctionProfile::for_call(&self.data),
+                },
+            ))));
+        }
         let zf = ZomeApiFunction::from_index(index);
         match zf {
             ZomeApiFunction::MissingNo => panic!("unknown function index"),
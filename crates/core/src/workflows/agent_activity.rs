@@ -0,0 +1,114 @@
+use crate::{
+    context::Context,
+    dht::actions::hold_aspect::hold_aspect,
+    network::{
+        actions::query::{query, QueryMethod},
+        query::NetworkQueryResult,
+    },
+    workflows::{chain_fork::record_chain_fork_workflow, warrant::verify_header_authenticity},
+    NEW_RELIC_LICENSE_KEY,
+};
+use holochain_core_types::{
+    agent_activity::AgentActivity, chain_header::ChainHeader, error::HolochainError,
+    network::entry_aspect::EntryAspect,
+};
+use holochain_wasm_utils::api_serialization::get_agent_activity::GetAgentActivityArgs;
+use std::sync::Arc;
+
+/// Verifies a header that got published to us as agent activity and holds it
+/// under the address of its author.
+/// We don't validate the entry of the header here, only that its author signed the whole
+/// header, so that nobody can make up activity of another agent.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub async fn hold_agent_activity_workflow(
+    header: ChainHeader,
+    context: Arc<Context>,
+) -> Result<(), HolochainError> {
+    verify_header_authenticity(&header).map_err(|err| {
+        log_warn!(
+            context,
+            "workflow/hold_agent_activity: Received header with invalid signature: {:?}",
+            err
+        );
+        err
+    })?;
+    record_chain_fork_workflow(&header, &context);
+    hold_aspect(EntryAspect::Header(header), context.clone()).await
+}
+
+/// Asks the authorities of the given agent for its published headers.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub async fn get_agent_activity_workflow<'a>(
+    context: &'a Arc<Context>,
+    args: &'a GetAgentActivityArgs,
+) -> Result<AgentActivity, HolochainError> {
+    let method = QueryMethod::AgentActivity(args.agent.clone(), args.filter.clone());
+    match query(context.clone(), method, args.timeout.clone()).await? {
+        NetworkQueryResult::AgentActivity(activity) => Ok(activity),
+        _ => Err(HolochainError::ErrorGeneric(
+            "Wrong type for response type AgentActivity".to_string(),
+        )),
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use crate::{
+        action::{Action, ActionWrapper},
+        dht::dht_reducers::reduce_hold_aspect,
+        instance::tests::test_context,
+        state::test_store,
+    };
+    use holochain_core_types::{
+        agent_activity::{AgentActivity, ChainStatus},
+        chain_header::ChainHeader,
+        entry::{test_entry_a, test_entry_b},
+        network::entry_aspect::EntryAspect,
+        signature::{Provenance, Signature},
+        time::test_iso_8601,
+    };
+    use holochain_persistence_api::cas::content::AddressableContent;
+    use test_utils::mock_signing::mock_signer;
+
+    #[test]
+    fn forking_headers_get_held_as_forked_agent_activity() {
+        let context = test_context("alice", None);
+        let agent = context.agent_id.address();
+        let headers: Vec<ChainHeader> = vec![test_entry_a(), test_entry_b()]
+            .into_iter()
+            .map(|entry| {
+                let signature = mock_signer(entry.address().to_string(), &context.agent_id);
+                let header = ChainHeader::new(
+                    &entry.entry_type(),
+                    &entry.address(),
+                    &vec![Provenance::new(agent.clone(), Signature::from(signature))],
+                    &None,
+                    &None,
+                    &None,
+                    &test_iso_8601(),
+                );
+                let header_signature = mock_signer(header.signing_payload(), &context.agent_id);
+                header.with_header_signature(Signature::from(header_signature))
+            })
+            .collect();
+
+        let store = test_store(context.clone());
+        let dht_store = headers
+            .iter()
+            .fold((*store.dht()).clone(), |dht_store, header| {
+                reduce_hold_aspect(
+                    &dht_store,
+                    &ActionWrapper::new(Action::HoldAspect(EntryAspect::Header(header.clone()))),
+                )
+                .expect("there should be a new store for holding a header")
+            });
+
+        let held = dht_store.get_agent_activity(&agent).unwrap();
+        assert_eq!(held.len(), 2);
+        let activity = AgentActivity::new(agent, held, &Default::default());
+        assert!(match activity.status {
+            ChainStatus::Forked(forks) => forks.len() == 1 && forks[0].link.is_none(),
+            _ => false,
+        });
+    }
+}
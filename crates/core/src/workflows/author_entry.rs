@@ -1,11 +1,15 @@
 use crate::{
-    agent::actions::commit::commit_entry,
+    agent::actions::commit::commit_entry_with_provenances,
     context::Context,
     entry::CanPublish,
     network::actions::{publish::publish, publish_header_entry::publish_header_entry},
     nucleus::{
         actions::build_validation_package::build_validation_package, validation::validate_entry,
     },
+    workflows::{
+        chain_fork::check_own_chain_not_forked, countersigning::check_chain_not_locked,
+        custom_validation_package::store_custom_validation_package_workflow,
+    },
     NEW_RELIC_LICENSE_KEY,
 };
 
@@ -38,7 +42,13 @@ pub async fn author_entry<'a>(
         entry
     );
 
-    // 0. If we are trying to author a link or link removal, make sure the linked entries exist:
+    // 0. Don't extend our source chain if others have seen it forked
+    check_own_chain_not_forked(context)?;
+
+    // 0.1 Nor while we are in a countersigning session about another entry
+    check_chain_not_locked(entry, context)?;
+
+    // 0.2 If we are trying to author a link or link removal, make sure the linked entries exist:
     if let Entry::LinkAdd(link_data) = entry {
         get_link_entries(&link_data.link, context)?;
     }
@@ -48,6 +58,10 @@ pub async fn author_entry<'a>(
 
     // 1. Build the context needed for validation of the entry
     let validation_package = build_validation_package(&entry, context.clone(), provenances)?;
+    let maybe_custom_package = validation_package
+        .custom_signature
+        .as_ref()
+        .map(|_| validation_package.clone());
     let validation_data = ValidationData {
         package: validation_package,
         lifecycle: EntryLifecycle::Chain,
@@ -74,9 +88,22 @@ pub async fn author_entry<'a>(
         "workflow/authoring_entry/{}: committing...",
         address
     );
-    let addr = commit_entry(entry.clone(), maybe_link_update_delete, &context).await?;
+    let addr = commit_entry_with_provenances(
+        entry.clone(),
+        maybe_link_update_delete,
+        provenances.clone(),
+        &context,
+    )
+    .await?;
     log_debug!(context, "workflow/authoring_entry/{}: committed", address);
 
+    // 3a. The custom package we validated with was signed for a pre-flight header.
+    // Sign and store it for the committed header so validators get it as it is now,
+    // at publish time
+    if let Some(custom_package) = maybe_custom_package {
+        store_custom_validation_package_workflow(entry, &custom_package, &context).await?;
+    }
+
     // 4. Publish the valid entry to DHT. This will call Hold to itself
     if entry.entry_type().can_publish(context) {
         log_debug!(
@@ -0,0 +1,215 @@
+//! Detection of forked source chains.
+//!
+//! An agent forks its source chain when it publishes two different headers that point to
+//! the same previous header, for example after restoring an old backup and committing from
+//! it. Nodes notice this when they hold a header by looking it up in the per-author index
+//! of headers they have seen before. Evidence of a fork is recorded in the local shard.
+//!
+//! Only headers their author signed as a whole count as evidence, so nobody can fake a
+//! fork by copying a header and changing its link.
+//!
+//! A node that finds a fork of its own agent's chain refuses to author new entries until
+//! the fork gets marked as resolved, so it doesn't keep extending a chain that others
+//! consider broken.
+
+use crate::{
+    action::{Action, ActionWrapper},
+    context::Context,
+    instance::dispatch_action,
+    workflows::warrant::verify_header_authenticity,
+    NEW_RELIC_LICENSE_KEY,
+};
+use holochain_core_types::{
+    agent_activity::ChainForkEvidence, chain_header::ChainHeader, error::HolochainError,
+};
+use holochain_persistence_api::cas::content::{Address, AddressableContent};
+use std::{collections::HashSet, sync::Arc};
+
+/// Returns evidence of a fork if we have seen another header of the same author
+/// that points to the same previous header as the given one.
+fn detect_chain_fork(
+    header: &ChainHeader,
+    context: &Arc<Context>,
+) -> Result<Option<ChainForkEvidence>, HolochainError> {
+    // Provenances only sign the entry address. A header can only be evidence
+    // if its author signed all of it, otherwise anybody could make one up.
+    if verify_header_authenticity(header).is_err() {
+        return Ok(None);
+    }
+    let author = match header.author() {
+        Some(author) => author,
+        None => return Ok(None),
+    };
+    let dht = context
+        .state()
+        .ok_or_else(|| HolochainError::ErrorGeneric("State not initialized".to_string()))?
+        .dht();
+    let maybe_evidence = dht
+        .get_agent_activity(&author)?
+        .iter()
+        .find_map(|seen| ChainForkEvidence::from_headers(header, seen));
+    match maybe_evidence {
+        Some(evidence)
+            if !dht
+                .get_chain_forks(Some(author))?
+                .iter()
+                .any(|recorded| recorded.address() == evidence.address()) =>
+        {
+            Ok(Some(evidence))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Checks the given header, which we are about to hold, against the headers of its
+/// author that we have seen and records the evidence if it forks the author's chain.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub fn record_chain_fork_workflow(header: &ChainHeader, context: &Arc<Context>) {
+    match detect_chain_fork(header, context) {
+        Ok(Some(evidence)) => {
+            log_warn!(
+                context,
+                "workflow/chain_fork: Agent {} forked its source chain at {:?} with headers {:?}",
+                evidence.author,
+                evidence.link,
+                evidence.fork().headers,
+            );
+            dispatch_action(
+                context.action_channel(),
+                ActionWrapper::new(Action::RecordChainFork(evidence)),
+            );
+        }
+        Ok(None) => (),
+        Err(err) => log_error!(
+            context,
+            "workflow/chain_fork: Could not check header {} for forks: {:?}",
+            header.address(),
+            err,
+        ),
+    }
+}
+
+/// True if the forking headers branch off our local chain and are not all part of it
+fn forks_own_chain(fork: &ChainForkEvidence, own_headers: &HashSet<Address>) -> bool {
+    fork.link
+        .as_ref()
+        .map_or(true, |link| own_headers.contains(link))
+        && fork
+            .headers
+            .iter()
+            .any(|header| !own_headers.contains(&header.address()))
+}
+
+/// Fails if a fork of our own source chain got recorded that has not been resolved.
+/// Recorded evidence is checked against our local chain first: we only consider our chain
+/// forked if the forking headers branch off our chain and are not all part of it.
+pub fn check_own_chain_not_forked(context: &Arc<Context>) -> Result<(), HolochainError> {
+    let agent = context.agent_id.address();
+    let state = context
+        .state()
+        .ok_or_else(|| HolochainError::ErrorGeneric("State not initialized".to_string()))?;
+    let forks = state.dht().get_unresolved_chain_forks(&agent)?;
+    if forks.is_empty() {
+        return Ok(());
+    }
+    let own_headers: HashSet<Address> = state
+        .agent()
+        .iter_chain()
+        .map(|header| header.address())
+        .collect();
+    let confirmed: Vec<ChainForkEvidence> = forks
+        .into_iter()
+        .filter(|fork| forks_own_chain(fork, &own_headers))
+        .collect();
+    if confirmed.is_empty() {
+        Ok(())
+    } else {
+        Err(HolochainError::ErrorGeneric(format!(
+            "Source chain of agent {} is forked at {:?}. Refusing to write to it until the fork is resolved",
+            agent,
+            confirmed.iter().map(|fork| fork.link.clone()).collect::<Vec<_>>(),
+        )))
+    }
+}
+
+/// Marks all recorded forks of our own source chain as resolved, which allows
+/// authoring entries again.
+pub fn resolve_own_chain_forks(context: &Arc<Context>) {
+    dispatch_action(
+        context.action_channel(),
+        ActionWrapper::new(Action::ResolveChainForks(context.agent_id.address())),
+    );
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::instance::tests::test_context;
+    use holochain_core_types::{
+        entry::{test_entry_a, test_entry_b, Entry},
+        signature::{Provenance, Signature},
+        time::test_iso_8601,
+    };
+    use test_utils::mock_signing::mock_signer;
+
+    fn signed_header(context: &Arc<Context>, entry: &Entry, link: Option<Address>) -> ChainHeader {
+        let signature = mock_signer(entry.address().to_string(), &context.agent_id);
+        let header = ChainHeader::new(
+            &entry.entry_type(),
+            &entry.address(),
+            &vec![Provenance::new(
+                context.agent_id.address(),
+                Signature::from(signature),
+            )],
+            &link,
+            &None,
+            &None,
+            &test_iso_8601(),
+        );
+        let header_signature = mock_signer(header.signing_payload(), &context.agent_id);
+        header.with_header_signature(Signature::from(header_signature))
+    }
+
+    #[test]
+    fn forged_headers_are_no_fork_evidence() {
+        let context = test_context("alice", None);
+        let genuine = signed_header(&context, &test_entry_a(), Some(Address::from("a")));
+        // Same signatures, different link
+        let forged = ChainHeader::new(
+            genuine.entry_type(),
+            genuine.entry_address(),
+            genuine.provenances(),
+            &Some(Address::from("b")),
+            &None,
+            &None,
+            genuine.timestamp(),
+        )
+        .with_header_signature(genuine.header_signature().unwrap().clone());
+
+        assert_eq!(detect_chain_fork(&forged, &context), Ok(None));
+    }
+
+    #[test]
+    fn own_chain_forks_need_to_branch_off_the_local_chain() {
+        let context = test_context("alice", None);
+        let previous = signed_header(&context, &test_entry_a(), None);
+        let ours = signed_header(&context, &test_entry_a(), Some(previous.address()));
+        let theirs = signed_header(&context, &test_entry_b(), Some(previous.address()));
+        let fork = ChainForkEvidence::from_headers(&ours, &theirs).unwrap();
+
+        let own_headers: HashSet<Address> = vec![previous.address(), ours.address()]
+            .into_iter()
+            .collect();
+        assert!(forks_own_chain(&fork, &own_headers));
+
+        // Both branches are unknown to our chain, so this is not a fork of it
+        let unrelated: HashSet<Address> = vec![Address::from("other")].into_iter().collect();
+        assert!(!forks_own_chain(&fork, &unrelated));
+
+        // Only our own headers, e.g. evidence built from a header we committed twice
+        let all: HashSet<Address> = vec![previous.address(), ours.address(), theirs.address()]
+            .into_iter()
+            .collect();
+        assert!(!forks_own_chain(&fork, &all));
+    }
+}
@@ -0,0 +1,687 @@
+//! Workflows of both sides of a countersigning session.
+//!
+//! The proposer signs the entry and sends a CountersigningRequest to all other signers
+//! named in the entry. Every signer validates the entry, signs its address and locks
+//! itself into the session until it gets the outcome. Once the proposer has all
+//! signatures it commits the entry and sends them to the others, who commit it too.
+//! If a signature is missing when the session expires, the proposer aborts it instead.
+//!
+//! A session locks the source chain of every signer: nothing else can be committed until
+//! it ends, so all signers commit the entry on top of the same chain state. Signers that
+//! got no outcome by the end of the commit grace period, because it got lost or their
+//! commit failed, resolve the session from the DHT: if the proposer has published the
+//! entry with all signatures they commit it too, otherwise the session counts as aborted.
+
+use crate::{
+    action::{Action, ActionWrapper, DirectMessageData},
+    context::Context,
+    instance::dispatch_action,
+    network::{
+        actions::request_countersignature::request_countersignature, direct_message::DirectMessage,
+    },
+    nucleus::{
+        actions::build_validation_package::build_validation_package,
+        validation::{countersigning::required_countersigners, validate_countersigning_proposal},
+    },
+    workflows::{author_entry::author_entry, get_entry_result::get_entry_with_meta_workflow},
+    NEW_RELIC_LICENSE_KEY,
+};
+use chrono::{DateTime, FixedOffset};
+use futures::future::join_all;
+use holochain_core_types::{
+    chain_header::ChainHeader,
+    countersigning::{CountersigningOutcome, CountersigningRequest},
+    entry::Entry,
+    error::HolochainError,
+    signature::{Provenance, Signature},
+    time::{Iso8601, Timeout},
+    validation::{EntryLifecycle, ValidationData},
+};
+use holochain_dpki::utils::Verify;
+use holochain_persistence_api::cas::content::{Address, AddressableContent};
+use holochain_wasm_utils::api_serialization::crypto::CryptoMethod;
+use std::{
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+/// How long after the expiry of a session signers wait for its outcome before they
+/// resolve it themselves, since the proposer might have collected the last signature
+/// just in time.
+pub const COUNTERSIGNING_COMMIT_GRACE: Duration = Duration::from_secs(30);
+
+/// The countersigning session an agent is in, see `Context::countersigning_session`
+#[derive(Clone, Debug, PartialEq)]
+pub struct CountersigningSession {
+    pub request: CountersigningRequest,
+    /// Set while the expired session gets resolved, so that only happens once at a time
+    pub resolving: bool,
+}
+
+/// Returns the agents that have to sign the given entry according to its entry type.
+fn countersigners(entry: &Entry, context: &Arc<Context>) -> Result<Vec<Address>, HolochainError> {
+    let (app_entry_type, entry_value) = match entry {
+        Entry::App(app_entry_type, entry_value) => (app_entry_type, entry_value),
+        _ => {
+            return Err(HolochainError::ErrorGeneric(String::from(
+                "Only app entries can be countersigned",
+            )))
+        }
+    };
+    let dna = context.get_dna().ok_or(HolochainError::DnaMissing)?;
+    let entry_type_name = String::from(app_entry_type.clone());
+    let entry_type_def = dna.get_entry_type_def(&entry_type_name).ok_or_else(|| {
+        HolochainError::ErrorGeneric(format!("Unknown entry type \"{}\"", entry_type_name))
+    })?;
+    let signers = required_countersigners(app_entry_type, entry_value, entry_type_def)?;
+    if signers.is_empty() {
+        return Err(HolochainError::ErrorGeneric(format!(
+            "Entry type \"{}\" is not countersigned",
+            entry_type_name
+        )));
+    }
+    Ok(signers)
+}
+
+fn sign_entry_address(
+    address: &Address,
+    context: &Arc<Context>,
+) -> Result<Provenance, HolochainError> {
+    let signature = Signature::from(
+        context
+            .conductor_api
+            .execute(address.to_string(), CryptoMethod::Sign)?,
+    );
+    Ok(Provenance::new(context.agent_id.address(), signature))
+}
+
+/// Checks that the given provenance is a valid signature of the entry address by the signer.
+fn verify_countersignature(
+    provenance: &Provenance,
+    signer: &Address,
+    address: &Address,
+) -> Result<(), HolochainError> {
+    if provenance.source() != *signer || !provenance.verify(address.to_string())? {
+        return Err(HolochainError::ValidationFailed(format!(
+            "Countersignature of entry {} from signer {} invalid",
+            address, signer
+        )));
+    }
+    Ok(())
+}
+
+fn expires_at(timeout: &Timeout) -> Iso8601 {
+    let expires_at = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("System time must not be before UNIX EPOCH")
+        + Duration::from(timeout);
+    Iso8601::new(expires_at.as_secs() as i64, expires_at.subsec_nanos())
+}
+
+fn is_expired(expires_at: &Iso8601, grace: Duration) -> bool {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("System time must not be before UNIX EPOCH");
+    DateTime::<FixedOffset>::from(expires_at).timestamp_millis() + (grace.as_millis() as i64)
+        < now.as_millis() as i64
+}
+
+fn locked_by(request: &CountersigningRequest) -> HolochainError {
+    HolochainError::ErrorGeneric(format!(
+        "Source chain is locked by the countersigning session of entry {}",
+        request.entry_address()
+    ))
+}
+
+/// Fails if we are in a countersigning session about another entry than the given one.
+/// Nothing else can be committed while the signers of a session might still commit its entry.
+pub fn check_chain_not_locked(entry: &Entry, context: &Arc<Context>) -> Result<(), HolochainError> {
+    let session = context
+        .countersigning_session
+        .lock()
+        .expect("Could not lock countersigning session");
+    match session.as_ref() {
+        Some(current) if current.request.entry_address() != entry.address() => {
+            Err(locked_by(&current.request))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Locks us into the given session.
+/// Fails if we are still in another one, even if it has expired but is not resolved yet.
+fn start_session(
+    request: &CountersigningRequest,
+    context: &Arc<Context>,
+) -> Result<(), HolochainError> {
+    let mut session = context
+        .countersigning_session
+        .lock()
+        .expect("Could not lock countersigning session");
+    if let Some(current) = session.as_ref() {
+        return Err(locked_by(&current.request));
+    }
+    *session = Some(CountersigningSession {
+        request: request.clone(),
+        resolving: false,
+    });
+    Ok(())
+}
+
+fn is_session_of(request: &CountersigningRequest, address: &Address, proposer: &Address) -> bool {
+    request.entry_address() == *address && request.proposer().source() == *proposer
+}
+
+/// Returns the request of the session about the given entry proposed by the given agent,
+/// if we are in that session.
+fn current_session(
+    address: &Address,
+    proposer: &Address,
+    context: &Arc<Context>,
+) -> Option<CountersigningRequest> {
+    context
+        .countersigning_session
+        .lock()
+        .expect("Could not lock countersigning session")
+        .as_ref()
+        .map(|current| current.request.clone())
+        .filter(|request| is_session_of(request, address, proposer))
+}
+
+/// Releases the session about the given entry proposed by the given agent
+/// and returns it, if we are in that session.
+fn end_session(
+    address: &Address,
+    proposer: &Address,
+    context: &Arc<Context>,
+) -> Option<CountersigningRequest> {
+    let mut session = context
+        .countersigning_session
+        .lock()
+        .expect("Could not lock countersigning session");
+    match session.as_ref() {
+        Some(current) if is_session_of(&current.request, address, proposer) => {
+            session.take().map(|current| current.request)
+        }
+        _ => None,
+    }
+}
+
+fn send_outcome(signers: &[Address], outcome: CountersigningOutcome, context: &Arc<Context>) {
+    for signer in signers {
+        let action_wrapper = ActionWrapper::new(Action::SendCountersigningOutcome((
+            signer.clone(),
+            outcome.clone(),
+        )));
+        dispatch_action(context.action_channel(), action_wrapper);
+    }
+}
+
+/// Proposes the given entry to all signers it names, and commits it once all of them
+/// signed it before the timeout. Fails if any of them refuses or doesn't respond in time.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub async fn commit_countersigned_entry_workflow(
+    entry: &Entry,
+    timeout: Timeout,
+    context: &Arc<Context>,
+) -> Result<Address, HolochainError> {
+    let agent_address = context.agent_id.address();
+    let address = entry.address();
+    let signers = countersigners(entry, context)?;
+    if !signers.contains(&agent_address) {
+        return Err(HolochainError::ErrorGeneric(format!(
+            "Agent {} is not a countersigner of entry {}",
+            agent_address, address
+        )));
+    }
+
+    let proposer = sign_entry_address(&address, context)?;
+    let request = CountersigningRequest::new(
+        entry.clone(),
+        signers.clone(),
+        proposer.clone(),
+        expires_at(&timeout),
+    );
+    start_session(&request, context)?;
+
+    let others: Vec<Address> = signers
+        .into_iter()
+        .filter(|signer| *signer != agent_address)
+        .collect();
+    log_debug!(
+        context,
+        "workflow/countersigning: Requesting signatures of entry {} from {:?}",
+        address,
+        others
+    );
+    let responses = join_all(others.iter().map(|signer| {
+        request_countersignature(
+            signer.clone(),
+            request.clone(),
+            timeout.clone(),
+            context.clone(),
+        )
+    }))
+    .await;
+
+    let maybe_provenances = others
+        .iter()
+        .zip(responses.into_iter())
+        .map(|(signer, response)| {
+            response.and_then(|provenance| {
+                verify_countersignature(&provenance, signer, &address).map(|_| provenance)
+            })
+        })
+        .collect::<Result<Vec<Provenance>, HolochainError>>()
+        .and_then(|provenances| {
+            if is_expired(request.expires_at(), Duration::from_secs(0)) {
+                Err(HolochainError::Timeout)
+            } else {
+                Ok(provenances)
+            }
+        });
+
+    let result = match maybe_provenances {
+        Ok(provenances) => author_entry(entry, None, context, &provenances)
+            .await
+            .map(|_| {
+                let mut all_provenances = provenances;
+                all_provenances.push(proposer);
+                send_outcome(
+                    &others,
+                    CountersigningOutcome::Commit(address.clone(), all_provenances),
+                    context,
+                );
+                address.clone()
+            }),
+        Err(error) => Err(error),
+    };
+    if let Err(error) = &result {
+        log_warn!(
+            context,
+            "workflow/countersigning: Aborting countersigning of entry {}: {}",
+            address,
+            error
+        );
+        send_outcome(
+            &others,
+            CountersigningOutcome::Abort(address.clone()),
+            context,
+        );
+    }
+    end_session(&address, &agent_address, context);
+    result
+}
+
+async fn sign_countersigning_request(
+    from: &Address,
+    request: &CountersigningRequest,
+    context: &Arc<Context>,
+) -> Result<Provenance, HolochainError> {
+    let address = request.entry_address();
+    verify_countersignature(request.proposer(), from, &address)?;
+    if is_expired(request.expires_at(), Duration::from_secs(0)) {
+        return Err(HolochainError::Timeout);
+    }
+    let signers = countersigners(request.entry(), context)?;
+    if *request.signers() != signers {
+        return Err(HolochainError::ValidationFailed(format!(
+            "Countersigning request names other signers than entry {}",
+            address
+        )));
+    }
+    if !signers.contains(&context.agent_id.address()) {
+        return Err(HolochainError::ErrorGeneric(format!(
+            "Agent {} is not a countersigner of entry {}",
+            context.agent_id.address(),
+            address
+        )));
+    }
+
+    let validation_package = build_validation_package(
+        request.entry(),
+        context.clone(),
+        &vec![request.proposer().clone()],
+    )?;
+    let validation_data = ValidationData {
+        package: validation_package,
+        lifecycle: EntryLifecycle::Chain,
+    };
+    validate_countersigning_proposal(request.entry().clone(), validation_data, context).await?;
+
+    start_session(request, context)?;
+    sign_entry_address(&address, context)
+}
+
+/// Signs the entry of a countersigning request we got if it is valid
+/// and we are not in another session, and responds with our signature.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub async fn respond_countersigning_request(
+    from: Address,
+    msg_id: String,
+    request: CountersigningRequest,
+    context: Arc<Context>,
+) {
+    let response = sign_countersigning_request(&from, &request, &context)
+        .await
+        .map_err(|error| {
+            log_warn!(
+                context,
+                "workflow/countersigning: Refusing to countersign entry {} proposed by {}: {}",
+                request.entry_address(),
+                from,
+                error
+            );
+            error.to_string()
+        });
+    let direct_message_data = DirectMessageData {
+        address: from,
+        message: DirectMessage::CountersigningSignature(response),
+        msg_id,
+        is_response: true,
+        deliver_later: false,
+    };
+    let action_wrapper = ActionWrapper::new(Action::SendDirectMessage((direct_message_data, None)));
+    dispatch_action(context.action_channel(), action_wrapper);
+}
+
+/// Checks that the given provenances hold valid signatures of all signers of the session
+/// and returns the ones of the others, which we commit the entry with.
+fn countersignatures(
+    request: &CountersigningRequest,
+    provenances: &[Provenance],
+    context: &Arc<Context>,
+) -> Result<Vec<Provenance>, HolochainError> {
+    let address = request.entry_address();
+    for signer in request.signers() {
+        let provenance = provenances
+            .iter()
+            .find(|provenance| provenance.source() == *signer)
+            .ok_or_else(|| {
+                HolochainError::ValidationFailed(format!(
+                    "Countersignature of entry {} from signer {} missing",
+                    address, signer
+                ))
+            })?;
+        verify_countersignature(provenance, signer, &address)?;
+    }
+    Ok(provenances
+        .iter()
+        .filter(|provenance| provenance.source() != context.agent_id.address())
+        .cloned()
+        .collect())
+}
+
+/// Handles the outcome of the session it is about. If the proposer collected all signatures
+/// we commit the entry with them as well, even if the outcome arrives late: the proposer
+/// has committed it already. The session only ends once we did, if our commit fails the
+/// chain stays locked until the session gets resolved.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub async fn handle_countersigning_outcome(
+    from: Address,
+    outcome: CountersigningOutcome,
+    context: Arc<Context>,
+) -> Result<(), HolochainError> {
+    let address = match &outcome {
+        CountersigningOutcome::Commit(address, _) | CountersigningOutcome::Abort(address) => {
+            address.clone()
+        }
+    };
+    let request = current_session(&address, &from, &context).ok_or_else(|| {
+        HolochainError::ErrorGeneric(format!(
+            "Got countersigning outcome from {} for unknown session of entry {}",
+            from, address
+        ))
+    })?;
+
+    match outcome {
+        CountersigningOutcome::Abort(_) => {
+            log_debug!(
+                context,
+                "workflow/countersigning: Countersigning of entry {} aborted",
+                address
+            );
+        }
+        CountersigningOutcome::Commit(_, provenances) => {
+            let others = countersignatures(&request, &provenances, &context)?;
+            author_entry(request.entry(), None, &context, &others).await?;
+        }
+    }
+    end_session(&address, &from, &context);
+    Ok(())
+}
+
+/// Marks our session as being resolved and returns its request if it expired more than
+/// `COUNTERSIGNING_COMMIT_GRACE` ago and nobody is resolving it yet.
+pub(crate) fn claim_expired_session(context: &Arc<Context>) -> Option<CountersigningRequest> {
+    let mut session = context
+        .countersigning_session
+        .lock()
+        .expect("Could not lock countersigning session");
+    match session.as_mut() {
+        Some(current)
+            if !current.resolving
+                && is_expired(current.request.expires_at(), COUNTERSIGNING_COMMIT_GRACE) =>
+        {
+            current.resolving = true;
+            Some(current.request.clone())
+        }
+        _ => None,
+    }
+}
+
+/// Returns the header of the entry that carries the signatures of all signers, if the
+/// proposer has published one.
+async fn published_countersigned_header(
+    request: &CountersigningRequest,
+    context: &Arc<Context>,
+) -> Result<Option<ChainHeader>, HolochainError> {
+    let address = request.entry_address();
+    Ok(
+        get_entry_with_meta_workflow(context, &address, &Timeout::default())
+            .await?
+            .and_then(|entry_with_meta_and_headers| {
+                entry_with_meta_and_headers
+                    .headers
+                    .into_iter()
+                    .find(|header| {
+                        countersignatures(request, header.provenances(), context).is_ok()
+                    })
+            }),
+    )
+}
+
+async fn resolve_session(
+    request: &CountersigningRequest,
+    context: &Arc<Context>,
+) -> Result<(), HolochainError> {
+    // Our own proposal ended with the commit workflow, only a crashed one leaves it behind
+    if request.proposer().source() == context.agent_id.address() {
+        return Ok(());
+    }
+    match published_countersigned_header(request, context).await? {
+        Some(header) => {
+            log_debug!(
+                context,
+                "workflow/countersigning: Committing entry {} found countersigned on the DHT",
+                request.entry_address()
+            );
+            let others = countersignatures(request, header.provenances(), context)?;
+            author_entry(request.entry(), None, context, &others).await?;
+        }
+        None => log_debug!(
+            context,
+            "workflow/countersigning: Countersigning of entry {} expired without being committed",
+            request.entry_address()
+        ),
+    }
+    Ok(())
+}
+
+/// Resolves a session whose outcome we didn't get or couldn't act on in time, see the
+/// module docs. If that fails the session stays and will be resolved again later.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub async fn resolve_countersigning_session_workflow(
+    request: CountersigningRequest,
+    context: Arc<Context>,
+) {
+    let address = request.entry_address();
+    let proposer = request.proposer().source();
+    match resolve_session(&request, &context).await {
+        Ok(()) => {
+            end_session(&address, &proposer, &context);
+        }
+        Err(error) => {
+            log_warn!(
+                context,
+                "workflow/countersigning: Could not resolve countersigning session of entry {}: {}",
+                address,
+                error
+            );
+            if let Some(current) = context
+                .countersigning_session
+                .lock()
+                .expect("Could not lock countersigning session")
+                .as_mut()
+            {
+                current.resolving = false;
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::instance::tests::test_context;
+    use futures::executor::block_on;
+    use holochain_core_types::{
+        agent::AgentId,
+        entry::{test_entry, test_entry_b},
+    };
+    use test_utils::mock_signing::{mock_signer, registered_test_agent};
+
+    fn signed(address: &Address, agent: &AgentId) -> Provenance {
+        Provenance::new(
+            agent.address(),
+            Signature::from(mock_signer(address.to_string(), agent)),
+        )
+    }
+
+    fn test_request(
+        context: &Arc<Context>,
+        proposer: &AgentId,
+        expires_at: Iso8601,
+    ) -> CountersigningRequest {
+        let entry = test_entry();
+        CountersigningRequest::new(
+            entry.clone(),
+            vec![proposer.address(), context.agent_id.address()],
+            signed(&entry.address(), proposer),
+            expires_at,
+        )
+    }
+
+    #[test]
+    fn sessions_lock_the_source_chain() {
+        let context = test_context("alice", None);
+        let bob = registered_test_agent("bob");
+        let request = test_request(&context, &bob, expires_at(&Timeout::default()));
+        assert_eq!(check_chain_not_locked(&test_entry_b(), &context), Ok(()));
+
+        start_session(&request, &context).unwrap();
+        assert!(check_chain_not_locked(&test_entry_b(), &context).is_err());
+        assert_eq!(check_chain_not_locked(&test_entry(), &context), Ok(()));
+
+        // Not even an expired session can be replaced before it got resolved
+        let expired = test_request(&context, &bob, Iso8601::new(0, 0));
+        assert!(start_session(&expired, &context).is_err());
+
+        assert_eq!(
+            end_session(&request.entry_address(), &bob.address(), &context),
+            Some(request)
+        );
+        assert_eq!(check_chain_not_locked(&test_entry_b(), &context), Ok(()));
+    }
+
+    #[test]
+    fn countersignatures_need_all_signers() {
+        let context = test_context("alice", None);
+        let bob = registered_test_agent("bob");
+        let request = test_request(&context, &bob, expires_at(&Timeout::default()));
+        let proposer = request.proposer().clone();
+        let alice = signed(&request.entry_address(), &context.agent_id);
+
+        assert_eq!(
+            countersignatures(&request, &[proposer.clone(), alice], &context),
+            Ok(vec![proposer.clone()])
+        );
+        assert!(countersignatures(&request, &[proposer.clone()], &context).is_err());
+
+        let other_entry = signed(&test_entry_b().address(), &context.agent_id);
+        assert!(countersignatures(&request, &[proposer, other_entry], &context).is_err());
+    }
+
+    #[test]
+    fn incomplete_commits_keep_the_chain_locked_until_aborted() {
+        let context = test_context("alice", None);
+        let bob = registered_test_agent("bob");
+        let request = test_request(&context, &bob, expires_at(&Timeout::default()));
+        start_session(&request, &context).unwrap();
+
+        // Our own signature is missing, so we can't commit the entry
+        let incomplete = CountersigningOutcome::Commit(
+            request.entry_address(),
+            vec![request.proposer().clone()],
+        );
+        assert!(block_on(handle_countersigning_outcome(
+            bob.address(),
+            incomplete,
+            context.clone()
+        ))
+        .is_err());
+        assert!(check_chain_not_locked(&test_entry_b(), &context).is_err());
+
+        // Only the proposer can end the session
+        let abort = CountersigningOutcome::Abort(request.entry_address());
+        assert!(block_on(handle_countersigning_outcome(
+            context.agent_id.address(),
+            abort.clone(),
+            context.clone()
+        ))
+        .is_err());
+        assert!(check_chain_not_locked(&test_entry_b(), &context).is_err());
+
+        assert_eq!(
+            block_on(handle_countersigning_outcome(
+                bob.address(),
+                abort,
+                context.clone()
+            )),
+            Ok(())
+        );
+        assert_eq!(check_chain_not_locked(&test_entry_b(), &context), Ok(()));
+    }
+
+    #[test]
+    fn expired_sessions_get_claimed_for_resolution_once() {
+        let context = test_context("alice", None);
+        let bob = registered_test_agent("bob");
+        let request = test_request(&context, &bob, expires_at(&Timeout::default()));
+        start_session(&request, &context).unwrap();
+        assert_eq!(claim_expired_session(&context), None);
+        end_session(&request.entry_address(), &bob.address(), &context);
+
+        // A proposal of our own that a crashed workflow left behind just gets dropped
+        let own = test_request(&context, &context.agent_id, Iso8601::new(0, 0));
+        start_session(&own, &context).unwrap();
+        assert_eq!(claim_expired_session(&context), Some(own.clone()));
+        assert_eq!(claim_expired_session(&context), None);
+
+        block_on(resolve_countersigning_session_workflow(
+            own,
+            context.clone(),
+        ));
+        assert_eq!(check_chain_not_locked(&test_entry_b(), &context), Ok(()));
+    }
+}
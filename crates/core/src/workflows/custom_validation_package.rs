@@ -0,0 +1,154 @@
+use crate::{
+    agent::{
+        actions::add_custom_validation_package::add_custom_validation_package, find_chain_header,
+    },
+    content_store::GetContent,
+    context::Context,
+    wasm_engine::callback::validation_package::build_custom_validation_package_data,
+};
+use holochain_core_types::{
+    chain_header::ChainHeader,
+    custom_validation_package::CustomValidationPackage,
+    entry::Entry,
+    error::HolochainError,
+    signature::{Provenance, Signature},
+    validation::ValidationPackage,
+};
+use holochain_persistence_api::cas::content::{Address, AddressableContent};
+use holochain_wasm_utils::api_serialization::crypto::CryptoMethod;
+use std::sync::Arc;
+
+/// Returns the custom validation package we have stored for the given header of our chain.
+pub fn get_custom_validation_package(
+    header_address: &Address,
+    context: &Arc<Context>,
+) -> Result<Option<CustomValidationPackage>, HolochainError> {
+    let state = context
+        .state()
+        .ok_or_else(|| HolochainError::ErrorGeneric("State not initialized".to_string()))?;
+    let address = CustomValidationPackage::address_for_header(header_address);
+    match state.agent().chain_store().get_raw(&address)? {
+        Some(content) => Ok(Some(CustomValidationPackage::try_from_content(&content)?)),
+        None => Ok(None),
+    }
+}
+
+/// Returns the stored custom validation package for the given header or, if there is none,
+/// calls the zome's callback to build one and signs it with our agent key.
+pub fn custom_validation_package(
+    entry: &Entry,
+    chain_header: &ChainHeader,
+    context: &Arc<Context>,
+) -> Result<CustomValidationPackage, HolochainError> {
+    let header_address = chain_header.address();
+    if let Some(package) = get_custom_validation_package(&header_address, context)? {
+        return Ok(package);
+    }
+
+    let data = build_custom_validation_package_data(entry, chain_header, context.clone())?;
+    sign_custom_validation_package(header_address, data, context)
+}
+
+fn sign_custom_validation_package(
+    header_address: Address,
+    data: String,
+    context: &Arc<Context>,
+) -> Result<CustomValidationPackage, HolochainError> {
+    let payload = CustomValidationPackage::signing_payload(&header_address, &data);
+    let signature = Signature::from(context.conductor_api.execute(payload, CryptoMethod::Sign)?);
+    Ok(CustomValidationPackage::new(
+        header_address,
+        data,
+        Provenance::new(context.agent_id.address(), signature),
+    ))
+}
+
+/// Signs and stores the custom validation package for the header of the given,
+/// just committed entry.
+/// This has to happen when the entry gets published so validators that request the package
+/// later get the chain context as it was at that time.
+/// The data is taken from the package the entry got validated with before the commit so the
+/// callback only runs once. It only gets built again if another entry got committed in between
+/// and the committed header follows another header than the one the package was built for.
+pub async fn store_custom_validation_package_workflow(
+    entry: &Entry,
+    validated_package: &ValidationPackage,
+    context: &Arc<Context>,
+) -> Result<(), HolochainError> {
+    let state = context
+        .state()
+        .ok_or_else(|| HolochainError::ErrorGeneric("State not initialized".to_string()))?;
+    let chain_header = find_chain_header(entry, &state).ok_or_else(|| {
+        HolochainError::ErrorGeneric(format!(
+            "No header found for committed entry {}",
+            entry.address()
+        ))
+    })?;
+
+    let package = match &validated_package.custom {
+        Some(data) if chain_header.link() == validated_package.chain_header.link() => {
+            sign_custom_validation_package(chain_header.address(), data.clone(), context)?
+        }
+        _ => custom_validation_package(entry, &chain_header, context)?,
+    };
+    log_debug!(
+        context,
+        "workflow/store_custom_validation_package: storing package for header {}",
+        package.header_address()
+    );
+    add_custom_validation_package(package, context).await?;
+    Ok(())
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{agent::actions::commit::commit_entry, instance::tests::test_instance_and_context};
+    use holochain_core_types::{chain_header::test_chain_header, entry::test_entry};
+
+    #[test]
+    fn stores_the_validated_package_data_for_the_committed_header() {
+        // The test zome has no custom validation package callback,
+        // so storing only works if the data we validated with gets reused
+        let dna = test_utils::create_test_dna_with_wat("test_zome", None);
+        let (_instance, context) =
+            test_instance_and_context(dna, None).expect("Could not initialize test instance");
+        let entry = test_entry();
+        context
+            .block_on(commit_entry(entry.clone(), None, &context))
+            .unwrap();
+        let header = find_chain_header(&entry, &context.state().unwrap())
+            .expect("committed entry must have a header");
+        let data = String::from("[\"previous entry\"]");
+
+        // Built for a header that follows another one than the committed header,
+        // so the callback has to build it again
+        let mut stale = ValidationPackage::only_header(test_chain_header());
+        stale.custom = Some(data.clone());
+        assert_ne!(stale.chain_header.link(), header.link());
+        assert!(context
+            .block_on(store_custom_validation_package_workflow(
+                &entry, &stale, &context
+            ))
+            .is_err());
+        assert_eq!(
+            get_custom_validation_package(&header.address(), &context),
+            Ok(None)
+        );
+
+        let mut validated = ValidationPackage::only_header(header.clone());
+        validated.custom = Some(data.clone());
+        assert_eq!(
+            context.block_on(store_custom_validation_package_workflow(
+                &entry, &validated, &context
+            )),
+            Ok(())
+        );
+        let stored = get_custom_validation_package(&header.address(), &context)
+            .unwrap()
+            .expect("package must be stored once the workflow returns");
+        assert_eq!(stored.header_address(), &header.address());
+        assert_eq!(stored.data(), &data);
+        assert_eq!(stored.author().source(), context.agent_id.address());
+    }
+}
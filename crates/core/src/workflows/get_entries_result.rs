@@ -0,0 +1,263 @@
+use crate::{
+    context::Context,
+    network::{self, actions::query::QueryMethod, query::NetworkQueryResult},
+    workflows::get_entry_result::{
+        get_entry_result_from, get_entry_result_workflow, get_entry_with_meta_without_network,
+    },
+    NEW_RELIC_LICENSE_KEY,
+};
+use futures::future;
+use holochain_core_types::{entry::EntryWithMetaAndHeader, error::HolochainError};
+use holochain_net::dht_location::{entry_location, forward_distance};
+use holochain_persistence_api::cas::content::Address;
+use holochain_wasm_utils::api_serialization::get_entry::{
+    CachePolicy, GetEntriesArgs, GetEntriesResult, GetEntryArgs,
+};
+use std::{collections::HashMap, sync::Arc};
+
+/// Maximum number of addresses asked for in one network query
+pub const MAX_ENTRIES_PER_QUERY: usize = 50;
+
+/// Maximum distance in the DHT location space from the first to the last address of a batch.
+/// Authorities hold the arc following their own location, so the authority a batch gets
+/// routed to by its first address is likely to hold the other addresses of the batch too.
+/// With sim2h's default redundancy of 50 agents per entry, authority arcs are bigger than
+/// this in networks of up to 50000 agents.
+pub const MAX_BATCH_LOCATION_SPAN: u32 = u32::max_value() / 1024;
+
+/// What we know about one requested address after the batched lookups
+#[derive(Clone)]
+enum Prefetched {
+    Found(EntryWithMetaAndHeader),
+    Failed(HolochainError),
+}
+
+/// Groups the given addresses into batches that the same authorities are responsible for.
+/// Addresses get sorted by their DHT location, so that every batch starts with its lowest
+/// location and its authorities, which hold the arc following it, cover the rest of it.
+fn authority_batches(addresses: Vec<Address>) -> Vec<Vec<Address>> {
+    let mut located: Vec<(u32, Address)> = addresses
+        .into_iter()
+        .map(|address| (entry_location(&address), address))
+        .collect();
+    located.sort();
+    located.dedup();
+
+    let mut batches: Vec<Vec<Address>> = Vec::new();
+    let mut batch_start = 0;
+    for (location, address) in located {
+        match batches.last_mut() {
+            Some(batch)
+                if batch.len() < MAX_ENTRIES_PER_QUERY
+                    && forward_distance(batch_start, location) <= MAX_BATCH_LOCATION_SPAN =>
+            {
+                batch.push(address)
+            }
+            _ => {
+                batch_start = location;
+                batches.push(vec![address]);
+            }
+        }
+    }
+    batches
+}
+
+/// Get GetEntriesResult workflow
+///
+/// Looks up all addresses in the local DHT shard and, as far as the cache policy allows, in the
+/// query cache first. The remaining ones are requested in batches with one network query each,
+/// all running concurrently. A batch query gets routed to an authority of its first address,
+/// which answers for every address of the batch it holds (all of them in a full-sync DHT).
+/// To make that work in a sharded DHT too, batches only hold addresses close to each other
+/// in the DHT, starting with the one of the lowest location, see `authority_batches`.
+/// Addresses the authority did not hold fall back to single get_entry queries.
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub async fn get_entries_result_workflow<'a>(
+    context: &'a Arc<Context>,
+    args: &'a GetEntriesArgs,
+) -> Result<GetEntriesResult, HolochainError> {
+    let mut prefetched: HashMap<Address, Prefetched> = HashMap::new();
+
+    // 1. Try to get the entries locally (i.e. local DHT shard or query cache)
+    let mut missing = Vec::new();
+    for address in args.addresses.iter() {
+        if prefetched.contains_key(address) {
+            continue;
+        }
+        match get_entry_with_meta_without_network(context, address, &args.options.cache_policy) {
+            Ok(Some(entry_with_meta_and_headers)) => {
+                prefetched.insert(
+                    address.clone(),
+                    Prefetched::Found(entry_with_meta_and_headers),
+                );
+            }
+            Ok(None) => missing.push(address.clone()),
+            Err(error) => {
+                prefetched.insert(address.clone(), Prefetched::Failed(error));
+            }
+        }
+    }
+
+    // 2. Query the network for the rest in batches, unless we must not
+    if args.options.cache_policy == CachePolicy::LocalOnly {
+        missing.clear();
+    }
+    let batches = authority_batches(missing);
+    let responses = future::join_all(batches.iter().map(|batch| {
+        network::actions::query::query(
+            context.clone(),
+            QueryMethod::Entries(batch.clone()),
+            args.options.timeout.clone(),
+        )
+    }))
+    .await;
+    for (batch, response) in batches.into_iter().zip(responses) {
+        match response {
+            Ok(NetworkQueryResult::Entries(entries)) => {
+                let mut query_cache = context
+                    .query_cache
+                    .lock()
+                    .expect("Could not lock query cache");
+                for (address, maybe_entry) in entries {
+                    if let Some(entry_with_meta_and_headers) = maybe_entry {
+                        query_cache
+                            .insert_entry(address.clone(), entry_with_meta_and_headers.clone());
+                        prefetched.insert(address, Prefetched::Found(entry_with_meta_and_headers));
+                    }
+                }
+            }
+            Ok(_) => {
+                for address in batch {
+                    prefetched.insert(
+                        address,
+                        Prefetched::Failed(HolochainError::ErrorGeneric(
+                            "Wrong respond type for Entries".to_string(),
+                        )),
+                    );
+                }
+            }
+            Err(error) => {
+                for address in batch {
+                    prefetched.insert(address, Prefetched::Failed(error.clone()));
+                }
+            }
+        }
+    }
+
+    // 3. Build the result of every address, following entry histories as requested.
+    // Addresses the batch authorities didn't hold are looked up with the regular workflow.
+    let results = future::join_all(args.addresses.iter().map(|address| {
+        let entry_args = GetEntryArgs {
+            address: address.clone(),
+            options: args.options.clone(),
+        };
+        let maybe_prefetched = prefetched.get(address).cloned();
+        async move {
+            match maybe_prefetched {
+                Some(Prefetched::Found(entry_with_meta_and_headers)) => {
+                    get_entry_result_from(context, &entry_args, Some(entry_with_meta_and_headers))
+                        .await
+                }
+                Some(Prefetched::Failed(error)) => Err(error),
+                None => get_entry_result_workflow(context, &entry_args).await,
+            }
+        }
+    }))
+    .await;
+
+    Ok(GetEntriesResult { results })
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        dht::actions::hold_aspect::hold_aspect, instance::tests::test_instance_and_context,
+    };
+    use holochain_core_types::{
+        chain_header::ChainHeader,
+        entry::{entry_type::test_app_entry_type, test_entry, Entry},
+        network::entry_aspect::EntryAspect,
+        time::test_iso_8601,
+    };
+    use holochain_json_api::json::{JsonString, RawString};
+    use holochain_persistence_api::cas::content::AddressableContent;
+    use holochain_wasm_utils::api_serialization::get_entry::GetEntryOptions;
+
+    fn test_addresses(count: usize) -> Vec<Address> {
+        (0..count)
+            .map(|i| {
+                Entry::App(
+                    test_app_entry_type(),
+                    JsonString::from(RawString::from(format!("entry {}", i))),
+                )
+                .address()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn batches_hold_close_addresses_starting_with_the_lowest_location() {
+        let addresses = test_addresses(200);
+        let batches =
+            authority_batches(addresses.iter().chain(addresses.iter()).cloned().collect());
+
+        let mut batched: Vec<Address> = batches.iter().flatten().cloned().collect();
+        batched.sort();
+        let mut expected = addresses.clone();
+        expected.sort();
+        assert_eq!(batched, expected);
+
+        for batch in batches {
+            assert!(batch.len() <= MAX_ENTRIES_PER_QUERY);
+            let first = entry_location(&batch[0]);
+            for address in batch.iter() {
+                assert!(entry_location(address) >= first);
+                assert!(
+                    forward_distance(first, entry_location(address)) <= MAX_BATCH_LOCATION_SPAN
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn gets_entries_from_the_local_shard() {
+        let dna = test_utils::create_test_dna_with_wat("test_zome", None);
+        let (_instance, context) =
+            test_instance_and_context(dna, None).expect("Could not initialize test instance");
+
+        let entry = test_entry();
+        let header = ChainHeader::new(
+            &entry.entry_type(),
+            &entry.address(),
+            &Vec::new(),
+            &None,
+            &None,
+            &None,
+            &test_iso_8601(),
+        );
+        context
+            .block_on(hold_aspect(
+                EntryAspect::Content(entry.clone(), header),
+                context.clone(),
+            ))
+            .unwrap();
+
+        let unknown = test_addresses(1).remove(0);
+        let args = GetEntriesArgs {
+            addresses: vec![unknown, entry.address()],
+            options: GetEntryOptions {
+                cache_policy: CachePolicy::LocalOnly,
+                ..Default::default()
+            },
+        };
+        let results = context
+            .block_on(get_entries_result_workflow(&context, &args))
+            .unwrap()
+            .results;
+
+        assert_eq!(results.len(), 2);
+        assert!(!results[0].as_ref().unwrap().found());
+        assert_eq!(results[1].as_ref().unwrap().latest(), Some(entry));
+    }
+}
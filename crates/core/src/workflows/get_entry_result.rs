@@ -1,69 +1,108 @@
 use crate::{
     context::Context,
     network::{self, actions::query::QueryMethod, query::NetworkQueryResult},
-    nucleus, NEW_RELIC_LICENSE_KEY,
+    nucleus,
+    wasm_engine::callback::conflict_resolution::resolve_conflict,
+    NEW_RELIC_LICENSE_KEY,
 };
-use holochain_core_types::{chain_header::ChainHeader, time::Timeout};
+use holochain_core_types::time::Timeout;
 
 use holochain_core_types::{
-    crud_status::CrudStatus, entry::EntryWithMetaAndHeader, error::HolochainError,
+    crud_status::CrudStatus,
+    entry::{Entry, EntryWithMeta, EntryWithMetaAndHeader},
+    error::HolochainError,
 };
-use holochain_persistence_api::cas::content::Address;
+use holochain_persistence_api::cas::content::{Address, AddressableContent};
 use holochain_wasm_utils::api_serialization::get_entry::{
-    GetEntryArgs, GetEntryResult, StatusRequestKind,
+    CachePolicy, GetEntryArgs, GetEntryResult, StatusRequestKind,
+};
+use std::{
+    collections::{BTreeSet, HashMap, VecDeque},
+    sync::Arc,
 };
-use std::sync::Arc;
 
-/// Get Entry workflow
-#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
-pub async fn get_entry_with_meta_workflow<'a>(
-    context: &'a Arc<Context>,
-    address: &'a Address,
-    timeout: &'a Timeout,
+/// Looks the entry up in the local DHT shard only.
+/// Returns None if we don't hold it or can't find its headers.
+fn get_entry_with_meta_locally(
+    context: &Arc<Context>,
+    address: &Address,
 ) -> Result<Option<EntryWithMetaAndHeader>, HolochainError> {
-    // 1. Try to get the entry locally (i.e. local DHT shard)
     let maybe_entry_with_meta =
         nucleus::actions::get_entry::get_entry_with_meta(context, address.clone())?;
-    // 2. No result, so try on the network
-    let method = QueryMethod::Entry(address.clone());
-    if let None = maybe_entry_with_meta {
-        let response =
-            network::actions::query::query(context.clone(), method.clone(), timeout.clone())
-                .await?;
-        match response {
-            NetworkQueryResult::Entry(maybe_entry) => Ok(maybe_entry),
-            _ => Err(HolochainError::ErrorGeneric(
-                "Wrong respond type for Entry".to_string(),
-            )),
-        }
-    } else {
-        // 3. If we've found the entry locally we also need to get the header from the local state:
-        let entry = maybe_entry_with_meta
-            .ok_or_else(|| HolochainError::ErrorGeneric("Could not get entry".to_string()))?;
-        match context
+    match maybe_entry_with_meta {
+        None => Ok(None),
+        // If we've found the entry locally we also need to get the header from the local state:
+        Some(entry_with_meta) => match context
             .state()
             .ok_or_else(|| HolochainError::ErrorGeneric("Could not get state".to_string()))?
             .get_headers(address.clone())
         {
             Ok(headers) => Ok(Some(EntryWithMetaAndHeader {
-                entry_with_meta: entry.clone(),
+                entry_with_meta,
                 headers,
             })),
-            Err(_) => {
-                let response = network::actions::query::query(
-                    context.clone(),
-                    method.clone(),
-                    timeout.clone(),
-                )
-                .await?;
-                match response {
-                    NetworkQueryResult::Entry(maybe_entry) => Ok(maybe_entry),
-                    _ => Err(HolochainError::ErrorGeneric(
-                        "Wrong respond type for Entry".to_string(),
-                    )),
-                }
+            Err(_) => Ok(None),
+        },
+    }
+}
+
+/// Looks the entry up without going to the network, i.e. in the local DHT shard and,
+/// if the cache policy allows it, in the query cache.
+pub(crate) fn get_entry_with_meta_without_network(
+    context: &Arc<Context>,
+    address: &Address,
+    cache_policy: &CachePolicy,
+) -> Result<Option<EntryWithMetaAndHeader>, HolochainError> {
+    if let Some(entry_with_meta_and_headers) = get_entry_with_meta_locally(context, address)? {
+        return Ok(Some(entry_with_meta_and_headers));
+    }
+    Ok(context
+        .query_cache
+        .lock()
+        .expect("Could not lock query cache")
+        .get_entry(address, cache_policy))
+}
+
+/// Get Entry workflow
+#[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
+pub async fn get_entry_with_meta_workflow<'a>(
+    context: &'a Arc<Context>,
+    address: &'a Address,
+    timeout: &'a Timeout,
+) -> Result<Option<EntryWithMetaAndHeader>, HolochainError> {
+    get_entry_with_meta_cached(context, address, timeout, &CachePolicy::NetworkOnly).await
+}
+
+/// Get Entry workflow for reads that may be answered from the query cache
+pub(crate) async fn get_entry_with_meta_cached<'a>(
+    context: &'a Arc<Context>,
+    address: &'a Address,
+    timeout: &'a Timeout,
+    cache_policy: &'a CachePolicy,
+) -> Result<Option<EntryWithMetaAndHeader>, HolochainError> {
+    // 1. Try to get the entry locally (i.e. local DHT shard or query cache)
+    let maybe_entry_with_meta_and_headers =
+        get_entry_with_meta_without_network(context, address, cache_policy)?;
+    if maybe_entry_with_meta_and_headers.is_some() || *cache_policy == CachePolicy::LocalOnly {
+        return Ok(maybe_entry_with_meta_and_headers);
+    }
+    // 2. No result, so try on the network
+    let method = QueryMethod::Entry(address.clone());
+    let response = network::actions::query::query(context.clone(), method, timeout.clone()).await?;
+    match response {
+        NetworkQueryResult::Entry(maybe_entry) => {
+            if let Some(entry_with_meta_and_headers) = &maybe_entry {
+                context
+                    .query_cache
+                    .lock()
+                    .expect("Could not lock query cache")
+                    .insert_entry(address.clone(), entry_with_meta_and_headers.clone());
             }
+            Ok(maybe_entry)
         }
+        _ => Err(HolochainError::ErrorGeneric(
+            "Wrong respond type for Entry".to_string(),
+        )),
     }
 }
 
@@ -72,93 +111,342 @@ pub async fn get_entry_with_meta_workflow<'a>(
 pub async fn get_entry_result_workflow<'a>(
     context: &'a Arc<Context>,
     args: &'a GetEntryArgs,
+) -> Result<GetEntryResult, HolochainError> {
+    let maybe_entry_with_meta_and_headers = get_entry_with_meta_cached(
+        context,
+        &args.address,
+        &args.options.timeout,
+        &args.options.cache_policy,
+    )
+    .await?;
+    get_entry_result_from(context, args, maybe_entry_with_meta_and_headers).await
+}
+
+/// Returns the addresses of all entries that updated or deleted the given entry.
+/// Falls back to the single crud-link for results of nodes that don't list all of them.
+fn links_update_delete(entry_with_meta: &EntryWithMeta) -> Vec<Address> {
+    if entry_with_meta.links_update_delete.is_empty() {
+        entry_with_meta
+            .maybe_link_update_delete
+            .iter()
+            .cloned()
+            .collect()
+    } else {
+        entry_with_meta.links_update_delete.clone()
+    }
+}
+
+/// Picks the head that counts as the latest entry of a forked update graph.
+/// If the entry type defines a conflict resolution callback it decides, otherwise
+/// the head with the lowest address wins. The heads are sorted by address, so both
+/// come to the same result on every node that sees the same graph.
+fn resolve_heads(
+    context: &Arc<Context>,
+    entry: &Entry,
+    heads: &[&EntryWithMetaAndHeader],
+) -> Result<Option<Address>, HolochainError> {
+    if heads.len() <= 1 {
+        return Ok(heads
+            .first()
+            .map(|head| head.entry_with_meta.entry.address()));
+    }
+    if let Entry::App(app_entry_type, _) = entry {
+        let resolves_conflicts = context
+            .get_dna()
+            .and_then(|dna| {
+                dna.get_entry_type_def(&String::from(app_entry_type.clone()))
+                    .map(|entry_type_def| entry_type_def.resolves_conflicts)
+            })
+            .unwrap_or(false);
+        if resolves_conflicts {
+            let head_entries = heads
+                .iter()
+                .map(|head| head.entry_with_meta.entry.clone())
+                .collect();
+            return resolve_conflict(app_entry_type, head_entries, context.clone()).map(Some);
+        }
+    }
+    Ok(Some(heads[0].entry_with_meta.entry.address()))
+}
+
+/// Builds the GetEntryResult for args starting from the already retrieved entry at args.address,
+/// getting further entries of its history as requested by the options.
+/// Concurrent updates fork the history, so all branches get followed to find every head.
+pub(crate) async fn get_entry_result_from<'a>(
+    context: &'a Arc<Context>,
+    args: &'a GetEntryArgs,
+    maybe_entry_with_meta_and_headers: Option<EntryWithMetaAndHeader>,
 ) -> Result<GetEntryResult, HolochainError> {
     // Setup
     let mut entry_result = GetEntryResult::new(args.options.status_request.clone(), None);
-    let mut maybe_address = Some(args.address.clone());
-
-    // Accumulate entry history in a loop unless only request initial.
-    while maybe_address.is_some() {
-        let address = maybe_address.unwrap();
-        maybe_address = None;
-        // Try to get entry
-        let maybe_entry_with_meta_and_headers =
-            get_entry_with_meta_workflow(context, &address, &args.options.timeout).await?;
-
-        // Entry found
-        if let Some(entry_with_meta_and_headers) = maybe_entry_with_meta_and_headers {
-            // Erase history if request is for latest
-            if args.options.status_request == StatusRequestKind::Latest
-                && entry_with_meta_and_headers.entry_with_meta.crud_status == CrudStatus::Deleted
-            {
-                entry_result.clear();
-                break;
-            }
+    let initial = match maybe_entry_with_meta_and_headers {
+        Some(entry_with_meta_and_headers) => entry_with_meta_and_headers,
+        None => return Ok(entry_result),
+    };
+    let headers_of = |entry_with_meta_and_headers: &EntryWithMetaAndHeader| {
+        if args.options.headers {
+            entry_with_meta_and_headers.headers.clone()
+        } else {
+            Vec::new()
+        }
+    };
 
-            // Add entry
-            let headers: Vec<ChainHeader> = if args.options.headers {
-                entry_with_meta_and_headers.headers
-            } else {
-                Vec::new()
-            };
-            entry_result.push(&entry_with_meta_and_headers.entry_with_meta, headers);
+    if args.options.status_request == StatusRequestKind::Initial {
+        entry_result.push(&initial.entry_with_meta, headers_of(&initial));
+        return Ok(entry_result);
+    }
 
-            if args.options.status_request == StatusRequestKind::Initial {
-                break;
+    // Walk the update graph breadth first, following every crud-link of entries
+    // that are not deleted. Entries without a successor we could get are its heads.
+    let initial_entry = initial.entry_with_meta.entry.clone();
+    let mut found: HashMap<Address, bool> = HashMap::new();
+    found.insert(initial_entry.address(), true);
+    let mut visited: Vec<EntryWithMetaAndHeader> = Vec::new();
+    let mut head_addresses: BTreeSet<Address> = BTreeSet::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(initial);
+    while let Some(entry_with_meta_and_headers) = queue.pop_front() {
+        let entry_with_meta = &entry_with_meta_and_headers.entry_with_meta;
+        if entry_with_meta.crud_status != CrudStatus::Deleted {
+            let mut has_successor = false;
+            for next_address in links_update_delete(entry_with_meta) {
+                let next_found = match found.get(&next_address) {
+                    Some(next_found) => *next_found,
+                    None => {
+                        let maybe_next = get_entry_with_meta_cached(
+                            context,
+                            &next_address,
+                            &args.options.timeout,
+                            &args.options.cache_policy,
+                        )
+                        .await?;
+                        found.insert(next_address, maybe_next.is_some());
+                        let next_found = maybe_next.is_some();
+                        queue.extend(maybe_next);
+                        next_found
+                    }
+                };
+                has_successor = has_successor || next_found;
+            }
+            if !has_successor {
+                head_addresses.insert(entry_with_meta.entry.address());
             }
+        }
+        visited.push(entry_with_meta_and_headers);
+    }
 
-            // Follow crud-link if possible
-            if entry_with_meta_and_headers
-                .entry_with_meta
-                .maybe_link_update_delete
-                .is_some()
-                && entry_with_meta_and_headers.entry_with_meta.crud_status != CrudStatus::Deleted
-                && args.options.status_request != StatusRequestKind::Initial
-            {
-                maybe_address = Some(
-                    entry_with_meta_and_headers
-                        .entry_with_meta
-                        .maybe_link_update_delete
-                        .unwrap(),
+    let heads: Vec<&EntryWithMetaAndHeader> = head_addresses
+        .iter()
+        .filter_map(|address| {
+            visited
+                .iter()
+                .find(|visited| visited.entry_with_meta.entry.address() == *address)
+        })
+        .collect();
+    let maybe_latest_address = resolve_heads(context, &initial_entry, &heads)?;
+    entry_result.heads = head_addresses.into_iter().collect();
+
+    match args.options.status_request {
+        StatusRequestKind::All => {
+            // The resolved head goes last so it is the latest entry of the history
+            let (latest, others): (Vec<_>, Vec<_>) = visited.iter().partition(|visited| {
+                Some(visited.entry_with_meta.entry.address()) == maybe_latest_address
+            });
+            for entry_with_meta_and_headers in others.into_iter().chain(latest) {
+                entry_result.push(
+                    &entry_with_meta_and_headers.entry_with_meta,
+                    headers_of(entry_with_meta_and_headers),
                 );
             }
         }
+        _ => {
+            // Without a head all branches got deleted
+            match visited.iter().find(|visited| {
+                Some(visited.entry_with_meta.entry.address()) == maybe_latest_address
+            }) {
+                Some(latest) => entry_result.push(&latest.entry_with_meta, headers_of(latest)),
+                None => entry_result.clear(),
+            }
+        }
     }
 
     Ok(entry_result)
 }
 
-//#[cfg(test)]
-//pub mod tests {
-//    use crate::instance::tests::test_context_with_state;
-//    use futures::executor::block_on;
-//    use holochain_core_types::{
-//        cas::content::AddressableContent,
-//        crud_status::{create_crud_status_eav, CrudStatus},
-//        entry::test_entry,
-//    };
-//    use holochain_wasm_utils::api_serialization::get_entry::*;
-//
-//    #[test]
-//    fn can_get_entry_result_workflow() {
-//        let entry = test_entry();
-//        let context = test_context_with_state();
-//        let args = GetEntryArgs {
-//            address: entry.address(),
-//            options: GetEntryOptions {
-//                status_request: StatusRequestKind::Latest,
-//            },
-//        };
-//        let maybe_entry_history = block_on(super::get_entry_result_workflow(&context, &args));
-////        assert_eq!(0, maybe_entry_history.unwrap().entries.len());
-////        context.state().unwrap().dht().add(&entry).unwrap();
-////        let status_eav = create_crud_status_eav(&entry.address(), CrudStatus::Live);
-////        let meta_storage = &context.state().unwrap().dht().meta_storage().clone();
-////        (*meta_storage.write().unwrap())
-////            .add_eavi(&status_eav)
-////            .unwrap();
-////        let maybe_entry_history = block_on(super::get_entry_result_workflow(&context, &args));
-////        let entry_history = maybe_entry_history.unwrap();
-////        assert_eq!(&entry, entry_history.entries.iter().next().unwrap());
-//    }
-//}
+#[cfg(test)]
+pub mod tests {
+    use super::{get_entry_result_from, resolve_heads};
+    use crate::{
+        context::Context,
+        instance::tests::test_instance_and_context,
+        wasm_engine::callback::conflict_resolution::tests::{
+            test_conflict_resolution_instance, test_heads,
+        },
+    };
+    use holochain_core_types::{
+        crud_status::CrudStatus,
+        entry::{test_entry, test_entry_with_value, Entry, EntryWithMeta, EntryWithMetaAndHeader},
+        error::HolochainError,
+        time::Timeout,
+    };
+    use holochain_persistence_api::cas::content::{Address, AddressableContent};
+    use holochain_wasm_utils::api_serialization::get_entry::{
+        CachePolicy, GetEntryArgs, GetEntryOptions, GetEntryResult, GetEntryResultType,
+        StatusRequestKind,
+    };
+    use std::sync::Arc;
+
+    fn with_meta(
+        entry: &Entry,
+        crud_status: CrudStatus,
+        mut links_update_delete: Vec<Address>,
+    ) -> EntryWithMetaAndHeader {
+        links_update_delete.sort();
+        EntryWithMetaAndHeader {
+            entry_with_meta: EntryWithMeta {
+                entry: entry.clone(),
+                crud_status,
+                maybe_link_update_delete: links_update_delete.first().cloned(),
+                links_update_delete,
+            },
+            headers: Vec::new(),
+        }
+    }
+
+    /// Caches the given heads with the given status and a deleted branch.
+    /// Returns `test_entry` as concurrently updated to all of them.
+    fn cache_forked_graph(
+        context: &Arc<Context>,
+        heads: &[Entry],
+        heads_status: CrudStatus,
+    ) -> EntryWithMetaAndHeader {
+        let deleted = test_entry_with_value("\"deleted branch\"");
+        let mut query_cache = context.query_cache.lock().unwrap();
+        for entry in heads {
+            query_cache.insert_entry(entry.address(), with_meta(entry, heads_status, Vec::new()));
+        }
+        query_cache.insert_entry(
+            deleted.address(),
+            with_meta(&deleted, CrudStatus::Deleted, Vec::new()),
+        );
+        let successors = heads
+            .iter()
+            .chain(Some(&deleted))
+            .map(|entry| entry.address())
+            .collect();
+        with_meta(&test_entry(), CrudStatus::Modified, successors)
+    }
+
+    fn get_entry_result(
+        context: &Arc<Context>,
+        initial: EntryWithMetaAndHeader,
+        status_request: StatusRequestKind,
+    ) -> Result<GetEntryResult, HolochainError> {
+        let mut options = GetEntryOptions::new(status_request, true, false, Timeout::default());
+        options.cache_policy = CachePolicy::LocalOnly;
+        let args = GetEntryArgs {
+            address: test_entry().address(),
+            options,
+        };
+        context.block_on(get_entry_result_from(context, &args, Some(initial)))
+    }
+
+    #[test]
+    fn resolve_heads_picks_the_lowest_head_without_callback() {
+        let dna = test_utils::create_test_dna_with_wat("test_zome", None);
+        let (_instance, context) = test_instance_and_context(dna, None).unwrap();
+        let heads = test_heads();
+        let head_a = with_meta(&heads[0], CrudStatus::Live, Vec::new());
+        let head_b = with_meta(&heads[1], CrudStatus::Live, Vec::new());
+
+        assert_eq!(resolve_heads(&context, &test_entry(), &[]), Ok(None));
+        assert_eq!(
+            resolve_heads(&context, &test_entry(), &[&head_b]),
+            Ok(Some(heads[1].address()))
+        );
+        assert_eq!(
+            resolve_heads(&context, &test_entry(), &[&head_a, &head_b]),
+            Ok(Some(heads[0].address()))
+        );
+    }
+
+    #[test]
+    fn resolve_heads_lets_the_callback_pick() {
+        let heads = test_heads();
+        let (_instance, context) =
+            test_conflict_resolution_instance(&format!("{{\"Ok\":\"{}\"}}", heads[1].address()));
+        let head_a = with_meta(&heads[0], CrudStatus::Live, Vec::new());
+        let head_b = with_meta(&heads[1], CrudStatus::Live, Vec::new());
+
+        assert_eq!(
+            resolve_heads(&context, &test_entry(), &[&head_a, &head_b]),
+            Ok(Some(heads[1].address()))
+        );
+        // a single head is the latest entry without asking the callback
+        assert_eq!(
+            resolve_heads(&context, &test_entry(), &[&head_a]),
+            Ok(Some(heads[0].address()))
+        );
+    }
+
+    #[test]
+    fn get_entry_result_follows_all_branches() {
+        let dna = test_utils::create_test_dna_with_wat("test_zome", None);
+        let (_instance, context) = test_instance_and_context(dna, None).unwrap();
+        let heads = test_heads();
+        let initial = cache_forked_graph(&context, &heads, CrudStatus::Live);
+
+        let result =
+            get_entry_result(&context, initial.clone(), StatusRequestKind::Latest).unwrap();
+        let head_addresses: Vec<Address> = heads.iter().map(|entry| entry.address()).collect();
+        assert_eq!(result.heads, head_addresses);
+        assert!(result.has_conflict());
+        assert_eq!(result.latest(), Some(heads[0].clone()));
+
+        let result = get_entry_result(&context, initial, StatusRequestKind::All).unwrap();
+        assert_eq!(result.heads, head_addresses);
+        match &result.result {
+            // the initial entry, both heads and the deleted branch
+            GetEntryResultType::All(history) => assert_eq!(history.items.len(), 4),
+            other => panic!("unexpected result: {:?}", other),
+        }
+        assert_eq!(result.latest(), Some(heads[0].clone()));
+    }
+
+    #[test]
+    fn get_entry_result_is_empty_if_all_branches_got_deleted() {
+        let dna = test_utils::create_test_dna_with_wat("test_zome", None);
+        let (_instance, context) = test_instance_and_context(dna, None).unwrap();
+        let initial = cache_forked_graph(&context, &test_heads(), CrudStatus::Deleted);
+
+        let result = get_entry_result(&context, initial, StatusRequestKind::Latest).unwrap();
+        assert!(result.heads.is_empty());
+        assert!(!result.found());
+    }
+
+    #[test]
+    fn get_entry_result_uses_the_head_picked_by_the_callback() {
+        let heads = test_heads();
+        let (_instance, context) =
+            test_conflict_resolution_instance(&format!("{{\"Ok\":\"{}\"}}", heads[1].address()));
+        let initial = cache_forked_graph(&context, &heads, CrudStatus::Live);
+
+        let result =
+            get_entry_result(&context, initial.clone(), StatusRequestKind::Latest).unwrap();
+        assert_eq!(result.latest(), Some(heads[1].clone()));
+        // the picked head goes last in the history
+        let result = get_entry_result(&context, initial, StatusRequestKind::All).unwrap();
+        assert_eq!(result.latest(), Some(heads[1].clone()));
+    }
+
+    #[test]
+    fn get_entry_result_rejects_callbacks_that_pick_no_head() {
+        let (_instance, context) = test_conflict_resolution_instance(&format!(
+            "{{\"Ok\":\"{}\"}}",
+            test_entry().address()
+        ));
+        let initial = cache_forked_graph(&context, &test_heads(), CrudStatus::Live);
+
+        assert!(get_entry_result(&context, initial, StatusRequestKind::Latest).is_err());
+    }
+}
@@ -1,7 +1,8 @@
 use crate::{
     context::Context,
     network::{
-        actions::query::{query, QueryMethod},
+        actions::query::{links_crud_status, query, QueryMethod},
+        handler::query::get_links,
         query::{
             GetLinksNetworkQuery, GetLinksNetworkResult, GetLinksQueryConfiguration,
             NetworkQueryResult,
@@ -10,22 +11,74 @@ use crate::{
     NEW_RELIC_LICENSE_KEY,
 };
 
-use holochain_core_types::error::HolochainError;
-use holochain_wasm_utils::api_serialization::get_links::{
-    GetLinksArgs, GetLinksResult, LinksResult,
+use holochain_core_types::{
+    error::HolochainError,
+    network::query::{GetLinkData, GetLinksCursor},
+};
+use holochain_wasm_utils::api_serialization::{
+    get_entry::CachePolicy,
+    get_links::{GetLinksArgs, GetLinksResult, LinksResult},
 };
 use std::sync::Arc;
 
+fn links_result(links: Vec<GetLinkData>, next_cursor: Option<GetLinksCursor>) -> GetLinksResult {
+    let links = links
+        .into_iter()
+        .map(|get_entry_crud| LinksResult {
+            address: get_entry_crud.target.clone(),
+            headers: get_entry_crud.headers.unwrap_or_default(),
+            status: get_entry_crud.crud_status,
+            tag: get_entry_crud.tag.clone(),
+        })
+        .collect::<Vec<LinksResult>>();
+    GetLinksResult::new(links).with_next_cursor(next_cursor)
+}
+
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_CORE)]
 pub async fn get_link_result_workflow<'a>(
     context: &'a Arc<Context>,
     link_args: &'a GetLinksArgs,
 ) -> Result<GetLinksResult, HolochainError> {
+    let cache_policy = &link_args.options.cache_policy;
     let config = GetLinksQueryConfiguration {
         headers: link_args.options.headers,
         pagination: link_args.options.pagination.clone(),
         sort_order: link_args.options.sort_order.clone(),
     };
+
+    // Without the network we serve the links our own DHT shard holds
+    // and fall back to results of earlier network queries
+    if *cache_policy == CachePolicy::LocalOnly {
+        let (links, next_cursor) = get_links(
+            context,
+            link_args.entry_address.clone(),
+            link_args.link_type.clone(),
+            link_args.tag.clone(),
+            links_crud_status(&link_args.options.status_request),
+            config,
+        )?;
+        if links.is_empty() {
+            let maybe_cached_links = context
+                .query_cache
+                .lock()
+                .expect("Could not lock query cache")
+                .get_links(link_args, cache_policy);
+            if let Some(cached_links) = maybe_cached_links {
+                return Ok(cached_links);
+            }
+        }
+        return Ok(links_result(links, next_cursor));
+    }
+
+    let maybe_cached_links = context
+        .query_cache
+        .lock()
+        .expect("Could not lock query cache")
+        .get_links(link_args, cache_policy);
+    if let Some(cached_links) = maybe_cached_links {
+        return Ok(cached_links);
+    }
+
     let method = QueryMethod::Link(link_args.clone(), GetLinksNetworkQuery::Links(config));
     let response = query(context.clone(), method, link_args.options.timeout.clone()).await?;
 
@@ -37,21 +90,126 @@ pub async fn get_link_result_workflow<'a>(
     }?;
 
     match links_result {
-        GetLinksNetworkResult::Links(links) => {
-            let get_links_result = links
-                .into_iter()
-                .map(|get_entry_crud| LinksResult {
-                    address: get_entry_crud.target.clone(),
-                    headers: get_entry_crud.headers.unwrap_or_default(),
-                    status: get_entry_crud.crud_status,
-                    tag: get_entry_crud.tag.clone(),
-                })
-                .collect::<Vec<LinksResult>>();
-
-            Ok(GetLinksResult::new(get_links_result))
+        GetLinksNetworkResult::Links(links, next_cursor) => {
+            let get_links_result = links_result(links, next_cursor);
+            context
+                .query_cache
+                .lock()
+                .expect("Could not lock query cache")
+                .insert_links(link_args, get_links_result.clone());
+            Ok(get_links_result)
         }
         _ => Err(HolochainError::ErrorGeneric(
             "Could not get links".to_string(),
         )),
     }
 }
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        dht::actions::hold_aspect::hold_aspect, instance::tests::test_instance_and_context,
+    };
+    use holochain_core_types::{
+        agent::test_agent_id,
+        chain_header::ChainHeader,
+        crud_status::CrudStatus,
+        entry::{test_entry, test_entry_b, Entry},
+        link::link_data::LinkData,
+        network::entry_aspect::EntryAspect,
+        time::test_iso_8601,
+    };
+    use holochain_persistence_api::cas::content::AddressableContent;
+    use holochain_wasm_utils::api_serialization::get_links::GetLinksOptions;
+
+    fn test_header(entry: &Entry) -> ChainHeader {
+        ChainHeader::new(
+            &entry.entry_type(),
+            &entry.address(),
+            &Vec::new(),
+            &None,
+            &None,
+            &None,
+            &test_iso_8601(),
+        )
+    }
+
+    #[test]
+    fn local_only_gets_links_from_the_local_shard() {
+        let dna = test_utils::create_test_dna_with_wat("test_zome", None);
+        let (_instance, context) =
+            test_instance_and_context(dna, None).expect("Could not initialize test instance");
+
+        let base = test_entry();
+        let target = test_entry_b();
+        let link_data = LinkData::new_add(
+            &base.address(),
+            &target.address(),
+            "test-tag",
+            "test-link",
+            test_header(&base),
+            test_agent_id(),
+        );
+        let link_entry = Entry::LinkAdd(link_data.clone());
+        let aspects = vec![
+            EntryAspect::Content(base.clone(), test_header(&base)),
+            EntryAspect::Content(link_entry.clone(), test_header(&link_entry)),
+            EntryAspect::LinkAdd(link_data, test_header(&link_entry)),
+        ];
+        for aspect in aspects {
+            context
+                .block_on(hold_aspect(aspect, context.clone()))
+                .unwrap();
+        }
+
+        let args = GetLinksArgs {
+            entry_address: base.address(),
+            link_type: "test-link".into(),
+            tag: "test-tag".into(),
+            options: GetLinksOptions {
+                cache_policy: CachePolicy::LocalOnly,
+                ..Default::default()
+            },
+        };
+        let result = context
+            .block_on(get_link_result_workflow(&context, &args))
+            .unwrap();
+        assert_eq!(result.addresses(), vec![target.address()]);
+    }
+
+    #[test]
+    fn local_only_falls_back_to_cached_links() {
+        let dna = test_utils::create_test_dna_with_wat("test_zome", None);
+        let (_instance, context) =
+            test_instance_and_context(dna, None).expect("Could not initialize test instance");
+
+        let base = test_entry();
+        let target = test_entry_b();
+        let args = GetLinksArgs {
+            entry_address: base.address(),
+            link_type: "test-link".into(),
+            tag: "test-tag".into(),
+            options: GetLinksOptions {
+                cache_policy: CachePolicy::LocalOnly,
+                ..Default::default()
+            },
+        };
+        let cached_links = GetLinksResult::new(vec![LinksResult {
+            address: target.address(),
+            headers: Vec::new(),
+            tag: "test-tag".into(),
+            status: CrudStatus::Live,
+        }]);
+        context
+            .query_cache
+            .lock()
+            .unwrap()
+            .insert_links(&args, cached_links);
+
+        let result = context
+            .block_on(get_link_result_workflow(&context, &args))
+            .unwrap();
+        assert_eq!(result.addresses(), vec![target.address()]);
+    }
+}
@@ -21,7 +21,7 @@ pub async fn get_link_result_count_workflow<'a>(
 
     let links_result = match response {
         NetworkQueryResult::Links(link_result, _, _) => Ok(link_result),
-        NetworkQueryResult::Entry(_) => Err(HolochainError::ErrorGeneric(
+        _ => Err(HolochainError::ErrorGeneric(
             "Could not get link".to_string(),
         )),
     }?;
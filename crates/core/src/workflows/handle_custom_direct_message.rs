@@ -52,6 +52,7 @@ pub async fn handle_custom_direct_message(
         message: direct_message,
         msg_id,
         is_response: true,
+        deliver_later: false,
     };
 
     let action_wrapper = ActionWrapper::new(Action::SendDirectMessage((direct_message_data, None)));
@@ -4,7 +4,15 @@ use crate::{
     NEW_RELIC_LICENSE_KEY,
 };
 
-use crate::{nucleus::validation::ValidationError, workflows::validation_package};
+use crate::{
+    nucleus::validation::ValidationError,
+    workflows::{
+        chain_fork::record_chain_fork_workflow,
+        validation_package,
+        validation_receipt::send_validation_receipt_workflow,
+        warrant::{issue_warrant_workflow, verify_header_authenticity},
+    },
+};
 use holochain_core_types::{
     error::HolochainError,
     network::entry_aspect::EntryAspect,
@@ -44,13 +52,34 @@ pub async fn hold_entry_workflow(
     };
 
     // 3. Validate the entry
-    validate_entry(
+    let validation_result = validate_entry(
         entry_with_header.entry.clone(),
         None,
         validation_data,
-        &context
-    ).await
-    .map_err(|err| {
+        &context,
+    )
+    .await;
+
+    // 3.1 If the entry is invalid, let the network know who authored it
+    if let Err(ValidationError::Fail(reason)) = &validation_result {
+        if let Err(err) = issue_warrant_workflow(
+            &entry_with_header.entry,
+            &entry_with_header.header,
+            reason.clone(),
+            context.clone(),
+        )
+        .await
+        {
+            log_warn!(
+                context,
+                "workflow/hold_entry: Could not issue warrant for invalid entry {}: {:?}",
+                entry_with_header.entry.address(),
+                err,
+            );
+        }
+    }
+
+    validation_result.map_err(|err| {
         if let ValidationError::UnresolvedDependencies(dependencies) = &err {
             log_debug!(context, "workflow/hold_entry: {} could not be validated due to unresolved dependencies and will be tried later. List of missing dependencies: {:?}",
                 entry_with_header.entry.address(),
@@ -72,7 +101,16 @@ pub async fn hold_entry_workflow(
         entry_with_header.entry.address()
     );
 
-    // 4. If valid store the entry aspect in the local DHT shard
+    // 4. Record it if the header forks the source chain of its author.
+    //    The entry itself is valid and gets held anyway. Headers from older nodes are not
+    //    signed as a whole, they get held but don't count as activity of their author.
+    //    A header signature that doesn't verify means the header was tampered with.
+    if entry_with_header.header.header_signature().is_some() {
+        verify_header_authenticity(&entry_with_header.header)?;
+        record_chain_fork_workflow(&entry_with_header.header, &context);
+    }
+
+    // 5. If valid store the entry aspect in the local DHT shard
     let aspect = EntryAspect::Content(
         entry_with_header.entry.clone(),
         entry_with_header.header.clone(),
@@ -85,6 +123,16 @@ pub async fn hold_entry_workflow(
         entry_with_header.entry.address()
     );
 
+    // 6. Let the author know we validated and hold their entry
+    if let Err(err) = send_validation_receipt_workflow(&entry_with_header.header, context.clone()) {
+        log_warn!(
+            context,
+            "workflow/hold_entry: Could not send validation receipt for entry {}: {:?}",
+            entry_with_header.entry.address(),
+            err,
+        );
+    }
+
     Ok(())
 }
 
@@ -29,8 +29,11 @@ pub async fn hold_update_workflow(
             log_debug!(context, "workflow/hold_update: Error was: {:?}", err);
             HolochainError::ValidationPending
         })?;
-    let validation_package = maybe_validation_package
-        .ok_or_else(|| "Could not get validation package from source".to_string())?;
+    let validation_package = maybe_validation_package.ok_or_else(|| {
+        let message = "Source did respond to request but did not deliver validation package! -> Add to pending";
+        log_debug!(context, "workflow/hold_update: {}", message);
+        HolochainError::ValidationPending
+    })?;
 
     // get link from header
     let link = header
@@ -6,7 +6,9 @@ use crate::{
 
 use crate::{
     nucleus::validation::ValidationError,
-    workflows::{hold_entry::hold_entry_workflow, validation_package},
+    workflows::{
+        hold_entry::hold_entry_workflow, validation_package, warrant::issue_warrant_workflow,
+    },
 };
 use holochain_core_types::{
     entry::Entry,
@@ -55,13 +57,34 @@ pub async fn hold_link_workflow(
 
     // 3. Validate the entry
     log_debug!(context, "workflow/hold_link: validate...");
-    validate_entry(
+    let validation_result = validate_entry(
         entry_with_header.entry.clone(),
         None,
         validation_data,
-        &context
-    ).await
-    .map_err(|err| {
+        &context,
+    )
+    .await;
+
+    // 3.1 If the link is invalid, let the network know who authored it
+    if let Err(ValidationError::Fail(reason)) = &validation_result {
+        if let Err(err) = issue_warrant_workflow(
+            &entry_with_header.entry,
+            &entry_with_header.header,
+            reason.clone(),
+            context.clone(),
+        )
+        .await
+        {
+            log_warn!(
+                context,
+                "workflow/hold_link: Could not issue warrant for invalid link {:?}: {:?}",
+                entry_with_header.entry,
+                err,
+            );
+        }
+    }
+
+    validation_result.map_err(|err| {
         if let ValidationError::UnresolvedDependencies(dependencies) = &err {
             log_debug!(context, "workflow/hold_link: Link could not be validated due to unresolved dependencies and will be tried later. List of missing dependencies: {:?}", dependencies);
             HolochainError::ValidationPending
@@ -83,6 +106,7 @@ pub async fn hold_link_workflow(
     log_debug!(context, "workflow/hold_link: added! {:?}", link);
 
     //4. store link_add entry so we have all we need to respond to get links queries without any other network look-up
+    // (this also sends our validation receipt to the author)
     hold_entry_workflow(&entry_with_header, context.clone()).await?;
     log_debug!(
         context,
@@ -1,8 +1,18 @@
 #[autotrace]
+pub mod agent_activity;
+#[autotrace]
 pub mod application;
 #[autotrace]
 pub mod author_entry;
 #[autotrace]
+pub mod chain_fork;
+#[autotrace]
+pub mod countersigning;
+#[autotrace]
+pub mod custom_validation_package;
+#[autotrace]
+pub mod get_entries_result;
+#[autotrace]
 pub mod get_entry_result;
 #[autotrace]
 pub mod get_link_result;
@@ -22,6 +32,10 @@ pub mod hold_link;
 pub mod remove_link;
 #[autotrace]
 pub mod respond_validation_package_request;
+#[autotrace]
+pub mod validation_receipt;
+#[autotrace]
+pub mod warrant;
 
 use crate::{
     context::Context,
@@ -43,6 +57,7 @@ use crate::{
     },
 };
 use holochain_core_types::{
+    entry::entry_type::EntryType,
     error::HolochainError,
     validation::{ValidationPackage, ValidationPackageDefinition},
 };
@@ -63,7 +78,11 @@ pub(crate) async fn try_make_local_validation_package(
     let entry_header = &entry_with_header.header;
 
     match validation_package_definition {
-        ValidationPackageDefinition::Entry => {
+        // The package of an AgentId entry carries the author's private membrane proof
+        // so only the author can build it
+        ValidationPackageDefinition::Entry
+            if entry_with_header.entry.entry_type() != EntryType::AgentId =>
+        {
             Ok(ValidationPackage::only_header(entry_header.clone()))
         }
         _ => {
@@ -193,12 +212,17 @@ async fn validation_package(
 
 #[cfg(test)]
 pub mod tests {
-    use super::validation_package;
+    use super::{try_make_local_validation_package, validation_package};
     use crate::{
+        entry::CanPublish, instance::tests::test_instance_and_context_with_membrane_proof,
         network::entry_with_header::EntryWithHeader, nucleus::actions::tests::*,
         workflows::author_entry::author_entry,
     };
-    use holochain_core_types::entry::Entry;
+    use holochain_core_types::{
+        entry::{entry_type::EntryType, Entry},
+        membrane_proof::MembraneProof,
+        validation::ValidationPackageDefinition,
+    };
     use holochain_json_api::json::JsonString;
     use std::{thread, time};
 
@@ -246,6 +270,57 @@ pub mod tests {
             2
         );
     }
+
+    #[test]
+    fn test_membrane_proof_only_comes_from_the_author() {
+        let mut dna = test_dna();
+        dna.uuid = "test_membrane_proof_only_comes_from_the_author".to_string();
+        let netname = Some("test_membrane_proof_only_comes_from_the_author, the network");
+        let membrane_proof = MembraneProof::new("invite code 42");
+        let (instance1, context1) = test_instance_and_context_with_membrane_proof(
+            dna.clone(),
+            "jill",
+            netname,
+            Some(membrane_proof.clone()),
+        )
+        .expect("Could not create test instance");
+        let context1 = instance1.initialize_context(context1);
+        let (_instance2, context2) = instance_by_name("jack", dna, netname);
+
+        // jill's AgentId got committed right after the membrane proof
+        let header = context1
+            .state()
+            .unwrap()
+            .agent()
+            .iter_chain()
+            .find(|header| *header.entry_type() == EntryType::AgentId)
+            .expect("Must be able to get header of jill's agent entry");
+        let entry_with_header = EntryWithHeader {
+            entry: Entry::AgentId(context1.agent_id.clone()),
+            header,
+        };
+
+        // Membrane proofs are not published, so jack can't build the package locally..
+        assert!(!EntryType::MembraneProof.can_publish(&context1));
+        assert!(context2
+            .block_on(try_make_local_validation_package(
+                &entry_with_header,
+                &ValidationPackageDefinition::Entry,
+                context2.clone(),
+            ))
+            .is_err());
+
+        // ..but gets it from jill including the membrane proof
+        let validation_package = context2
+            .block_on(validation_package(&entry_with_header, context2.clone()))
+            .expect("Could not recover a validation package as the non-author")
+            .expect("Author must deliver the validation package of the agent entry");
+        assert_eq!(validation_package.membrane_proof, Some(membrane_proof));
+        let previous_header = validation_package
+            .previous_header
+            .expect("Package of the agent entry must hold the membrane proof's header");
+        assert_eq!(*previous_header.entry_type(), EntryType::MembraneProof);
+    }
 }
 
 /// Runs the given pending validation using the right holding workflow
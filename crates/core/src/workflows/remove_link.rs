@@ -41,8 +41,11 @@ pub async fn remove_link_workflow(
             HolochainError::ValidationPending
         })?;
 
-    let validation_package = maybe_validation_package
-        .ok_or_else(|| "Could not get validation package from source".to_string())?;
+    let validation_package = maybe_validation_package.ok_or_else(|| {
+        let message = "Source did respond to request but did not deliver validation package! -> Add to pending";
+        log_debug!(context, "workflow/remove_link: {}", message);
+        HolochainError::ValidationPending
+    })?;
     log_debug!(context, "workflow/remove_link: got validation package!");
 
     // 2. Create validation data struct
@@ -47,6 +47,7 @@ pub fn respond_validation_package_request(
         message: direct_message,
         msg_id,
         is_response: true,
+        deliver_later: false,
     };
 
     let action_wrapper = ActionWrapper::new(Action::SendDirectMessage((direct_message_data, None)));
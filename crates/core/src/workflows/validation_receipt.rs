@@ -0,0 +1,184 @@
+use crate::{
+    action::{Action, ActionWrapper},
+    context::Context,
+    instance::dispatch_action,
+};
+use holochain_core_types::{
+    chain_header::ChainHeader,
+    error::HolochainError,
+    signature::{Provenance, Signature},
+    validation_receipt::ValidationReceipt,
+};
+use holochain_dpki::utils::Verify;
+use holochain_persistence_api::cas::content::{Address, AddressableContent};
+use holochain_wasm_utils::api_serialization::crypto::CryptoMethod;
+use std::sync::Arc;
+
+/// Checks that the validator named in the receipt really signed it.
+pub fn verify_validation_receipt(receipt: &ValidationReceipt) -> Result<(), HolochainError> {
+    let payload =
+        ValidationReceipt::signing_payload(receipt.header_address(), receipt.entry_address());
+    if !receipt.validator().verify(payload)? {
+        return Err(HolochainError::ValidationFailed(format!(
+            "Signature of validation receipt from validator {} invalid",
+            receipt.validator().source(),
+        )));
+    }
+    Ok(())
+}
+
+/// Signs a receipt for the given header that we have just validated and are now holding,
+/// and sends it to all authors of the header.
+pub fn send_validation_receipt_workflow(
+    header: &ChainHeader,
+    context: Arc<Context>,
+) -> Result<(), HolochainError> {
+    let payload = ValidationReceipt::signing_payload(&header.address(), header.entry_address());
+    let signature = Signature::from(context.conductor_api.execute(payload, CryptoMethod::Sign)?);
+    let receipt = ValidationReceipt::new(
+        header,
+        Provenance::new(context.agent_id.address(), signature),
+    );
+
+    header
+        .provenances()
+        .iter()
+        .map(|provenance| provenance.source())
+        .filter(|author| *author != context.agent_id.address())
+        .for_each(|author| {
+            log_debug!(
+                context,
+                "workflow/send_validation_receipt: Sending receipt for entry {} to author {}",
+                header.entry_address(),
+                author,
+            );
+            let action_wrapper =
+                ActionWrapper::new(Action::SendValidationReceipt((author, receipt.clone())));
+            dispatch_action(context.action_channel(), action_wrapper);
+        });
+    Ok(())
+}
+
+/// Stores a validation receipt that got sent to us if it is about one of our
+/// headers and its entry, and was signed by the validator.
+pub fn hold_validation_receipt_workflow(
+    receipt: ValidationReceipt,
+    context: Arc<Context>,
+) -> Result<(), HolochainError> {
+    verify_validation_receipt(&receipt)?;
+
+    let agent = context
+        .state()
+        .ok_or_else(|| HolochainError::ErrorGeneric("State not initialized".to_string()))?
+        .agent();
+    let own_header = agent
+        .chain_store()
+        .get_chain_header(&agent.top_chain_header(), receipt.header_address())?
+        .ok_or_else(|| {
+            HolochainError::ErrorGeneric(format!(
+                "Got validation receipt for unknown header {}",
+                receipt.header_address()
+            ))
+        })?;
+    if own_header.entry_address() != receipt.entry_address() {
+        return Err(HolochainError::ErrorGeneric(format!(
+            "Got validation receipt for header {} with entry {} instead of {}",
+            receipt.header_address(),
+            receipt.entry_address(),
+            own_header.entry_address(),
+        )));
+    }
+
+    let action_wrapper = ActionWrapper::new(Action::AddValidationReceipt(receipt));
+    dispatch_action(context.action_channel(), action_wrapper);
+    Ok(())
+}
+
+/// Returns the addresses of all validators we got a receipt from for the header
+/// or entry with the given address.
+pub fn get_validation_receipt_signers(
+    address: &Address,
+    context: &Arc<Context>,
+) -> Result<Vec<Address>, HolochainError> {
+    let mut signers: Vec<Address> = context
+        .state()
+        .ok_or_else(|| HolochainError::ErrorGeneric("State not initialized".to_string()))?
+        .dht()
+        .get_validation_receipts(address)?
+        .into_iter()
+        .map(|receipt| receipt.validator().source())
+        .collect();
+    signers.sort();
+    signers.dedup();
+    Ok(signers)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        dht::dht_reducers::reduce_add_validation_receipt, instance::tests::test_context,
+        state::test_store,
+    };
+    use holochain_core_types::{chain_header::test_chain_header, entry::test_entry_b};
+    use test_utils::mock_signing::mock_signer;
+
+    fn test_signed_receipt(context: &Arc<Context>) -> ValidationReceipt {
+        let header = test_chain_header();
+        let signature = mock_signer(
+            ValidationReceipt::signing_payload(&header.address(), header.entry_address()),
+            &context.agent_id,
+        );
+        ValidationReceipt::new(
+            &header,
+            Provenance::new(context.agent_id.address(), Signature::from(signature)),
+        )
+    }
+
+    #[test]
+    fn signed_receipts_verify_and_get_stored() {
+        let context = test_context("alice", None);
+        let receipt = test_signed_receipt(&context);
+        assert_eq!(verify_validation_receipt(&receipt), Ok(()));
+
+        let store = test_store(context.clone());
+        let new_dht_store = reduce_add_validation_receipt(
+            &store.dht(),
+            &ActionWrapper::new(Action::AddValidationReceipt(receipt.clone())),
+        )
+        .expect("there should be a new store for adding a validation receipt");
+
+        assert_eq!(
+            new_dht_store.get_validation_receipts(&test_chain_header().address()),
+            Ok(vec![receipt.clone()])
+        );
+        assert_eq!(
+            new_dht_store.get_validation_receipts(test_chain_header().entry_address()),
+            Ok(vec![receipt])
+        );
+    }
+
+    #[test]
+    fn receipts_have_to_be_signed_for_header_and_entry() {
+        let context = test_context("alice", None);
+        let header = test_chain_header();
+        let signature = mock_signer(
+            ValidationReceipt::signing_payload(&header.address(), &test_entry_b().address()),
+            &context.agent_id,
+        );
+        let receipt = ValidationReceipt::new(
+            &header,
+            Provenance::new(context.agent_id.address(), Signature::from(signature)),
+        );
+        assert!(verify_validation_receipt(&receipt).is_err());
+    }
+
+    #[test]
+    fn forged_receipts_do_not_verify() {
+        let receipt = ValidationReceipt::new(
+            &test_chain_header(),
+            Provenance::new(Address::from("validator"), Signature::fake()),
+        );
+        assert!(verify_validation_receipt(&receipt).is_err());
+    }
+}
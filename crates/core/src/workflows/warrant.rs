@@ -0,0 +1,401 @@
+use crate::{
+    context::Context,
+    dht::actions::hold_aspect::hold_aspect,
+    network::{actions::publish_warrant::publish_warrant, entry_with_header::EntryWithHeader},
+    nucleus::validation::{validate_entry, ValidationError},
+    workflows::validation_package,
+};
+use holochain_core_types::{
+    chain_header::ChainHeader,
+    entry::Entry,
+    error::HolochainError,
+    network::entry_aspect::EntryAspect,
+    signature::{Provenance, Signature},
+    validation::{EntryLifecycle, ValidationData},
+    warrant::Warrant,
+};
+use holochain_dpki::utils::Verify;
+use holochain_persistence_api::cas::content::{Address, AddressableContent};
+use holochain_wasm_utils::api_serialization::crypto::CryptoMethod;
+use std::sync::Arc;
+
+/// Checks that all authors named in the header really signed it.
+/// Without this a warrant could be issued against an agent that had nothing
+/// to do with the invalid entry.
+pub(crate) fn verify_header_signatures(header: &ChainHeader) -> Result<(), HolochainError> {
+    for provenance in header.provenances() {
+        if !provenance.verify(header.entry_address().to_string())? {
+            return Err(HolochainError::ValidationFailed(format!(
+                "Signature of entry {} from author {} invalid",
+                header.entry_address(),
+                provenance.source(),
+            )));
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the author signed the whole header, including its link and timestamp,
+/// on top of the signatures of all provenances over its entry.
+/// Only headers that pass this can be used as evidence about the author's chain.
+pub(crate) fn verify_header_authenticity(header: &ChainHeader) -> Result<(), HolochainError> {
+    verify_header_signatures(header)?;
+    let author = header.author().ok_or_else(|| {
+        HolochainError::ValidationFailed(format!("Header {} has no author", header.address()))
+    })?;
+    let signature = header.header_signature().ok_or_else(|| {
+        HolochainError::ValidationFailed(format!(
+            "Header {} is not signed by its author",
+            header.address()
+        ))
+    })?;
+    if !Provenance::new(author.clone(), signature.clone()).verify(header.signing_payload())? {
+        return Err(HolochainError::ValidationFailed(format!(
+            "Header signature of {} from author {} invalid",
+            header.address(),
+            author,
+        )));
+    }
+    Ok(())
+}
+
+/// Checks the signatures of a warrant we have received:
+/// the warrantor must have signed it, the warranted header must have been signed
+/// by the agents the warrant is against and it must be the header of the warranted entry.
+/// This does not tell if the entry is invalid, see `confirm_invalid_entry`.
+pub fn verify_warrant(warrant: &Warrant) -> Result<(), HolochainError> {
+    let payload = Warrant::signing_payload(warrant.invalid_header(), warrant.reason());
+    if !warrant.warrantor().verify(payload)? {
+        return Err(HolochainError::ValidationFailed(format!(
+            "Signature of warrant from warrantor {} invalid",
+            warrant.warrantor().source(),
+        )));
+    }
+    if warrant.invalid_entry().address() != *warrant.invalid_header().entry_address() {
+        return Err(HolochainError::ValidationFailed(format!(
+            "Warrant header is not the header of entry {}",
+            warrant.invalid_entry().address(),
+        )));
+    }
+    verify_header_signatures(warrant.invalid_header())
+}
+
+/// Validates the warranted entry ourselves. A warrant is only believed if the entry
+/// really fails validation, otherwise any node could get others blocked by signing
+/// made up reasons about their valid entries.
+pub(crate) async fn confirm_invalid_entry(
+    warrant: &Warrant,
+    context: Arc<Context>,
+) -> Result<(), HolochainError> {
+    let entry_with_header = EntryWithHeader {
+        entry: warrant.invalid_entry().clone(),
+        header: warrant.invalid_header().clone(),
+    };
+    let validation_package = validation_package(&entry_with_header, context.clone())
+        .await?
+        .ok_or(HolochainError::ValidationPending)?;
+    let lifecycle = match entry_with_header.entry {
+        Entry::LinkAdd(_) | Entry::LinkRemove(_) => EntryLifecycle::Meta,
+        _ => EntryLifecycle::Dht,
+    };
+    let validation_data = ValidationData {
+        package: validation_package,
+        lifecycle,
+    };
+    match validate_entry(entry_with_header.entry, None, validation_data, &context).await {
+        Err(ValidationError::Fail(_)) => Ok(()),
+        Ok(()) => Err(HolochainError::ValidationFailed(format!(
+            "Warranted entry {} is valid",
+            warrant.invalid_entry().address(),
+        ))),
+        Err(ValidationError::UnresolvedDependencies(_)) => Err(HolochainError::ValidationPending),
+        Err(err) => Err(HolochainError::from(err)),
+    }
+}
+
+/// Creates a warrant about the given entry and its header which failed validation
+/// with the given reason, signs it with our agent key, holds it in our own shard
+/// and publishes it to the DHT.
+pub async fn issue_warrant_workflow(
+    invalid_entry: &Entry,
+    invalid_header: &ChainHeader,
+    reason: String,
+    context: Arc<Context>,
+) -> Result<Address, HolochainError> {
+    // We only issue warrants against agents that did sign the invalid data
+    verify_header_signatures(invalid_header)?;
+
+    let payload = Warrant::signing_payload(invalid_header, &reason);
+    let signature = Signature::from(context.conductor_api.execute(payload, CryptoMethod::Sign)?);
+    let warrant = Warrant::new(
+        invalid_entry.clone(),
+        invalid_header.clone(),
+        reason,
+        Provenance::new(context.agent_id.address(), signature),
+    );
+    log_debug!(
+        context,
+        "workflow/issue_warrant: issuing {:?}",
+        EntryAspect::Warrant(warrant.clone())
+    );
+
+    hold_aspect(EntryAspect::Warrant(warrant.clone()), context.clone()).await?;
+    publish_warrant(warrant, &context).await
+}
+
+/// Verifies a warrant that got sent to us and holds it if the warranted entry
+/// fails our own validation too. Only held warrants get agents blocked.
+/// Warrants we can't validate yet are dropped, gossip will bring them again.
+pub async fn hold_warrant_workflow(
+    warrant: Warrant,
+    context: Arc<Context>,
+) -> Result<(), HolochainError> {
+    let verified = match verify_warrant(&warrant) {
+        Ok(()) => confirm_invalid_entry(&warrant, context.clone()).await,
+        Err(err) => Err(err),
+    };
+    verified.map_err(|err| {
+        log_warn!(
+            context,
+            "workflow/hold_warrant: Received invalid warrant: {:?}",
+            err
+        );
+        err
+    })?;
+    hold_aspect(EntryAspect::Warrant(warrant), context.clone()).await
+}
+
+/// Returns true if this instance is configured to block warranted agents
+/// and we hold a warrant against the given agent.
+pub fn is_blocked_agent(agent: &Address, context: &Arc<Context>) -> bool {
+    context.auto_block_warranted_agents
+        && context
+            .state()
+            .map(|state| {
+                state
+                    .dht()
+                    .get_warrants(agent)
+                    .map(|warrants| warrants.iter().any(|w| w.authors().contains(agent)))
+                    .unwrap_or(false)
+            })
+            .unwrap_or(false)
+}
+
+#[cfg(test)]
+pub mod tests {
+    use self::wabt::Wat2Wasm;
+    use super::*;
+    use crate::{
+        action::{Action, ActionWrapper},
+        dht::dht_reducers::reduce_hold_aspect,
+        instance::tests::{test_context, test_instance_and_context},
+        state::test_store,
+    };
+    use holochain_core_types::{
+        chain_header::test_chain_header,
+        entry::{
+            cap_entries::{CapFunctions, CapTokenGrant, CapabilityType},
+            test_entry, test_entry_b,
+        },
+        time::test_iso_8601,
+    };
+    use test_utils::mock_signing::mock_signer;
+    use wabt;
+
+    /// Offsets of the callback results in the memory of the test WASM,
+    /// far enough from the input the ribosome writes at the start of the memory.
+    const PACKAGE_DEFINITION_OFFSET: u64 = 32768;
+    const VALIDATION_RESULT_OFFSET: u64 = 32800;
+
+    /// WASM whose validation callback rejects every app entry
+    fn test_rejecting_validation_wasm() -> Vec<u8> {
+        let package_definition = "\"Entry\"";
+        let validation_result = "\"invalid\"";
+        let encode = |offset: u64, result: &str| (offset << 32) | result.len() as u64;
+        Wat2Wasm::new()
+            .canonicalize_lebs(false)
+            .write_debug_names(true)
+            .convert(format!(
+                r#"
+(module
+    (memory 1)
+    (export "memory" (memory 0))
+    (data (i32.const {}) "{}")
+    (data (i32.const {}) "{}")
+    (func (export "__hdk_validate_agent_entry") (param $allocation i64) (result i64)
+        (i64.const 0)
+    )
+    (func (export "__hdk_get_validation_package_for_entry_type") (param $allocation i64) (result i64)
+        (i64.const {})
+    )
+    (func (export "__hdk_validate_app_entry") (param $allocation i64) (result i64)
+        (i64.const {})
+    )
+)
+"#,
+                PACKAGE_DEFINITION_OFFSET,
+                package_definition.replace('"', "\\\""),
+                VALIDATION_RESULT_OFFSET,
+                validation_result.replace('"', "\\\""),
+                encode(PACKAGE_DEFINITION_OFFSET, package_definition),
+                encode(VALIDATION_RESULT_OFFSET, validation_result),
+            ))
+            .unwrap()
+            .as_ref()
+            .to_vec()
+    }
+
+    fn test_signed_warrant(context: &Arc<Context>, entry: Entry) -> Warrant {
+        let author_signature = mock_signer(entry.address().to_string(), &context.agent_id);
+        let header = ChainHeader::new(
+            &entry.entry_type(),
+            &entry.address(),
+            &vec![Provenance::new(
+                context.agent_id.address(),
+                Signature::from(author_signature),
+            )],
+            &None,
+            &None,
+            &None,
+            &test_iso_8601(),
+        );
+        let reason = String::from("invalid");
+        let warrantor_signature = mock_signer(
+            Warrant::signing_payload(&header, &reason),
+            &context.agent_id,
+        );
+        Warrant::new(
+            entry,
+            header,
+            reason,
+            Provenance::new(
+                context.agent_id.address(),
+                Signature::from(warrantor_signature),
+            ),
+        )
+    }
+
+    #[test]
+    fn signed_warrants_verify_and_get_held() {
+        let context = test_context("alice", None);
+        let warrant = test_signed_warrant(&context, test_entry());
+        assert_eq!(verify_warrant(&warrant), Ok(()));
+
+        let store = test_store(context.clone());
+        let new_dht_store = reduce_hold_aspect(
+            &store.dht(),
+            &ActionWrapper::new(Action::HoldAspect(EntryAspect::Warrant(warrant.clone()))),
+        )
+        .expect("there should be a new store for holding a warrant");
+
+        assert!(new_dht_store
+            .get_holding_map()
+            .contains(&EntryAspect::Warrant(warrant.clone())));
+        assert_eq!(
+            new_dht_store.get_warrants(&test_entry().address()),
+            Ok(vec![warrant.clone()])
+        );
+        assert_eq!(
+            new_dht_store.get_warrants(&context.agent_id.address()),
+            Ok(vec![warrant])
+        );
+    }
+
+    #[test]
+    fn forged_warrants_do_not_verify() {
+        let warrant = Warrant::new(
+            test_entry(),
+            test_chain_header(),
+            String::from("invalid"),
+            Provenance::new(Address::from("warrantor"), Signature::fake()),
+        );
+        assert!(verify_warrant(&warrant).is_err());
+    }
+
+    #[test]
+    fn warrants_need_the_header_of_the_warranted_entry() {
+        let context = test_context("alice", None);
+        let warrant = test_signed_warrant(&context, test_entry());
+        let swapped = Warrant::new(
+            test_entry_b(),
+            warrant.invalid_header().clone(),
+            warrant.reason().clone(),
+            warrant.warrantor().clone(),
+        );
+        assert!(verify_warrant(&swapped).is_err());
+    }
+
+    #[test]
+    fn warrants_only_get_held_if_the_entry_is_invalid() {
+        let dna =
+            test_utils::create_test_dna_with_wasm("test_zome", test_rejecting_validation_wasm());
+        let (_instance, context) =
+            test_instance_and_context(dna, None).expect("Could not initialize test instance");
+
+        // capability grants don't get app validation and are always valid
+        let mut functions = CapFunctions::new();
+        functions.insert(String::from("test_zome"), vec![String::from("test")]);
+        let grant =
+            CapTokenGrant::create("grant", CapabilityType::Transferable, None, functions).unwrap();
+        let valid = test_signed_warrant(&context, Entry::CapTokenGrant(grant));
+        assert_eq!(verify_warrant(&valid), Ok(()));
+        assert_eq!(
+            context.block_on(hold_warrant_workflow(valid.clone(), context.clone())),
+            Err(HolochainError::ValidationFailed(format!(
+                "Warranted entry {} is valid",
+                valid.invalid_entry().address()
+            )))
+        );
+
+        let invalid = test_signed_warrant(&context, test_entry());
+        assert_eq!(
+            context.block_on(hold_warrant_workflow(invalid.clone(), context.clone())),
+            Ok(())
+        );
+
+        let warrants = context
+            .state()
+            .unwrap()
+            .dht()
+            .get_warrants(&context.agent_id.address())
+            .unwrap();
+        assert_eq!(warrants, vec![invalid]);
+    }
+
+    #[test]
+    fn headers_are_only_authentic_if_signed_as_a_whole() {
+        let context = test_context("alice", None);
+        let entry = test_entry();
+        let entry_signature = mock_signer(entry.address().to_string(), &context.agent_id);
+        let header_with_link = |link: Option<Address>| {
+            ChainHeader::new(
+                &entry.entry_type(),
+                &entry.address(),
+                &vec![Provenance::new(
+                    context.agent_id.address(),
+                    Signature::from(entry_signature.clone()),
+                )],
+                &link,
+                &None,
+                &None,
+                &test_iso_8601(),
+            )
+        };
+        let unsigned = header_with_link(None);
+        let header_signature =
+            Signature::from(mock_signer(unsigned.signing_payload(), &context.agent_id));
+        let signed = unsigned
+            .clone()
+            .with_header_signature(header_signature.clone());
+
+        assert_eq!(verify_header_signatures(&unsigned), Ok(()));
+        assert!(verify_header_authenticity(&unsigned).is_err());
+        assert_eq!(verify_header_authenticity(&signed), Ok(()));
+
+        // Copying the signatures onto a header with another link must not work
+        let forged = header_with_link(Some(Address::from("other previous header")))
+            .with_header_signature(header_signature);
+        assert_eq!(verify_header_signatures(&forged), Ok(()));
+        assert!(verify_header_authenticity(&forged).is_err());
+    }
+}
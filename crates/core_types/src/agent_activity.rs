@@ -0,0 +1,265 @@
+//! Agent activity is the set of headers an agent has published, held by the authorities
+//! for the agent's address. It lets other agents see what an agent did without reading
+//! its source chain, and reveals forks: an honest agent never has two headers that
+//! point to the same previous header.
+
+use crate::{chain_header::ChainHeader, entry::entry_type::EntryType, time::Iso8601};
+use holochain_json_api::{error::JsonError, json::JsonString};
+use holochain_persistence_api::cas::content::{Address, AddressableContent, Content};
+use std::{collections::BTreeMap, convert::TryFrom};
+
+/// Selects which of the held headers of an agent get returned.
+/// Forks are always detected on all held headers.
+#[derive(Deserialize, Debug, Serialize, DefaultJson, Clone, PartialEq, Default)]
+pub struct AgentActivityFilter {
+    /// Only headers of entries of these types, all headers if empty
+    #[serde(default)]
+    pub entry_types: Vec<EntryType>,
+    /// Only headers with a timestamp at or after this time
+    #[serde(default)]
+    pub since: Option<Iso8601>,
+    /// Only headers with a timestamp before this time
+    #[serde(default)]
+    pub until: Option<Iso8601>,
+    /// At most this many of the newest headers
+    #[serde(default)]
+    pub limit: Option<usize>,
+}
+
+impl AgentActivityFilter {
+    pub fn matches(&self, header: &ChainHeader) -> bool {
+        (self.entry_types.is_empty() || self.entry_types.contains(header.entry_type()))
+            && self
+                .since
+                .as_ref()
+                .map_or(true, |since| header.timestamp() >= since)
+            && self
+                .until
+                .as_ref()
+                .map_or(true, |until| header.timestamp() < until)
+    }
+}
+
+/// Headers of the same agent that all point to the same previous header
+/// (or are all the first header of a chain if `link` is None).
+#[derive(Deserialize, Debug, Serialize, DefaultJson, Clone, PartialEq)]
+pub struct ChainFork {
+    pub link: Option<Address>,
+    /// Addresses of the forking headers, sorted
+    pub headers: Vec<Address>,
+}
+
+#[derive(Deserialize, Debug, Serialize, DefaultJson, Clone, PartialEq)]
+pub enum ChainStatus {
+    /// No headers of the agent are held
+    Empty,
+    /// The held headers form a single chain as far as they are known
+    Valid,
+    /// The agent published conflicting versions of its chain
+    Forked(Vec<ChainFork>),
+}
+
+/// Public headers of an agent as held by an authority of its address
+#[derive(Deserialize, Debug, Serialize, DefaultJson, Clone, PartialEq)]
+pub struct AgentActivity {
+    pub agent: Address,
+    /// Headers matching the filter, newest first
+    pub headers: Vec<ChainHeader>,
+    pub status: ChainStatus,
+}
+
+impl AgentActivity {
+    /// Builds the activity of the agent from all its held headers.
+    pub fn new(
+        agent: Address,
+        held_headers: Vec<ChainHeader>,
+        filter: &AgentActivityFilter,
+    ) -> Self {
+        let status = if held_headers.is_empty() {
+            ChainStatus::Empty
+        } else {
+            let forks = detect_forks(&held_headers);
+            if forks.is_empty() {
+                ChainStatus::Valid
+            } else {
+                ChainStatus::Forked(forks)
+            }
+        };
+        let mut headers: Vec<ChainHeader> = held_headers
+            .into_iter()
+            .filter(|header| filter.matches(header))
+            .collect();
+        headers.sort_by(|a, b| {
+            b.timestamp()
+                .cmp(a.timestamp())
+                .then_with(|| b.address().cmp(&a.address()))
+        });
+        if let Some(limit) = filter.limit {
+            headers.truncate(limit);
+        }
+        AgentActivity {
+            agent,
+            headers,
+            status,
+        }
+    }
+}
+
+/// Proof that an agent forked its source chain: different headers signed by
+/// the agent that all point to the same previous header.
+#[derive(Deserialize, Debug, Serialize, DefaultJson, Clone, PartialEq)]
+pub struct ChainForkEvidence {
+    pub author: Address,
+    pub link: Option<Address>,
+    /// The forking headers, sorted by address
+    pub headers: Vec<ChainHeader>,
+}
+
+impl ChainForkEvidence {
+    /// Returns evidence of a fork if the two headers are different headers
+    /// of the same author that share the same previous header.
+    pub fn from_headers(first: &ChainHeader, second: &ChainHeader) -> Option<Self> {
+        let author = first.author()?;
+        if second.author()? != author
+            || first.link() != second.link()
+            || first.address() == second.address()
+        {
+            return None;
+        }
+        let mut headers = vec![first.clone(), second.clone()];
+        headers.sort_by_key(|header| header.address());
+        Some(ChainForkEvidence {
+            author,
+            link: first.link(),
+            headers,
+        })
+    }
+
+    pub fn fork(&self) -> ChainFork {
+        ChainFork {
+            link: self.link.clone(),
+            headers: self.headers.iter().map(|header| header.address()).collect(),
+        }
+    }
+}
+
+impl AddressableContent for ChainForkEvidence {
+    fn content(&self) -> Content {
+        self.to_owned().into()
+    }
+
+    fn try_from_content(content: &Content) -> Result<Self, JsonError> {
+        Self::try_from(content.to_owned())
+    }
+}
+
+/// Returns all groups of distinct headers that share the same previous header.
+pub fn detect_forks(headers: &[ChainHeader]) -> Vec<ChainFork> {
+    let mut by_link: BTreeMap<Option<Address>, Vec<Address>> = BTreeMap::new();
+    for header in headers {
+        let successors = by_link.entry(header.link()).or_default();
+        let address = header.address();
+        if !successors.contains(&address) {
+            successors.push(address);
+        }
+    }
+    by_link
+        .into_iter()
+        .filter(|(_, successors)| successors.len() > 1)
+        .map(|(link, mut successors)| {
+            successors.sort();
+            ChainFork {
+                link,
+                headers: successors,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        chain_header::test_provenances,
+        entry::{test_entry, test_entry_a, test_entry_b},
+    };
+
+    fn header(entry: &crate::entry::Entry, link: Option<Address>, time: i64) -> ChainHeader {
+        ChainHeader::new(
+            &entry.entry_type(),
+            &entry.address(),
+            &test_provenances("sig"),
+            &link,
+            &None,
+            &None,
+            &Iso8601::from(time),
+        )
+    }
+
+    #[test]
+    fn headers_sharing_a_link_are_forks() {
+        let first = header(&test_entry(), None, 1);
+        let a = header(&test_entry_a(), Some(first.address()), 2);
+        let b = header(&test_entry_b(), Some(first.address()), 3);
+
+        assert_eq!(detect_forks(&[first.clone(), a.clone()]), vec![]);
+        let mut forking = vec![a.address(), b.address()];
+        forking.sort();
+        assert_eq!(
+            detect_forks(&[first.clone(), a.clone(), b.clone(), a.clone()]),
+            vec![ChainFork {
+                link: Some(first.address()),
+                headers: forking,
+            }]
+        );
+    }
+
+    #[test]
+    fn evidence_needs_different_headers_with_the_same_link() {
+        let first = header(&test_entry(), None, 1);
+        let a = header(&test_entry_a(), Some(first.address()), 2);
+        let b = header(&test_entry_b(), Some(first.address()), 3);
+
+        assert_eq!(ChainForkEvidence::from_headers(&a, &a), None);
+        assert_eq!(ChainForkEvidence::from_headers(&first, &a), None);
+        let evidence = ChainForkEvidence::from_headers(&b, &a).expect("a and b fork");
+        assert_eq!(
+            ChainForkEvidence::from_headers(&a, &b),
+            Some(evidence.clone())
+        );
+        assert_eq!(detect_forks(&[a, b]), vec![evidence.fork()]);
+    }
+
+    #[test]
+    fn activity_is_filtered_but_status_covers_all_headers() {
+        let first = header(&test_entry(), None, 1);
+        let a = header(&test_entry_a(), Some(first.address()), 2);
+        let b = header(&test_entry_b(), Some(first.address()), 3);
+        let agent = Address::from("agent");
+
+        let activity = AgentActivity::new(
+            agent.clone(),
+            vec![first.clone(), a.clone()],
+            &Default::default(),
+        );
+        assert_eq!(activity.headers, vec![a.clone(), first.clone()]);
+        assert_eq!(activity.status, ChainStatus::Valid);
+
+        let filter = AgentActivityFilter {
+            since: Some(Iso8601::from(2)),
+            limit: Some(1),
+            ..Default::default()
+        };
+        let activity = AgentActivity::new(agent.clone(), vec![first, a, b.clone()], &filter);
+        assert_eq!(activity.headers, vec![b]);
+        assert!(match activity.status {
+            ChainStatus::Forked(forks) => forks.len() == 1,
+            _ => false,
+        });
+
+        assert_eq!(
+            AgentActivity::new(agent, vec![], &filter).status,
+            ChainStatus::Empty
+        );
+    }
+}
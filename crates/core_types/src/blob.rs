@@ -0,0 +1,152 @@
+//! Blobs are binary data that is too large to be handled as one entry, like files or images.
+//! A blob gets split into chunks of `BLOB_CHUNK_SIZE` bytes which are committed as
+//! `BlobChunk` system entries, so every chunk is content-addressed and can be fetched and
+//! checked on its own. A `BlobManifest` entry lists the addresses of all chunks in order;
+//! its address is the address of the blob.
+
+use crate::error::HolochainError;
+use base64;
+use holochain_json_api::{error::JsonError, json::JsonString};
+use holochain_persistence_api::cas::content::Address;
+use serde::{self, Deserialize, Deserializer, Serializer};
+
+/// Size of all chunks of a blob except for the last one, which holds the rest
+pub const BLOB_CHUNK_SIZE: usize = 256 * 1024;
+
+fn serialize_base64<S>(data: &[u8], serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    serializer.serialize_str(&base64::encode(data))
+}
+
+fn deserialize_base64<'de, D>(deserializer: D) -> Result<Vec<u8>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let encoded = String::deserialize(deserializer)?;
+    base64::decode(&encoded).map_err(serde::de::Error::custom)
+}
+
+/// A piece of a blob, serialized as base64
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, DefaultJson)]
+pub struct BlobChunk(
+    #[serde(
+        serialize_with = "serialize_base64",
+        deserialize_with = "deserialize_base64"
+    )]
+    Vec<u8>,
+);
+
+impl BlobChunk {
+    pub fn new(data: Vec<u8>) -> Self {
+        BlobChunk(data)
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn check(&self) -> Result<(), HolochainError> {
+        if self.is_empty() || self.len() > BLOB_CHUNK_SIZE {
+            return Err(HolochainError::ValidationFailed(format!(
+                "Blob chunks must hold between 1 and {} bytes, got {}",
+                BLOB_CHUNK_SIZE,
+                self.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Lists the chunks a blob consists of
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, DefaultJson)]
+pub struct BlobManifest {
+    /// Size of the whole blob in bytes
+    pub size: u64,
+    /// Addresses of the BlobChunk entries, in order
+    pub chunks: Vec<Address>,
+}
+
+impl BlobManifest {
+    pub fn new(size: u64, chunks: Vec<Address>) -> Self {
+        BlobManifest { size, chunks }
+    }
+
+    /// Number of bytes the chunk at the given index must hold
+    pub fn chunk_size(&self, index: usize) -> u64 {
+        let start = index as u64 * BLOB_CHUNK_SIZE as u64;
+        self.size.saturating_sub(start).min(BLOB_CHUNK_SIZE as u64)
+    }
+
+    /// Checks that the number of chunks fits the size of the blob
+    pub fn check(&self) -> Result<(), HolochainError> {
+        let expected_chunks =
+            self.size / BLOB_CHUNK_SIZE as u64 + (self.size % BLOB_CHUNK_SIZE as u64 != 0) as u64;
+        if self.chunks.len() as u64 != expected_chunks {
+            return Err(HolochainError::ValidationFailed(format!(
+                "A blob of {} bytes needs {} chunks, the manifest lists {}",
+                self.size,
+                expected_chunks,
+                self.chunks.len()
+            )));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::entry::Entry;
+    use holochain_persistence_api::cas::content::AddressableContent;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn blob_chunks_roundtrip_as_base64() {
+        let chunk = BlobChunk::new(vec![0, 1, 2, 254, 255]);
+        let json = JsonString::from(chunk.clone());
+        assert_eq!(json.to_string(), "\"AAEC/v8=\"");
+        assert_eq!(BlobChunk::try_from(json), Ok(chunk.clone()));
+
+        let entry = Entry::BlobChunk(chunk);
+        assert_eq!(Entry::try_from_content(&entry.content()), Ok(entry));
+    }
+
+    #[test]
+    fn chunks_and_manifests_get_checked() {
+        assert!(BlobChunk::new(vec![1]).check().is_ok());
+        assert!(BlobChunk::new(vec![]).check().is_err());
+        assert!(BlobChunk::new(vec![1; BLOB_CHUNK_SIZE + 1])
+            .check()
+            .is_err());
+
+        let size = BLOB_CHUNK_SIZE as u64 + 10;
+        let manifest = BlobManifest::new(size, vec![Address::from("a"), Address::from("b")]);
+        assert!(manifest.check().is_ok());
+        assert_eq!(manifest.chunk_size(0), BLOB_CHUNK_SIZE as u64);
+        assert_eq!(manifest.chunk_size(1), 10);
+        assert!(BlobManifest::new(size, vec![Address::from("a")])
+            .check()
+            .is_err());
+        assert!(BlobManifest::new(0, vec![]).check().is_ok());
+
+        // counting the chunks of huge blobs must not overflow
+        assert_eq!(
+            BlobManifest::new(std::u64::MAX, vec![]).check(),
+            Err(HolochainError::ValidationFailed(format!(
+                "A blob of {} bytes needs {} chunks, the manifest lists 0",
+                std::u64::MAX,
+                std::u64::MAX / BLOB_CHUNK_SIZE as u64 + 1
+            )))
+        );
+    }
+}
@@ -45,6 +45,11 @@ pub struct ChainHeader {
     link_update_delete: Option<Address>,
     /// ISO8601 time stamp
     timestamp: Iso8601,
+    /// Signature of the author over all other fields, see `signing_payload()`.
+    /// Provenances only sign the entry address, so without this anybody could make up
+    /// headers of an agent with a different link or timestamp.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    header_signature: Option<Signature>,
 }
 
 impl PartialEq for ChainHeader {
@@ -79,9 +84,29 @@ impl ChainHeader {
             link_same_type: link_same_type.to_owned(),
             link_update_delete: link_update_delete.to_owned(),
             timestamp: timestamp.to_owned(),
+            header_signature: None,
         }
     }
 
+    /// Returns this header with the author's signature over its other fields attached
+    pub fn with_header_signature(mut self, signature: Signature) -> Self {
+        self.header_signature = Some(signature);
+        self
+    }
+
+    /// The string the author signs to authenticate the whole header:
+    /// the header without its header signature
+    pub fn signing_payload(&self) -> String {
+        let mut unsigned = self.clone();
+        unsigned.header_signature = None;
+        String::from(unsigned.content())
+    }
+
+    /// header_signature getter
+    pub fn header_signature(&self) -> Option<&Signature> {
+        self.header_signature.as_ref()
+    }
+
     /// entry_type getter
     pub fn entry_type(&self) -> &EntryType {
         &self.entry_type
@@ -116,6 +141,14 @@ impl ChainHeader {
     pub fn provenances(&self) -> &Vec<Provenance> {
         &self.provenances
     }
+
+    /// Address of the agent whose source chain this header belongs to.
+    /// The author signs last, after any countersigners or other provenances.
+    pub fn author(&self) -> Option<Address> {
+        self.provenances
+            .last()
+            .map(|provenance| provenance.source())
+    }
 }
 
 impl AddressableContent for ChainHeader {
@@ -161,6 +194,7 @@ pub mod tests {
             entry_type::{test_entry_type, test_entry_type_a, test_entry_type_b},
             test_entry, test_entry_a, test_entry_b,
         },
+        signature::Signature,
         time::test_iso_8601,
     };
     use holochain_persistence_api::cas::content::{Address, AddressableContent};
@@ -402,4 +436,27 @@ pub mod tests {
             .address(),
         );
     }
+
+    #[test]
+    /// the header signature is not part of what it signs, but part of the address
+    fn header_signature_signs_all_other_fields() {
+        let header = test_chain_header();
+        let signed = header
+            .clone()
+            .with_header_signature(Signature::from("header sig"));
+        assert_eq!(header.header_signature(), None);
+        assert_eq!(
+            signed.header_signature(),
+            Some(&Signature::from("header sig"))
+        );
+        assert_eq!(signed.signing_payload(), header.signing_payload());
+        assert_eq!(header.signing_payload(), String::from(header.content()));
+        assert_ne!(signed.address(), header.address());
+        assert_eq!(
+            ChainHeader::try_from_content(&signed.content())
+                .unwrap()
+                .header_signature(),
+            signed.header_signature()
+        );
+    }
 }
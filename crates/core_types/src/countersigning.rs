@@ -0,0 +1,68 @@
+//! Countersigning lets several agents commit the same entry, each with a header that
+//! carries the signatures of all of them. The proposer sends a CountersigningRequest
+//! to every other signer. Signers that accept it check the entry, sign its address and
+//! wait for the CountersigningOutcome: either all signatures, so they commit the entry
+//! too, or an abort.
+
+use crate::{entry::Entry, signature::Provenance, time::Iso8601};
+use holochain_json_api::{error::JsonError, json::JsonString};
+use holochain_persistence_api::cas::content::{Address, AddressableContent};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, DefaultJson)]
+pub struct CountersigningRequest {
+    /// The entry all signers are going to commit
+    entry: Entry,
+    /// Addresses of all agents that have to sign the entry, including the proposer
+    signers: Vec<Address>,
+    /// Address of the proposer and its signature of the entry address
+    proposer: Provenance,
+    /// Signatures have to be collected until then, otherwise nobody commits the entry
+    expires_at: Iso8601,
+}
+
+impl CountersigningRequest {
+    pub fn new(
+        entry: Entry,
+        signers: Vec<Address>,
+        proposer: Provenance,
+        expires_at: Iso8601,
+    ) -> Self {
+        CountersigningRequest {
+            entry,
+            signers,
+            proposer,
+            expires_at,
+        }
+    }
+
+    pub fn entry(&self) -> &Entry {
+        &self.entry
+    }
+
+    pub fn entry_address(&self) -> Address {
+        self.entry.address()
+    }
+
+    pub fn signers(&self) -> &Vec<Address> {
+        &self.signers
+    }
+
+    pub fn proposer(&self) -> &Provenance {
+        &self.proposer
+    }
+
+    pub fn expires_at(&self) -> &Iso8601 {
+        &self.expires_at
+    }
+}
+
+/// What the proposer tells the other signers once the signatures are collected
+/// or the session failed.
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, DefaultJson)]
+pub enum CountersigningOutcome {
+    /// All signers signed the entry at the given address. The provenances hold all of
+    /// their signatures so every signer can commit the entry with them.
+    Commit(Address, Vec<Provenance>),
+    /// The session for the entry at the given address failed and nobody commits it.
+    Abort(Address),
+}
@@ -0,0 +1,106 @@
+//! A CustomValidationPackage holds the data a zome's custom validation package callback
+//! built for one of the author's headers at the time the entry got published, together
+//! with the author's signature of it.
+//! Authors store these next to their headers and serve them to validators that request
+//! the validation package, so validators get exactly the chain context the DNA asks for.
+
+use crate::signature::Provenance;
+use holochain_json_api::{error::JsonError, json::JsonString};
+use holochain_persistence_api::cas::content::{Address, AddressableContent, Content};
+use std::convert::TryFrom;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, DefaultJson)]
+pub struct CustomValidationPackage {
+    /// Address of the header this package was built for
+    header_address: Address,
+    /// The data returned by the callback, serialized as JSON
+    data: String,
+    /// Address of the author and its signature of
+    /// `CustomValidationPackage::signing_payload()`
+    author: Provenance,
+}
+
+impl CustomValidationPackage {
+    pub fn new(header_address: Address, data: String, author: Provenance) -> Self {
+        CustomValidationPackage {
+            header_address,
+            data,
+            author,
+        }
+    }
+
+    /// The string an author has to sign to vouch for the given custom package data
+    /// of the given header.
+    pub fn signing_payload(header_address: &Address, data: &str) -> String {
+        format!("custom_validation_package:{}:{}", header_address, data)
+    }
+
+    /// The address a custom validation package for the given header gets stored under.
+    /// Packages are looked up by their header, so their address does not depend on their
+    /// content (much like agent entries are stored under the agent's key).
+    pub fn address_for_header(header_address: &Address) -> Address {
+        JsonString::from_json(&format!("custom_validation_package:{}", header_address)).address()
+    }
+
+    pub fn header_address(&self) -> &Address {
+        &self.header_address
+    }
+
+    pub fn data(&self) -> &String {
+        &self.data
+    }
+
+    pub fn author(&self) -> &Provenance {
+        &self.author
+    }
+}
+
+impl AddressableContent for CustomValidationPackage {
+    fn address(&self) -> Address {
+        CustomValidationPackage::address_for_header(&self.header_address)
+    }
+
+    fn content(&self) -> Content {
+        self.to_owned().into()
+    }
+
+    fn try_from_content(content: &Content) -> Result<Self, JsonError> {
+        Self::try_from(content.to_owned())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::signature::Signature;
+
+    pub fn test_custom_validation_package() -> CustomValidationPackage {
+        CustomValidationPackage::new(
+            Address::from("header"),
+            String::from("{\"previous\":[]}"),
+            Provenance::new(Address::from("author"), Signature::fake()),
+        )
+    }
+
+    #[test]
+    fn custom_validation_package_is_addressed_by_its_header() {
+        let package = test_custom_validation_package();
+        assert_eq!(
+            package.address(),
+            CustomValidationPackage::address_for_header(&Address::from("header"))
+        );
+        assert_ne!(
+            package.address(),
+            CustomValidationPackage::address_for_header(&Address::from("other header"))
+        );
+    }
+
+    #[test]
+    fn custom_validation_package_content_roundtrip() {
+        let package = test_custom_validation_package();
+        assert_eq!(
+            CustomValidationPackage::try_from_content(&package.content()).unwrap(),
+            package
+        );
+    }
+}
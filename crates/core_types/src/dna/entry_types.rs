@@ -4,8 +4,38 @@ use dna::zome::ZomeEntryTypes;
 use entry::entry_type::EntryType;
 use holochain_json_api::{error::JsonError, json::JsonString};
 use serde::{ser::SerializeMap, Deserialize, Deserializer, Serializer};
+use serde_json::{self, Value};
 use std::collections::BTreeMap;
 
+/// JSON Schema keywords that app entries get checked against natively
+pub const SUPPORTED_SCHEMA_KEYWORDS: &[&str] = &[
+    "type",
+    "enum",
+    "const",
+    "properties",
+    "required",
+    "additionalProperties",
+    "items",
+    "minItems",
+    "maxItems",
+    "minLength",
+    "maxLength",
+    "pattern",
+    "minimum",
+    "maximum",
+];
+
+/// JSON Schema keywords that only annotate a schema and don't restrict values
+const SCHEMA_ANNOTATIONS: &[&str] = &[
+    "$schema",
+    "$id",
+    "$comment",
+    "title",
+    "description",
+    "default",
+    "examples",
+];
+
 /// Enum for Zome EntryType "sharing" property.
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Hash)]
 #[serde(rename_all = "lowercase")]
@@ -137,12 +167,36 @@ pub struct EntryTypeDef {
     /// An array of link definitions for links pointing to entries of this type
     #[serde(default)]
     pub linked_from: Vec<LinkedFrom>,
+
+    /// Optional JSON Schema that the content of entries of this type has to match.
+    /// Gets checked natively before the validation callback of the zome is called.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema: Option<JsonString>,
+
+    /// Optional maximum size in bytes of the serialized content of entries of this type.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_size: Option<usize>,
+
+    /// Names of top-level fields of the entry content that hold the address (or an array of
+    /// addresses) of agents who all have to sign entries of this type.
+    /// Such entries can only be committed through countersigning.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub countersigners: Vec<String>,
+
+    /// Whether the zome defines a conflict resolution callback for this entry type that
+    /// picks the latest entry if agents updated an entry concurrently.
+    #[serde(default, skip_serializing_if = "is_false")]
+    pub resolves_conflicts: bool,
 }
 
 fn empty_properties() -> JsonString {
     JsonString::empty_object()
 }
 
+fn is_false(value: &bool) -> bool {
+    !*value
+}
+
 impl Default for EntryTypeDef {
     fn default() -> Self {
         EntryTypeDef {
@@ -150,6 +204,10 @@ impl Default for EntryTypeDef {
             sharing: Sharing::default(),
             links_to: Vec::default(),
             linked_from: Vec::default(),
+            schema: None,
+            max_size: None,
+            countersigners: Vec::new(),
+            resolves_conflicts: false,
         }
     }
 }
@@ -159,6 +217,58 @@ impl EntryTypeDef {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// Checks that the schema, if given, is valid JSON and only uses supported keywords.
+    /// Unsupported keywords would not get checked, so entries that the schema is meant
+    /// to reject would pass.
+    pub fn check_schema(&self) -> Result<(), String> {
+        match self.schema {
+            None => Ok(()),
+            Some(ref schema) => {
+                let schema: Value = serde_json::from_str(&schema.to_string())
+                    .map_err(|e| format!("schema is not valid JSON: {}", e))?;
+                check_schema_keywords(&schema, "")
+            }
+        }
+    }
+}
+
+fn check_schema_keywords(schema: &Value, path: &str) -> Result<(), String> {
+    let location = if path.is_empty() { "/" } else { path };
+    let schema = match schema {
+        Value::Bool(_) => return Ok(()),
+        Value::Object(schema) => schema,
+        _ => return Err(format!("{}: schema has to be an object", location)),
+    };
+    for (keyword, value) in schema {
+        if SCHEMA_ANNOTATIONS.contains(&keyword.as_str()) {
+            continue;
+        }
+        if !SUPPORTED_SCHEMA_KEYWORDS.contains(&keyword.as_str()) {
+            return Err(format!(
+                "{}: unsupported schema keyword \"{}\"",
+                location, keyword
+            ));
+        }
+        match keyword.as_str() {
+            "properties" => {
+                let properties = value
+                    .as_object()
+                    .ok_or_else(|| format!("{}: \"properties\" has to be an object", location))?;
+                for (name, property_schema) in properties {
+                    check_schema_keywords(
+                        property_schema,
+                        &format!("{}/properties/{}", path, name),
+                    )?;
+                }
+            }
+            "additionalProperties" | "items" => {
+                check_schema_keywords(value, &format!("{}/{}", path, keyword))?
+            }
+            _ => (),
+        }
+    }
+    Ok(())
 }
 
 #[cfg(test)]
@@ -166,6 +276,51 @@ mod tests {
     use super::*;
     use serde_json;
 
+    fn entry_type_def_with_schema(schema: &str) -> EntryTypeDef {
+        let mut entry_type_def = EntryTypeDef::new();
+        entry_type_def.schema = Some(JsonString::from_json(schema));
+        entry_type_def
+    }
+
+    #[test]
+    fn check_schema_rejects_unsupported_keywords() {
+        assert_eq!(EntryTypeDef::new().check_schema(), Ok(()));
+        assert_eq!(
+            entry_type_def_with_schema(
+                r#"{
+                    "$schema": "http://json-schema.org/draft-07/schema#",
+                    "title": "post",
+                    "type": "object",
+                    "required": ["content"],
+                    "properties": {
+                        "content": {"type": "string", "maxLength": 280},
+                        "tags": {"type": "array", "items": {"type": "string"}}
+                    },
+                    "additionalProperties": false
+                }"#
+            )
+            .check_schema(),
+            Ok(())
+        );
+        assert_eq!(
+            entry_type_def_with_schema(
+                r#"{"properties": {"tags": {"items": {"oneOf": [{"type": "string"}]}}}}"#
+            )
+            .check_schema(),
+            Err(String::from(
+                "/properties/tags/items: unsupported schema keyword \"oneOf\""
+            ))
+        );
+        assert_eq!(
+            entry_type_def_with_schema(r#"{"format": "email"}"#).check_schema(),
+            Err(String::from("/: unsupported schema keyword \"format\""))
+        );
+        assert!(entry_type_def_with_schema(r#"{"properties": []}"#)
+            .check_schema()
+            .is_err());
+        assert!(entry_type_def_with_schema("{").check_schema().is_err());
+    }
+
     #[test]
     fn can_publish() {
         assert!(Sharing::Public.can_publish());
@@ -210,4 +365,28 @@ mod tests {
 
         assert_eq!(fixture, entry);
     }
+
+    #[test]
+    fn schema_and_max_size_are_optional() {
+        let fixture: EntryTypeDef = serde_json::from_str(
+            r#"{
+                "properties": "{}",
+                "sharing": "public",
+                "schema": "{\"type\": \"object\", \"required\": [\"content\"]}",
+                "max_size": 1024
+            }"#,
+        )
+        .unwrap();
+
+        let mut entry = EntryTypeDef::new();
+        entry.schema = Some(JsonString::from_json(
+            "{\"type\": \"object\", \"required\": [\"content\"]}",
+        ));
+        entry.max_size = Some(1024);
+        assert_eq!(fixture, entry);
+
+        let serialized = serde_json::to_string(&EntryTypeDef::new()).unwrap();
+        assert!(!serialized.contains("schema"));
+        assert!(!serialized.contains("max_size"));
+    }
 }
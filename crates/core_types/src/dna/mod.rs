@@ -229,6 +229,15 @@ impl Dna {
         None
     }
 
+    /// Return the name of the zome that defines a validating entry type for the given
+    /// system entry type, like the blob manifests a zome can opt in to validate
+    pub fn get_zome_name_for_sys_entry_type(&self, entry_type: &EntryType) -> Option<String> {
+        self.zomes
+            .iter()
+            .find(|(_, zome)| zome.entry_types.contains_key(entry_type))
+            .map(|(zome_name, _)| zome_name.clone())
+    }
+
     /// Return the entry_type definition of a specified app entry_type
     pub fn get_entry_type_def(&self, entry_type_name: &str) -> Option<&EntryTypeDef> {
         // pre-condition: must be a valid app entry_type name
@@ -261,9 +270,10 @@ impl Dna {
     }
 
     // Check that all the zomes in the DNA have code with the required callbacks
+    // and that the schemas of their entry types can be checked
     // TODO: Add more advanced checks that actually try and call required functions
     pub fn verify(&self) -> HcResult<()> {
-        let errors: Vec<HolochainError> = self
+        let mut errors: Vec<HolochainError> = self
             .zomes
             .iter()
             .map(|(zome_name, zome)| {
@@ -279,6 +289,18 @@ impl Dna {
             })
             .filter_map(|r| r.err())
             .collect();
+        for (zome_name, zome) in &self.zomes {
+            for (entry_type, entry_type_def) in &zome.entry_types {
+                if let Err(reason) = entry_type_def.check_schema() {
+                    errors.push(HolochainError::ErrorGeneric(format!(
+                        "Schema of entry type {} in zome {} is invalid: {}",
+                        String::from(entry_type.clone()),
+                        zome_name,
+                        reason
+                    )));
+                }
+            }
+        }
         if errors.is_empty() {
             Ok(())
         } else {
@@ -474,6 +496,20 @@ pub mod tests {
         assert!(dna.verify().is_err())
     }
 
+    #[test]
+    fn test_dna_verify_fails_on_unsupported_schema_keywords() {
+        let mut dna = test_dna();
+        let mut entry_type_def = EntryTypeDef::new();
+        entry_type_def.schema = Some(JsonString::from_json(r#"{"anyOf": []}"#));
+        dna.zomes
+            .values_mut()
+            .next()
+            .unwrap()
+            .entry_types
+            .insert("post".into(), entry_type_def);
+        assert!(dna.verify().is_err())
+    }
+
     static UNIT_UUID: &'static str = "00000000-0000-0000-0000-000000000000";
 
     fn test_empty_dna() -> Dna {
@@ -859,6 +895,24 @@ pub mod tests {
             .is_none());
     }
 
+    #[test]
+    fn test_get_zome_name_for_sys_entry_type() {
+        let mut dna = Dna::new();
+        let mut zome = zome::Zome::empty();
+        zome.entry_types
+            .insert("%blob_manifest".into(), EntryTypeDef::new());
+        dna.zomes.insert("blobs".to_string(), zome);
+        dna.zomes.insert("other".to_string(), zome::Zome::empty());
+
+        assert_eq!(
+            dna.get_zome_name_for_sys_entry_type(&EntryType::BlobManifest),
+            Some("blobs".to_string())
+        );
+        assert!(dna
+            .get_zome_name_for_sys_entry_type(&EntryType::BlobChunk)
+            .is_none());
+    }
+
     #[test]
     fn test_get_required_bridges() {
         let dna = Dna::try_from(JsonString::from_json(
@@ -11,18 +11,23 @@ use crate::{
 };
 
 use holochain_json_api::{error::JsonError, json::JsonString};
+use serde_json::{Map, Value};
 
 use dna::entry_types::{self, deserialize_entry_types, serialize_entry_types, EntryTypeDef};
 use std::collections::BTreeMap;
 
 /// Represents the "config" object on a "zome".
-#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Hash)]
-pub struct Config {}
+/// This can be any JSON value. Zome code reads it with `hdk::zome_config()`.
+/// Conductors can override it per instance. Overrides do not change the DNA (and its hash),
+/// which always holds the config given here.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(transparent)]
+pub struct Config(Value);
 
 impl Default for Config {
     /// Provide defaults for the "zome" "config" object.
     fn default() -> Self {
-        Config {}
+        Config(Value::Object(Map::new()))
     }
 }
 
@@ -31,6 +36,17 @@ impl Config {
     pub fn new() -> Self {
         Default::default()
     }
+
+    /// The JSON value of this config.
+    pub fn value(&self) -> &Value {
+        &self.0
+    }
+}
+
+impl From<Value> for Config {
+    fn from(value: Value) -> Self {
+        Config(value)
+    }
 }
 
 pub type ZomeEntryTypes = BTreeMap<EntryType, EntryTypeDef>;
@@ -45,8 +61,6 @@ pub struct Zome {
     pub description: String,
 
     /// Configuration associated with this zome.
-    /// Note, this should perhaps be a more free-form serde_json::Value,
-    /// "throw-errors" may not make sense for wasm, or other ribosome types.
     #[serde(default)]
     pub config: Config,
 
@@ -182,6 +196,25 @@ pub mod tests {
         assert_eq!(fixture, zome);
     }
 
+    #[test]
+    fn zome_config_can_be_any_json() {
+        let zome: Zome = serde_json::from_str(
+            r#"{
+                "config": { "max_length": 140, "tags": ["a", "b"] },
+                "code": {
+                    "code": ""
+                }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            zome.config.value(),
+            &json!({ "max_length": 140, "tags": ["a", "b"] })
+        );
+        assert_eq!(Zome::empty().config.value(), &json!({}));
+    }
+
     #[test]
     fn zome_json_test() {
         let mut entry_types = BTreeMap::new();
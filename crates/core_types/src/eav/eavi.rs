@@ -38,6 +38,8 @@ pub type Entity = Address;
 )]
 #[serde(rename_all = "snake_case")]
 pub enum Attribute {
+    AgentActivity,
+    ChainFork,
     CrudStatus,
     CrudLink,
     EntryHeader,
@@ -46,7 +48,10 @@ pub enum Attribute {
     LinkTag(String, String),
     RemovedLink(String, String),
     PendingEntry,
+    ResolvedChainFork,
     Target,
+    ValidationReceipt,
+    Warrant,
 }
 
 impl Default for Attribute {
@@ -75,6 +80,8 @@ impl From<AttributeError> for HolochainError {
 impl fmt::Display for Attribute {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            Attribute::AgentActivity => write!(f, "agent-activity"),
+            Attribute::ChainFork => write!(f, "chain-fork"),
             Attribute::CrudStatus => write!(f, "crud-status"),
             Attribute::CrudLink => write!(f, "crud-link"),
             Attribute::EntryHeader => write!(f, "entry-header"),
@@ -85,7 +92,10 @@ impl fmt::Display for Attribute {
                 write!(f, "removed_link__{}__{}", link_type, tag)
             }
             Attribute::PendingEntry => write!(f, "pending-entry"),
+            Attribute::ResolvedChainFork => write!(f, "resolved-chain-fork"),
             Attribute::Target => write!(f, "target"),
+            Attribute::ValidationReceipt => write!(f, "validation-receipt"),
+            Attribute::Warrant => write!(f, "warrant"),
         }
     }
 }
@@ -112,13 +122,18 @@ impl TryFrom<&str> for Attribute {
             Ok(RemovedLink(link_type, link_tag))
         } else {
             match s {
+                "agent-activity" => Ok(AgentActivity),
+                "chain-fork" => Ok(ChainFork),
                 "crud-status" => Ok(CrudStatus),
                 "crud-link" => Ok(CrudLink),
                 "entry-header" => Ok(EntryHeader),
                 "link" => Ok(Link),
                 "link_remove" => Ok(LinkRemove),
                 "pending-entry" => Ok(PendingEntry),
+                "resolved-chain-fork" => Ok(ResolvedChainFork),
                 "target" => Ok(Target),
+                "validation-receipt" => Ok(ValidationReceipt),
+                "warrant" => Ok(Warrant),
                 a => Err(AttributeError::Unrecognized(a.to_string())),
             }
         }
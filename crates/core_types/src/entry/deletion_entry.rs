@@ -1,5 +1,7 @@
+use chain_header::ChainHeader;
+use entry::Entry;
 use holochain_json_api::{error::JsonError, json::JsonString};
-use holochain_persistence_api::cas::content::Address;
+use holochain_persistence_api::cas::content::{Address, AddressableContent};
 
 //-------------------------------------------------------------------------------------------------
 // DeletionEntry
@@ -8,25 +10,58 @@ use holochain_persistence_api::cas::content::Address;
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, DefaultJson, Eq)]
 pub struct DeletionEntry {
     deleted_entry_address: Address,
+    /// Asks the holders of the deleted entry to drop its content as well.
+    /// Left out of the serialization if not set so that plain deletions keep their address.
+    #[serde(default, skip_serializing_if = "is_false")]
+    purge: bool,
+}
+
+fn is_false(value: &bool) -> bool {
+    !*value
 }
 
 impl DeletionEntry {
     pub fn new(deleted_entry_address: Address) -> Self {
         DeletionEntry {
             deleted_entry_address,
+            purge: false,
         }
     }
 
+    /// A deletion that also purges the content of the deleted entry
+    pub fn new_purge(deleted_entry_address: Address) -> Self {
+        DeletionEntry {
+            deleted_entry_address,
+            purge: true,
+        }
+    }
+
+    /// Reconstructs the deletion entry a deletion header was created for.
+    /// Returns None if the header has no deletion link or doesn't match
+    /// a plain or a purging deletion of the linked entry.
+    pub fn from_header(header: &ChainHeader) -> Option<Self> {
+        let deleted_entry_address = header.link_update_delete()?;
+        vec![
+            DeletionEntry::new(deleted_entry_address.clone()),
+            DeletionEntry::new_purge(deleted_entry_address),
+        ]
+        .into_iter()
+        .find(|deletion| Entry::Deletion(deletion.clone()).address() == *header.entry_address())
+    }
+
     pub fn deleted_entry_address(&self) -> &Address {
         &self.deleted_entry_address
     }
+
+    pub fn is_purge(&self) -> bool {
+        self.purge
+    }
 }
 
 #[cfg(test)]
 pub mod tests {
     use super::*;
-    use crate::entry::test_entry_a;
-    use holochain_persistence_api::cas::content::AddressableContent;
+    use crate::{chain_header::test_provenances, entry::test_entry_a, time::test_iso_8601};
 
     pub fn test_deletion_entry() -> DeletionEntry {
         let entry = test_entry_a();
@@ -40,4 +75,32 @@ pub mod tests {
             test_deletion_entry().deleted_entry_address().clone()
         );
     }
+
+    #[test]
+    fn deletion_entry_can_be_reconstructed_from_its_header() {
+        let deleted_entry_address = test_entry_a().address();
+        for deletion in vec![
+            DeletionEntry::new(deleted_entry_address.clone()),
+            DeletionEntry::new_purge(deleted_entry_address.clone()),
+        ] {
+            let entry = Entry::Deletion(deletion.clone());
+            let header = ChainHeader::new(
+                &entry.entry_type(),
+                &entry.address(),
+                &test_provenances("sig"),
+                &None,
+                &None,
+                &Some(deleted_entry_address.clone()),
+                &test_iso_8601(),
+            );
+            assert_eq!(DeletionEntry::from_header(&header), Some(deletion));
+        }
+        assert_eq!(
+            JsonString::from(test_deletion_entry()).to_string(),
+            format!(
+                "{{\"deleted_entry_address\":\"{}\"}}",
+                deleted_entry_address
+            ),
+        );
+    }
 }
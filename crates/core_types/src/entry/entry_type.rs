@@ -60,6 +60,9 @@ pub enum EntryType {
     ChainMigrate,
     CapTokenGrant,
     CapTokenClaim,
+    MembraneProof,
+    BlobChunk,
+    BlobManifest,
 }
 
 impl From<AppEntryType> for EntryType {
@@ -117,6 +120,9 @@ impl FromStr for EntryType {
             sys_prefix!("chain_migrate") => EntryType::ChainMigrate,
             sys_prefix!("cap_token_claim") => EntryType::CapTokenClaim,
             sys_prefix!("cap_token_grant") => EntryType::CapTokenGrant,
+            sys_prefix!("membrane_proof") => EntryType::MembraneProof,
+            sys_prefix!("blob_chunk") => EntryType::BlobChunk,
+            sys_prefix!("blob_manifest") => EntryType::BlobManifest,
             _ => EntryType::App(AppEntryType(s.into())),
         })
     }
@@ -136,6 +142,9 @@ impl From<EntryType> for String {
             EntryType::ChainMigrate => sys_prefix!("chain_migrate"),
             EntryType::CapTokenClaim => sys_prefix!("cap_token_claim"),
             EntryType::CapTokenGrant => sys_prefix!("cap_token_grant"),
+            EntryType::MembraneProof => sys_prefix!("membrane_proof"),
+            EntryType::BlobChunk => sys_prefix!("blob_chunk"),
+            EntryType::BlobManifest => sys_prefix!("blob_manifest"),
         })
     }
 }
@@ -205,6 +214,9 @@ pub mod tests {
             EntryType::ChainMigrate,
             EntryType::CapTokenClaim,
             EntryType::CapTokenGrant,
+            EntryType::MembraneProof,
+            EntryType::BlobChunk,
+            EntryType::BlobManifest,
         ]
     }
 
@@ -243,6 +255,9 @@ pub mod tests {
             (sys_prefix!("chain_migrate"), EntryType::ChainMigrate),
             (sys_prefix!("cap_token_claim"), EntryType::CapTokenClaim),
             (sys_prefix!("cap_token_grant"), EntryType::CapTokenGrant),
+            (sys_prefix!("membrane_proof"), EntryType::MembraneProof),
+            (sys_prefix!("blob_chunk"), EntryType::BlobChunk),
+            (sys_prefix!("blob_manifest"), EntryType::BlobManifest),
         ] {
             assert_eq!(
                 variant,
@@ -6,12 +6,14 @@
 pub mod cap_entries;
 pub mod deletion_entry;
 pub mod entry_type;
+pub mod purged_entry;
 
 use self::{
     cap_entries::{CapTokenClaim, CapTokenGrant},
     deletion_entry::DeletionEntry,
 };
 use agent::{test_agent_id, AgentId};
+use blob::{BlobChunk, BlobManifest};
 use chain_header::ChainHeader;
 use chain_migrate::ChainMigrate;
 use crud_status::CrudStatus;
@@ -23,6 +25,7 @@ use holochain_json_api::{
 };
 use holochain_persistence_api::cas::content::{Address, AddressableContent, Content};
 use link::{link_data::LinkData, link_list::LinkList};
+use membrane_proof::MembraneProof;
 use multihash::Hash;
 use serde::{ser::SerializeTuple, Deserialize, Deserializer, Serializer};
 use snowflake;
@@ -77,6 +80,9 @@ pub enum Entry {
     ChainMigrate(ChainMigrate),
     CapTokenClaim(CapTokenClaim),
     CapTokenGrant(CapTokenGrant),
+    MembraneProof(MembraneProof),
+    BlobChunk(BlobChunk),
+    BlobManifest(BlobManifest),
 }
 
 impl Entry {
@@ -93,6 +99,9 @@ impl Entry {
             Entry::ChainMigrate(_) => EntryType::ChainMigrate,
             Entry::CapTokenClaim(_) => EntryType::CapTokenClaim,
             Entry::CapTokenGrant(_) => EntryType::CapTokenGrant,
+            Entry::MembraneProof(_) => EntryType::MembraneProof,
+            Entry::BlobChunk(_) => EntryType::BlobChunk,
+            Entry::BlobManifest(_) => EntryType::BlobManifest,
         }
     }
 }
@@ -130,6 +139,11 @@ pub struct EntryWithMeta {
     pub entry: Entry,
     pub crud_status: CrudStatus,
     pub maybe_link_update_delete: Option<Address>,
+    /// Addresses of all entries that updated or deleted this entry, sorted.
+    /// There is more than one if agents updated the entry concurrently, in which case
+    /// `maybe_link_update_delete` holds the first of them.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub links_update_delete: Vec<Address>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq, DefaultJson)]
@@ -0,0 +1,66 @@
+use holochain_json_api::{error::JsonError, json::JsonString};
+use holochain_persistence_api::cas::content::{Address, AddressableContent, Content};
+use std::convert::TryFrom;
+
+//-------------------------------------------------------------------------------------------------
+// PurgedEntry
+//-------------------------------------------------------------------------------------------------
+
+/// Tombstone that replaces the content of a purged entry in a CAS.
+/// It is stored under the address of the purged entry, so adding it overwrites
+/// the entry's content while headers and metadata pointing to that address stay intact.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, DefaultJson, Eq)]
+pub struct PurgedEntry {
+    purged_entry_address: Address,
+    /// Address of the deletion entry that asked for the purge
+    deletion_address: Address,
+}
+
+impl PurgedEntry {
+    pub fn new(purged_entry_address: Address, deletion_address: Address) -> Self {
+        PurgedEntry {
+            purged_entry_address,
+            deletion_address,
+        }
+    }
+
+    pub fn purged_entry_address(&self) -> &Address {
+        &self.purged_entry_address
+    }
+
+    pub fn deletion_address(&self) -> &Address {
+        &self.deletion_address
+    }
+}
+
+impl AddressableContent for PurgedEntry {
+    fn content(&self) -> Content {
+        self.to_owned().into()
+    }
+
+    fn try_from_content(content: &Content) -> Result<Self, JsonError> {
+        Self::try_from(content.to_owned())
+    }
+
+    fn address(&self) -> Address {
+        self.purged_entry_address.clone()
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::entry::{test_entry_a, Entry};
+
+    #[test]
+    fn purged_entry_takes_the_address_of_the_entry() {
+        let entry = test_entry_a();
+        let tombstone = PurgedEntry::new(entry.address(), Address::from("deletion"));
+        assert_eq!(tombstone.address(), entry.address());
+        assert!(Entry::try_from_content(&tombstone.content()).is_err());
+        assert_eq!(
+            PurgedEntry::try_from_content(&tombstone.content()),
+            Ok(tombstone)
+        );
+    }
+}
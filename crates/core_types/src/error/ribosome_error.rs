@@ -158,6 +158,7 @@ pub enum RibosomeErrorCode {
     MismatchWasmCallDataType        = 11 << 32,
     EntryNotFound                   = 12 << 32,
     WorkflowFailed                  = 13 << 32,
+    UnknownQueryCursor              = 14 << 32,
 }
 
 #[rustfmt::skip]
@@ -177,6 +178,7 @@ impl RibosomeErrorCode {
             MismatchWasmCallDataType        => "Mismatched WasmCallData type",
             EntryNotFound                   => "Entry Could Not Be Found",
             WorkflowFailed                  => "Workflow failed",
+            UnknownQueryCursor              => "Unknown query cursor",
         }
     }
 }
@@ -240,6 +242,7 @@ impl RibosomeErrorCode {
             10 => UnknownEntryType,
             12 => EntryNotFound,
             13 => WorkflowFailed,
+            14 => UnknownQueryCursor,
             1 | _ => Unspecified,
         }
     }
@@ -295,6 +298,7 @@ impl FromStr for RibosomeErrorCode {
             "Unknown entry type" => Ok(RibosomeErrorCode::UnknownEntryType),
             "Entry Could Not Be Found" => Ok(EntryNotFound),
             "Workflow failed" => Ok(WorkflowFailed),
+            "Unknown query cursor" => Ok(UnknownQueryCursor),
             _ => Err(HolochainError::ErrorGeneric(String::from(
                 "Unknown RibosomeErrorCode",
             ))),
@@ -319,7 +323,7 @@ pub mod tests {
     fn error_conversion() {
         // TODO could use strum crate to iteratively
         // gather all known codes.
-        for code in 1..=13 {
+        for code in 1..=14 {
             let mut err = RibosomeErrorCode::from_code_int(code);
 
             let err_str = err.as_str().to_owned();
@@ -39,23 +39,30 @@ extern crate wasmi;
 extern crate log;
 
 pub mod chain_header;
+pub mod countersigning;
 pub mod crud_status;
+pub mod custom_validation_package;
 pub mod eav;
 pub mod entry;
 pub mod error;
 #[macro_use]
 extern crate objekt;
 pub mod agent;
+pub mod agent_activity;
 pub mod bits_n_pieces;
+pub mod blob;
 pub mod chain_migrate;
 pub mod dna;
 pub mod hdk_version;
 pub mod link;
+pub mod membrane_proof;
 pub mod network;
 pub mod signature;
 pub mod time;
 pub mod ugly;
 pub mod validation;
+pub mod validation_receipt;
+pub mod warrant;
 #[macro_use]
 extern crate holochain_logging;
 
@@ -0,0 +1,59 @@
+//! A MembraneProof is the credential an agent presents when joining a DNA, like an invite
+//! code or a certificate signed by an authority key. Holochain treats it as an opaque blob.
+//! It gets committed right before the agent's AgentId entry during genesis and is handed
+//! to the agent validation callbacks as part of the validation package of that entry,
+//! so a DNA can decide who may become a member.
+//! It is a private entry that never gets published: validators of the AgentId entry
+//! receive it from the author together with the validation package.
+
+use holochain_json_api::{error::JsonError, json::JsonString};
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, Hash, DefaultJson)]
+pub struct MembraneProof(String);
+
+impl MembraneProof {
+    pub fn new(proof: &str) -> Self {
+        MembraneProof(proof.to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl From<String> for MembraneProof {
+    fn from(proof: String) -> Self {
+        MembraneProof(proof)
+    }
+}
+
+impl From<MembraneProof> for String {
+    fn from(proof: MembraneProof) -> Self {
+        proof.0
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::entry::Entry;
+    use holochain_persistence_api::cas::content::AddressableContent;
+
+    pub fn test_membrane_proof() -> MembraneProof {
+        MembraneProof::new("invite code 42")
+    }
+
+    #[test]
+    fn membrane_proof_entry_roundtrip() {
+        let entry = Entry::MembraneProof(test_membrane_proof());
+        assert_eq!(Entry::try_from_content(&entry.content()).unwrap(), entry);
+    }
+
+    #[test]
+    fn membrane_proof_is_serialized_as_plain_string() {
+        assert_eq!(
+            JsonString::from(test_membrane_proof()),
+            JsonString::from_json("\"invite code 42\""),
+        );
+    }
+}
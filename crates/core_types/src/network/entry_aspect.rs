@@ -1,4 +1,4 @@
-use crate::{chain_header::ChainHeader, entry::Entry, link::link_data::LinkData};
+use crate::{chain_header::ChainHeader, entry::Entry, link::link_data::LinkData, warrant::Warrant};
 use holochain_json_api::{error::JsonError, json::JsonString};
 use holochain_persistence_api::cas::content::{Address, AddressableContent, Content};
 use std::{
@@ -70,6 +70,10 @@ pub enum EntryAspect {
     // `Entry::Deletion(address)`.
     // Deletion(Address, ChainHeader),
     Deletion(ChainHeader),
+
+    // A signed statement that the entry of the contained header
+    // failed validation. Held at the address of that invalid entry.
+    Warrant(Warrant),
 }
 
 impl EntryAspect {
@@ -81,6 +85,7 @@ impl EntryAspect {
             EntryAspect::LinkRemove(_, _) => String::from("link_remove"),
             EntryAspect::Update(_, _) => String::from("update"),
             EntryAspect::Deletion(_) => String::from("deletion"),
+            EntryAspect::Warrant(_) => String::from("warrant"),
         }
     }
     pub fn header(&self) -> &ChainHeader {
@@ -91,6 +96,7 @@ impl EntryAspect {
             EntryAspect::LinkRemove(_, header) => header,
             EntryAspect::Update(_, header) => header,
             EntryAspect::Deletion(header) => header,
+            EntryAspect::Warrant(warrant) => warrant.invalid_header(),
         }
     }
     pub fn entry_address(&self) -> &Address {
@@ -144,6 +150,13 @@ impl fmt::Debug for EntryAspect {
             EntryAspect::Deletion(header) => {
                 write!(f, "EntryAspect::Deletion({})", format_header(header))
             }
+            EntryAspect::Warrant(warrant) => write!(
+                f,
+                "EntryAspect::Warrant({}, reason: {}, warrantor: {})",
+                format_header(warrant.invalid_header()),
+                warrant.reason(),
+                warrant.warrantor().source(),
+            ),
         }
     }
 }
@@ -1,5 +1,8 @@
 use crate::{
-    chain_header::ChainHeader, crud_status::CrudStatus, entry::EntryWithMetaAndHeader,
+    agent_activity::{AgentActivity, AgentActivityFilter},
+    chain_header::ChainHeader,
+    crud_status::CrudStatus,
+    entry::EntryWithMetaAndHeader,
     time::Iso8601,
 };
 use holochain_json_api::{error::JsonError, json::JsonString};
@@ -29,10 +32,46 @@ pub struct SizePagination {
     pub page_number: usize,
     pub page_size: usize,
 }
+/// Opaque position in a list of links: the creation time and LinkAdd address of the
+/// last link of a page. Authorities order links by both, so resuming after a cursor neither
+/// repeats nor skips links, even if new links got added in between.
+#[derive(Deserialize, Debug, Serialize, Clone, PartialEq, Eq, Hash, DefaultJson)]
+pub struct GetLinksCursor {
+    timestamp: i64,
+    link_add_address: Address,
+}
+
+impl GetLinksCursor {
+    pub fn new(timestamp: i64, link_add_address: Address) -> GetLinksCursor {
+        GetLinksCursor {
+            timestamp,
+            link_add_address,
+        }
+    }
+
+    /// Nanoseconds since the epoch as used for the index of link EAVIs
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    pub fn link_add_address(&self) -> &Address {
+        &self.link_add_address
+    }
+}
+
+/// Returns up to `limit` links following `cursor`, or the first ones if `cursor` is None.
+/// The cursor for the next page is returned with the links.
+#[derive(Deserialize, Default, Debug, Serialize, Clone, PartialEq, Eq, Hash, DefaultJson)]
+pub struct CursorPagination {
+    pub cursor: Option<GetLinksCursor>,
+    pub limit: usize,
+}
+
 #[derive(Deserialize, Debug, Serialize, Clone, PartialEq, Eq, Hash, DefaultJson)]
 pub enum Pagination {
     Size(SizePagination),
     Time(TimePagination),
+    Cursor(CursorPagination),
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, DefaultJson, Clone, Default)]
@@ -77,13 +116,18 @@ impl GetLinkData {
 #[derive(Debug, Serialize, Deserialize, PartialEq, DefaultJson, Clone)]
 pub enum GetLinksNetworkResult {
     Count(usize),
-    Links(Vec<GetLinkData>),
+    /// Links and, for a full page of cursor pagination, the cursor of the next page
+    Links(Vec<GetLinkData>, Option<GetLinksCursor>),
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, DefaultJson, Clone)]
 pub enum NetworkQuery {
     GetEntry,
     GetLinks(String, String, Option<CrudStatus>, GetLinksNetworkQuery),
+    /// Several entries at once, in addition to the one the query is addressed to
+    GetEntries(Vec<Address>),
+    /// The published headers of the agent the query is addressed to
+    GetAgentActivity(AgentActivityFilter),
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, DefaultJson, Clone)]
@@ -91,4 +135,7 @@ pub enum NetworkQuery {
 pub enum NetworkQueryResult {
     Entry(Option<EntryWithMetaAndHeader>),
     Links(GetLinksNetworkResult, String, String),
+    /// One item per requested address, in the order of the query. None if not held by the responder.
+    Entries(Vec<(Address, Option<EntryWithMetaAndHeader>)>),
+    AgentActivity(AgentActivity),
 }
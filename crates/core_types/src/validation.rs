@@ -10,6 +10,8 @@ use crate::{
     },
     error::HolochainError,
     link::link_data::LinkData,
+    membrane_proof::MembraneProof,
+    signature::Provenance,
 };
 
 use holochain_json_api::{error::JsonError, json::JsonString};
@@ -25,6 +27,19 @@ pub struct ValidationPackage {
     pub source_chain_entries: Option<Vec<Entry>>,
     pub source_chain_headers: Option<Vec<ChainHeader>>,
     pub custom: Option<String>,
+    /// Signature of the author over `custom` if it was built by the zome's custom
+    /// validation package callback (see `ValidationPackageDefinition::CustomCallback`)
+    #[serde(default)]
+    pub custom_signature: Option<Provenance>,
+    /// The membrane proof the author committed before its AgentId entry.
+    /// Only set in validation packages of AgentId entries.
+    #[serde(default)]
+    pub membrane_proof: Option<MembraneProof>,
+    /// The header the AgentId entry's header links to, so validators can check that
+    /// `membrane_proof` is the one the author committed right before its AgentId entry.
+    /// Only set in validation packages of AgentId entries.
+    #[serde(default)]
+    pub previous_header: Option<ChainHeader>,
 }
 
 impl ValidationPackage {
@@ -34,6 +49,9 @@ impl ValidationPackage {
             source_chain_entries: None,
             source_chain_headers: None,
             custom: None,
+            custom_signature: None,
+            membrane_proof: None,
+            previous_header: None,
         }
     }
 }
@@ -50,6 +68,9 @@ pub enum ValidationPackageDefinition {
     ChainFull,
     /// sending something custom
     Custom(String),
+    /// sending the data built by the entry type's custom validation package callback
+    /// when the entry got published, signed by the author
+    CustomCallback,
 }
 
 ///This struct carries information needed for Validating Entry Data,
@@ -137,6 +158,9 @@ impl Default for ValidationData {
                 source_chain_entries: None,
                 source_chain_headers: None,
                 custom: None,
+                custom_signature: None,
+                membrane_proof: None,
+                previous_header: None,
             },
             lifecycle: EntryLifecycle::default(),
         }
@@ -0,0 +1,87 @@
+//! A ValidationReceipt is a signed statement of a holding node (the validator)
+//! that it has validated and is now holding an entry. Validators send receipts
+//! back to the authors of the entry so authors can tell how many peers
+//! confirmed their data.
+
+use crate::{chain_header::ChainHeader, signature::Provenance};
+use holochain_json_api::{error::JsonError, json::JsonString};
+use holochain_persistence_api::cas::content::{Address, AddressableContent, Content};
+use std::convert::TryFrom;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, DefaultJson)]
+pub struct ValidationReceipt {
+    /// Address of the author's header of the validated entry
+    header_address: Address,
+    /// Address of the validated entry
+    entry_address: Address,
+    /// Address of the agent that validated the entry and its signature of
+    /// `ValidationReceipt::signing_payload()` over both addresses above
+    validator: Provenance,
+}
+
+impl ValidationReceipt {
+    pub fn new(header: &ChainHeader, validator: Provenance) -> Self {
+        ValidationReceipt {
+            header_address: header.address(),
+            entry_address: header.entry_address().clone(),
+            validator,
+        }
+    }
+
+    /// The string a validator has to sign in order to confirm that it holds
+    /// the given entry with the header with the given address.
+    pub fn signing_payload(header_address: &Address, entry_address: &Address) -> String {
+        format!("receipt:{}:{}", header_address, entry_address)
+    }
+
+    pub fn header_address(&self) -> &Address {
+        &self.header_address
+    }
+
+    pub fn entry_address(&self) -> &Address {
+        &self.entry_address
+    }
+
+    pub fn validator(&self) -> &Provenance {
+        &self.validator
+    }
+}
+
+impl AddressableContent for ValidationReceipt {
+    fn content(&self) -> Content {
+        self.to_owned().into()
+    }
+
+    fn try_from_content(content: &Content) -> Result<Self, JsonError> {
+        Self::try_from(content.to_owned())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{chain_header::test_chain_header, signature::Signature};
+
+    pub fn test_validation_receipt() -> ValidationReceipt {
+        ValidationReceipt::new(
+            &test_chain_header(),
+            Provenance::new(Address::from("validator"), Signature::fake()),
+        )
+    }
+
+    #[test]
+    fn validation_receipt_names_header_and_entry() {
+        let receipt = test_validation_receipt();
+        assert_eq!(receipt.header_address(), &test_chain_header().address());
+        assert_eq!(receipt.entry_address(), test_chain_header().entry_address());
+    }
+
+    #[test]
+    fn validation_receipt_content_roundtrip() {
+        let receipt = test_validation_receipt();
+        assert_eq!(
+            ValidationReceipt::try_from_content(&receipt.content()).unwrap(),
+            receipt
+        );
+    }
+}
@@ -0,0 +1,120 @@
+//! A Warrant is a signed statement of an agent (the warrantor) about having found
+//! a DHT entry that failed validation. It carries the invalid entry and its header,
+//! which has the signatures of the authors, and the reason validation failed.
+//! Nodes that receive a warrant validate the entry themselves before they believe it.
+//! Warrants get held and gossiped as entry aspects of the invalid entry so other
+//! nodes can learn about misbehaving agents.
+
+use crate::{chain_header::ChainHeader, entry::Entry, signature::Provenance};
+use holochain_json_api::{error::JsonError, json::JsonString};
+use holochain_persistence_api::cas::content::{Address, AddressableContent, Content};
+use std::convert::TryFrom;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq, DefaultJson)]
+pub struct Warrant {
+    /// The entry that failed validation
+    invalid_entry: Entry,
+    /// Header of the entry that failed validation
+    invalid_header: ChainHeader,
+    /// The validation error
+    reason: String,
+    /// Address of the agent that issued this warrant and its signature of
+    /// `Warrant::signing_payload()`
+    warrantor: Provenance,
+}
+
+impl Warrant {
+    pub fn new(
+        invalid_entry: Entry,
+        invalid_header: ChainHeader,
+        reason: String,
+        warrantor: Provenance,
+    ) -> Self {
+        Warrant {
+            invalid_entry,
+            invalid_header,
+            reason,
+            warrantor,
+        }
+    }
+
+    /// The string a warrantor has to sign in order to issue a warrant
+    /// about the given header with the given reason.
+    pub fn signing_payload(invalid_header: &ChainHeader, reason: &str) -> String {
+        format!("warrant:{}:{}", invalid_header.address(), reason)
+    }
+
+    pub fn invalid_entry(&self) -> &Entry {
+        &self.invalid_entry
+    }
+
+    pub fn invalid_header(&self) -> &ChainHeader {
+        &self.invalid_header
+    }
+
+    pub fn reason(&self) -> &String {
+        &self.reason
+    }
+
+    pub fn warrantor(&self) -> &Provenance {
+        &self.warrantor
+    }
+
+    /// The agents that signed the invalid header, i.e. the agents this warrant is against
+    pub fn authors(&self) -> Vec<Address> {
+        self.invalid_header
+            .provenances()
+            .iter()
+            .map(|provenance| provenance.source())
+            .collect()
+    }
+}
+
+impl AddressableContent for Warrant {
+    fn content(&self) -> Content {
+        self.to_owned().into()
+    }
+
+    fn try_from_content(content: &Content) -> Result<Self, JsonError> {
+        Self::try_from(content.to_owned())
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::{
+        chain_header::test_chain_header,
+        entry::test_entry,
+        signature::{Provenance, Signature},
+    };
+
+    pub fn test_warrant() -> Warrant {
+        Warrant::new(
+            test_entry(),
+            test_chain_header(),
+            String::from("invalid"),
+            Provenance::new(Address::from("warrantor"), Signature::fake()),
+        )
+    }
+
+    #[test]
+    fn warrant_names_the_authors_of_the_invalid_header() {
+        let warrant = test_warrant();
+        let authors: Vec<Address> = test_chain_header()
+            .provenances()
+            .iter()
+            .map(|provenance| provenance.source())
+            .collect();
+        assert_eq!(warrant.authors(), authors);
+    }
+
+    #[test]
+    fn warrant_content_roundtrip() {
+        let warrant = test_warrant();
+        assert_eq!(
+            Warrant::try_from_content(&warrant.content()).unwrap(),
+            warrant
+        );
+    }
+}
@@ -0,0 +1,267 @@
+use crate::{
+    error::{ZomeApiError, ZomeApiResult},
+    Dispatch,
+};
+use holochain_core_types::blob::{BlobChunk, BlobManifest, BLOB_CHUNK_SIZE};
+use holochain_persistence_api::cas::content::Address;
+use holochain_wasm_utils::api_serialization::blob::{GetBlobArgs, GetBlobResult, StoreBlobArgs};
+use std::io::{self, Read};
+
+/// Name of the entry type a zome defines to validate the manifests of blobs, see `entry!`
+pub const BLOB_MANIFEST_ENTRY_TYPE: &str = "%blob_manifest";
+
+/// Number of chunks a BlobReader fetches at once
+const CHUNKS_PER_FETCH: usize = 4;
+
+/// Stores binary data that is too large for one entry, like a file or an image.
+/// The data gets read to its end and split into chunks which are committed as separate
+/// entries, followed by a manifest entry listing them. Returns the address of the manifest,
+/// which is the address of the blob.
+/// # Examples
+/// ```rust
+/// # extern crate hdk;
+/// # extern crate holochain_persistence_api;
+/// # use holochain_persistence_api::cas::content::Address;
+/// # use hdk::error::ZomeApiResult;
+/// # use std::io::Read;
+///
+/// # fn main() {
+/// pub fn handle_store_picture(picture: Vec<u8>) -> ZomeApiResult<Address> {
+///     hdk::store_blob(picture.as_slice())
+/// }
+///
+/// pub fn handle_get_picture(address: Address) -> ZomeApiResult<Vec<u8>> {
+///     let mut picture = Vec::new();
+///     hdk::get_blob(&address)?
+///         .read_to_end(&mut picture)
+///         .map_err(|err| hdk::error::ZomeApiError::Internal(err.to_string()))?;
+///     Ok(picture)
+/// }
+/// # }
+/// ```
+pub fn store_blob<R: Read>(mut reader: R) -> ZomeApiResult<Address> {
+    let mut size = 0u64;
+    let mut chunks = Vec::new();
+    loop {
+        let mut data = vec![0; BLOB_CHUNK_SIZE];
+        let mut filled = 0;
+        while filled < BLOB_CHUNK_SIZE {
+            match reader.read(&mut data[filled..]) {
+                Ok(0) => break,
+                Ok(read) => filled += read,
+                Err(ref err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(ZomeApiError::Internal(err.to_string())),
+            }
+        }
+        if filled == 0 {
+            break;
+        }
+        data.truncate(filled);
+        size += filled as u64;
+        chunks.push(Dispatch::StoreBlob.with_input(StoreBlobArgs::Chunk(BlobChunk::new(data)))?);
+        if filled < BLOB_CHUNK_SIZE {
+            break;
+        }
+    }
+    Dispatch::StoreBlob.with_input(StoreBlobArgs::Manifest(BlobManifest::new(size, chunks)))
+}
+
+/// Gets a blob stored with `store_blob`. Returns a reader that fetches the chunks of the
+/// blob a few at a time while it gets read. Every chunk is checked against the manifest,
+/// so reading fails instead of returning data that does not belong to the blob.
+pub fn get_blob(address: &Address) -> ZomeApiResult<BlobReader> {
+    BlobReader::new(address, fetch_chunks)
+}
+
+/// Fetches a window of chunks of a blob, and its manifest if not given in the arguments
+type FetchChunks = fn(&GetBlobArgs) -> ZomeApiResult<GetBlobResult>;
+
+fn fetch_chunks(args: &GetBlobArgs) -> ZomeApiResult<GetBlobResult> {
+    Dispatch::GetBlob.with_input(args.clone())
+}
+
+/// Reads a blob chunk by chunk, see `get_blob`
+pub struct BlobReader {
+    address: Address,
+    fetch: FetchChunks,
+    manifest: BlobManifest,
+    /// Fetched chunks that have not been read yet, in order
+    chunks: Vec<BlobChunk>,
+    /// Index of the chunk that gets read after the current one
+    next_chunk: usize,
+    buffer: Vec<u8>,
+    position: usize,
+    read: u64,
+}
+
+impl BlobReader {
+    /// Fetches the manifest together with the first window of chunks
+    fn new(address: &Address, fetch: FetchChunks) -> ZomeApiResult<Self> {
+        let result = fetch(&GetBlobArgs {
+            address: address.clone(),
+            chunk_count: CHUNKS_PER_FETCH,
+            ..Default::default()
+        })?;
+        Ok(BlobReader {
+            address: address.clone(),
+            fetch,
+            manifest: result.manifest,
+            chunks: result.chunks,
+            next_chunk: 0,
+            buffer: Vec::new(),
+            position: 0,
+            read: 0,
+        })
+    }
+
+    pub fn manifest(&self) -> &BlobManifest {
+        &self.manifest
+    }
+
+    /// Size of the whole blob in bytes
+    pub fn size(&self) -> u64 {
+        self.manifest.size
+    }
+
+    /// Moves the next chunk into the buffer, fetching more chunks if needed.
+    /// Returns false at the end of the blob.
+    fn next_chunk(&mut self) -> io::Result<bool> {
+        if self.next_chunk >= self.manifest.chunks.len() {
+            if self.read != self.manifest.size {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!(
+                        "Blob {} holds {} bytes, expected {}",
+                        self.address, self.read, self.manifest.size
+                    ),
+                ));
+            }
+            return Ok(false);
+        }
+        if self.chunks.is_empty() {
+            self.chunks = (self.fetch)(&GetBlobArgs {
+                address: self.address.clone(),
+                first_chunk: self.next_chunk,
+                chunk_count: CHUNKS_PER_FETCH,
+                manifest: Some(self.manifest.clone()),
+                ..Default::default()
+            })
+            .map_err(|err| io::Error::new(io::ErrorKind::Other, err.to_string()))?
+            .chunks;
+            if self.chunks.is_empty() {
+                return Err(io::Error::new(
+                    io::ErrorKind::UnexpectedEof,
+                    format!("Chunk {} of blob {} missing", self.next_chunk, self.address),
+                ));
+            }
+        }
+        self.buffer = self.chunks.remove(0).data().to_vec();
+        self.position = 0;
+        self.next_chunk += 1;
+        Ok(true)
+    }
+}
+
+impl Read for BlobReader {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        while self.position >= self.buffer.len() {
+            if !self.next_chunk()? {
+                return Ok(0);
+            }
+        }
+        let count = buf.len().min(self.buffer.len() - self.position);
+        buf[..count].copy_from_slice(&self.buffer[self.position..self.position + count]);
+        self.position += count;
+        self.read += count as u64;
+        Ok(count)
+    }
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use holochain_core_types::entry::Entry;
+    use holochain_persistence_api::cas::content::AddressableContent;
+    use std::cell::RefCell;
+
+    thread_local! {
+        /// Chunks of the blob the fake fetch serves and the arguments it got called with
+        static CHUNKS: RefCell<Vec<BlobChunk>> = RefCell::new(Vec::new());
+        static FETCHES: RefCell<Vec<GetBlobArgs>> = RefCell::new(Vec::new());
+    }
+
+    fn test_manifest(size: u64) -> BlobManifest {
+        let chunks = CHUNKS.with(|chunks| {
+            chunks
+                .borrow()
+                .iter()
+                .map(|chunk| Entry::BlobChunk(chunk.clone()).address())
+                .collect()
+        });
+        BlobManifest::new(size, chunks)
+    }
+
+    /// Serves the chunks in CHUNKS, with a manifest claiming the size in the address
+    fn fake_fetch(args: &GetBlobArgs) -> ZomeApiResult<GetBlobResult> {
+        FETCHES.with(|fetches| fetches.borrow_mut().push(args.clone()));
+        let size = String::from(args.address.clone()).parse().unwrap();
+        let chunks = CHUNKS.with(|chunks| {
+            chunks
+                .borrow()
+                .iter()
+                .skip(args.first_chunk)
+                .take(args.chunk_count)
+                .cloned()
+                .collect()
+        });
+        Ok(GetBlobResult {
+            manifest: args.manifest.clone().unwrap_or_else(|| test_manifest(size)),
+            chunks,
+        })
+    }
+
+    fn set_chunks(chunks: Vec<Vec<u8>>) {
+        CHUNKS.with(|c| *c.borrow_mut() = chunks.into_iter().map(BlobChunk::new).collect());
+        FETCHES.with(|fetches| fetches.borrow_mut().clear());
+    }
+
+    #[test]
+    fn blob_reader_fetches_windows_with_known_manifest() {
+        let data: Vec<Vec<u8>> = (0..CHUNKS_PER_FETCH as u8 + 2)
+            .map(|i| vec![i; 3])
+            .collect();
+        set_chunks(data.clone());
+        let size = data.len() as u64 * 3;
+
+        let mut reader = BlobReader::new(&Address::from(size.to_string()), fake_fetch).unwrap();
+        assert_eq!(reader.size(), size);
+        let mut read = Vec::new();
+        reader.read_to_end(&mut read).unwrap();
+        assert_eq!(read, data.concat());
+
+        let fetches = FETCHES.with(|fetches| fetches.borrow().clone());
+        assert_eq!(fetches.len(), 2);
+        assert_eq!(fetches[0].first_chunk, 0);
+        assert_eq!(fetches[0].manifest, None);
+        assert_eq!(fetches[1].first_chunk, CHUNKS_PER_FETCH);
+        assert_eq!(fetches[1].manifest, Some(reader.manifest().clone()));
+    }
+
+    #[test]
+    fn blob_reader_fails_on_size_mismatch() {
+        set_chunks(vec![vec![1; 3], vec![2; 3]]);
+        let mut reader = BlobReader::new(&Address::from("7"), fake_fetch).unwrap();
+        let err = reader.read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn blob_reader_fails_on_missing_chunks() {
+        set_chunks(vec![vec![1; 3]; CHUNKS_PER_FETCH + 1]);
+        let mut reader = BlobReader::new(&Address::from("15"), fake_fetch).unwrap();
+        // drop the chunk of the second window before it gets fetched
+        CHUNKS.with(|chunks| chunks.borrow_mut().truncate(CHUNKS_PER_FETCH));
+        let err = reader.read_to_end(&mut Vec::new()).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+}
@@ -1,8 +1,8 @@
 use crate::{error::ZomeApiResult, Dispatch};
-use holochain_core_types::entry::Entry;
+use holochain_core_types::{entry::Entry, time::Timeout};
 use holochain_persistence_api::cas::content::Address;
 use holochain_wasm_utils::api_serialization::commit_entry::{
-    CommitEntryArgs, CommitEntryOptions, CommitEntryResult,
+    CommitCountersignedEntryArgs, CommitEntryArgs, CommitEntryOptions, CommitEntryResult,
 };
 
 /// Attempts to commit an entry to the local source chain. The entry
@@ -74,3 +74,21 @@ pub fn commit_entry_result(
         options,
     })
 }
+
+/// Commits an entry of a countersigned entry type (one with `countersigners` in its
+/// definition) together with all agents it names as countersigners.
+///
+/// The entry gets sent to the other countersigners, who validate and sign it. Once all
+/// of them signed before the timeout, every one of them commits the entry with a header
+/// carrying all signatures. Otherwise nobody commits it and an error is returned.
+/// Each agent can only take part in one countersigning session at a time, and can't
+/// commit anything else until it ends.
+/// Returns the address of the committed entry.
+pub fn commit_countersigned_entry(entry: &Entry, timeout: Timeout) -> ZomeApiResult<Address> {
+    let result: CommitEntryResult =
+        Dispatch::CommitCountersignedEntry.with_input(CommitCountersignedEntryArgs {
+            entry: entry.clone(),
+            timeout,
+        })?;
+    Ok(result.address())
+}
@@ -1,6 +1,7 @@
 use crate::{error::ZomeApiResult, Dispatch};
 use holochain_json_api::json::JsonString;
-use holochain_wasm_utils::api_serialization::emit_signal::EmitSignalArgs;
+use holochain_persistence_api::cas::content::Address;
+use holochain_wasm_utils::api_serialization::emit_signal::{EmitSignalArgs, RemoteSignalArgs};
 
 /// Emits a signal that listeners can receive.
 /// (Status: MVP)
@@ -58,3 +59,48 @@ pub fn emit_signal<S: Into<String>, J: Into<JsonString>>(
     // return Ok(()) unconditionally instead of the "error" from success
     Ok(())
 }
+
+/// Emits a signal on the conductors of the given agents, so it reaches their UIs
+/// like a signal from their own [emit_signal](fn.emit_signal.html) would.
+/// The received signal carries our address as `sender`.
+///
+/// Unlike [send](fn.send.html) this does not wait for, or even get, a response:
+/// signals to agents that are offline or have no UI listening are silently lost.
+/// # Examples
+/// ```rust
+/// # #[macro_use]
+/// # extern crate hdk;
+/// # extern crate holochain_persistence_api;
+/// # use hdk::error::ZomeApiResult;
+/// # use hdk::holochain_json_api::json::JsonString;
+/// # use hdk::holochain_core_types::error::RibosomeEncodingBits;
+/// # use hdk::holochain_core_types::error::RibosomeEncodedValue;
+/// # use holochain_persistence_api::cas::content::Address;
+/// # #[no_mangle]
+/// # pub fn hc_remote_signal(_: RibosomeEncodingBits) -> RibosomeEncodingBits { RibosomeEncodedValue::Success.into() }
+///
+/// # fn main() {
+/// pub fn handle_post_chat_message(members: Vec<Address>, message: String) -> ZomeApiResult<()> {
+///     // ...
+///     hdk::remote_signal(members, "message_received", JsonString::from_json(&format!(
+///         "{{\"message\": \"{}\"}}", message
+///     )))
+/// }
+///
+/// # }
+/// ```
+pub fn remote_signal<S: Into<String>, J: Into<JsonString>>(
+    agents: Vec<Address>,
+    name: S,
+    arguments: J,
+) -> ZomeApiResult<()> {
+    let _: ZomeApiResult<()> = Dispatch::RemoteSignal.with_input(RemoteSignalArgs {
+        agents,
+        signal: EmitSignalArgs {
+            name: name.into(),
+            arguments: arguments.into(),
+        },
+    });
+    // Same as for emit_signal: success is a zero length allocation
+    Ok(())
+}
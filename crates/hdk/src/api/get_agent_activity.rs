@@ -0,0 +1,43 @@
+use crate::{error::ZomeApiResult, Dispatch};
+use holochain_core_types::agent_activity::{AgentActivity, AgentActivityFilter};
+use holochain_persistence_api::cas::content::Address;
+use holochain_wasm_utils::api_serialization::get_agent_activity::GetAgentActivityArgs;
+
+/// Returns the public headers the given agent has published, newest first, as held by
+/// the authorities of the agent's address.
+/// Every header of a public entry gets published to those authorities, so this shows what
+/// an agent did without having to go through validation packages.
+/// The filter selects the returned headers by entry type, time range and count.
+///
+/// The returned status says if the agent forked its source chain, i.e. published
+/// two different headers that point to the same previous header. Fork detection always
+/// considers all held headers of the agent, regardless of the filter.
+/// # Examples
+/// ```rust
+/// # extern crate hdk;
+/// # extern crate holochain_core_types;
+/// # extern crate holochain_persistence_api;
+/// # use holochain_persistence_api::cas::content::Address;
+/// # use holochain_core_types::agent_activity::{AgentActivityFilter, ChainStatus};
+/// # use hdk::error::ZomeApiResult;
+///
+/// # fn main() {
+/// pub fn handle_is_honest(agent: Address) -> ZomeApiResult<bool> {
+///     let activity = hdk::get_agent_activity(&agent, AgentActivityFilter::default())?;
+///     Ok(match activity.status {
+///         ChainStatus::Forked(_) => false,
+///         _ => true,
+///     })
+/// }
+/// # }
+/// ```
+pub fn get_agent_activity(
+    agent: &Address,
+    filter: AgentActivityFilter,
+) -> ZomeApiResult<AgentActivity> {
+    Dispatch::GetAgentActivity.with_input(GetAgentActivityArgs {
+        agent: agent.clone(),
+        filter,
+        ..Default::default()
+    })
+}
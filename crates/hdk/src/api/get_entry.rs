@@ -5,8 +5,8 @@ use crate::{
 use holochain_core_types::entry::Entry;
 use holochain_persistence_api::cas::content::Address;
 use holochain_wasm_utils::api_serialization::get_entry::{
-    EntryHistory, GetEntryArgs, GetEntryOptions, GetEntryResult, GetEntryResultType,
-    StatusRequestKind,
+    EntryHistory, GetEntriesArgs, GetEntriesResult, GetEntryArgs, GetEntryOptions, GetEntryResult,
+    GetEntryResultType, StatusRequestKind,
 };
 
 /// Retrieves latest version of an entry from the local chain or the DHT, by looking it up using
@@ -87,3 +87,34 @@ pub fn get_entry_result(
         options,
     })
 }
+
+/// Retrieves several entries and their metadata like [get_entry_result](fn.get_entry_result.html)
+/// does for one, but with as few network round trips as possible instead of one per address.
+/// Returns one result per address in the same order, so entries that can't be retrieved
+/// don't fail the others.
+/// # Examples
+/// ```rust
+/// # extern crate hdk;
+/// # extern crate holochain_wasm_utils;
+/// # extern crate holochain_persistence_api;
+/// # use hdk::error::ZomeApiResult;
+/// # use holochain_persistence_api::cas::content::Address;
+/// # use holochain_wasm_utils::api_serialization::get_entry::{GetEntryOptions, GetEntryResult};
+/// # fn main() {
+/// pub fn handle_get_posts(addresses: Vec<Address>) -> ZomeApiResult<Vec<ZomeApiResult<GetEntryResult>>> {
+///     hdk::get_entries(addresses, GetEntryOptions::default())
+/// }
+/// # }
+/// ```
+pub fn get_entries(
+    addresses: Vec<Address>,
+    options: GetEntryOptions,
+) -> ZomeApiResult<Vec<ZomeApiResult<GetEntryResult>>> {
+    let entries_result: GetEntriesResult =
+        Dispatch::GetEntries.with_input(GetEntriesArgs { addresses, options })?;
+    Ok(entries_result
+        .results
+        .into_iter()
+        .map(|result| result.map_err(ZomeApiError::from))
+        .collect())
+}
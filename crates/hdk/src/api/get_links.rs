@@ -1,5 +1,5 @@
 use crate::{
-    api::get_entry::get_entry_result,
+    api::get_entry::get_entries,
     error::{ZomeApiError, ZomeApiResult},
     Dispatch,
 };
@@ -19,6 +19,9 @@ use holochain_wasm_utils::api_serialization::{
 /// Links are created using the Zome API function [link_entries](::link_entries()).
 /// If you also need the content of the entry consider using one of the helper functions:
 /// [get_links_result](get_links_result()) or [get_links_and_load](get_links_and_load())
+/// To page through many links, set `Pagination::Cursor` in the options and pass the `next_cursor()`
+/// of each result in the options of the following call until it returns None.
+/// Unlike page numbers, cursors don't repeat or skip links that got added in between calls.
 /// # Examples
 /// ```rust
 /// # extern crate hdk;
@@ -138,12 +141,7 @@ pub fn get_links_result(
     get_entry_options: GetEntryOptions,
 ) -> ZomeApiResult<Vec<ZomeApiResult<GetEntryResult>>> {
     let get_links_result = get_links_with_options(base, link_type, tag, options)?;
-    let result = get_links_result
-        .addresses()
-        .iter()
-        .map(|address| get_entry_result(&address, get_entry_options.clone()))
-        .collect();
-    Ok(result)
+    get_entries(get_links_result.addresses(), get_entry_options)
 }
 
 /// Helper function for get_links. Returns a vector of the entries themselves
@@ -0,0 +1,30 @@
+use crate::{error::ZomeApiResult, Dispatch};
+use holochain_persistence_api::cas::content::Address;
+use holochain_wasm_utils::api_serialization::get_validation_receipts::{
+    GetValidationReceiptsArgs, ValidationReceiptsResult,
+};
+
+/// Returns how many validators confirmed holding the entry with the given address,
+/// and who they are. The address can either be the address of an entry this agent
+/// authored or the address of its header.
+/// Validators send a signed receipt to the author after they have validated and
+/// stored an entry, so this can be used to show that data got confirmed by a number of
+/// peers or to detect entries that are not held by enough nodes.
+/// # Examples
+/// ```rust
+/// # extern crate hdk;
+/// # extern crate holochain_persistence_api;
+/// # use holochain_persistence_api::cas::content::Address;
+/// # use hdk::error::ZomeApiResult;
+///
+/// # fn main() {
+/// pub fn handle_confirmations(post_address: Address) -> ZomeApiResult<usize> {
+///     Ok(hdk::get_validation_receipts(&post_address)?.count)
+/// }
+/// # }
+/// ```
+pub fn get_validation_receipts(address: &Address) -> ZomeApiResult<ValidationReceiptsResult> {
+    Dispatch::GetValidationReceipts.with_input(GetValidationReceiptsArgs {
+        address: address.clone(),
+    })
+}
@@ -0,0 +1,31 @@
+use crate::{error::ZomeApiResult, Dispatch};
+use holochain_core_types::warrant::Warrant;
+use holochain_persistence_api::cas::content::Address;
+use holochain_wasm_utils::api_serialization::get_warrants::{GetWarrantsArgs, GetWarrantsResult};
+
+/// Returns the warrants this node holds about the given address, which can either be
+/// the address of an entry that failed validation or the address of an agent that authored
+/// invalid data.
+/// Warrants get issued by nodes that fail to validate an entry or link they were asked to hold.
+/// Each warrant carries the invalid header, the validation error and the signature of the
+/// node that issued it.
+/// # Examples
+/// ```rust
+/// # extern crate hdk;
+/// # extern crate holochain_core_types;
+/// # extern crate holochain_persistence_api;
+/// # use holochain_persistence_api::cas::content::Address;
+/// # use hdk::error::ZomeApiResult;
+///
+/// # fn main() {
+/// pub fn handle_is_trustworthy(agent: Address) -> ZomeApiResult<bool> {
+///     Ok(hdk::get_warrants(&agent)?.is_empty())
+/// }
+/// # }
+/// ```
+pub fn get_warrants(address: &Address) -> ZomeApiResult<Vec<Warrant>> {
+    let result: GetWarrantsResult = Dispatch::GetWarrants.with_input(GetWarrantsArgs {
+        address: address.clone(),
+    })?;
+    Ok(result.warrants)
+}
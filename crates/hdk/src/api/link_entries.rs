@@ -61,7 +61,7 @@ use holochain_wasm_utils::api_serialization::link_entries::LinkEntriesArgs;
 ///
 ///     if let Some(in_reply_to_address) = in_reply_to {
 ///         // return with Err if in_reply_to_address points to missing entry
-///         hdk::get_entry_result(&in_reply_to_address, GetEntryOptions { status_request: StatusRequestKind::All, entry: false, headers: false, timeout: Default::default() })?;
+///         hdk::get_entry_result(&in_reply_to_address, GetEntryOptions { status_request: StatusRequestKind::All, entry: false, headers: false, timeout: Default::default(), cache_policy: Default::default() })?;
 ///         hdk::link_entries(&in_reply_to_address, &address, "comments", "")?;
 ///     }
 ///
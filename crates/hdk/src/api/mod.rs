@@ -19,6 +19,7 @@ use holochain_wasm_utils::{
 use crate::init_globals::init_globals;
 use std::convert::{TryFrom, TryInto};
 
+mod blob;
 mod bundle;
 mod call;
 mod capability;
@@ -29,8 +30,11 @@ mod emit_signal;
 mod encrypt;
 mod entry_address;
 mod entry_type_properties;
+mod get_agent_activity;
 mod get_entry;
 mod get_links;
+mod get_validation_receipts;
+mod get_warrants;
 mod keystore;
 mod link_entries;
 mod property;
@@ -41,36 +45,42 @@ mod sign;
 mod sleep;
 mod update_remove;
 mod version;
+mod zome_config;
 
 pub use self::{
+    blob::{get_blob, store_blob, BlobReader, BLOB_MANIFEST_ENTRY_TYPE},
     bundle::{close_bundle, start_bundle},
     call::call,
     capability::{commit_capability_claim, commit_capability_grant},
-    commit_entry::{commit_entry, commit_entry_result},
+    commit_entry::{commit_countersigned_entry, commit_entry, commit_entry_result},
     debug::debug,
     decrypt::decrypt,
-    emit_signal::emit_signal,
+    emit_signal::{emit_signal, remote_signal},
     encrypt::encrypt,
     entry_address::entry_address,
     entry_type_properties::entry_type_properties,
-    get_entry::{get_entry, get_entry_history, get_entry_initial, get_entry_result},
+    get_agent_activity::get_agent_activity,
+    get_entry::{get_entries, get_entry, get_entry_history, get_entry_initial, get_entry_result},
     get_links::{
         get_links, get_links_and_load, get_links_count, get_links_count_with_options,
         get_links_result, get_links_with_options,
     },
+    get_validation_receipts::get_validation_receipts,
+    get_warrants::get_warrants,
     keystore::{
         keystore_derive_key, keystore_derive_seed, keystore_get_public_key, keystore_list,
         keystore_new_random, keystore_sign,
     },
     link_entries::link_entries,
     property::property,
-    query::{query, query_result},
+    query::{query, query_page, query_result},
     remove_link::remove_link,
-    send::send,
+    send::{send, send_with_options},
     sign::{sign, sign_one_time, verify_signature},
     sleep::sleep,
-    update_remove::{remove_entry, update_agent, update_entry},
+    update_remove::{purge_entry, remove_entry, update_agent, update_entry},
     version::{version, version_hash},
+    zome_config::zome_config,
 };
 
 macro_rules! def_api_fns {
@@ -170,11 +180,14 @@ macro_rules! def_api_fns {
 def_api_fns! {
     hc_init_globals, InitGlobals;
     hc_commit_entry, CommitEntry;
+    hc_commit_countersigned_entry, CommitCountersignedEntry;
     hc_get_entry, GetEntry;
+    hc_get_entries, GetEntries;
     hc_entry_address, EntryAddress;
     hc_query, Query;
     hc_update_entry, UpdateEntry;
     hc_remove_entry, RemoveEntry;
+    hc_purge_entry, PurgeEntry;
     hc_send, Send;
     hc_debug, Debug;
     hc_call, Call;
@@ -185,6 +198,11 @@ def_api_fns! {
     hc_remove_link, RemoveLink;
     hc_get_links, GetLinks;
     hc_get_links_count,GetLinksCount;
+    hc_get_warrants, GetWarrants;
+    hc_get_agent_activity, GetAgentActivity;
+    hc_store_blob, StoreBlob;
+    hc_get_blob, GetBlob;
+    hc_get_validation_receipts, GetValidationReceipts;
     hc_sleep, Sleep;
     hc_meta,Meta;
     hc_keystore_list, KeystoreList;
@@ -196,6 +214,8 @@ def_api_fns! {
     hc_commit_capability_grant, CommitCapabilityGrant;
     hc_commit_capability_claim, CommitCapabilityClaim;
     hc_emit_signal, EmitSignal;
+    hc_remote_signal, RemoteSignal;
+    hc_zome_config, ZomeConfig;
 }
 
 //--------------------------------------------------------------------------------------------------
@@ -4,7 +4,7 @@ use crate::{
 };
 use holochain_persistence_api::cas::content::Address;
 use holochain_wasm_utils::api_serialization::{
-    QueryArgs, QueryArgsNames, QueryArgsOptions, QueryResult,
+    QueryArgs, QueryArgsNames, QueryArgsOptions, QueryResult, QueryResultPage,
 };
 
 /// Returns a list of entries from your local source chain that match a given entry type name or names.
@@ -82,6 +82,7 @@ pub fn query(
             limit,
             headers: false,
             entries: false,
+            ..Default::default()
         },
     )
     .and_then(|result| match result {
@@ -94,6 +95,51 @@ pub fn query_result(
     entry_type_names: QueryArgsNames,
     options: QueryArgsOptions,
 ) -> ZomeApiResult<QueryResult> {
+    query_page(entry_type_names, options).map(|page| page.result)
+}
+
+/// Like hdk::query_result, but also returns a cursor when the page is full.
+/// Set it as `cursor` in the QueryArgsOptions of the next call to get the following page.
+/// A cursor only works with the entry type names and options of the query that returned it,
+/// otherwise the call fails instead of returning an empty page:
+///
+/// ```rust
+/// # extern crate hdk;
+/// # extern crate holochain_persistence_api;
+/// # extern crate holochain_wasm_utils;
+/// # use hdk::error::ZomeApiResult;
+/// # use holochain_persistence_api::cas::content::Address;
+/// # use holochain_wasm_utils::api_serialization::{QueryArgsOptions, QueryResult};
+///
+/// # fn main() {
+/// pub fn get_all_posts_oldest_first() -> ZomeApiResult<Vec<Address>> {
+///     let mut addresses = Vec::new();
+///     let mut cursor = None;
+///     loop {
+///         let page = hdk::query_page(
+///             "post".into(),
+///             QueryArgsOptions {
+///                 limit: 100,
+///                 reverse: true,
+///                 cursor,
+///                 ..Default::default()
+///             },
+///         )?;
+///         if let QueryResult::Addresses(page_addresses) = page.result {
+///             addresses.extend(page_addresses);
+///         }
+///         match page.next_cursor {
+///             Some(next) => cursor = Some(next),
+///             None => return Ok(addresses),
+///         }
+///     }
+/// }
+/// # }
+/// ```
+pub fn query_page(
+    entry_type_names: QueryArgsNames,
+    options: QueryArgsOptions,
+) -> ZomeApiResult<QueryResultPage> {
     Dispatch::Query.with_input(QueryArgs {
         entry_type_names,
         options,
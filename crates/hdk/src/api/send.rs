@@ -1,7 +1,7 @@
 use crate::{error::ZomeApiResult, Dispatch};
 use holochain_core_types::time::Timeout;
 use holochain_persistence_api::cas::content::Address;
-use holochain_wasm_utils::api_serialization::send::{SendArgs, SendOptions};
+use holochain_wasm_utils::api_serialization::send::{SendArgs, SendOptions, SendResult};
 
 /// Sends a node-to-node message to the given agent, specified by their address.
 /// Addresses of agents can be accessed using [hdk::AGENT_ADDRESS](struct.AGENT_ADDRESS.html).
@@ -38,6 +38,18 @@ use holochain_wasm_utils::api_serialization::send::{SendArgs, SendOptions};
 /// # #[no_mangle]
 /// # pub fn hc_get_entry(_: RibosomeEncodingBits) -> RibosomeEncodingBits { RibosomeEncodedValue::Success.into() }
 /// # #[no_mangle]
+/// # pub fn hc_get_entries(_: RibosomeEncodingBits) -> RibosomeEncodingBits { RibosomeEncodedValue::Success.into() }
+/// # #[no_mangle]
+/// # pub fn hc_commit_countersigned_entry(_: RibosomeEncodingBits) -> RibosomeEncodingBits { RibosomeEncodedValue::Success.into() }
+/// # #[no_mangle]
+/// # pub fn hc_remote_signal(_: RibosomeEncodingBits) -> RibosomeEncodingBits { RibosomeEncodedValue::Success.into() }
+/// # #[no_mangle]
+/// # pub fn hc_get_agent_activity(_: RibosomeEncodingBits) -> RibosomeEncodingBits { RibosomeEncodedValue::Success.into() }
+/// # #[no_mangle]
+/// # pub fn hc_store_blob(_: RibosomeEncodingBits) -> RibosomeEncodingBits { RibosomeEncodedValue::Success.into() }
+/// # #[no_mangle]
+/// # pub fn hc_get_blob(_: RibosomeEncodingBits) -> RibosomeEncodingBits { RibosomeEncodedValue::Success.into() }
+/// # #[no_mangle]
 /// # pub fn hc_entry_address(_: RibosomeEncodingBits) -> RibosomeEncodingBits { RibosomeEncodedValue::Success.into() }
 /// # #[no_mangle]
 /// # pub fn hc_query(_: RibosomeEncodingBits) -> RibosomeEncodingBits { RibosomeEncodedValue::Success.into() }
@@ -56,6 +68,8 @@ use holochain_wasm_utils::api_serialization::send::{SendArgs, SendOptions};
 /// # #[no_mangle]
 /// # pub fn hc_remove_entry(_: RibosomeEncodingBits) -> RibosomeEncodingBits { RibosomeEncodedValue::Success.into() }
 /// # #[no_mangle]
+/// # pub fn hc_purge_entry(_: RibosomeEncodingBits) -> RibosomeEncodingBits { RibosomeEncodedValue::Success.into() }
+/// # #[no_mangle]
 /// # pub fn hc_send(_: RibosomeEncodingBits) -> RibosomeEncodingBits { RibosomeEncodedValue::Success.into() }
 /// /// # #[no_mangle]
 /// # pub fn hc_encrypt(_: RibosomeEncodingBits) -> RibosomeEncodingBits { RibosomeEncodedValue::Success.into() }
@@ -68,6 +82,12 @@ use holochain_wasm_utils::api_serialization::send::{SendArgs, SendOptions};
 /// # #[no_mangle]
 /// # pub fn hc_get_links_count(_: RibosomeEncodingBits) -> RibosomeEncodingBits { RibosomeEncodedValue::Success.into() }
 /// # #[no_mangle]
+/// # pub fn hc_get_warrants(_: RibosomeEncodingBits) -> RibosomeEncodingBits { RibosomeEncodedValue::Success.into() }
+/// # #[no_mangle]
+/// # pub fn hc_get_validation_receipts(_: RibosomeEncodingBits) -> RibosomeEncodingBits { RibosomeEncodedValue::Success.into() }
+/// # #[no_mangle]
+/// # pub fn hc_zome_config(_: RibosomeEncodingBits) -> RibosomeEncodingBits { RibosomeEncodedValue::Success.into() }
+/// # #[no_mangle]
 /// # pub fn hc_link_entries(_: RibosomeEncodingBits) -> RibosomeEncodingBits { RibosomeEncodedValue::Success.into() }
 /// # #[no_mangle]
 /// # pub fn hc_remove_link(_: RibosomeEncodingBits) -> RibosomeEncodingBits { RibosomeEncodedValue::Success.into() }
@@ -131,6 +151,57 @@ pub fn send(to_agent: Address, payload: String, timeout: Timeout) -> ZomeApiResu
     Dispatch::Send.with_input(SendArgs {
         to_agent,
         payload,
-        options: SendOptions(timeout),
+        options: SendOptions {
+            timeout,
+            deliver_later: false,
+        },
     })
 }
+
+/// Like [send](fn.send.html), but with options.
+///
+/// With `deliver_later` set, a message to an agent that is not connected right now
+/// does not time out. Instead the network keeps it and delivers it in order with other
+/// stored messages once the agent reconnects, and `send_with_options` returns
+/// `SendResult::Queued` right away. The response of the recipient to a queued message
+/// is dropped. This needs a sim2h server with an enabled mailbox, otherwise the send
+/// fails if the recipient is not connected.
+/// # Examples
+/// ```rust
+/// # extern crate hdk;
+/// # extern crate holochain_persistence_api;
+/// # use hdk::error::ZomeApiResult;
+/// # use hdk::holochain_core_types::error::RibosomeEncodingBits;
+/// # use hdk::holochain_core_types::error::RibosomeEncodedValue;
+/// # use holochain_persistence_api::cas::content::Address;
+/// # use hdk::holochain_wasm_utils::api_serialization::send::{SendOptions, SendResult};
+/// # #[no_mangle]
+/// # pub fn hc_send(_: RibosomeEncodingBits) -> RibosomeEncodingBits { RibosomeEncodedValue::Success.into() }
+///
+/// # fn main() {
+/// pub fn handle_send_invitation(to_agent: Address, invitation: String) -> ZomeApiResult<bool> {
+///     let result = hdk::send_with_options(to_agent, invitation, SendOptions {
+///         timeout: 10000.into(),
+///         deliver_later: true,
+///     })?;
+///     // true if the invitation got accepted right away
+///     Ok(result == SendResult::Response(String::from("accepted")))
+/// }
+/// # }
+/// ```
+pub fn send_with_options(
+    to_agent: Address,
+    payload: String,
+    options: SendOptions,
+) -> ZomeApiResult<SendResult> {
+    if options.deliver_later {
+        Dispatch::Send.with_input(SendArgs {
+            to_agent,
+            payload,
+            options,
+        })
+    } else {
+        // without deliver_later the host returns just the response
+        send(to_agent, payload, options.timeout).map(SendResult::Response)
+    }
+}
@@ -30,3 +30,11 @@ pub fn update_agent() -> ZomeApiResult<Address> {
 pub fn remove_entry(address: &Address) -> ZomeApiResult<Address> {
     Dispatch::RemoveEntry.with_input(address.to_owned())
 }
+
+/// Like `remove_entry`, but also asks the holders of the entry to drop its content.
+/// They keep its headers and replace its content with a tombstone, if the entry was
+/// committed by you alone. Content of private entries gets dropped from your own
+/// chain storage. Use this to honor requests to delete personal data.
+pub fn purge_entry(address: &Address) -> ZomeApiResult<Address> {
+    Dispatch::PurgeEntry.with_input(address.to_owned())
+}
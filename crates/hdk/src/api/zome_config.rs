@@ -0,0 +1,26 @@
+use crate::{error::ZomeApiResult, Dispatch};
+use holochain_json_api::json::JsonString;
+
+/// Returns the config of the zome this code is running in.
+/// This is the `config` value of the zome in the DNA, unless the conductor replaced it for
+/// this instance (with `zome_config` in the instance configuration), which makes it possible
+/// to deploy the same zome with different parameters.
+/// Replacing the config does not change the DNA hash, so different agents of the same DNA
+/// can see different configs. That's why it can not be called from validation callbacks.
+/// # Examples
+/// ```rust
+/// # extern crate hdk;
+/// # extern crate serde_json;
+/// # use hdk::error::{ZomeApiError, ZomeApiResult};
+///
+/// # fn main() {
+/// pub fn handle_max_length() -> ZomeApiResult<u64> {
+///     let config: serde_json::Value = serde_json::from_str(&hdk::zome_config()?.to_string())
+///         .map_err(|_| ZomeApiError::from("Zome config is not valid JSON".to_string()))?;
+///     Ok(config["max_length"].as_u64().unwrap_or(280))
+/// }
+/// # }
+/// ```
+pub fn zome_config() -> ZomeApiResult<JsonString> {
+    Dispatch::ZomeConfig.without_input()
+}
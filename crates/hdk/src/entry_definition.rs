@@ -4,17 +4,30 @@
 use crate::error::{ZomeApiError, ZomeApiResult};
 use holochain_core_types::{
     agent::AgentId,
+    chain_header::ChainHeader,
     dna::entry_types::EntryTypeDef,
     entry::{entry_type::EntryType, AppEntryValue, Entry},
     validation::{EntryValidationData, LinkValidationData, ValidationPackageDefinition},
 };
+use holochain_json_api::json::JsonString;
+use holochain_persistence_api::cas::content::Address;
 use holochain_wasm_utils::api_serialization::validation::LinkDirection;
 use std::convert::TryFrom;
 
 pub type PackageCreator = Box<dyn FnMut() -> ValidationPackageDefinition + Sync>;
 
+pub type CustomPackageBuilder =
+    Box<dyn FnMut(Entry, ChainHeader) -> Result<JsonString, String> + Sync>;
+
+/// Picks the entry that counts as the latest one among the heads of an entry's update graph
+/// if agents updated it concurrently. Gets the head entries sorted by address and has to
+/// return the address of one of them.
+pub type ConflictResolver = Box<dyn FnMut(Vec<Entry>) -> Result<Address, String> + Sync>;
+
 pub type Validator = Box<dyn FnMut(EntryValidationData<Entry>) -> Result<(), String> + Sync>;
 
+/// Validates the AgentId entry of an agent joining the DNA. The membrane proof the agent
+/// presented, if any, is passed in `validation_data.package.membrane_proof`.
 pub type AgentValidator = Box<dyn FnMut(EntryValidationData<AgentId>) -> Result<(), String> + Sync>;
 pub type LinkValidator = Box<dyn FnMut(LinkValidationData) -> Result<(), String> + Sync>;
 
@@ -36,6 +49,11 @@ pub struct ValidatingEntryType {
     /// Callback that returns a validation package definition that Holochain reads in order
     /// to create the right validation package to pass in to the validator callback on validation.
     pub package_creator: PackageCreator,
+    /// Callback that builds the data of the validation package when an entry of this type
+    /// gets published, if `package_creator` returns `ValidationPackageDefinition::CustomCallback`.
+    pub custom_package_builder: Option<CustomPackageBuilder>,
+    /// Callback that picks the latest entry if entries of this type got updated concurrently.
+    pub conflict_resolver: Option<ConflictResolver>,
     /// This is the validation callback that is used to determine if an entry is valid.
     pub validator: Validator,
 
@@ -81,11 +99,78 @@ pub struct ValidatingLinkDefinition {
 ///      It always expects two arguments, the first of which is the entry attempting to be validated,
 ///      the second is the validation `context`, which offers a variety of metadata useful for validation.
 ///      See [ValidationData](ValidationData) for more details.
+///      Validation must come to the same result on every node, so only deterministic API functions
+///      can be used inside of it: [debug](api::debug()), [entry_address](api::entry_address()),
+///      [verify_signature](api::verify_signature()) and the globals like [DNA_ADDRESS](api::DNA_ADDRESS).
+///      Calling any other function (like [get_entry](api::get_entry()) or [send](api::send())) returns an error.
 /// 7. links: `links` is a vector of link definitions represented by `ValidatingLinkDefinition`.
 ///     Links can be defined with the `link!` macro or, more concise, with either the `to!` or `from!` macro,
 ///     to define an association pointing from this entry type to another, or one that points back from
 ///     the other entry type to this one.
 ///     See [link!](link!), [to!](to!) and [from!](from!) for more details.
+///
+/// Optionally, `schema` and `max_size` can be given right after `sharing`:
+/// * schema: `schema` is a JSON Schema (as string) that the content of entries of this type has to match.
+///   Only the keywords in [SUPPORTED_SCHEMA_KEYWORDS](holochain_core_types::dna::entry_types::SUPPORTED_SCHEMA_KEYWORDS)
+///   and annotations like `title` can be used, other keywords make loading the DNA fail.
+/// * max_size: `max_size` is the maximum size in bytes of the serialized content of entries of this type.
+///
+/// Both get checked natively by Holochain before the `validation` callback is called.
+///
+/// Entry types that need the signatures of several agents can name the fields of their content
+/// that hold the addresses of these agents with `countersigners` right after `max_size`:
+/// ```rust,ignore
+/// countersigners: ["spender", "receiver"],
+/// ```
+/// Entries of such a type can only be committed with `hdk::commit_countersigned_entry`,
+/// which collects the signatures of all of them.
+///
+/// If `validation_package` returns `ValidationPackageDefinition::CustomCallback`, a
+/// `custom_validation_package` callback has to be given right after it. It gets called with the
+/// entry and its header when the entry gets published and returns the data validators need
+/// (like selected prior entries or a Merkle proof) as `Result<JsonString, String>`.
+/// Holochain signs the data, stores it next to the header and hands it to validators in
+/// `validation_data.package.custom`:
+/// ```rust,ignore
+/// validation_package: || hdk::ValidationPackageDefinition::CustomCallback,
+/// custom_validation_package: |_entry, _header| {
+///     let previous: Vec<Address> = hdk::query("post".into(), 0, 5).map_err(|e| e.to_string())?;
+///     Ok(JsonString::from(previous))
+/// },
+/// ```
+///
+/// Blob manifests stored with [store_blob](api::store_blob()) only get checked to be
+/// well-formed. A zome can validate them like app entries by defining an entry type named
+/// [BLOB_MANIFEST_ENTRY_TYPE](api::BLOB_MANIFEST_ENTRY_TYPE) with `BlobManifest` as
+/// native type, e.g. to limit the size of blobs:
+/// ```rust,ignore
+/// entry!(
+///     name: hdk::BLOB_MANIFEST_ENTRY_TYPE,
+///     description: "Blobs of at most 1MB",
+///     sharing: Sharing::Public,
+///     validation_package: || hdk::ValidationPackageDefinition::Entry,
+///     validation: |validation_data: hdk::EntryValidationData<holochain_core_types::blob::BlobManifest>| {
+///         match validation_data {
+///             hdk::EntryValidationData::Create { entry, .. } if entry.size <= 1024 * 1024 => Ok(()),
+///             _ => Err(String::from("Blob too large")),
+///         }
+///     }
+/// )
+/// ```
+///
+/// If agents update the same entry concurrently, its update graph forks and
+/// [get_entry_result](api::get_entry_result()) lists all heads in `heads`. By default the head
+/// with the lowest address counts as the latest entry. A `resolve_conflict` callback given
+/// right before `validation` picks it instead. It gets the head entries sorted by address and
+/// has to be deterministic, so every node picks the same one. As in `validation`, only the
+/// deterministic API functions can be called inside of it:
+/// ```rust,ignore
+/// resolve_conflict: |heads| {
+///     heads.iter().max_by_key(|entry| entry.content().to_string().len())
+///         .map(|entry| entry.address())
+///         .ok_or_else(|| String::from("no heads"))
+/// },
+/// ```
 /// # Examples
 /// The following is a standalone Rust file that exports a function which can be called
 /// to get a `ValidatingEntryType` of a "post".
@@ -175,9 +260,14 @@ macro_rules! entry {
         name: $name:expr,
         description: $properties:expr,
         sharing: $sharing:expr,
+        $(schema: $schema:expr,)?
+        $(max_size: $max_size:expr,)?
+        $(countersigners: [$($countersigner:expr),*],)?
        // $(native_type: $native_type:ty,)*
 
         validation_package: || $package_creator:expr,
+        $(custom_validation_package: | $custom_entry:ident, $custom_header:ident | $custom_package_builder:expr,)?
+        $(resolve_conflict: | $heads:ident | $conflict_resolver:expr,)?
         validation: | $validation_data:ident : hdk::EntryValidationData<$native_type:ty> | $entry_validation:expr
 
         $(
@@ -193,6 +283,9 @@ macro_rules! entry {
             let mut entry_type = $crate::holochain_core_types::dna::entry_types::EntryTypeDef::new();
             entry_type.properties = $crate::holochain_json_api::json::JsonString::from($properties);
             entry_type.sharing = $sharing;
+            $(entry_type.schema = Some($crate::holochain_json_api::json::JsonString::from_json($schema));)?
+            $(entry_type.max_size = Some($max_size);)?
+            $(entry_type.countersigners = vec![$(String::from($countersigner)),*];)?
 
             $($(
                 match $link_expr.direction {
@@ -220,6 +313,17 @@ macro_rules! entry {
                 $package_creator
             });
 
+            let custom_package_builder: Option<$crate::entry_definition::CustomPackageBuilder> = None
+                $(.or_else(|| Some(Box::new(|$custom_entry: $crate::holochain_core_types::entry::Entry, $custom_header: $crate::holochain_core_types::chain_header::ChainHeader| {
+                    $custom_package_builder
+                }))))?;
+
+            let conflict_resolver: Option<$crate::entry_definition::ConflictResolver> = None
+                $(.or_else(|| Some(Box::new(|$heads: Vec<$crate::holochain_core_types::entry::Entry>| {
+                    $conflict_resolver
+                }))))?;
+            entry_type.resolves_conflicts = conflict_resolver.is_some();
+
             let validator = Box::new(|validation_data: $crate::holochain_wasm_utils::holochain_core_types::validation::EntryValidationData<$crate::holochain_core_types::entry::Entry>| {
                 let $validation_data = $crate::entry_definition::entry_to_native_type::<$native_type>(validation_data.clone())?;
                 use std::convert::TryFrom;
@@ -232,6 +336,10 @@ macro_rules! entry {
                     {
                         $entry_validation
                     }
+                    $crate::holochain_core_types::entry::entry_type::EntryType::BlobManifest =>
+                    {
+                        $entry_validation
+                    }
                     _ => {
                         Err(String::from("Schema validation failed"))?
                     }
@@ -239,9 +347,11 @@ macro_rules! entry {
             });
 
             $crate::entry_definition::ValidatingEntryType {
-                name: $crate::holochain_core_types::entry::entry_type::EntryType::App($crate::holochain_core_types::entry::entry_type::AppEntryType::from($name.to_string())),
+                name: $crate::holochain_core_types::entry::entry_type::EntryType::from($name.to_string()),
                 entry_type_definition: entry_type,
                 package_creator,
+                custom_package_builder,
+                conflict_resolver,
                 validator,
                 links: vec![
                     $($(
@@ -415,6 +525,9 @@ fn convert_entry_validation_to_native<T: TryFrom<AppEntryValue> + Clone>(
                 .join(&String::new()),
             )
         }),
+        Entry::BlobManifest(manifest) => T::try_from(AppEntryValue::from(manifest)).map_err(|_| {
+            ZomeApiError::Internal("Could not convert blob manifest to requested type".to_string())
+        }),
         _ => Err(ZomeApiError::Internal(
             "Entry did not return an app entry".to_string(),
         )),
@@ -34,6 +34,7 @@ pub mod error;
 pub mod global_fns;
 pub mod init_globals;
 pub mod macros;
+pub mod path;
 
 pub use holochain_wasm_utils::api_serialization::{validation::*, THIS_INSTANCE};
 
@@ -20,8 +20,12 @@ use serde_derive::{Deserialize, Serialize};
 use holochain_json_api::{error::JsonError, json::JsonString};
 
 use holochain_wasm_utils::{
-    api_serialization::validation::{
-        AgentIdValidationArgs, EntryValidationArgs, LinkValidationArgs, LinkValidationPackageArgs,
+    api_serialization::{
+        get_entry::ConflictResolutionArgs,
+        validation::{
+            AgentIdValidationArgs, CustomValidationPackageArgs, EntryValidationArgs,
+            LinkValidationArgs, LinkValidationPackageArgs,
+        },
     },
     holochain_core_types::error::RibosomeErrorCode,
     memory::{
@@ -103,6 +107,82 @@ pub extern "C" fn __hdk_get_validation_package_for_entry_type(
     }
 }
 
+#[no_mangle]
+pub extern "C" fn __hdk_build_custom_validation_package(
+    encoded_allocation_of_input: RibosomeEncodingBits,
+) -> RibosomeEncodingBits {
+    if let Err(allocation_error) =
+        crate::global_fns::init_global_memory_from_ribosome_encoding(encoded_allocation_of_input)
+    {
+        return allocation_error.as_ribosome_encoding();
+    }
+
+    let mut zd = ZomeDefinition::new();
+    unsafe { zome_setup(&mut zd) };
+
+    // Deserialize input
+    let input: CustomValidationPackageArgs =
+        match load_ribosome_encoded_json(encoded_allocation_of_input) {
+            Ok(v) => v,
+            Err(e) => return RibosomeEncodedValue::from(e).into(),
+        };
+
+    let entry_type = input.entry.entry_type();
+    match zd
+        .entry_types
+        .into_iter()
+        .find(|ref validating_entry_type| validating_entry_type.name == entry_type)
+        .and_then(|entry_type_definition| entry_type_definition.custom_package_builder)
+    {
+        None => RibosomeEncodedValue::Failure(RibosomeErrorCode::CallbackFailed).into(),
+        Some(mut custom_package_builder) => {
+            let result = (*custom_package_builder)(input.entry, input.chain_header);
+            return_code_for_allocation_result(crate::global_fns::write_json(JsonString::from(
+                result,
+            )))
+            .into()
+        }
+    }
+}
+
+#[no_mangle]
+pub extern "C" fn __hdk_resolve_conflict(
+    encoded_allocation_of_input: RibosomeEncodingBits,
+) -> RibosomeEncodingBits {
+    if let Err(allocation_error) =
+        crate::global_fns::init_global_memory_from_ribosome_encoding(encoded_allocation_of_input)
+    {
+        return allocation_error.as_ribosome_encoding();
+    }
+
+    let mut zd = ZomeDefinition::new();
+    unsafe { zome_setup(&mut zd) };
+
+    // Deserialize input
+    let input: ConflictResolutionArgs =
+        match load_ribosome_encoded_json(encoded_allocation_of_input) {
+            Ok(v) => v,
+            Err(e) => return RibosomeEncodedValue::from(e).into(),
+        };
+
+    let entry_type = EntryType::App(AppEntryType::from(input.entry_type));
+    match zd
+        .entry_types
+        .into_iter()
+        .find(|ref validating_entry_type| validating_entry_type.name == entry_type)
+        .and_then(|entry_type_definition| entry_type_definition.conflict_resolver)
+    {
+        None => RibosomeEncodedValue::Failure(RibosomeErrorCode::CallbackFailed).into(),
+        Some(mut conflict_resolver) => {
+            let result = (*conflict_resolver)(input.heads);
+            return_code_for_allocation_result(crate::global_fns::write_json(JsonString::from(
+                result,
+            )))
+            .into()
+        }
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn __hdk_validate_app_entry(
     encoded_allocation_of_input: RibosomeEncodingBits,
@@ -356,9 +436,13 @@ pub extern "C" fn __hdk_get_json_definition(
 #[cfg(test)]
 pub mod tests {
     use crate::{meta::PartialZome, prelude::*, ValidationPackageDefinition};
-    use holochain_core_types::dna::{
-        entry_types::Sharing,
-        zome::{ZomeFnDeclarations, ZomeTraits},
+    use holochain_core_types::{
+        chain_header::test_chain_header,
+        dna::{
+            entry_types::Sharing,
+            zome::{ZomeFnDeclarations, ZomeTraits},
+        },
+        entry::test_entry,
     };
     use holochain_json_api::{error::JsonError, json::JsonString};
     use std::collections::BTreeMap;
@@ -417,4 +501,104 @@ pub mod tests {
             JsonString::from_json("{\"entry_types\":{\"post\":{\"properties\":\"{\\\"description\\\": \\\"blog entry post\\\"}\",\"sharing\":\"public\",\"links_to\":[],\"linked_from\":[]}},\"traits\":{},\"fn_declarations\":[]}"),
         );
     }
+
+    #[test]
+    fn partial_zome_json_with_schema() {
+        #[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+        pub struct Post {
+            content: String,
+        }
+
+        let mut entry_types = BTreeMap::new();
+
+        let validating_entry_type = entry!(
+            name: "post",
+            description: "{}",
+            sharing: Sharing::Public,
+            schema: r#"{"type":"object","required":["content"]}"#,
+            max_size: 1024,
+
+            validation_package: || {
+                ValidationPackageDefinition::Entry
+            },
+
+            validation: |_validation_data: hdk::EntryValidationData<Post>| {
+                Ok(())
+            }
+
+        );
+        entry_types.insert(
+            validating_entry_type.name,
+            validating_entry_type.entry_type_definition,
+        );
+
+        let partial_zome = PartialZome {
+            entry_types,
+            ..Default::default()
+        };
+
+        assert_eq!(
+            JsonString::from(partial_zome),
+            JsonString::from_json("{\"entry_types\":{\"post\":{\"properties\":\"{}\",\"sharing\":\"public\",\"links_to\":[],\"linked_from\":[],\"schema\":\"{\\\"type\\\":\\\"object\\\",\\\"required\\\":[\\\"content\\\"]}\",\"max_size\":1024}},\"traits\":{},\"fn_declarations\":[]}"),
+        );
+    }
+
+    #[test]
+    fn entry_with_custom_validation_package() {
+        #[derive(Serialize, Deserialize, Debug, DefaultJson, Clone)]
+        pub struct Post {
+            content: String,
+        }
+
+        let validating_entry_type = entry!(
+            name: "post",
+            description: "{}",
+            sharing: Sharing::Public,
+
+            validation_package: || {
+                ValidationPackageDefinition::CustomCallback
+            },
+
+            custom_validation_package: |_entry, header| {
+                Ok(JsonString::from_json(&format!("\"{}\"", header.address())))
+            },
+
+            validation: |_validation_data: hdk::EntryValidationData<Post>| {
+                Ok(())
+            }
+
+        );
+
+        let mut package_creator = validating_entry_type.package_creator;
+        assert_eq!(
+            package_creator(),
+            ValidationPackageDefinition::CustomCallback
+        );
+        let mut custom_package_builder = validating_entry_type
+            .custom_package_builder
+            .expect("entry! must set the custom validation package callback");
+        assert_eq!(
+            custom_package_builder(test_entry(), test_chain_header()),
+            Ok(JsonString::from_json(&format!(
+                "\"{}\"",
+                test_chain_header().address()
+            ))),
+        );
+
+        let without_custom_package = entry!(
+            name: "post",
+            description: "{}",
+            sharing: Sharing::Public,
+
+            validation_package: || {
+                ValidationPackageDefinition::Entry
+            },
+
+            validation: |_validation_data: hdk::EntryValidationData<Post>| {
+                Ok(())
+            }
+
+        );
+        assert!(without_custom_package.custom_package_builder.is_none());
+    }
 }
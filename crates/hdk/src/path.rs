@@ -0,0 +1,308 @@
+//! Paths index entries under well-known, human readable names, replacing the "anchor" pattern.
+//! A path like `tags/rust/async` maps to a deterministic entry of the `hdk_path` type for each
+//! of its levels, `tags`, `tags/rust` and `tags/rust/async`, each linked from its parent
+//! with the full path of the child as tag.
+//! Every agent that ensures a path ends up with the same addresses, so entries linked under
+//! a path can be found by everyone who knows its name.
+//!
+//! To use paths, add [path_entry_def](fn.path_entry_def.html) to the entries of your zome and
+//! define links from [PATH_ENTRY_TYPE](constant.PATH_ENTRY_TYPE.html) to your own entry types.
+
+use crate::{
+    self as hdk,
+    entry_definition::ValidatingEntryType,
+    error::{ZomeApiError, ZomeApiResult},
+    holochain_core_types::{
+        dna::entry_types::Sharing,
+        entry::{entry_type::AppEntryType, Entry},
+        link::LinkMatch,
+    },
+    holochain_persistence_api::cas::content::{Address, AddressableContent},
+    holochain_wasm_utils::api_serialization::get_links::GetLinksResult,
+    LinkValidationData,
+};
+use holochain_json_api::{error::JsonError, json::JsonString};
+use holochain_json_derive::DefaultJson;
+use serde_derive::{Deserialize, Serialize};
+use std::fmt;
+
+/// Name of the entry type path entries get committed as
+pub const PATH_ENTRY_TYPE: &str = "hdk_path";
+
+/// Type of the links from a path to its children
+pub const PATH_CHILD_LINK_TYPE: &str = "hdk_path_child";
+
+/// Separates the components of a path in its string form
+pub const PATH_SEPARATOR: char = '/';
+
+/// A hierarchical name. The root path has no components; it is the parent of all
+/// single component paths, so they can be listed like any other children.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq, Hash, Default, DefaultJson)]
+pub struct Path(Vec<String>);
+
+impl Path {
+    pub fn root() -> Self {
+        Path(Vec::new())
+    }
+
+    pub fn is_root(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn components(&self) -> &[String] {
+        &self.0
+    }
+
+    /// The last component, None for the root path
+    pub fn name(&self) -> Option<&str> {
+        self.0.last().map(String::as_str)
+    }
+
+    /// None for the root path
+    pub fn parent(&self) -> Option<Path> {
+        if self.is_root() {
+            None
+        } else {
+            Some(Path(self.0[..self.0.len() - 1].to_vec()))
+        }
+    }
+
+    pub fn child<S: Into<String>>(&self, name: S) -> Path {
+        let mut components = self.0.clone();
+        components.push(name.into());
+        Path(components)
+    }
+
+    /// Components must not be empty or contain the separator, so every path has
+    /// exactly one string form
+    pub fn check(&self) -> Result<(), String> {
+        match self
+            .0
+            .iter()
+            .find(|component| component.is_empty() || component.contains(PATH_SEPARATOR))
+        {
+            Some(component) => Err(format!("Invalid path component: {:?}", component)),
+            None => Ok(()),
+        }
+    }
+
+    pub fn entry(&self) -> Entry {
+        Entry::App(AppEntryType::from(PATH_ENTRY_TYPE), self.clone().into())
+    }
+
+    /// Address of the path's entry, which is the same for all agents
+    pub fn address(&self) -> ZomeApiResult<Address> {
+        hdk::entry_address(&self.entry())
+    }
+
+    pub fn exists(&self) -> ZomeApiResult<bool> {
+        Ok(hdk::get_entry(&self.address()?)?.is_some())
+    }
+
+    /// Commits the entries of this path and all its ancestors that don't exist yet and
+    /// links each of them from its parent. Returns the address of the path.
+    /// The links get added even for existing entries so that an earlier ensure that
+    /// failed after committing gets repaired; `children` drops the duplicates.
+    pub fn ensure(&self) -> ZomeApiResult<Address> {
+        self.check().map_err(ZomeApiError::ValidationFailed)?;
+        let address = self.address()?;
+        let maybe_parent_address = match self.parent() {
+            Some(parent) => Some(parent.ensure()?),
+            None => None,
+        };
+        if hdk::get_entry(&address)?.is_none() {
+            hdk::commit_entry(&self.entry())?;
+        }
+        if let Some(parent_address) = maybe_parent_address {
+            hdk::link_entries(
+                &parent_address,
+                &address,
+                PATH_CHILD_LINK_TYPE,
+                self.to_string(),
+            )?;
+        }
+        Ok(address)
+    }
+
+    /// Lists the paths directly below this one, sorted and without duplicates that
+    /// concurrent ensures by several agents can create
+    pub fn children(&self) -> ZomeApiResult<Vec<Path>> {
+        let mut children: Vec<Path> = self
+            .get_links(LinkMatch::Exactly(PATH_CHILD_LINK_TYPE), LinkMatch::Any)?
+            .tags()
+            .iter()
+            .map(|tag| Path::from(tag.as_str()))
+            .filter(|child| child.parent().as_ref() == Some(self))
+            .collect();
+        children.sort_by(|a, b| a.0.cmp(&b.0));
+        children.dedup();
+        Ok(children)
+    }
+
+    /// Ensures this path and links the target from it. The link type has to be defined
+    /// for links from `PATH_ENTRY_TYPE` to the type of the target.
+    pub fn link_entry<S: Into<String>>(
+        &self,
+        target: &Address,
+        link_type: S,
+        tag: S,
+    ) -> ZomeApiResult<Address> {
+        let address = self.ensure()?;
+        hdk::link_entries(&address, target, link_type, tag)
+    }
+
+    /// Gets the links from this path, see [get_links](../api/fn.get_links.html)
+    pub fn get_links(
+        &self,
+        link_type: LinkMatch<&str>,
+        tag: LinkMatch<&str>,
+    ) -> ZomeApiResult<GetLinksResult> {
+        hdk::get_links(&self.address()?, link_type, tag)
+    }
+}
+
+/// Empty components are skipped, so "a//b/" is the path "a/b" and "" is the root path
+impl From<&str> for Path {
+    fn from(path: &str) -> Self {
+        Path(
+            path.split(PATH_SEPARATOR)
+                .filter(|component| !component.is_empty())
+                .map(String::from)
+                .collect(),
+        )
+    }
+}
+
+impl fmt::Display for Path {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0.join(&PATH_SEPARATOR.to_string()))
+    }
+}
+
+/// Child links carry the full path of the child in their tag. Validation rebuilds the child
+/// and its parent from it and checks their entry addresses against target and base, so it
+/// does not need to get any entries.
+fn validate_child_link(base: &Address, target: &Address, tag: &str) -> Result<(), String> {
+    let child = Path::from(tag);
+    child.check()?;
+    let parent = child
+        .parent()
+        .ok_or_else(|| "The root path is nobody's child".to_string())?;
+    if child.entry().address() != *target || parent.entry().address() != *base {
+        return Err(format!(
+            "Link tagged {} does not point from its parent to the path",
+            tag
+        ));
+    }
+    Ok(())
+}
+
+/// Definition of the path entry type. Add it to the entries of every zome that uses paths.
+pub fn path_entry_def() -> ValidatingEntryType {
+    entry!(
+        name: PATH_ENTRY_TYPE,
+        description: "a hierarchical name entries can be linked under",
+        sharing: Sharing::Public,
+
+        validation_package: || {
+            hdk::ValidationPackageDefinition::Entry
+        },
+
+        validation: |validation_data: hdk::EntryValidationData<Path>| {
+            match validation_data {
+                hdk::EntryValidationData::Create { entry, .. } => entry.check(),
+                _ => Err("Paths can't be modified or deleted".to_string()),
+            }
+        },
+
+        links: [
+            to!(
+                PATH_ENTRY_TYPE,
+                link_type: PATH_CHILD_LINK_TYPE,
+
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+
+                validation: |validation_data: hdk::LinkValidationData| {
+                    match validation_data {
+                        LinkValidationData::LinkAdd { link, .. } => validate_child_link(
+                            link.link().base(),
+                            link.link().target(),
+                            link.link().tag(),
+                        ),
+                        LinkValidationData::LinkRemove { .. } => Ok(()),
+                    }
+                }
+            )
+        ]
+    )
+}
+
+#[cfg(test)]
+pub mod tests {
+    use super::*;
+    use crate::holochain_core_types::{
+        agent::test_agent_id, chain_header::test_chain_header, link::link_data::LinkData,
+        validation::ValidationData,
+    };
+
+    #[test]
+    fn paths_roundtrip_through_strings() {
+        let path = Path::from("tags//rust/async/");
+        assert_eq!(path.components(), &["tags", "rust", "async"]);
+        assert_eq!(path.to_string(), "tags/rust/async");
+        assert_eq!(path.name(), Some("async"));
+        assert_eq!(path.parent(), Some(Path::from("tags/rust")));
+        assert_eq!(Path::from("tags").parent(), Some(Path::root()));
+        assert_eq!(Path::root().parent(), None);
+        assert_eq!(Path::from(""), Path::root());
+        assert_eq!(Path::root().child("tags"), Path::from("tags"));
+    }
+
+    #[test]
+    fn path_entries_are_deterministic() {
+        assert_eq!(
+            Path::from("a/b").entry(),
+            Path::root().child("a").child("b").entry()
+        );
+        assert_ne!(Path::from("a/b").entry(), Path::from("b/a").entry());
+        assert!(Path::from("a/b").check().is_ok());
+        assert!(Path::root().child("").check().is_err());
+        assert!(Path::root().child("a/b").check().is_err());
+    }
+
+    #[test]
+    fn child_links_get_validated_against_the_tag() {
+        let child_link = |base: &Path, target: &Path, tag: &str| {
+            let mut definition = path_entry_def()
+                .links
+                .into_iter()
+                .find(|link| link.link_type == PATH_CHILD_LINK_TYPE)
+                .unwrap();
+            (*definition.validator)(LinkValidationData::LinkAdd {
+                link: LinkData::new_add(
+                    &base.entry().address(),
+                    &target.entry().address(),
+                    tag,
+                    PATH_CHILD_LINK_TYPE,
+                    test_chain_header(),
+                    test_agent_id(),
+                ),
+                validation_data: ValidationData::default(),
+            })
+        };
+        let parent = Path::from("tags/rust");
+        let child = Path::from("tags/rust/async");
+
+        assert_eq!(child_link(&parent, &child, "tags/rust/async"), Ok(()));
+        assert_eq!(
+            child_link(&Path::root(), &parent.parent().unwrap(), "tags"),
+            Ok(())
+        );
+        assert!(child_link(&parent, &child, "async").is_err());
+        assert!(child_link(&Path::root(), &child, "tags/rust/async").is_err());
+        assert!(child_link(&parent, &Path::from("tags/go"), "tags/rust/async").is_err());
+        assert!(child_link(&Path::root(), &Path::root(), "").is_err());
+    }
+}
@@ -19,7 +19,9 @@ pub use crate::{
         entry::{entry_type::EntryType, Entry},
         error::HolochainError,
         link::LinkMatch,
-        network::query::{Pagination, SizePagination, SortOrder, TimePagination},
+        network::query::{
+            CursorPagination, GetLinksCursor, Pagination, SizePagination, SortOrder, TimePagination,
+        },
         time::Iso8601,
     },
     holochain_json_api::{error::JsonError, json::JsonString},
@@ -27,10 +29,12 @@ pub use crate::{
     holochain_wasm_utils::api_serialization::{
         commit_entry::CommitEntryOptions,
         get_entry::{
-            EntryHistory, GetEntryOptions, GetEntryResult, GetEntryResultType, StatusRequestKind,
+            CachePolicy, EntryHistory, GetEntryOptions, GetEntryResult, GetEntryResultType,
+            StatusRequestKind,
         },
         get_links::{GetLinksOptions, GetLinksResult, GetLinksResultCount, LinksStatusRequestKind},
-        QueryArgsOptions, QueryResult,
+        QueryArgsOptions, QueryCursor, QueryResult, QueryResultPage,
     },
+    path::Path,
     EntryValidationData, LinkValidationData, ValidationPackageDefinition,
 };
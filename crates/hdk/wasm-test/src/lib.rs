@@ -21,6 +21,7 @@ use hdk::{
     api::G_MEM_STACK,
     error::{ZomeApiError, ZomeApiResult},
     global_fns::init_global_memory,
+    path::{path_entry_def, Path, PATH_ENTRY_TYPE},
 };
 use holochain_wasm_utils::{
     api_serialization::{
@@ -706,8 +707,35 @@ pub fn handle_sign_payload(payload: String) -> ZomeApiResult<String>
     hdk::sign(payload)
 }
 
+fn handle_ensure_path(path: String) -> ZomeApiResult<Address> {
+    Path::from(path.as_str()).ensure()
+}
+
+fn handle_path_children(path: String) -> ZomeApiResult<Vec<String>> {
+    Ok(Path::from(path.as_str())
+        .children()?
+        .iter()
+        .map(Path::to_string)
+        .collect())
+}
+
+fn handle_link_under_path(path: String, stuff: String) -> ZomeApiResult<Address> {
+    let entry = Entry::App("testEntryType".into(), EntryStruct { stuff }.into());
+    let address = hdk::commit_entry(&entry)?;
+    Path::from(path.as_str()).link_entry(&address, "path_item", "")?;
+    Ok(address)
+}
+
+fn handle_path_items(path: String) -> ZomeApiResult<Vec<Address>> {
+    Ok(Path::from(path.as_str())
+        .get_links(LinkMatch::Exactly("path_item"), LinkMatch::Any)?
+        .addresses())
+}
+
 define_zome! {
     entries: [
+        path_entry_def(),
+
         entry!(
             name: "testEntryType",
             description: "\"test-properties-string\"",
@@ -761,6 +789,16 @@ define_zome! {
                 validation: | validation_data: hdk::LinkValidationData | {
                     Ok(())
                 }
+               ),
+               from!(
+                PATH_ENTRY_TYPE,
+                link_type: "path_item",
+                validation_package: || {
+                    hdk::ValidationPackageDefinition::Entry
+                },
+                validation: | validation_data: hdk::LinkValidationData | {
+                    Ok(())
+                }
                )
             ]
         ),
@@ -1140,6 +1178,30 @@ define_zome! {
             outputs: |version: ZomeApiResult<String>|,
             handler: handle_sign_payload
         }
+
+        ensure_path: {
+            inputs: |path: String|,
+            outputs: |result: ZomeApiResult<Address>|,
+            handler: handle_ensure_path
+        }
+
+        path_children: {
+            inputs: |path: String|,
+            outputs: |result: ZomeApiResult<Vec<String>>|,
+            handler: handle_path_children
+        }
+
+        link_under_path: {
+            inputs: |path: String, stuff: String|,
+            outputs: |result: ZomeApiResult<Address>|,
+            handler: handle_link_under_path
+        }
+
+        path_items: {
+            inputs: |path: String|,
+            outputs: |result: ZomeApiResult<Vec<Address>>|,
+            handler: handle_path_items
+        }
     
     ]
 
@@ -0,0 +1,48 @@
+//! Locations of addresses in the DHT, as the sim2h server computes them to find the
+//! agents responsible for holding an entry.
+//! Agents are responsible for the entries in an arc following their own location,
+//! so entries close to each other tend to share their authorities.
+
+use holochain_persistence_api::cas::content::Address;
+use lib3h_crypto_api::CryptoSystem;
+use lib3h_protocol::types::EntryHash;
+use lib3h_sodium::SodiumCryptoSystem;
+use sim2h::naive_sharding::entry_location as sim2h_entry_location;
+
+thread_local! {
+    static CRYPTO: Box<dyn CryptoSystem> = Box::new(SodiumCryptoSystem::new());
+}
+
+/// The DHT location of the entry with the given address
+pub fn entry_location(address: &Address) -> u32 {
+    let entry_hash: EntryHash = address.clone().into();
+    CRYPTO.with(|crypto| (sim2h_entry_location(crypto, &entry_hash).0).0)
+}
+
+/// How far `to` lies after `from`, going forward around the DHT location space
+pub fn forward_distance(from: u32, to: u32) -> u32 {
+    to.wrapping_sub(from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn entry_locations_are_stable() {
+        let address = Address::from("QmYDk4Z1Eo2wUNexYZj8V4bLvzkXxNTwq3KX1WKGUU3vJ4");
+        assert_eq!(entry_location(&address), entry_location(&address));
+        assert_ne!(
+            entry_location(&address),
+            entry_location(&Address::from(
+                "QmSbKv6KyZ5nXh3ofGDxUBRnG1sbmbRrNPDWxzqQJhNgaN"
+            ))
+        );
+    }
+
+    #[test]
+    fn forward_distance_wraps_around() {
+        assert_eq!(forward_distance(10, 15), 5);
+        assert_eq!(forward_distance(u32::max_value() - 1, 3), 5);
+    }
+}
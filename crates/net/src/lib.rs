@@ -25,6 +25,7 @@ extern crate serde_derive;
 #[macro_use]
 extern crate holochain_tracing_macros;
 pub mod connection;
+pub mod dht_location;
 pub mod error;
 pub mod in_memory;
 pub mod lib3h_worker;
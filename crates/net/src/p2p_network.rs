@@ -15,10 +15,11 @@ use crate::{
     NEW_RELIC_LICENSE_KEY,
 };
 use lib3h_protocol::{
-    protocol_client::Lib3hClientProtocol, protocol_server::Lib3hServerProtocol, Address,
+    data_types::DirectMessageData, protocol_client::Lib3hClientProtocol,
+    protocol_server::Lib3hServerProtocol, Address,
 };
 
-use crate::sim2h_worker::Sim2hWorker;
+use crate::sim2h_worker::{DeliverLaterRequests, Sim2hWorker};
 use crossbeam_channel;
 use holochain_conductor_lib_api::conductor_api::ConductorApi;
 use holochain_json_api::json::JsonString;
@@ -36,6 +37,8 @@ const P2P_READY_TIMEOUT_MS: u64 = 5000;
 #[derive(Clone)]
 pub struct P2pNetwork {
     connection: NetConnectionThread,
+    /// only set for backends that can keep direct messages for disconnected agents
+    deliver_later: Option<DeliverLaterRequests>,
 }
 
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_NET)]
@@ -57,6 +60,11 @@ impl P2pNetwork {
 
         let p2p_config_str = p2p_config.clone().as_str();
         let p2p_config2 = p2p_config.clone();
+        let deliver_later = match p2p_config.backend_kind {
+            P2pBackendKind::SIM2H => Some(DeliverLaterRequests::default()),
+            _ => None,
+        };
+        let worker_deliver_later = deliver_later.clone().unwrap_or_default();
 
         // Provide worker factory depending on backend kind
         let worker_factory: NetWorkerFactory = match &p2p_config.clone().backend_kind {
@@ -108,6 +116,7 @@ impl P2pNetwork {
                         .clone()
                         .expect("Can't construct Sim2hWorker without conductor API"),
                     tracer.clone(),
+                    worker_deliver_later.clone(),
                 )?) as Box<dyn NetWorker>)
             }),
         };
@@ -150,7 +159,10 @@ impl P2pNetwork {
         }
 
         // Done
-        Ok(P2pNetwork { connection })
+        Ok(P2pNetwork {
+            connection,
+            deliver_later,
+        })
     }
 
     fn should_wait_for_p2p_ready(p2p_config: &P2pConfig) -> bool {
@@ -192,6 +204,22 @@ impl P2pNetwork {
     pub fn p2p_endpoint(&self) -> url::Url {
         self.connection.p2p_endpoint.clone()
     }
+
+    /// Sends a direct message that the network should keep for its recipient if that is not
+    /// connected, and deliver once it reconnects. Only sim2h backends support this, others
+    /// send it like any other direct message.
+    pub fn send_direct_message_later(
+        &mut self,
+        span_wrap: ht::EncodedSpanWrap<DirectMessageData>,
+    ) -> NetResult<()> {
+        if let Some(deliver_later) = &self.deliver_later {
+            deliver_later
+                .lock()
+                .expect("Could not lock deliver later requests")
+                .insert(span_wrap.data.request_id.clone());
+        }
+        self.send(span_wrap.map(Lib3hClientProtocol::SendDirectMessage))
+    }
 }
 
 impl std::fmt::Debug for P2pNetwork {
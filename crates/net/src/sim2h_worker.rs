@@ -11,6 +11,7 @@ use crate::{
 use failure::_core::time::Duration;
 use holochain_conductor_lib_api::{ConductorApi, CryptoMethod};
 use holochain_json_api::{error::JsonError, json::JsonString};
+use holochain_locksmith::Mutex;
 use holochain_metrics::{DefaultMetricPublisher, MetricPublisher};
 use in_stream::*;
 use lib3h_protocol::{
@@ -27,9 +28,9 @@ use lib3h_protocol::{
 use log::*;
 use sim2h::{
     crypto::{Provenance, SignedWireMessage},
-    TcpWss, WireError, WireMessage, WIRE_VERSION,
+    MailboxReceiptData, MailboxStatus, TcpWss, WireError, WireMessage, WIRE_VERSION,
 };
-use std::{convert::TryFrom, time::Instant};
+use std::{collections::HashSet, convert::TryFrom, sync::Arc, time::Instant};
 use url::Url;
 use url2::prelude::*;
 
@@ -37,6 +38,18 @@ const INITIAL_CONNECTION_TIMEOUT_MS: u64 = 2000; // The real initial is 4 second
 const MAX_CONNECTION_TIMEOUT_MS: u64 = 60000;
 const SIM2H_WORKER_INTERNAL_REQUEST_ID: &str = "SIM2H_WORKER";
 
+/// Request ids of direct messages that sim2h should store if the recipient is not
+/// connected, and deliver once it reconnects. Filled by
+/// [P2pNetwork::send_direct_message_later](../p2p_network/struct.P2pNetwork.html),
+/// the worker takes the ids out again when it sends the messages.
+/// The sim2h server reports what happened to them with a result for the request id:
+/// a `SuccessResult` with one of the `MAILBOX_*` infos below if it was stored or delivered
+/// later, a `FailureResult` with the reason if it was rejected.
+pub type DeliverLaterRequests = Arc<Mutex<HashSet<String>>>;
+
+pub const MAILBOX_STORED: &str = "stored";
+pub const MAILBOX_DELIVERED: &str = "delivered";
+
 fn connect(url: Lib3hUri, timeout_ms: u64) -> NetResult<TcpWss> {
     //    let config = WssConnectConfig::new(TlsConnectConfig::new(TcpConnectConfig::default()));
     let config = WssConnectConfig::new(TcpConnectConfig {
@@ -72,6 +85,7 @@ pub struct Sim2hWorker {
     has_self_stored_authored_aspects: bool,
     is_full_sync_DHT: bool,
     tracer: Option<ht::Tracer>,
+    deliver_later: DeliverLaterRequests,
 }
 
 #[holochain_tracing_macros::newrelic_autotrace(HOLOCHAIN_NET)]
@@ -87,6 +101,7 @@ impl Sim2hWorker {
         agent_id: Address,
         conductor_api: ConductorApi,
         tracer: Option<ht::Tracer>,
+        deliver_later: DeliverLaterRequests,
     ) -> NetResult<Self> {
         let reconnect_interval = Duration::from_millis(INITIAL_CONNECTION_TIMEOUT_MS);
         let mut instance = Self {
@@ -113,6 +128,7 @@ impl Sim2hWorker {
             has_self_stored_authored_aspects: false,
             is_full_sync_DHT: false,
             tracer,
+            deliver_later,
         };
 
         instance.send_wire_message(WireMessage::Hello(WIRE_VERSION))?;
@@ -308,9 +324,20 @@ impl Sim2hWorker {
             // Send a message directly to another agent on the network
             Lib3hClientProtocol::SendDirectMessage(dm_data) => {
                 //let log_context = "ClientToLib3h::SendDirectMessage";
-                self.send_wire_message(WireMessage::ClientToLib3h(
-                    span_wrap.swapped(ClientToLib3h::SendDirectMessage(dm_data)),
-                ))
+                let deliver_later = self
+                    .deliver_later
+                    .lock()
+                    .expect("Could not lock deliver later requests")
+                    .remove(&dm_data.request_id);
+                if deliver_later {
+                    self.send_wire_message(WireMessage::StoreAndForwardDirectMessage(
+                        span_wrap.swapped(dm_data),
+                    ))
+                } else {
+                    self.send_wire_message(WireMessage::ClientToLib3h(
+                        span_wrap.swapped(ClientToLib3h::SendDirectMessage(dm_data)),
+                    ))
+                }
             }
             // Our response to a direct message from another agent.
             Lib3hClientProtocol::HandleSendDirectMessageResult(dm_data) => {
@@ -516,10 +543,44 @@ impl Sim2hWorker {
                 self.set_full_sync(response.redundant_count == 0);
             }
             WireMessage::StatusResponse(_) => error!("Got a StatusResponse from the Sim2h server, weird! Ignoring (I use Hello not Status)"),
+            WireMessage::StoreAndForwardDirectMessage(m) => error!(
+                "Got a StoreAndForwardDirectMessage from the Sim2h server, weird! Ignoring: {:?}",
+                m
+            ),
+            WireMessage::MailboxReceipt(receipt) => self.handle_mailbox_receipt(receipt),
         };
         Ok(())
     }
 
+    /// tell core what happened to a direct message it wanted delivered later
+    fn handle_mailbox_receipt(&mut self, receipt: MailboxReceiptData) {
+        let to_agent_id = AgentPubKey::from(self.agent_id.to_string());
+        let msg = match receipt.status {
+            MailboxStatus::Stored => Lib3hServerProtocol::SuccessResult(GenericResultData {
+                request_id: receipt.request_id,
+                space_address: receipt.space_address,
+                to_agent_id,
+                result_info: MAILBOX_STORED.into(),
+            }),
+            MailboxStatus::Delivered => Lib3hServerProtocol::SuccessResult(GenericResultData {
+                request_id: receipt.request_id,
+                space_address: receipt.space_address,
+                to_agent_id,
+                result_info: MAILBOX_DELIVERED.into(),
+            }),
+            MailboxStatus::Rejected(reason) => {
+                Lib3hServerProtocol::FailureResult(GenericResultData {
+                    request_id: receipt.request_id,
+                    space_address: receipt.space_address,
+                    to_agent_id,
+                    result_info: reason.into(),
+                })
+            }
+        };
+        let span = ht::top_follower("mailbox_receipt");
+        self.to_core.push(span.wrap(msg).into());
+    }
+
     pub fn set_full_sync(&mut self, full_sync: bool) {
         self.is_full_sync_DHT = full_sync;
     }
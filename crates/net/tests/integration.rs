@@ -46,6 +46,7 @@ impl Server {
                 Lib3hUri(url.into()),
                 DhtAlgorithm::FullSync,
                 None,
+                None,
             );
             rt.block_on(async move {
                 tokio::task::spawn(async move {
@@ -182,6 +183,7 @@ fn sim2h_worker_talks_to_sim2h() {
         agent_id.clone().into(),
         ConductorApi::new(io.clone()),
         None,
+        Default::default(),
     )
     .unwrap();
     worker.set_full_sync(true);
@@ -20,7 +20,7 @@ extern crate newrelic;
 extern crate holochain_common;
 
 #[allow(dead_code)]
-mod naive_sharding;
+pub mod naive_sharding;
 #[allow(dead_code)]
 mod schedule;
 #[allow(unused_imports)]
@@ -29,6 +29,7 @@ pub mod connection_state;
 pub mod crypto;
 pub mod error;
 use lib3h_protocol::types::*;
+pub mod mailbox;
 mod message_log;
 pub mod websocket;
 pub mod wire_message;
@@ -39,8 +40,11 @@ use connection_state::*;
 use lib3h_crypto_api::CryptoSystem;
 use lib3h_protocol::{data_types::*, protocol::*, types::SpaceHash, uri::Lib3hUri};
 
+use mailbox::Mailbox;
+pub use mailbox::MailboxConfig;
 pub use wire_message::{
-    HelloData, StatusData, WireError, WireMessage, WireMessageVersion, WIRE_VERSION,
+    HelloData, MailboxReceiptData, MailboxStatus, StatusData, WireError, WireMessage,
+    WireMessageVersion, WIRE_VERSION,
 };
 
 use futures::{
@@ -50,7 +54,7 @@ use futures::{
 use in_stream::*;
 use log::*;
 use rand::{seq::SliceRandom, thread_rng};
-use std::convert::TryFrom;
+use std::{convert::TryFrom, sync::Arc};
 
 use holochain_locksmith::Mutex;
 use holochain_metrics::{config::MetricPublisherConfig, Metric};
@@ -98,6 +102,9 @@ impl<T> SendExt<T> for crossbeam_channel::Sender<T> {
 /// but it does cause a mutate, so we don't want to spam it too hard
 const RETRY_FETCH_MISSING_ASPECTS_INTERVAL_MS: u64 = 500; // half second
 
+/// how often expired messages get dropped from the mailbox
+const MAILBOX_PRUNE_INTERVAL_MS: u64 = 60000; // one minute
+
 fn open_lifecycle(desc: &str, uuid: &str, uri: &Lib3hUri) {
     debug!("connection event open_conns: {} for {}@{}", desc, uuid, uri);
 }
@@ -203,6 +210,8 @@ struct Sim2hHandle {
     connection_mgr: ConnectionMgrHandle,
     connection_count: ConnectionCount,
     tracer: Option<ht::Tracer>,
+    /// only set if direct messages to disconnected agents should be stored
+    mailbox: Option<Arc<Mutex<Mailbox>>>,
 }
 
 impl Sim2hHandle {
@@ -213,6 +222,7 @@ impl Sim2hHandle {
         connection_mgr: ConnectionMgrHandle,
         connection_count: ConnectionCount,
         tracer: Option<ht::Tracer>,
+        mailbox: Option<MailboxConfig>,
     ) -> Self {
         let redundancy = match dht_algorithm {
             DhtAlgorithm::FullSync => 0,
@@ -225,6 +235,7 @@ impl Sim2hHandle {
             connection_mgr,
             connection_count,
             tracer,
+            mailbox: mailbox.map(|config| Arc::new(Mutex::new(Mailbox::new(config)))),
         }
     }
 
@@ -238,6 +249,11 @@ impl Sim2hHandle {
         &self.dht_algorithm
     }
 
+    /// access our mailbox, if we store messages for disconnected agents
+    pub fn mailbox(&self) -> Option<&Arc<Mutex<Mailbox>>> {
+        self.mailbox.as_ref()
+    }
+
     /// access our connection manager handle
     pub fn connection_mgr(&self) -> &ConnectionMgrHandle {
         &self.connection_mgr
@@ -412,6 +428,15 @@ impl Sim2hHandle {
                         }
                     }
                 }
+                WireMessage::StoreAndForwardDirectMessage(span_wrap) => {
+                    return spawn_handle_message_store_and_forward_dm(
+                        sim2h_handle,
+                        uri,
+                        signer,
+                        space_hash,
+                        span_wrap,
+                    );
+                }
                 message @ _ => {
                     error!("unhandled message type {:?}", message);
                     return;
@@ -522,16 +547,91 @@ fn spawn_handle_message_join_space(
     let span = ht::top_follower("inner");
     sim2h_handle.send(
         data.agent_id.clone(),
-        uri,
+        uri.clone(),
         &WireMessage::Lib3hToClient(
             span.wrap(Lib3hToClient::HandleGetAuthoringEntryList(GetListData {
                 request_id: "".into(),
                 space_address: data.space_address.clone(),
-                provider_agent_id: data.agent_id,
+                provider_agent_id: data.agent_id.clone(),
             }))
             .into(),
         ),
     );
+
+    spawn_deliver_stored_messages(sim2h_handle, uri, data);
+}
+
+/// hand all messages and receipts stored while the agent was away to it.
+/// senders of the messages get a receipt, or get it stored if they are not connected
+fn spawn_deliver_stored_messages(sim2h_handle: Sim2hHandle, uri: Lib3hUri, data: SpaceData) {
+    let (stored, stored_receipts) = match sim2h_handle.mailbox() {
+        None => return,
+        Some(mailbox) => {
+            let mut mailbox = mailbox.f_lock();
+            mailbox.add_known_agent(data.space_address.clone(), data.agent_id.clone());
+            (
+                mailbox.take(&data.space_address, &data.agent_id),
+                mailbox.take_receipts(&data.space_address, &data.agent_id),
+            )
+        }
+    };
+    if stored.is_empty() && stored_receipts.is_empty() {
+        return;
+    }
+
+    tokio::task::spawn(async move {
+        debug!(
+            "delivering {} stored messages and {} receipts to {}",
+            stored.len(),
+            stored_receipts.len(),
+            data.agent_id
+        );
+        for receipt in stored_receipts {
+            sim2h_handle.send(
+                data.agent_id.clone(),
+                uri.clone(),
+                &WireMessage::MailboxReceipt(receipt),
+            );
+        }
+
+        let mut receipts = Vec::new();
+        for dm_data in stored {
+            receipts.push((
+                dm_data.from_agent_id.clone(),
+                MailboxReceiptData {
+                    space_address: dm_data.space_address.clone(),
+                    request_id: dm_data.request_id.clone(),
+                    to_agent_id: dm_data.to_agent_id.clone(),
+                    status: MailboxStatus::Delivered,
+                },
+            ));
+            sim2h_handle.send(
+                data.agent_id.clone(),
+                uri.clone(),
+                &WireMessage::Lib3hToClient(
+                    ht::top_follower("mailbox")
+                        .wrap(Lib3hToClient::HandleSendDirectMessage(dm_data))
+                        .into(),
+                ),
+            );
+        }
+
+        let state = sim2h_handle.state().get_clone().await;
+        for (from_agent_id, receipt) in receipts {
+            match state.lookup_joined(&data.space_address, &from_agent_id) {
+                Some(from_url) => sim2h_handle.send(
+                    from_agent_id,
+                    from_url.clone(),
+                    &WireMessage::MailboxReceipt(receipt),
+                ),
+                None => {
+                    if let Some(mailbox) = sim2h_handle.mailbox() {
+                        mailbox.f_lock().store_receipt(from_agent_id, receipt);
+                    }
+                }
+            }
+        }
+    });
 }
 
 fn inner_spawn_handle_message_send_dmx(
@@ -585,6 +685,62 @@ fn spawn_handle_message_send_dm(
     );
 }
 
+/// send a direct message if the recipient is connected, otherwise store it
+/// in its mailbox and tell the sender if that worked
+fn spawn_handle_message_store_and_forward_dm(
+    sim2h_handle: Sim2hHandle,
+    uri: Lib3hUri,
+    signer: AgentId,
+    space_hash: MonoRef<SpaceHash>,
+    span_wrap: ht::EncodedSpanWrap<DirectMessageData>,
+) {
+    // Avoid clone of data
+    let (span_wrap, data) = {
+        let s = span_wrap.swapped(());
+        (s, span_wrap.data)
+    };
+    if data.space_address != *space_hash {
+        error!(
+            "space mismatch - agent is in {}, message is for {}",
+            *space_hash, data.space_address
+        );
+        return;
+    }
+
+    tokio::task::spawn(async move {
+        let state = sim2h_handle.state().get_clone().await;
+        if let Some(to_url) = state.lookup_joined(&space_hash, &data.to_agent_id) {
+            sim2h_handle.send(
+                data.to_agent_id.clone(),
+                to_url.clone(),
+                &WireMessage::Lib3hToClient(
+                    span_wrap.swapped(Lib3hToClient::HandleSendDirectMessage(data)),
+                ),
+            );
+            return;
+        }
+
+        let mut receipt = MailboxReceiptData {
+            space_address: data.space_address.clone(),
+            request_id: data.request_id.clone(),
+            to_agent_id: data.to_agent_id.clone(),
+            status: MailboxStatus::Stored,
+        };
+        let stored = match sim2h_handle.mailbox() {
+            None => Err(String::from("sim2h does not store messages")),
+            Some(mailbox) => mailbox.f_lock().store(data),
+        };
+        if let Err(reason) = stored {
+            debug!(
+                "could not store message {} for {}: {}",
+                receipt.request_id, receipt.to_agent_id, reason
+            );
+            receipt.status = MailboxStatus::Rejected(reason);
+        }
+        sim2h_handle.send(signer, uri, &WireMessage::MailboxReceipt(receipt));
+    });
+}
+
 fn spawn_handle_message_send_dm_result(
     sim2h_handle: Sim2hHandle,
     _uri: Lib3hUri,
@@ -921,6 +1077,7 @@ pub fn run_sim2h(
     bind_spec: Lib3hUri,
     dht_algorithm: DhtAlgorithm,
     tracer: Option<ht::Tracer>,
+    mailbox: Option<MailboxConfig>,
 ) -> (
     tokio::runtime::Runtime,
     tokio::sync::oneshot::Receiver<Lib3hUri>,
@@ -936,7 +1093,7 @@ pub fn run_sim2h(
     let (bind_send, bind_recv) = tokio::sync::oneshot::channel();
 
     rt.spawn(async move {
-        let sim2h = Sim2h::new(crypto, bind_spec, dht_algorithm, tracer);
+        let sim2h = Sim2h::new(crypto, bind_spec, dht_algorithm, tracer, mailbox);
         let _ = bind_send.send(sim2h.bound_uri.clone().unwrap());
 
         /*
@@ -1016,6 +1173,8 @@ pub struct Sim2h {
     num_ticks: u64,
     /// when should we try to resync nodes that are still missing aspect data
     missing_aspects_resync_schedule: Schedule,
+    /// when should we drop expired messages from the mailbox
+    mailbox_prune_schedule: Schedule,
     sim2h_handle: Sim2hHandle,
     metric_gen: MetricsTimerGenerator,
 }
@@ -1029,6 +1188,7 @@ impl Sim2h {
         bind_spec: Lib3hUri,
         dht_algorithm: DhtAlgorithm,
         tracer: Option<ht::Tracer>,
+        mailbox: Option<MailboxConfig>,
     ) -> Self {
         let (metric_gen, metric_task) = MetricsTimerGenerator::new();
 
@@ -1042,6 +1202,7 @@ impl Sim2h {
             connection_mgr,
             connection_count,
             tracer,
+            mailbox,
         );
 
         let config = TcpBindConfig::default();
@@ -1064,12 +1225,16 @@ impl Sim2h {
             missing_aspects_resync_schedule: Schedule::new(std::time::Duration::from_millis(
                 RETRY_FETCH_MISSING_ASPECTS_INTERVAL_MS,
             )),
+            mailbox_prune_schedule: Schedule::new(std::time::Duration::from_millis(
+                MAILBOX_PRUNE_INTERVAL_MS,
+            )),
             sim2h_handle,
             metric_gen,
         };
 
         // trigger an initial schedule ready event
         let _ = sim2h.missing_aspects_resync_schedule.get_guard();
+        let _ = sim2h.mailbox_prune_schedule.get_guard();
 
         sim2h
     }
@@ -1277,6 +1442,13 @@ impl Sim2h {
             tokio::task::spawn(missing_aspects_resync(sim2h_handle, schedule_guard));
         }
 
+        if self.mailbox_prune_schedule.should_proceed() {
+            let _schedule_guard = self.mailbox_prune_schedule.get_guard();
+            if let Some(mailbox) = self.sim2h_handle.mailbox() {
+                mailbox.f_lock().prune();
+            }
+        }
+
         Ok(did_work)
     }
 }
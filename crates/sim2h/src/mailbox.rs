@@ -0,0 +1,383 @@
+//! Store-and-forward of direct messages to agents that are not connected.
+//!
+//! Senders that ask for it get their direct message kept here if the recipient
+//! has been in the space before but is not connected at the moment. Stored messages
+//! get delivered in order when the recipient joins the space again, and their senders
+//! get a receipt; senders that are not connected then get it when they join again.
+//! Messages and receipts expire after a TTL. Every recipient has a limited number of
+//! bytes, and the number of agents we remember and the bytes stored in total are capped.
+//! `prune()` drops everything that expired and has to be called periodically.
+use crate::{connection_state::AgentId, wire_message::MailboxReceiptData};
+use lib3h_protocol::{data_types::DirectMessageData, types::SpaceHash};
+use std::{
+    collections::{HashMap, VecDeque},
+    time::{Duration, Instant},
+};
+
+/// how long stored messages wait for their recipient (one day)
+pub const DEFAULT_MAILBOX_TTL_SECS: u64 = 60 * 60 * 24;
+
+/// how many bytes of message content we keep per recipient (1 MiB)
+pub const DEFAULT_MAILBOX_MAX_BYTES_PER_AGENT: usize = 1024 * 1024;
+
+/// how many bytes of message content we keep for all recipients together (256 MiB)
+pub const DEFAULT_MAILBOX_MAX_TOTAL_BYTES: usize = 256 * 1024 * 1024;
+
+/// how many agents we remember as members of their spaces
+pub const DEFAULT_MAILBOX_MAX_KNOWN_AGENTS: usize = 100_000;
+
+/// agents that have not joined their space for this long get forgotten (30 days)
+pub const DEFAULT_MAILBOX_KNOWN_AGENT_TTL_SECS: u64 = 60 * 60 * 24 * 30;
+
+/// how many receipts we keep per disconnected sender
+pub const DEFAULT_MAILBOX_MAX_RECEIPTS_PER_AGENT: usize = 1000;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MailboxConfig {
+    pub ttl: Duration,
+    pub max_bytes_per_agent: usize,
+    pub max_total_bytes: usize,
+    pub max_known_agents: usize,
+    pub known_agent_ttl: Duration,
+    pub max_receipts_per_agent: usize,
+}
+
+impl Default for MailboxConfig {
+    fn default() -> Self {
+        Self {
+            ttl: Duration::from_secs(DEFAULT_MAILBOX_TTL_SECS),
+            max_bytes_per_agent: DEFAULT_MAILBOX_MAX_BYTES_PER_AGENT,
+            max_total_bytes: DEFAULT_MAILBOX_MAX_TOTAL_BYTES,
+            max_known_agents: DEFAULT_MAILBOX_MAX_KNOWN_AGENTS,
+            known_agent_ttl: Duration::from_secs(DEFAULT_MAILBOX_KNOWN_AGENT_TTL_SECS),
+            max_receipts_per_agent: DEFAULT_MAILBOX_MAX_RECEIPTS_PER_AGENT,
+        }
+    }
+}
+
+struct StoredMessage {
+    data: DirectMessageData,
+    stored_at: Instant,
+}
+
+struct StoredReceipt {
+    receipt: MailboxReceiptData,
+    stored_at: Instant,
+}
+
+#[derive(Default)]
+struct Inbox {
+    messages: VecDeque<StoredMessage>,
+    bytes: usize,
+}
+
+impl Inbox {
+    /// drops expired messages, returns the number of bytes freed
+    fn prune(&mut self, ttl: Duration) -> usize {
+        let before = self.bytes;
+        while let Some(message) = self.messages.front() {
+            if message.stored_at.elapsed() < ttl {
+                break;
+            }
+            self.bytes -= message.data.content.len();
+            self.messages.pop_front();
+        }
+        before - self.bytes
+    }
+}
+
+type MailboxKey = (SpaceHash, AgentId);
+
+pub(crate) struct Mailbox {
+    config: MailboxConfig,
+    /// agents that joined a space and when they did last, only they get messages stored
+    known_agents: HashMap<MailboxKey, Instant>,
+    inboxes: HashMap<MailboxKey, Inbox>,
+    /// bytes of all messages in all inboxes
+    total_bytes: usize,
+    /// receipts for senders that were not connected when their message got delivered
+    receipts: HashMap<MailboxKey, VecDeque<StoredReceipt>>,
+}
+
+impl Mailbox {
+    pub fn new(config: MailboxConfig) -> Self {
+        Self {
+            config,
+            known_agents: HashMap::new(),
+            inboxes: HashMap::new(),
+            total_bytes: 0,
+            receipts: HashMap::new(),
+        }
+    }
+
+    /// remember that this agent is part of the space.
+    /// if we know too many agents, the one that joined longest ago gets forgotten,
+    /// together with its stored messages
+    pub fn add_known_agent(&mut self, space_address: SpaceHash, agent_id: AgentId) {
+        let joined = (space_address, agent_id);
+        self.known_agents.insert(joined.clone(), Instant::now());
+        if self.known_agents.len() > self.config.max_known_agents {
+            let oldest = self
+                .known_agents
+                .iter()
+                .filter(|(key, _)| **key != joined)
+                .min_by_key(|(_, joined_at)| **joined_at)
+                .map(|(key, _)| key.clone());
+            if let Some(key) = oldest {
+                self.forget(&key);
+            }
+        }
+    }
+
+    fn forget(&mut self, key: &MailboxKey) {
+        self.known_agents.remove(key);
+        if let Some(inbox) = self.inboxes.remove(key) {
+            self.total_bytes -= inbox.bytes;
+        }
+    }
+
+    /// keep a message for its recipient
+    /// fails if the recipient is unknown or its inbox or the whole mailbox is full
+    pub fn store(&mut self, data: DirectMessageData) -> Result<(), String> {
+        let key = (data.space_address.clone(), data.to_agent_id.clone());
+        if !self.known_agents.contains_key(&key) {
+            return Err(format!(
+                "agent {} is unknown in space {}",
+                data.to_agent_id, data.space_address
+            ));
+        }
+
+        let ttl = self.config.ttl;
+        let max_bytes = self.config.max_bytes_per_agent;
+        let inbox = self.inboxes.entry(key).or_default();
+        self.total_bytes -= inbox.prune(ttl);
+        let size = data.content.len();
+        if inbox.bytes + size > max_bytes {
+            return Err(format!("mailbox of agent {} is full", data.to_agent_id));
+        }
+        if self.total_bytes + size > self.config.max_total_bytes {
+            return Err(String::from("mailbox is full"));
+        }
+        inbox.bytes += size;
+        self.total_bytes += size;
+        inbox.messages.push_back(StoredMessage {
+            data,
+            stored_at: Instant::now(),
+        });
+        Ok(())
+    }
+
+    /// remove and return all unexpired messages for this agent, oldest first
+    pub fn take(
+        &mut self,
+        space_address: &SpaceHash,
+        agent_id: &AgentId,
+    ) -> Vec<DirectMessageData> {
+        match self
+            .inboxes
+            .remove(&(space_address.clone(), agent_id.clone()))
+        {
+            None => Vec::new(),
+            Some(mut inbox) => {
+                self.total_bytes -= inbox.bytes;
+                inbox.prune(self.config.ttl);
+                inbox.messages.into_iter().map(|m| m.data).collect()
+            }
+        }
+    }
+
+    /// keep a receipt for a sender that is not connected,
+    /// dropping its oldest one if it has too many
+    pub fn store_receipt(&mut self, sender: AgentId, receipt: MailboxReceiptData) {
+        let max_receipts = self.config.max_receipts_per_agent;
+        let receipts = self
+            .receipts
+            .entry((receipt.space_address.clone(), sender))
+            .or_default();
+        receipts.push_back(StoredReceipt {
+            receipt,
+            stored_at: Instant::now(),
+        });
+        while receipts.len() > max_receipts {
+            receipts.pop_front();
+        }
+    }
+
+    /// remove and return all unexpired receipts for this sender, oldest first
+    pub fn take_receipts(
+        &mut self,
+        space_address: &SpaceHash,
+        agent_id: &AgentId,
+    ) -> Vec<MailboxReceiptData> {
+        let ttl = self.config.ttl;
+        self.receipts
+            .remove(&(space_address.clone(), agent_id.clone()))
+            .map(|receipts| {
+                receipts
+                    .into_iter()
+                    .filter(|stored| stored.stored_at.elapsed() < ttl)
+                    .map(|stored| stored.receipt)
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// drop expired messages and receipts, and forget agents that did not join for too long
+    pub fn prune(&mut self) {
+        let ttl = self.config.ttl;
+        let known_agent_ttl = self.config.known_agent_ttl;
+        let mut freed = 0;
+        self.inboxes.retain(|_, inbox| {
+            freed += inbox.prune(ttl);
+            !inbox.messages.is_empty()
+        });
+        self.total_bytes -= freed;
+
+        let stale: Vec<MailboxKey> = self
+            .known_agents
+            .iter()
+            .filter(|(_, joined)| joined.elapsed() >= known_agent_ttl)
+            .map(|(key, _)| key.clone())
+            .collect();
+        for key in stale {
+            self.forget(&key);
+        }
+
+        self.receipts.retain(|_, receipts| {
+            receipts.retain(|stored| stored.stored_at.elapsed() < ttl);
+            !receipts.is_empty()
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wire_message::MailboxStatus;
+
+    fn message(request_id: &str, to_agent_id: &str, content: &str) -> DirectMessageData {
+        DirectMessageData {
+            space_address: "space".into(),
+            request_id: request_id.into(),
+            to_agent_id: to_agent_id.into(),
+            from_agent_id: "sender".into(),
+            content: content.into(),
+        }
+    }
+
+    fn mailbox(config: MailboxConfig) -> Mailbox {
+        let mut mailbox = Mailbox::new(config);
+        mailbox.add_known_agent("space".into(), "alice".into());
+        mailbox
+    }
+
+    #[test]
+    fn delivers_stored_messages_in_order() {
+        let mut mailbox = mailbox(MailboxConfig::default());
+        assert_eq!(mailbox.store(message("1", "alice", "hello")), Ok(()));
+        assert_eq!(mailbox.store(message("2", "alice", "again")), Ok(()));
+
+        let ids: Vec<String> = mailbox
+            .take(&"space".into(), &"alice".into())
+            .into_iter()
+            .map(|m| m.request_id)
+            .collect();
+        assert_eq!(ids, vec![String::from("1"), String::from("2")]);
+        assert!(mailbox.take(&"space".into(), &"alice".into()).is_empty());
+    }
+
+    #[test]
+    fn rejects_messages_for_unknown_agents() {
+        let mut mailbox = mailbox(MailboxConfig::default());
+        assert!(mailbox.store(message("1", "bob", "hello")).is_err());
+    }
+
+    #[test]
+    fn rejects_messages_above_size_cap() {
+        let mut mailbox = mailbox(MailboxConfig {
+            max_bytes_per_agent: 8,
+            ..Default::default()
+        });
+        assert_eq!(mailbox.store(message("1", "alice", "hello")), Ok(()));
+        assert!(mailbox.store(message("2", "alice", "again")).is_err());
+        assert_eq!(mailbox.take(&"space".into(), &"alice".into()).len(), 1);
+    }
+
+    #[test]
+    fn drops_expired_messages() {
+        let mut mailbox = mailbox(MailboxConfig {
+            ttl: Duration::from_millis(0),
+            ..Default::default()
+        });
+        assert_eq!(mailbox.store(message("1", "alice", "hello")), Ok(()));
+        assert!(mailbox.take(&"space".into(), &"alice".into()).is_empty());
+    }
+
+    #[test]
+    fn rejects_messages_above_total_cap() {
+        let mut mailbox = mailbox(MailboxConfig {
+            max_total_bytes: 8,
+            ..Default::default()
+        });
+        mailbox.add_known_agent("space".into(), "bob".into());
+        assert_eq!(mailbox.store(message("1", "alice", "hello")), Ok(()));
+        assert!(mailbox.store(message("2", "bob", "again")).is_err());
+
+        // taking messages frees their space
+        assert_eq!(mailbox.take(&"space".into(), &"alice".into()).len(), 1);
+        assert_eq!(mailbox.store(message("2", "bob", "again")), Ok(()));
+    }
+
+    #[test]
+    fn forgets_the_agent_that_joined_longest_ago_when_full() {
+        let mut mailbox = mailbox(MailboxConfig {
+            max_known_agents: 1,
+            ..Default::default()
+        });
+        assert_eq!(mailbox.store(message("1", "alice", "hello")), Ok(()));
+        mailbox.add_known_agent("space".into(), "bob".into());
+
+        assert!(mailbox.store(message("2", "alice", "again")).is_err());
+        assert!(mailbox.take(&"space".into(), &"alice".into()).is_empty());
+        assert_eq!(mailbox.store(message("3", "bob", "hello")), Ok(()));
+    }
+
+    #[test]
+    fn prunes_expired_messages_and_stale_agents() {
+        let mut mailbox = mailbox(MailboxConfig {
+            ttl: Duration::from_millis(0),
+            known_agent_ttl: Duration::from_millis(0),
+            ..Default::default()
+        });
+        assert_eq!(mailbox.store(message("1", "alice", "hello")), Ok(()));
+        mailbox.prune();
+
+        assert!(mailbox.inboxes.is_empty());
+        assert_eq!(mailbox.total_bytes, 0);
+        assert!(mailbox.known_agents.is_empty());
+        assert!(mailbox.store(message("2", "alice", "again")).is_err());
+    }
+
+    #[test]
+    fn keeps_receipts_for_disconnected_senders() {
+        let mut mailbox = mailbox(MailboxConfig {
+            max_receipts_per_agent: 2,
+            ..Default::default()
+        });
+        let receipt = |request_id: &str| MailboxReceiptData {
+            space_address: "space".into(),
+            request_id: request_id.into(),
+            to_agent_id: "alice".into(),
+            status: MailboxStatus::Delivered,
+        };
+        for request_id in &["1", "2", "3"] {
+            mailbox.store_receipt("sender".into(), receipt(request_id));
+        }
+
+        let receipts = mailbox.take_receipts(&"space".into(), &"sender".into());
+        assert_eq!(receipts, vec![receipt("2"), receipt("3")]);
+        assert!(mailbox
+            .take_receipts(&"space".into(), &"sender".into())
+            .is_empty());
+    }
+}
@@ -1,6 +1,10 @@
 //! encapsulates lib3h ghostmessage for sim2h including security challenge
 use crate::{error::Sim2hError, NEW_RELIC_LICENSE_KEY};
-use lib3h_protocol::{data_types::Opaque, protocol::*};
+use lib3h_protocol::{
+    data_types::{DirectMessageData, Opaque},
+    protocol::*,
+    types::{AgentPubKey, SpaceHash},
+};
 use std::convert::TryFrom;
 
 pub type WireMessageVersion = u32;
@@ -27,6 +31,26 @@ pub struct HelloData {
     pub extra: Option<String>,
 }
 
+/// What became of a direct message that should be kept in the mailbox
+/// of its recipient if it is not connected
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MailboxStatus {
+    /// the recipient is offline, the message waits in its mailbox
+    Stored,
+    /// the recipient reconnected and got the stored message
+    Delivered,
+    /// the message could neither be delivered nor stored
+    Rejected(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MailboxReceiptData {
+    pub space_address: SpaceHash,
+    pub request_id: String,
+    pub to_agent_id: AgentPubKey,
+    pub status: MailboxStatus,
+}
+
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum WireMessage {
@@ -42,6 +66,12 @@ pub enum WireMessage {
     HelloResponse(HelloData),
     Status,
     StatusResponse(StatusData),
+    /// like SendDirectMessage, but sim2h keeps the message for the recipient
+    /// if it is not connected (only if sim2h runs with a mailbox).
+    /// only clients that want messages delivered later send this, and only they get
+    /// MailboxReceipts, so older peers never see either and the wire version stays.
+    StoreAndForwardDirectMessage(ht::EncodedSpanWrap<DirectMessageData>),
+    MailboxReceipt(MailboxReceiptData),
 }
 
 #[holochain_tracing_macros::newrelic_autotrace(SIM2H)]
@@ -54,6 +84,8 @@ impl WireMessage {
             WireMessage::StatusResponse(_) => "StatusResponse",
             WireMessage::Hello(_) => "Hello",
             WireMessage::HelloResponse(_) => "HelloResponse",
+            WireMessage::StoreAndForwardDirectMessage(_) => "[C>L]StoreAndForwardDirectMessage",
+            WireMessage::MailboxReceipt(_) => "MailboxReceipt",
             WireMessage::ClientToLib3h(span_wrap) => match span_wrap.data {
                 ClientToLib3h::Bootstrap(_) => "[C>L]Bootstrap",
                 ClientToLib3h::FetchEntry(_) => "[C>L]FetchEntry",
@@ -10,8 +10,14 @@ use lib3h_protocol::uri::Builder;
 use lib3h_sodium::SodiumCryptoSystem;
 use log::*;
 use newrelic::{LogLevel, LogOutput, NewRelicConfig};
-use sim2h::{run_sim2h, DhtAlgorithm, MESSAGE_LOGGER};
-use std::path::PathBuf;
+use sim2h::{
+    mailbox::{
+        DEFAULT_MAILBOX_MAX_BYTES_PER_AGENT, DEFAULT_MAILBOX_MAX_KNOWN_AGENTS,
+        DEFAULT_MAILBOX_MAX_TOTAL_BYTES, DEFAULT_MAILBOX_TTL_SECS,
+    },
+    run_sim2h, DhtAlgorithm, MailboxConfig, MESSAGE_LOGGER,
+};
+use std::{path::PathBuf, time::Duration};
 use structopt::StructOpt;
 
 #[derive(StructOpt)]
@@ -46,6 +52,24 @@ struct Cli {
         help = "The service name to use for Jaeger tracing spans. No tracing is done if not specified."
     )]
     tracing_name: Option<String>,
+
+    #[structopt(
+        long,
+        help = "Store direct messages to disconnected agents until they reconnect"
+    )]
+    mailbox: bool,
+
+    #[structopt(long, help = "Seconds stored direct messages are kept at most")]
+    mailbox_ttl: Option<u64>,
+
+    #[structopt(long, help = "Maximum bytes of stored direct messages per agent")]
+    mailbox_max_bytes: Option<usize>,
+
+    #[structopt(long, help = "Maximum bytes of stored direct messages of all agents")]
+    mailbox_max_total_bytes: Option<usize>,
+
+    #[structopt(long, help = "Maximum number of agents messages get stored for")]
+    mailbox_max_agents: Option<usize>,
 }
 
 new_relic_setup!("NEW_RELIC_LICENSE_KEY");
@@ -86,6 +110,24 @@ fn main() {
         MESSAGE_LOGGER.lock().start();
     }
 
+    let mailbox = if args.mailbox {
+        Some(MailboxConfig {
+            ttl: Duration::from_secs(args.mailbox_ttl.unwrap_or(DEFAULT_MAILBOX_TTL_SECS)),
+            max_bytes_per_agent: args
+                .mailbox_max_bytes
+                .unwrap_or(DEFAULT_MAILBOX_MAX_BYTES_PER_AGENT),
+            max_total_bytes: args
+                .mailbox_max_total_bytes
+                .unwrap_or(DEFAULT_MAILBOX_MAX_TOTAL_BYTES),
+            max_known_agents: args
+                .mailbox_max_agents
+                .unwrap_or(DEFAULT_MAILBOX_MAX_KNOWN_AGENTS),
+            ..Default::default()
+        })
+    } else {
+        None
+    };
+
     let (mut rt, _) = run_sim2h(
         Box::new(SodiumCryptoSystem::new()),
         uri,
@@ -93,6 +135,7 @@ fn main() {
             redundant_count: args.sharding,
         },
         tracer,
+        mailbox,
     );
 
     // just park the main thread indefinitely...
@@ -201,6 +201,7 @@ pub fn main() {
         Lib3hUri(url.into()),
         DhtAlgorithm::FullSync,
         None,
+        None,
     );
     rt.block_on(async move {
         let bound_uri = binding.await.unwrap();
@@ -606,6 +606,7 @@ impl Suite {
                 Lib3hUri(url.into()),
                 DhtAlgorithm::FullSync,
                 None,
+                None,
             );
             rt.block_on(async move {
                 tokio::task::spawn(async move {
@@ -0,0 +1,38 @@
+use holochain_core_types::{
+    blob::{BlobChunk, BlobManifest},
+    time::Timeout,
+};
+use holochain_json_api::{error::JsonError, json::*};
+use holochain_persistence_api::cas::content::Address;
+
+/// A blob gets stored by committing all of its chunks first and then its manifest
+#[derive(Deserialize, Debug, Serialize, DefaultJson, Clone, PartialEq)]
+pub enum StoreBlobArgs {
+    Chunk(BlobChunk),
+    Manifest(BlobManifest),
+}
+
+#[derive(Deserialize, Default, Debug, Serialize, Clone, PartialEq, DefaultJson)]
+pub struct GetBlobArgs {
+    /// Address of the blob's manifest
+    pub address: Address,
+    /// Index of the first chunk to fetch
+    #[serde(default)]
+    pub first_chunk: usize,
+    /// Number of chunks to fetch, starting at `first_chunk`
+    #[serde(default)]
+    pub chunk_count: usize,
+    /// The blob's manifest if the caller already got it with an earlier window,
+    /// so it does not get fetched again. Must be the manifest stored at `address`.
+    #[serde(default)]
+    pub manifest: Option<BlobManifest>,
+    #[serde(default)]
+    pub timeout: Timeout,
+}
+
+#[derive(Deserialize, Debug, Serialize, Clone, PartialEq, DefaultJson)]
+pub struct GetBlobResult {
+    pub manifest: BlobManifest,
+    /// The requested chunks, checked against the manifest
+    pub chunks: Vec<BlobChunk>,
+}
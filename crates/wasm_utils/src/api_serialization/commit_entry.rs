@@ -1,4 +1,4 @@
-use holochain_core_types::{entry::Entry, signature::Provenance};
+use holochain_core_types::{entry::Entry, signature::Provenance, time::Timeout};
 
 use holochain_json_api::{error::JsonError, json::*};
 use holochain_persistence_api::cas::content::Address;
@@ -47,6 +47,14 @@ impl CommitEntryArgs {
     }
 }
 
+/// The arguments required to execute a commit_countersigned_entry() call.
+#[derive(Deserialize, Debug, Serialize, DefaultJson)]
+pub struct CommitCountersignedEntryArgs {
+    pub entry: Entry,
+    /// How long to wait for the signatures of the other countersigners
+    pub timeout: Timeout,
+}
+
 /// Represents any useful information to return after
 /// entries are committed
 #[derive(Deserialize, Debug, Clone, Serialize, DefaultJson)]
@@ -1,4 +1,5 @@
 use holochain_json_api::{error::JsonError, json::JsonString};
+use holochain_persistence_api::cas::content::Address;
 
 /// Struct for input data received when invoke_emit_signal is called
 #[derive(Deserialize, Clone, PartialEq, Eq, Hash, Debug, Serialize, DefaultJson)]
@@ -6,3 +7,11 @@ pub struct EmitSignalArgs {
     pub name: String,
     pub arguments: JsonString,
 }
+
+/// Struct for input data received when invoke_remote_signal is called
+#[derive(Deserialize, Clone, PartialEq, Eq, Hash, Debug, Serialize, DefaultJson)]
+pub struct RemoteSignalArgs {
+    /// Agents whose conductors emit the signal
+    pub agents: Vec<Address>,
+    pub signal: EmitSignalArgs,
+}
@@ -0,0 +1,13 @@
+use holochain_core_types::{agent_activity::AgentActivityFilter, time::Timeout};
+use holochain_json_api::{error::JsonError, json::*};
+use holochain_persistence_api::cas::content::Address;
+
+#[derive(Deserialize, Default, Debug, Serialize, Clone, PartialEq, DefaultJson)]
+pub struct GetAgentActivityArgs {
+    /// Address of the agent whose published headers we want
+    pub agent: Address,
+    #[serde(default)]
+    pub filter: AgentActivityFilter,
+    #[serde(default)]
+    pub timeout: Timeout,
+}
@@ -2,6 +2,7 @@ use holochain_core_types::{
     chain_header::ChainHeader,
     crud_status::CrudStatus,
     entry::{entry_type::EntryType, Entry, EntryWithMeta},
+    error::HolochainError,
     time::Timeout,
 };
 use holochain_json_api::{error::JsonError, json::*};
@@ -21,6 +22,31 @@ impl Default for StatusRequestKind {
     }
 }
 
+/// Where get_entry and get_links may take their results from.
+/// Entries and links held in our own DHT shard are always used since we are an authority
+/// for them.
+/// Results of network queries are cached by the instance until they expire or we see
+/// newer CRUD or link data for them.
+#[derive(Deserialize, Debug, Serialize, DefaultJson, Clone, PartialEq, Eq, Hash)]
+pub enum CachePolicy {
+    /// Use results of earlier network queries while they are younger than the cache TTL
+    /// of the instance and no newer CRUD or link data has been seen for them.
+    /// Falls back to the network otherwise.
+    CacheOk,
+    /// Always ask the network. The fresh results still get cached for later reads.
+    NetworkOnly,
+    /// Never ask the network. Uses what our own DHT shard holds,
+    /// otherwise cached results regardless of their age.
+    /// Returns no entry or no links if neither has them.
+    LocalOnly,
+}
+
+impl Default for CachePolicy {
+    fn default() -> Self {
+        CachePolicy::NetworkOnly
+    }
+}
+
 /// Structure used to specify what should be returned to a call to get_entry_result()
 /// The default is to return the latest entry.
 #[derive(Deserialize, Debug, Serialize, DefaultJson, PartialEq, Clone)]
@@ -29,6 +55,8 @@ pub struct GetEntryOptions {
     pub entry: bool,
     pub headers: bool,
     pub timeout: Timeout,
+    #[serde(default)]
+    pub cache_policy: CachePolicy,
 }
 
 impl Default for GetEntryOptions {
@@ -38,6 +66,7 @@ impl Default for GetEntryOptions {
             entry: true,
             headers: false,
             timeout: Default::default(),
+            cache_policy: CachePolicy::default(),
         }
     }
 }
@@ -54,6 +83,7 @@ impl GetEntryOptions {
             entry,
             headers,
             timeout,
+            cache_policy: CachePolicy::default(),
         }
     }
 }
@@ -64,6 +94,18 @@ pub struct GetEntryArgs {
     pub options: GetEntryOptions,
 }
 
+#[derive(Deserialize, Debug, Serialize, DefaultJson)]
+pub struct GetEntriesArgs {
+    pub addresses: Vec<Address>,
+    pub options: GetEntryOptions,
+}
+
+/// Results of a get_entries request, one for each requested address in the same order
+#[derive(Deserialize, Debug, Serialize, DefaultJson, Clone)]
+pub struct GetEntriesResult {
+    pub results: Vec<Result<GetEntryResult, HolochainError>>,
+}
+
 #[derive(Deserialize, Debug, Serialize, DefaultJson, Clone)]
 pub struct EntryResultMeta {
     pub address: Address,
@@ -134,6 +176,13 @@ pub enum GetEntryResultType {
 #[derive(Deserialize, Debug, Serialize, DefaultJson, Clone)]
 pub struct GetEntryResult {
     pub result: GetEntryResultType,
+    /// Addresses of the live ends of the entry's update graph, sorted.
+    /// More than one means agents updated the entry concurrently. The latest entry of
+    /// the result is then the head picked by the conflict resolution callback of the
+    /// entry type, or the first head if it has none.
+    /// Empty for `StatusRequestKind::Initial` and if all branches got deleted.
+    #[serde(default)]
+    pub heads: Vec<Address>,
 }
 impl GetEntryResult {
     pub fn new(
@@ -144,6 +193,7 @@ impl GetEntryResult {
             StatusRequestKind::All => {
                 let mut entry_result = GetEntryResult {
                     result: GetEntryResultType::All(EntryHistory::new()),
+                    heads: Vec::new(),
                 };
                 if let Some((entry_with_meta, headers)) = maybe_entry_with_meta_and_headers {
                     entry_result.push(entry_with_meta, headers);
@@ -154,6 +204,7 @@ impl GetEntryResult {
                 result: GetEntryResultType::Single(GetEntryResultItem::new(
                     maybe_entry_with_meta_and_headers,
                 )),
+                heads: Vec::new(),
             },
         }
     }
@@ -187,6 +238,11 @@ impl GetEntryResult {
         };
     }
 
+    /// true if agents updated the entry concurrently and its history has several heads
+    pub fn has_conflict(&self) -> bool {
+        self.heads.len() > 1
+    }
+
     /// returns the entry searched for.  Note that if the GetEntryOptions did not
     /// include a request for the entry value, this function will return None even if the
     /// entry was found.
@@ -201,6 +257,15 @@ impl GetEntryResult {
     }
 }
 
+/// Input of the conflict resolution callback of an entry type. It gets called with the
+/// entries at all heads of an entry's update graph, sorted by address, and returns the
+/// address of the one that get_entry should treat as the latest.
+#[derive(Deserialize, Debug, Serialize, DefaultJson, Clone)]
+pub struct ConflictResolutionArgs {
+    pub entry_type: String,
+    pub heads: Vec<Entry>,
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -228,6 +293,7 @@ mod tests {
                 entry: test_entry(),
                 crud_status: CrudStatus::Live,
                 maybe_link_update_delete: None,
+                links_update_delete: Vec::new(),
             },
             vec![test_chain_header()],
         );
@@ -244,6 +310,7 @@ mod tests {
                 entry: test_entry_a(),
                 crud_status: CrudStatus::Modified,
                 maybe_link_update_delete: None,
+                links_update_delete: Vec::new(),
             },
             vec![test_chain_header()],
         );
@@ -252,6 +319,7 @@ mod tests {
                 entry: test_entry_b(),
                 crud_status: CrudStatus::Live,
                 maybe_link_update_delete: None,
+                links_update_delete: Vec::new(),
             },
             vec![test_chain_header()],
         );
@@ -267,6 +335,7 @@ mod tests {
                 entry: test_entry(),
                 crud_status: CrudStatus::Live,
                 maybe_link_update_delete: None,
+                links_update_delete: Vec::new(),
             },
             vec![test_chain_header()],
         );
@@ -280,6 +349,7 @@ mod tests {
                 entry: test_entry(),
                 crud_status: CrudStatus::Live,
                 maybe_link_update_delete: None,
+                links_update_delete: Vec::new(),
             },
             vec![test_chain_header()],
         );
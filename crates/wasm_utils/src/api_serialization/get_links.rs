@@ -1,7 +1,8 @@
+use crate::api_serialization::get_entry::CachePolicy;
 use holochain_core_types::{
     chain_header::ChainHeader,
     crud_status::CrudStatus,
-    network::query::{Pagination, SortOrder},
+    network::query::{GetLinksCursor, Pagination, SortOrder},
     time::Timeout,
 };
 use holochain_json_api::{error::JsonError, json::*};
@@ -35,6 +36,8 @@ pub struct GetLinksOptions {
     pub timeout: Timeout,
     pub pagination: Option<Pagination>,
     pub sort_order: Option<SortOrder>,
+    #[serde(default)]
+    pub cache_policy: CachePolicy,
 }
 
 #[derive(Deserialize, Clone, Serialize, Debug, DefaultJson, PartialEq)]
@@ -48,6 +51,8 @@ pub struct LinksResult {
 #[derive(Deserialize, Clone, Serialize, Debug, DefaultJson, PartialEq)]
 pub struct GetLinksResult {
     links: Vec<LinksResult>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    next_cursor: Option<GetLinksCursor>,
 }
 
 #[derive(Deserialize, Serialize, Debug, DefaultJson)]
@@ -57,7 +62,21 @@ pub struct GetLinksResultCount {
 
 impl GetLinksResult {
     pub fn new(links: Vec<LinksResult>) -> GetLinksResult {
-        GetLinksResult { links }
+        GetLinksResult {
+            links,
+            next_cursor: None,
+        }
+    }
+
+    pub fn with_next_cursor(mut self, next_cursor: Option<GetLinksCursor>) -> GetLinksResult {
+        self.next_cursor = next_cursor;
+        self
+    }
+
+    /// Cursor to pass in Pagination::Cursor to get the next page of links.
+    /// Only set if the links were requested with cursor pagination and the page is full.
+    pub fn next_cursor(&self) -> Option<GetLinksCursor> {
+        self.next_cursor.clone()
     }
 
     pub fn tags(&self) -> Vec<String> {
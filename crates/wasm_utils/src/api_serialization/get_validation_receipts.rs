@@ -0,0 +1,16 @@
+use holochain_json_api::{error::JsonError, json::*};
+use holochain_persistence_api::cas::content::Address;
+
+#[derive(Deserialize, Default, Debug, Serialize, Clone, PartialEq, Eq, Hash, DefaultJson)]
+pub struct GetValidationReceiptsArgs {
+    /// Address of one of our entries or of its header
+    pub address: Address,
+}
+
+#[derive(Deserialize, Default, Clone, Serialize, Debug, DefaultJson, PartialEq)]
+pub struct ValidationReceiptsResult {
+    /// Number of distinct validators that confirmed holding the entry
+    pub count: usize,
+    /// Addresses of these validators
+    pub signers: Vec<Address>,
+}
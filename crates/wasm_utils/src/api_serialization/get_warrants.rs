@@ -0,0 +1,14 @@
+use holochain_core_types::warrant::Warrant;
+use holochain_json_api::{error::JsonError, json::*};
+use holochain_persistence_api::cas::content::Address;
+
+#[derive(Deserialize, Default, Debug, Serialize, Clone, PartialEq, Eq, Hash, DefaultJson)]
+pub struct GetWarrantsArgs {
+    /// Address of an entry or agent
+    pub address: Address,
+}
+
+#[derive(Deserialize, Clone, Serialize, Debug, DefaultJson, PartialEq)]
+pub struct GetWarrantsResult {
+    pub warrants: Vec<Warrant>,
+}
@@ -1,4 +1,3 @@
-mod call;
 /// This module holds structs for all arguments and return types
 /// that get serialized and deserialized between core native and
 /// the WASM based ribosome.
@@ -9,12 +8,17 @@ mod call;
 ///
 /// For the case of HDK-rust we can use the exact same types by
 /// importing this module.
+pub mod blob;
+mod call;
 pub mod capabilities;
 pub mod commit_entry;
 pub mod crypto;
 pub mod emit_signal;
+pub mod get_agent_activity;
 pub mod get_entry;
 pub mod get_links;
+pub mod get_validation_receipts;
+pub mod get_warrants;
 pub mod keystore;
 pub mod link_entries;
 pub mod meta;
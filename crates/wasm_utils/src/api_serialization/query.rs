@@ -1,6 +1,7 @@
 use holochain_core_types::{
     chain_header::ChainHeader,
     entry::{entry_type::EntryType, Entry},
+    time::Iso8601,
 };
 
 use holochain_json_api::{error::JsonError, json::*};
@@ -62,10 +63,38 @@ pub struct QueryArgs {
 pub struct QueryArgsOptions {
     pub start: usize,
     pub limit: usize,
-    // pub ordering: bool,  // forward or reverse (requires doubly-linked chain perhaps)
-    // pub filter_by: ???,
     pub headers: bool,
     pub entries: bool,
+    /// Return the oldest entries first instead of the newest
+    #[serde(default)]
+    pub reverse: bool,
+    /// Only include entries committed at or after this time
+    #[serde(default)]
+    pub since: Option<Iso8601>,
+    /// Only include entries committed at or before this time
+    #[serde(default)]
+    pub until: Option<Iso8601>,
+    /// Continue a previous query after the last result it returned
+    #[serde(default)]
+    pub cursor: Option<QueryCursor>,
+}
+
+/// Opaque position in the source chain, returned with a full page of query results.
+/// Passing it back with the same query returns the next page, unaffected by entries that were
+/// committed in between.
+#[derive(Deserialize, Debug, Serialize, DefaultJson, Clone, PartialEq)]
+pub struct QueryCursor(Address);
+
+impl From<Address> for QueryCursor {
+    fn from(address: Address) -> QueryCursor {
+        QueryCursor(address)
+    }
+}
+
+impl From<QueryCursor> for Address {
+    fn from(cursor: QueryCursor) -> Address {
+        cursor.0
+    }
 }
 
 #[derive(Deserialize, Debug, Serialize, DefaultJson, Clone, PartialEq)]
@@ -75,3 +104,9 @@ pub enum QueryResult {
     Entries(Vec<(Address, Entry)>),
     HeadersWithEntries(Vec<(ChainHeader, Entry)>),
 }
+
+#[derive(Deserialize, Debug, Serialize, DefaultJson, Clone, PartialEq)]
+pub struct QueryResultPage {
+    pub result: QueryResult,
+    pub next_cursor: Option<QueryCursor>,
+}
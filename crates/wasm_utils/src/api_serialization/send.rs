@@ -10,5 +10,80 @@ pub struct SendArgs {
     pub options: SendOptions,
 }
 
+#[derive(Deserialize, Default, Clone, PartialEq, Debug, Serialize, DefaultJson)]
+#[serde(from = "SendOptionsFormat")]
+pub struct SendOptions {
+    pub timeout: Timeout,
+    /// If the recipient is not connected, have the network keep the message and deliver
+    /// it once the recipient reconnects, instead of waiting for a response.
+    /// Only sim2h servers with an enabled mailbox support this.
+    #[serde(default)]
+    pub deliver_later: bool,
+}
+
+/// HDKs from before `deliver_later` send the options as just the timeout.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum SendOptionsFormat {
+    Options {
+        timeout: Timeout,
+        #[serde(default)]
+        deliver_later: bool,
+    },
+    Timeout(Timeout),
+}
+
+impl From<SendOptionsFormat> for SendOptions {
+    fn from(format: SendOptionsFormat) -> SendOptions {
+        match format {
+            SendOptionsFormat::Options {
+                timeout,
+                deliver_later,
+            } => SendOptions {
+                timeout,
+                deliver_later,
+            },
+            SendOptionsFormat::Timeout(timeout) => SendOptions {
+                timeout,
+                deliver_later: false,
+            },
+        }
+    }
+}
+
+/// Result of a send() with `deliver_later` set
 #[derive(Deserialize, Clone, PartialEq, Debug, Serialize, DefaultJson)]
-pub struct SendOptions(pub Timeout);
+pub enum SendResult {
+    /// The recipient was connected and responded with this
+    Response(String),
+    /// The recipient is not connected. The network will deliver the message
+    /// once it reconnects, but there will be no response.
+    Queued,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::convert::TryFrom;
+
+    #[test]
+    fn send_options_roundtrip() {
+        let options = SendOptions {
+            timeout: Timeout::new(1000),
+            deliver_later: true,
+        };
+        let json: JsonString = options.clone().into();
+        assert_eq!(SendOptions::try_from(json).unwrap(), options);
+    }
+
+    #[test]
+    fn send_options_from_old_hdks_are_just_the_timeout() {
+        assert_eq!(
+            SendOptions::try_from(JsonString::from_json("1000")).unwrap(),
+            SendOptions {
+                timeout: Timeout::new(1000),
+                deliver_later: false,
+            }
+        );
+    }
+}
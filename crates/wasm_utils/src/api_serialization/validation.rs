@@ -1,5 +1,6 @@
 use holochain_core_types::{
     agent::AgentId,
+    chain_header::ChainHeader,
     entry::Entry,
     link::Link,
     validation::{EntryValidationData, LinkValidationData},
@@ -30,6 +31,14 @@ pub struct LinkValidationPackageArgs {
     pub direction: LinkDirection,
 }
 
+/// Input of the custom validation package callback that gets called when an entry
+/// of a type with `ValidationPackageDefinition::CustomCallback` gets published.
+#[derive(Deserialize, Debug, Serialize, DefaultJson, Clone)]
+pub struct CustomValidationPackageArgs {
+    pub entry: Entry,
+    pub chain_header: ChainHeader,
+}
+
 #[derive(Deserialize, Debug, Serialize, DefaultJson, Clone)]
 pub struct LinkValidationArgs {
     pub entry_type: String,
@@ -45,7 +45,10 @@ use holochain_wasm_utils::{
     wasm_target_dir,
 };
 
-use hdk::error::{ZomeApiError, ZomeApiResult};
+use hdk::{
+    error::{ZomeApiError, ZomeApiResult},
+    path::{PATH_CHILD_LINK_TYPE, PATH_ENTRY_TYPE},
+};
 
 use std::{
     collections::{hash_map::DefaultHasher, BTreeMap},
@@ -431,6 +434,10 @@ pub fn start_holochain_instance<T: Into<String>>(
         "create_priv_entry",
         "get_version",
         "sign_payload",
+        "ensure_path",
+        "path_children",
+        "link_under_path",
+        "path_items",
     ]);
     let mut dna = create_test_dna_with_defs("test_zome", defs, &wasm);
     dna.uuid = uuid.into();
@@ -476,6 +483,24 @@ pub fn start_holochain_instance<T: Into<String>>(
         entry_types.insert(EntryType::from("link_validator"), link_validator);
     }
 
+    {
+        let entry_types = &mut dna.zomes.get_mut("test_zome").unwrap().entry_types;
+        let mut path_def = EntryTypeDef::new();
+        path_def.links_to.push(LinksTo {
+            target_type: String::from(PATH_ENTRY_TYPE),
+            link_type: String::from(PATH_CHILD_LINK_TYPE),
+        });
+        entry_types.insert(EntryType::from(PATH_ENTRY_TYPE), path_def);
+        entry_types
+            .get_mut(&EntryType::from("testEntryType"))
+            .unwrap()
+            .linked_from
+            .push(LinkedFrom {
+                base_type: String::from(PATH_ENTRY_TYPE),
+                link_type: String::from("path_item"),
+            });
+    }
+
     //set this environmental variable to set up the backend for running tests.
     //if none has been set it will default to the legacy in memory worker implementation
     let test_config = env::var("INTEGRATION_TEST_CONFIG")